@@ -0,0 +1,10 @@
+//! Speech analytics module
+//!
+//! Measures the speaking rate and the filler words of each dictation, and
+//! keeps them in a log summarized into daily trends.
+
+mod speech;
+mod trends;
+
+pub use speech::{SpeechAnalytics, SpeechStats};
+pub use trends::{SpeechLog, SpeechTrends};
@@ -0,0 +1,104 @@
+//! Speaking rate and filler words of a dictation
+
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Optional analysis pass run on each dictation
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SpeechAnalytics {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Words and phrases counted as fillers (case-insensitive)
+    #[serde(default = "default_filler_words")]
+    pub filler_words: Vec<String>,
+}
+
+impl Default for SpeechAnalytics {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            filler_words: default_filler_words(),
+        }
+    }
+}
+
+fn default_filler_words() -> Vec<String> {
+    ["euh", "heu", "bah", "um", "uh", "erm", "hmm"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+/// Measures of one dictation
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SpeechStats {
+    /// End of the dictation (milliseconds since Unix epoch)
+    pub created_at: u64,
+    /// Recording saved for the dictation, if any
+    #[serde(default)]
+    pub recording_id: Option<String>,
+    pub words: usize,
+    /// Recording time, pauses included
+    pub duration_secs: f64,
+    pub words_per_minute: f64,
+    /// Occurrences of each filler heard
+    #[serde(default)]
+    pub fillers: BTreeMap<String, usize>,
+}
+
+impl SpeechStats {
+    /// Fillers heard, all words together
+    pub fn filler_count(&self) -> usize {
+        self.fillers.values().sum()
+    }
+}
+
+impl SpeechAnalytics {
+    /// Measure `text`, spoken over `duration_secs`
+    pub fn analyze(&self, text: &str, duration_secs: f64, recording_id: Option<String>) -> SpeechStats {
+        let words = tokens(text);
+
+        let mut fillers = BTreeMap::new();
+        for filler in &self.filler_words {
+            let phrase = tokens(filler);
+            if phrase.is_empty() {
+                continue;
+            }
+            let count = words.windows(phrase.len()).filter(|window| *window == phrase.as_slice()).count();
+            if count > 0 {
+                *fillers.entry(phrase.join(" ")).or_default() += count;
+            }
+        }
+
+        let words_per_minute = if duration_secs > 0.0 {
+            words.len() as f64 / (duration_secs / 60.0)
+        } else {
+            0.0
+        };
+
+        SpeechStats {
+            created_at: now_millis(),
+            recording_id,
+            words: words.len(),
+            duration_secs,
+            words_per_minute,
+            fillers,
+        }
+    }
+}
+
+/// Lowercase words of `text`, elisions kept whole ("l'ordre")
+fn tokens(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'' && c != '’')
+        .map(|word| word.trim_matches(|c| c == '\'' || c == '’'))
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
@@ -0,0 +1,126 @@
+//! Speech stats log, persisted as JSON and summarized per day
+
+use super::SpeechStats;
+use chrono::{Local, NaiveDate, TimeZone};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Dictations kept in the log, the oldest ones are dropped first
+const MAX_ENTRIES: usize = 10_000;
+
+/// Measures of every analyzed dictation, oldest first
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct SpeechLog {
+    #[serde(skip)]
+    path: PathBuf,
+    #[serde(default)]
+    entries: Vec<SpeechStats>,
+}
+
+/// Totals of one day of dictation
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DailySpeechStats {
+    /// Local date, "YYYY-MM-DD"
+    pub date: String,
+    pub dictations: usize,
+    pub words: usize,
+    pub duration_secs: f64,
+    /// Over the whole day, long dictations weighing more
+    pub words_per_minute: f64,
+    pub fillers: usize,
+    pub fillers_per_100_words: f64,
+}
+
+impl DailySpeechStats {
+    fn new(date: NaiveDate) -> Self {
+        Self {
+            date: date.format("%Y-%m-%d").to_string(),
+            dictations: 0,
+            words: 0,
+            duration_secs: 0.0,
+            words_per_minute: 0.0,
+            fillers: 0,
+            fillers_per_100_words: 0.0,
+        }
+    }
+
+    fn add(&mut self, stats: &SpeechStats) {
+        self.dictations += 1;
+        self.words += stats.words;
+        self.duration_secs += stats.duration_secs;
+        self.fillers += stats.filler_count();
+        if self.duration_secs > 0.0 {
+            self.words_per_minute = self.words as f64 / (self.duration_secs / 60.0);
+        }
+        if self.words > 0 {
+            self.fillers_per_100_words = self.fillers as f64 * 100.0 / self.words as f64;
+        }
+    }
+}
+
+/// Trends returned to the frontend
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SpeechTrends {
+    /// Days with at least one dictation, oldest first
+    pub daily: Vec<DailySpeechStats>,
+    /// Latest dictations, most recent first
+    pub recent: Vec<SpeechStats>,
+}
+
+impl SpeechLog {
+    /// Read the log, starting empty if missing or unreadable
+    pub fn load(path: &Path) -> Self {
+        let mut log: Self = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| match serde_json::from_str(&content) {
+                Ok(log) => Some(log),
+                Err(e) => {
+                    tracing::warn!("Speech stats unreadable, starting over: {}", e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        log.path = path.to_path_buf();
+        log
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Speech stats dir error: {}", e))?;
+        }
+        let content = serde_json::to_string(self).map_err(|e| e.to_string())?;
+        std::fs::write(&self.path, content).map_err(|e| format!("Speech stats write error: {}", e))
+    }
+
+    /// Add the measures of a finished dictation
+    pub fn record(&mut self, stats: SpeechStats) {
+        self.entries.push(stats);
+        if self.entries.len() > MAX_ENTRIES {
+            let excess = self.entries.len() - MAX_ENTRIES;
+            self.entries.drain(..excess);
+        }
+    }
+
+    /// Daily totals over the last `days` days and the last `recent` dictations
+    pub fn trends(&self, days: u32, recent: usize) -> SpeechTrends {
+        let first_day = Local::now().date_naive() - chrono::Duration::days(i64::from(days.max(1)) - 1);
+        let mut daily: BTreeMap<NaiveDate, DailySpeechStats> = BTreeMap::new();
+        for stats in &self.entries {
+            let Some(date) = Local
+                .timestamp_millis_opt(stats.created_at as i64)
+                .single()
+                .map(|time| time.date_naive())
+            else {
+                continue;
+            };
+            if date >= first_day {
+                daily.entry(date).or_insert_with(|| DailySpeechStats::new(date)).add(stats);
+            }
+        }
+
+        SpeechTrends {
+            daily: daily.into_values().collect(),
+            recent: self.entries.iter().rev().take(recent).cloned().collect(),
+        }
+    }
+}
@@ -0,0 +1,272 @@
+//! Audio accumulator with disk spill
+//!
+//! Engines accumulate the whole dictation before sending it. Beyond a
+//! threshold, samples are appended to a temp file instead of RAM so that
+//! long recordings (meetings) keep a bounded memory footprint.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::ops::Range;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Default in-memory limit: 5 minutes at 16kHz
+pub const DEFAULT_MEMORY_LIMIT_SAMPLES: usize = 16000 * 60 * 5;
+
+/// Samples read back per chunk from the spill file
+const READ_CHUNK_SAMPLES: usize = 16000;
+
+/// Unique suffix for spill file names
+static SPILL_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Spill file, deleted on drop
+struct SpillFile {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    samples: usize,
+}
+
+impl SpillFile {
+    fn create() -> std::io::Result<Self> {
+        let path = std::env::temp_dir().join(format!(
+            "dictea-spill-{}-{}.pcm",
+            std::process::id(),
+            SPILL_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let writer = BufWriter::new(File::create(&path)?);
+        tracing::info!("Audio spill to {}", path.display());
+        Ok(Self {
+            path,
+            writer,
+            samples: 0,
+        })
+    }
+
+    fn write(&mut self, samples: &[f32]) -> std::io::Result<()> {
+        for sample in samples {
+            self.writer.write_all(&sample.to_le_bytes())?;
+        }
+        self.samples += samples.len();
+        Ok(())
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// PCM float32 mono buffer, spilled to disk beyond `memory_limit` samples
+pub struct AudioAccumulator {
+    /// Samples kept in RAM (those after the spilled ones)
+    memory: Vec<f32>,
+    spill: Option<SpillFile>,
+    memory_limit: usize,
+}
+
+impl Default for AudioAccumulator {
+    fn default() -> Self {
+        Self::new(DEFAULT_MEMORY_LIMIT_SAMPLES)
+    }
+}
+
+impl AudioAccumulator {
+    pub fn new(memory_limit: usize) -> Self {
+        Self {
+            memory: Vec::new(),
+            spill: None,
+            memory_limit,
+        }
+    }
+
+    /// Change the in-memory limit (applies to the next pushes)
+    pub fn set_memory_limit(&mut self, memory_limit: usize) {
+        self.memory_limit = memory_limit;
+    }
+
+    /// Total number of samples
+    pub fn len(&self) -> usize {
+        self.spill.as_ref().map(|s| s.samples).unwrap_or(0) + self.memory.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Append samples, moving the in-memory part to disk once over the limit
+    pub fn extend_from_slice(&mut self, pcm: &[f32]) {
+        self.memory.extend_from_slice(pcm);
+        if self.memory_limit == 0 || self.memory.len() <= self.memory_limit {
+            return;
+        }
+
+        if self.spill.is_none() {
+            match SpillFile::create() {
+                Ok(spill) => self.spill = Some(spill),
+                Err(e) => {
+                    tracing::error!("Audio spill file error, keeping audio in memory: {}", e);
+                    self.memory_limit = 0;
+                    return;
+                }
+            }
+        }
+        if let Some(ref mut spill) = self.spill {
+            match spill.write(&self.memory) {
+                Ok(()) => self.memory.clear(),
+                Err(e) => {
+                    tracing::error!("Audio spill write error, keeping audio in memory: {}", e);
+                    self.memory_limit = 0;
+                }
+            }
+        }
+    }
+
+    /// Drop all samples
+    pub fn clear(&mut self) {
+        self.memory.clear();
+        self.spill = None;
+    }
+
+    /// Move the content out, leaving an empty accumulator with the same limit
+    pub fn take(&mut self) -> Self {
+        std::mem::replace(self, Self::new(self.memory_limit))
+    }
+
+    /// Visit all samples in order, in chunks
+    pub fn try_for_each_chunk<E, F>(&mut self, f: F) -> Result<(), E>
+    where
+        F: FnMut(&[f32]) -> Result<(), E>,
+        E: From<std::io::Error>,
+    {
+        let len = self.len();
+        self.try_for_each_chunk_in(0..len, f)
+    }
+
+    /// Visit the samples of `range` in order, in chunks
+    pub fn try_for_each_chunk_in<E, F>(&mut self, range: Range<usize>, mut f: F) -> Result<(), E>
+    where
+        F: FnMut(&[f32]) -> Result<(), E>,
+        E: From<std::io::Error>,
+    {
+        let end = range.end.min(self.len());
+        let spilled = self.spill.as_ref().map_or(0, |s| s.samples);
+        if let Some(ref mut spill) = self.spill {
+            if range.start < spilled {
+                spill.writer.flush()?;
+                let mut reader = BufReader::new(File::open(&spill.path)?);
+                reader.seek(SeekFrom::Start(range.start as u64 * 4))?;
+                let mut bytes = vec![0u8; READ_CHUNK_SAMPLES * 4];
+                let mut chunk = Vec::with_capacity(READ_CHUNK_SAMPLES);
+                let mut remaining = end.min(spilled).saturating_sub(range.start);
+                while remaining > 0 {
+                    let count = remaining.min(READ_CHUNK_SAMPLES);
+                    reader.read_exact(&mut bytes[..count * 4])?;
+                    chunk.clear();
+                    chunk.extend(
+                        bytes[..count * 4]
+                            .chunks_exact(4)
+                            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+                    );
+                    f(&chunk)?;
+                    remaining -= count;
+                }
+            }
+        }
+        // RAM holds the samples after the spilled ones
+        let memory = range.start.saturating_sub(spilled)..end.saturating_sub(spilled);
+        if !memory.is_empty() {
+            f(&self.memory[memory])?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ramp(range: Range<usize>) -> Vec<f32> {
+        range.map(|i| i as f32).collect()
+    }
+
+    fn collect(audio: &mut AudioAccumulator, range: Range<usize>) -> Vec<f32> {
+        let mut samples = Vec::new();
+        audio
+            .try_for_each_chunk_in(range, |chunk| {
+                samples.extend_from_slice(chunk);
+                Ok::<_, std::io::Error>(())
+            })
+            .unwrap();
+        samples
+    }
+
+    #[test]
+    fn appends_stay_in_memory_up_to_the_limit() {
+        let mut audio = AudioAccumulator::new(100);
+        audio.extend_from_slice(&ramp(0..60));
+        audio.extend_from_slice(&ramp(60..100));
+        assert!(audio.spill.is_none());
+        assert_eq!(audio.len(), 100);
+        assert_eq!(collect(&mut audio, 0..100), ramp(0..100));
+    }
+
+    #[test]
+    fn appends_past_the_limit_are_spilled_in_order() {
+        let mut audio = AudioAccumulator::new(100);
+        audio.extend_from_slice(&ramp(0..60));
+        audio.extend_from_slice(&ramp(60..150));
+        audio.extend_from_slice(&ramp(150..170));
+        assert_eq!(audio.spill.as_ref().map(|spill| spill.samples), Some(150));
+        assert_eq!(audio.memory.len(), 20);
+        assert_eq!(audio.len(), 170);
+
+        // Read back across the file and the memory, whole or in part
+        assert_eq!(collect(&mut audio, 0..170), ramp(0..170));
+        assert_eq!(collect(&mut audio, 140..160), ramp(140..160));
+        assert_eq!(collect(&mut audio, 160..500), ramp(160..170));
+    }
+
+    #[test]
+    fn spilled_reads_are_chunked() {
+        let total = READ_CHUNK_SAMPLES * 2 + 10;
+        let mut audio = AudioAccumulator::new(1);
+        audio.extend_from_slice(&ramp(0..total));
+        let mut chunks = Vec::new();
+        audio
+            .try_for_each_chunk(|chunk| {
+                chunks.push(chunk.len());
+                Ok::<_, std::io::Error>(())
+            })
+            .unwrap();
+        assert_eq!(chunks, [READ_CHUNK_SAMPLES, READ_CHUNK_SAMPLES, 10]);
+    }
+
+    #[test]
+    fn zero_limit_never_spills() {
+        let mut audio = AudioAccumulator::new(0);
+        audio.extend_from_slice(&ramp(0..10_000));
+        assert!(audio.spill.is_none());
+        assert_eq!(audio.len(), 10_000);
+    }
+
+    #[test]
+    fn clear_and_take_remove_the_spill_file() {
+        let mut audio = AudioAccumulator::new(10);
+        audio.extend_from_slice(&ramp(0..50));
+        let path = audio.spill.as_ref().map(|spill| spill.path.clone()).unwrap();
+        assert!(path.exists());
+        audio.clear();
+        assert!(audio.is_empty());
+        assert!(!path.exists());
+
+        audio.extend_from_slice(&ramp(0..50));
+        let path = audio.spill.as_ref().map(|spill| spill.path.clone()).unwrap();
+        let mut taken = audio.take();
+        assert!(audio.is_empty());
+        assert_eq!(audio.memory_limit, 10);
+        assert_eq!(collect(&mut taken, 0..50), ramp(0..50));
+        drop(taken);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,72 @@
+//! Noise suppression
+//!
+//! Steady background noise (laptop fan, hum) and keyboard clicks make the
+//! models hallucinate or drop words. The microphone audio goes through
+//! RNNoise (nnnoiseless port) before it reaches the engine. RNNoise works
+//! on 10ms frames at 48kHz, so the 16kHz capture is resampled around it.
+
+use super::encode::{LinearResampler, CAPTURE_SAMPLE_RATE};
+use nnnoiseless::DenoiseState;
+
+/// Sample rate RNNoise is trained for
+const DENOISE_SAMPLE_RATE: u32 = 48000;
+
+/// RNNoise expects samples in the 16-bit range
+const PCM_SCALE: f32 = 32767.0;
+
+/// Settings of the noise suppression stage
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct NoiseSuppression {
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+/// Denoises a microphone stream, chunk after chunk
+pub struct Denoiser {
+    state: Box<DenoiseState<'static>>,
+    upsampler: LinearResampler,
+    downsampler: LinearResampler,
+    /// 48kHz samples short of a full frame, carried over to the next chunk
+    pending: Vec<f32>,
+}
+
+impl Denoiser {
+    pub fn new() -> Self {
+        Self {
+            state: DenoiseState::new(),
+            upsampler: LinearResampler::new(CAPTURE_SAMPLE_RATE, DENOISE_SAMPLE_RATE),
+            downsampler: LinearResampler::new(DENOISE_SAMPLE_RATE, CAPTURE_SAMPLE_RATE),
+            pending: Vec::with_capacity(DenoiseState::FRAME_SIZE * 2),
+        }
+    }
+
+    /// Denoise a chunk of 16kHz samples
+    ///
+    /// The output lags the input by less than a frame (10ms).
+    pub fn process(&mut self, samples: &[f32]) -> Vec<f32> {
+        self.upsampler.process(samples, &mut self.pending);
+        let frames = self.pending.len() / DenoiseState::FRAME_SIZE;
+
+        let mut input = [0.0f32; DenoiseState::FRAME_SIZE];
+        let mut output = [0.0f32; DenoiseState::FRAME_SIZE];
+        let mut denoised = Vec::with_capacity(frames * DenoiseState::FRAME_SIZE);
+        for frame in self.pending.chunks_exact(DenoiseState::FRAME_SIZE) {
+            for (scaled, sample) in input.iter_mut().zip(frame) {
+                *scaled = sample * PCM_SCALE;
+            }
+            self.state.process_frame(&mut output, &input);
+            denoised.extend(output.iter().map(|sample| (sample / PCM_SCALE).clamp(-1.0, 1.0)));
+        }
+        self.pending.drain(..frames * DenoiseState::FRAME_SIZE);
+
+        let mut resampled = Vec::with_capacity(samples.len());
+        self.downsampler.process(&denoised, &mut resampled);
+        resampled
+    }
+}
+
+impl Default for Denoiser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
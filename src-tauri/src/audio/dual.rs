@@ -0,0 +1,58 @@
+//! Dual capture: microphone + system audio
+//!
+//! Both sources run their own capture thread and are kept as separate
+//! streams, tagged with their origin, so each can be transcribed and
+//! labeled on its own.
+
+use super::microphone::{AudioConfig, AudioHandle, MicrophoneError};
+use std::sync::Arc;
+
+/// Origin of a captured stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioSource {
+    /// Local microphone (the user)
+    Microphone,
+    /// System audio played by the computer (the other participants)
+    System,
+}
+
+/// Handle to the two capture threads, stopped on drop
+pub struct DualCapture {
+    microphone: AudioHandle,
+    system: AudioHandle,
+}
+
+impl DualCapture {
+    /// Start capturing both sources, samples are delivered with their origin
+    pub fn start<F>(
+        microphone: AudioConfig,
+        system: AudioConfig,
+        sample_callback: F,
+    ) -> Result<Self, MicrophoneError>
+    where
+        F: Fn(AudioSource, Vec<f32>) + Send + Sync + 'static,
+    {
+        let callback = Arc::new(sample_callback);
+
+        let mic_callback = Arc::clone(&callback);
+        let microphone = AudioHandle::start(microphone, move |samples| {
+            mic_callback(AudioSource::Microphone, samples)
+        })?;
+
+        let system = AudioHandle::start(
+            AudioConfig {
+                loopback: true,
+                ..system
+            },
+            move |samples| callback(AudioSource::System, samples),
+        )?;
+
+        Ok(Self { microphone, system })
+    }
+
+    /// Stop both captures
+    pub fn stop(&mut self) {
+        self.microphone.stop();
+        self.system.stop();
+    }
+}
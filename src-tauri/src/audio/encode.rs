@@ -0,0 +1,359 @@
+//! Upload encoding of the captured audio
+//!
+//! Capture always produces PCM float32 mono at 16kHz. Each STT engine
+//! declares the format it wants to receive, and the audio is resampled and
+//! encoded accordingly right before the upload. FLAC and Opus go through
+//! the ffmpeg found on PATH, which is not bundled with the app: uploads fall
+//! back to WAV, with a warning, when it is missing or fails.
+
+use super::AudioAccumulator;
+use std::io::Cursor;
+use thiserror::Error;
+
+/// Sample rate of the captured audio
+pub const CAPTURE_SAMPLE_RATE: u32 = 16000;
+
+/// Sample rates the audio can be uploaded at
+pub const UPLOAD_SAMPLE_RATES: &[u32] = &[8000, 16000, 24000];
+
+/// Program compressing FLAC and Opus uploads, looked up on PATH
+const FFMPEG: &str = "ffmpeg";
+
+/// Container/sample layout of the uploaded audio
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioEncoding {
+    /// WAV file, signed 16-bit mono
+    Wav,
+    /// Headerless signed 16-bit little-endian mono (WebSocket streaming APIs)
+    Pcm16,
+    /// FLAC file, lossless (about half the size of WAV)
+    Flac,
+    /// Opus in an Ogg container, lossy (about a tenth of the size of WAV)
+    Opus,
+}
+
+/// Codec of the HTTP uploads, chosen per engine
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UploadCodec {
+    #[default]
+    Wav,
+    Flac,
+    Opus,
+}
+
+impl UploadCodec {
+    pub fn encoding(self) -> AudioEncoding {
+        match self {
+            Self::Wav => AudioEncoding::Wav,
+            Self::Flac => AudioEncoding::Flac,
+            Self::Opus => AudioEncoding::Opus,
+        }
+    }
+}
+
+/// Audio format expected by an engine
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AudioFormat {
+    pub encoding: AudioEncoding,
+    pub sample_rate: u32,
+}
+
+impl AudioFormat {
+    /// 16kHz 16-bit WAV, accepted by every HTTP transcription API
+    pub const WAV_16K: Self = Self {
+        encoding: AudioEncoding::Wav,
+        sample_rate: CAPTURE_SAMPLE_RATE,
+    };
+
+    /// MIME type to declare in the upload
+    pub fn mime_type(&self) -> &'static str {
+        match self.encoding {
+            AudioEncoding::Wav => "audio/wav",
+            AudioEncoding::Pcm16 => "audio/pcm",
+            AudioEncoding::Flac => "audio/flac",
+            AudioEncoding::Opus => "audio/ogg",
+        }
+    }
+
+    /// File name to declare in multipart uploads
+    pub fn file_name(&self) -> &'static str {
+        match self.encoding {
+            AudioEncoding::Wav => "audio.wav",
+            AudioEncoding::Pcm16 => "audio.pcm",
+            AudioEncoding::Flac => "audio.flac",
+            AudioEncoding::Opus => "audio.ogg",
+        }
+    }
+}
+
+impl Default for AudioFormat {
+    fn default() -> Self {
+        Self::WAV_16K
+    }
+}
+
+/// Audio encoding errors
+#[derive(Error, Debug)]
+pub enum EncodeError {
+    #[error("WAV error: {0}")]
+    Wav(#[from] hound::Error),
+
+    #[error("Audio read error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Codec error: {0}")]
+    Codec(String),
+}
+
+/// Streaming linear resampler, continuous across chunk boundaries
+pub(crate) struct LinearResampler {
+    /// Source samples per output sample
+    step: f64,
+    /// Position of the next output sample, in source samples
+    next_position: f64,
+    /// Source samples consumed by previous chunks
+    consumed: usize,
+    /// Last sample of the previous chunk
+    previous: f32,
+}
+
+impl LinearResampler {
+    pub(crate) fn new(source_rate: u32, target_rate: u32) -> Self {
+        Self {
+            step: source_rate as f64 / target_rate as f64,
+            next_position: 0.0,
+            consumed: 0,
+            previous: 0.0,
+        }
+    }
+
+    /// Resample `input`, appending to `output`
+    pub(crate) fn process(&mut self, input: &[f32], output: &mut Vec<f32>) {
+        if input.is_empty() {
+            return;
+        }
+        let base = self.consumed as f64;
+        let end = base + input.len() as f64 - 1.0;
+        while self.next_position < end {
+            let relative = self.next_position - base;
+            let index = relative.floor();
+            let frac = (relative - index) as f32;
+            // index is -1 when interpolating from the previous chunk's last sample
+            let a = if index < 0.0 { self.previous } else { input[index as usize] };
+            let b = input[(index + 1.0) as usize];
+            output.push(a * (1.0 - frac) + b * frac);
+            self.next_position += self.step;
+        }
+        self.consumed += input.len();
+        self.previous = input[input.len() - 1];
+    }
+}
+
+/// Convert a float sample to signed 16-bit
+fn to_i16(sample: f32) -> i16 {
+    (sample * 32767.0).clamp(-32768.0, 32767.0) as i16
+}
+
+/// Visit the audio resampled to `sample_rate`, in chunks
+fn for_each_resampled<F>(audio: &mut AudioAccumulator, sample_rate: u32, mut f: F) -> Result<(), EncodeError>
+where
+    F: FnMut(&[f32]) -> Result<(), EncodeError>,
+{
+    if sample_rate == CAPTURE_SAMPLE_RATE {
+        return audio.try_for_each_chunk(|chunk| f(chunk));
+    }
+
+    let mut resampler = LinearResampler::new(CAPTURE_SAMPLE_RATE, sample_rate);
+    let mut resampled = Vec::new();
+    audio.try_for_each_chunk(|chunk| {
+        resampled.clear();
+        resampler.process(chunk, &mut resampled);
+        f(&resampled)
+    })
+}
+
+/// Encode the whole accumulated audio in `format`
+pub fn encode(audio: &mut AudioAccumulator, format: AudioFormat) -> Result<Vec<u8>, EncodeError> {
+    encode_with(FFMPEG, audio, format)
+}
+
+/// `encode`, compressing with the `ffmpeg` program
+fn encode_with(ffmpeg: &str, audio: &mut AudioAccumulator, format: AudioFormat) -> Result<Vec<u8>, EncodeError> {
+    match format.encoding {
+        AudioEncoding::Wav => {
+            let spec = hound::WavSpec {
+                channels: 1,
+                sample_rate: format.sample_rate,
+                bits_per_sample: 16,
+                sample_format: hound::SampleFormat::Int,
+            };
+            let mut cursor = Cursor::new(Vec::new());
+            {
+                let mut writer = hound::WavWriter::new(&mut cursor, spec)?;
+                for_each_resampled(audio, format.sample_rate, |samples| {
+                    for &sample in samples {
+                        writer.write_sample(to_i16(sample))?;
+                    }
+                    Ok(())
+                })?;
+                writer.finalize()?;
+            }
+            Ok(cursor.into_inner())
+        }
+        AudioEncoding::Pcm16 => {
+            let mut bytes = Vec::with_capacity(audio.len() * 2);
+            for_each_resampled(audio, format.sample_rate, |samples| {
+                for &sample in samples {
+                    bytes.extend_from_slice(&to_i16(sample).to_le_bytes());
+                }
+                Ok(())
+            })?;
+            Ok(bytes)
+        }
+        AudioEncoding::Flac | AudioEncoding::Opus => {
+            let pcm = encode_with(
+                ffmpeg,
+                audio,
+                AudioFormat {
+                    encoding: AudioEncoding::Pcm16,
+                    sample_rate: format.sample_rate,
+                },
+            )?;
+            compress(ffmpeg, &pcm, format)
+        }
+    }
+}
+
+/// Encode the audio for an upload, returning the format actually used
+///
+/// A failed FLAC or Opus encoding (ffmpeg missing from PATH or failing)
+/// falls back to WAV, so the dictation is still sent.
+pub fn encode_upload(audio: &mut AudioAccumulator, format: AudioFormat) -> Result<(Vec<u8>, AudioFormat), EncodeError> {
+    encode_upload_with(FFMPEG, audio, format)
+}
+
+/// `encode_upload`, compressing with the `ffmpeg` program
+fn encode_upload_with(
+    ffmpeg: &str,
+    audio: &mut AudioAccumulator,
+    format: AudioFormat,
+) -> Result<(Vec<u8>, AudioFormat), EncodeError> {
+    match encode_with(ffmpeg, audio, format) {
+        Err(EncodeError::Codec(e)) => {
+            tracing::warn!("{:?} upload unavailable, falling back to WAV: {}", format.encoding, e);
+            let format = AudioFormat {
+                encoding: AudioEncoding::Wav,
+                ..format
+            };
+            Ok((encode_with(ffmpeg, audio, format)?, format))
+        }
+        result => result.map(|bytes| (bytes, format)),
+    }
+}
+
+/// Compress signed 16-bit PCM to FLAC or Ogg/Opus with the `ffmpeg` program
+fn compress(ffmpeg: &str, pcm: &[u8], format: AudioFormat) -> Result<Vec<u8>, EncodeError> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let codec: &[&str] = match format.encoding {
+        AudioEncoding::Flac => &["-c:a", "flac", "-f", "flac"],
+        // Speech tuned, 24 kbit/s is transparent for transcription
+        AudioEncoding::Opus => &["-c:a", "libopus", "-b:a", "24k", "-application", "voip", "-f", "ogg"],
+        AudioEncoding::Wav | AudioEncoding::Pcm16 => {
+            return Err(EncodeError::Codec(format!("{:?} is not a compressed encoding", format.encoding)))
+        }
+    };
+    let mut child = Command::new(ffmpeg)
+        .args(["-loglevel", "error", "-f", "s16le", "-ac", "1", "-ar", &format.sample_rate.to_string(), "-i", "-"])
+        .args(codec)
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| EncodeError::Codec(format!("ffmpeg launch error (is ffmpeg installed?): {}", e)))?;
+
+    // Fed from another thread, so a full output pipe cannot block the input
+    let mut stdin = child.stdin.take().ok_or_else(|| EncodeError::Codec("ffmpeg stdin unavailable".to_string()))?;
+    let output = std::thread::scope(|scope| {
+        let writer = scope.spawn(move || stdin.write_all(pcm));
+        let output = child.wait_with_output();
+        let written = writer.join().unwrap_or_else(|_| Err(std::io::Error::other("ffmpeg writer panicked")));
+        written.and(output)
+    })
+    .map_err(|e| EncodeError::Codec(format!("ffmpeg pipe error: {}", e)))?;
+    if !output.status.success() {
+        return Err(EncodeError::Codec(format!(
+            "ffmpeg error: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(output.stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 200ms of a 440Hz tone at the capture rate
+    fn tone() -> AudioAccumulator {
+        let samples: Vec<f32> = (0..3200).map(|i| 0.3 * (i as f32 * 0.17).sin()).collect();
+        let mut audio = AudioAccumulator::new(0);
+        audio.extend_from_slice(&samples);
+        audio
+    }
+
+    fn wav_samples(bytes: &[u8]) -> (hound::WavSpec, usize) {
+        let reader = hound::WavReader::new(Cursor::new(bytes)).expect("valid WAV");
+        (reader.spec(), reader.len() as usize)
+    }
+
+    #[test]
+    fn wav_upload_is_sent_as_requested() {
+        let format = AudioFormat {
+            encoding: AudioEncoding::Wav,
+            sample_rate: 8000,
+        };
+        let (bytes, used) = encode_upload(&mut tone(), format).unwrap();
+        assert_eq!(used, format);
+        let (spec, samples) = wav_samples(&bytes);
+        assert_eq!(spec.sample_rate, 8000);
+        assert_eq!(spec.bits_per_sample, 16);
+        assert_eq!(samples, 1600);
+    }
+
+    #[test]
+    fn compressed_upload_falls_back_to_wav_without_ffmpeg() {
+        for encoding in [AudioEncoding::Flac, AudioEncoding::Opus] {
+            let format = AudioFormat {
+                encoding,
+                sample_rate: CAPTURE_SAMPLE_RATE,
+            };
+            let (bytes, used) = encode_upload_with("/nonexistent/ffmpeg", &mut tone(), format).unwrap();
+            assert_eq!(used, AudioFormat::WAV_16K);
+            assert_eq!(wav_samples(&bytes).1, 3200);
+            assert_eq!(bytes, encode(&mut tone(), used).unwrap());
+        }
+    }
+
+    #[test]
+    fn compressed_upload_goes_through_ffmpeg() {
+        let installed = std::process::Command::new(FFMPEG)
+            .arg("-version")
+            .output()
+            .is_ok_and(|output| output.status.success());
+        if !installed {
+            eprintln!("ffmpeg not on PATH, compressed upload not tested");
+            return;
+        }
+        let format = AudioFormat {
+            encoding: AudioEncoding::Flac,
+            sample_rate: CAPTURE_SAMPLE_RATE,
+        };
+        let (bytes, used) = encode_upload(&mut tone(), format).unwrap();
+        assert_eq!(used, format);
+        assert!(bytes.starts_with(b"fLaC"));
+    }
+}
@@ -0,0 +1,84 @@
+//! Audio file decoding
+//!
+//! Reads an audio file into 16kHz mono samples, the capture format the
+//! engines expect. WAV is decoded in process; other formats (voice messages
+//! saved from messengers: Opus, AAC, MP3...) go through ffmpeg when installed.
+
+use super::encode::{LinearResampler, CAPTURE_SAMPLE_RATE};
+use std::path::Path;
+
+/// Extensions read as audio files
+pub const AUDIO_EXTENSIONS: &[&str] = &[
+    "wav", "mp3", "m4a", "aac", "ogg", "oga", "opus", "flac", "webm", "amr", "caf", "aiff",
+];
+
+/// `path` looks like an audio file, by its extension
+pub fn is_audio_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| AUDIO_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+/// Decode the audio file at `path` into 16kHz mono samples
+pub fn read_audio_file(path: &Path) -> Result<Vec<f32>, String> {
+    let is_wav = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("wav"));
+    if is_wav {
+        match read_wav(path) {
+            Ok(samples) => return Ok(samples),
+            // Compressed WAV variants are left to ffmpeg
+            Err(e) => tracing::warn!("{}, trying ffmpeg", e),
+        }
+    }
+    read_with_ffmpeg(path)
+}
+
+/// Decode a PCM WAV file, mixing the channels down
+fn read_wav(path: &Path) -> Result<Vec<f32>, String> {
+    let mut reader = hound::WavReader::open(path).map_err(|e| format!("WAV read error: {}", e))?;
+    let spec = reader.spec();
+    let interleaved: Vec<f32> = match spec.sample_format {
+        hound::SampleFormat::Float => reader
+            .samples::<f32>()
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("WAV read error: {}", e))?,
+        hound::SampleFormat::Int => {
+            let scale = (1i64 << (spec.bits_per_sample.max(1) - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|sample| sample.map(|sample| sample as f32 / scale))
+                .collect::<Result<_, _>>()
+                .map_err(|e| format!("WAV read error: {}", e))?
+        }
+    };
+    let channels = spec.channels.max(1) as usize;
+    let mono: Vec<f32> = interleaved
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .collect();
+    if spec.sample_rate == CAPTURE_SAMPLE_RATE {
+        return Ok(mono);
+    }
+    let mut resampled = Vec::with_capacity(mono.len() * CAPTURE_SAMPLE_RATE as usize / spec.sample_rate.max(1) as usize);
+    LinearResampler::new(spec.sample_rate, CAPTURE_SAMPLE_RATE).process(&mono, &mut resampled);
+    Ok(resampled)
+}
+
+/// Decode any format ffmpeg reads into raw 32-bit float samples
+fn read_with_ffmpeg(path: &Path) -> Result<Vec<f32>, String> {
+    let output = std::process::Command::new("ffmpeg")
+        .args(["-nostdin", "-loglevel", "error", "-i"])
+        .arg(path)
+        .args(["-f", "f32le", "-ac", "1", "-ar", &CAPTURE_SAMPLE_RATE.to_string(), "-"])
+        .output()
+        .map_err(|e| format!("ffmpeg launch error (is ffmpeg installed?): {}", e))?;
+    if !output.status.success() {
+        return Err(format!("ffmpeg error: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(output
+        .stdout
+        .chunks_exact(4)
+        .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        .collect())
+}
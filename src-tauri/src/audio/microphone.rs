@@ -3,7 +3,9 @@
 //! Uses cpal for cross-platform capture.
 //! Audio is captured in a dedicated thread and resampled to 16kHz for Whisper.
 
+use super::encode::LinearResampler;
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::mpsc;
 use std::thread::{self, JoinHandle};
 use thiserror::Error;
@@ -15,6 +17,8 @@ pub struct AudioConfig {
     pub target_sample_rate: u32,
     /// Device name to use. None = system default.
     pub device_name: Option<String>,
+    /// Capture what an output device plays (system audio) instead of a microphone
+    pub loopback: bool,
 }
 
 impl Default for AudioConfig {
@@ -22,6 +26,7 @@ impl Default for AudioConfig {
         Self {
             target_sample_rate: 16000,
             device_name: None,
+            loopback: false,
         }
     }
 }
@@ -43,8 +48,11 @@ pub enum MicrophoneError {
     NotStarted,
 }
 
+/// Capture threads currently running
+static OPEN_STREAMS: AtomicUsize = AtomicUsize::new(0);
+
 /// Commands to control the audio thread
-enum AudioCommand {
+pub(super) enum AudioCommand {
     Stop,
 }
 
@@ -60,18 +68,28 @@ impl AudioHandle {
     where
         F: Fn(Vec<f32>) + Send + 'static,
     {
-        let (command_tx, command_rx) = mpsc::channel();
-
-        let thread_handle = thread::spawn(move || {
+        Ok(Self::spawn(move |command_rx| {
             if let Err(e) = run_audio_capture(config, sample_callback, command_rx) {
                 tracing::error!("Audio capture error: {}", e);
             }
-        });
+        }))
+    }
 
-        Ok(Self {
+    /// Run a capture loop in a dedicated thread, until it gets `Stop`
+    pub(super) fn spawn<R>(run: R) -> Self
+    where
+        R: FnOnce(mpsc::Receiver<AudioCommand>) + Send + 'static,
+    {
+        let (command_tx, command_rx) = mpsc::channel();
+        OPEN_STREAMS.fetch_add(1, Ordering::Relaxed);
+        let thread_handle = thread::spawn(move || {
+            run(command_rx);
+            OPEN_STREAMS.fetch_sub(1, Ordering::Relaxed);
+        });
+        Self {
             command_tx,
             thread_handle: Some(thread_handle),
-        })
+        }
     }
 
     /// Stop audio capture
@@ -82,6 +100,11 @@ impl AudioHandle {
         }
     }
 
+    /// Number of capture threads still running, 0 when idle
+    pub fn open_streams() -> usize {
+        OPEN_STREAMS.load(Ordering::Relaxed)
+    }
+
     /// List available input devices
     pub fn list_devices() -> Vec<String> {
         let host = cpal::default_host();
@@ -89,6 +112,23 @@ impl AudioHandle {
             .map(|devices| devices.filter_map(|d| d.name().ok()).collect())
             .unwrap_or_default()
     }
+
+    /// List devices usable as system audio source
+    ///
+    /// Monitor/loopback inputs (PulseAudio monitors, BlackHole...) and, on
+    /// Windows, output devices captured through WASAPI loopback.
+    pub fn list_loopback_devices() -> Vec<String> {
+        let mut names = Self::list_devices();
+        if cfg!(target_os = "windows") {
+            let host = cpal::default_host();
+            if let Ok(devices) = host.output_devices() {
+                names.extend(devices.filter_map(|d| d.name().ok()));
+            }
+        }
+        names.sort();
+        names.dedup();
+        names
+    }
 }
 
 impl Drop for AudioHandle {
@@ -97,39 +137,23 @@ impl Drop for AudioHandle {
     }
 }
 
-/// Find an input device by name
-fn find_device_by_name(name: &str) -> Option<cpal::Device> {
+/// Find an input device by name (or an output device when capturing loopback)
+fn find_device_by_name(name: &str, loopback: bool) -> Option<cpal::Device> {
     let host = cpal::default_host();
-    host.input_devices()
+    let input = host
+        .input_devices()
         .ok()?
-        .find(|d| d.name().ok().as_deref() == Some(name))
-}
-
-/// Simple linear resample from source_rate to target_rate
-fn resample(samples: &[f32], source_rate: u32, target_rate: u32) -> Vec<f32> {
-    if source_rate == target_rate {
-        return samples.to_vec();
+        .find(|d| d.name().ok().as_deref() == Some(name));
+    if input.is_some() || !loopback {
+        return input;
     }
-
-    let ratio = source_rate as f64 / target_rate as f64;
-    let output_len = (samples.len() as f64 / ratio) as usize;
-    let mut output = Vec::with_capacity(output_len);
-
-    for i in 0..output_len {
-        let src_idx = i as f64 * ratio;
-        let idx_floor = src_idx.floor() as usize;
-        let idx_ceil = (idx_floor + 1).min(samples.len() - 1);
-        let frac = src_idx - idx_floor as f64;
-
-        let sample = samples[idx_floor] * (1.0 - frac as f32) + samples[idx_ceil] * frac as f32;
-        output.push(sample);
-    }
-
-    output
+    host.output_devices()
+        .ok()?
+        .find(|d| d.name().ok().as_deref() == Some(name))
 }
 
 /// Convert stereo to mono
-fn stereo_to_mono(samples: &[f32], channels: u16) -> Vec<f32> {
+pub(super) fn stereo_to_mono(samples: &[f32], channels: u16) -> Vec<f32> {
     if channels == 1 {
         return samples.to_vec();
     }
@@ -152,7 +176,7 @@ where
     let host = cpal::default_host();
     let device = match &config.device_name {
         Some(name) if !name.is_empty() => {
-            find_device_by_name(name)
+            find_device_by_name(name, config.loopback)
                 .or_else(|| {
                     tracing::warn!("Device '{}' not found, falling back to default", name);
                     host.default_input_device()
@@ -164,10 +188,14 @@ where
 
     tracing::info!("Audio device: {:?}", device.name());
 
-    // Use the device's default configuration
-    let supported_config = device
-        .default_input_config()
-        .map_err(|e| MicrophoneError::ConfigError(e.to_string()))?;
+    // Use the device's default configuration (output config for loopback on output devices)
+    let supported_config = match device.default_input_config() {
+        Ok(supported) => supported,
+        Err(_) if config.loopback => device
+            .default_output_config()
+            .map_err(|e| MicrophoneError::ConfigError(e.to_string()))?,
+        Err(e) => return Err(MicrophoneError::ConfigError(e.to_string())),
+    };
 
     let source_sample_rate = supported_config.sample_rate().0;
     let source_channels = supported_config.channels();
@@ -181,6 +209,8 @@ where
     );
 
     let stream_config = supported_config.into();
+    let mut resampler = (source_sample_rate != target_rate)
+        .then(|| LinearResampler::new(source_sample_rate, target_rate));
 
     let stream = device
         .build_input_stream(
@@ -189,8 +219,15 @@ where
                 // Convert to mono if needed
                 let mono = stereo_to_mono(data, source_channels);
 
-                // Resample to 16kHz
-                let resampled = resample(&mono, source_sample_rate, target_rate);
+                // Resample to 16kHz, continuously across callbacks
+                let resampled = match resampler {
+                    Some(ref mut resampler) => {
+                        let mut resampled = Vec::with_capacity(mono.len());
+                        resampler.process(&mono, &mut resampled);
+                        resampled
+                    }
+                    None => mono,
+                };
 
                 if !resampled.is_empty() {
                     sample_callback(resampled);
@@ -1,7 +1,26 @@
 //! Audio capture module
 //!
-//! Handles microphone capture in a dedicated thread.
+//! Handles microphone (and optional system audio) capture in dedicated threads,
+//! or the reception of a network stream instead of the microphone, and the
+//! decoding of audio files.
 
+mod accumulator;
+mod denoise;
+mod dual;
+mod encode;
+mod file;
 mod microphone;
+mod mute;
+mod network;
+mod stretch;
 
+pub use accumulator::AudioAccumulator;
+pub use denoise::{Denoiser, NoiseSuppression};
+pub use dual::{AudioSource, DualCapture};
+pub use encode::{encode, encode_upload, AudioFormat, UploadCodec, UPLOAD_SAMPLE_RATES};
+pub(crate) use encode::LinearResampler;
+pub use file::{is_audio_file, read_audio_file};
 pub use microphone::{AudioConfig, AudioHandle, MicrophoneError};
+pub use mute::{system_input_muted, MuteAwareness, MuteDetector};
+pub use network::{NetworkAudio, NetworkProtocol};
+pub use stretch::SpeedNormalization;
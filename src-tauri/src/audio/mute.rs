@@ -0,0 +1,131 @@
+//! Microphone mute awareness
+//!
+//! A microphone muted at the OS level (menu bar mute, headset or keyboard
+//! mute key) keeps delivering audio, made of exact zeros. The mute state is
+//! read from the OS where it is exposed (macOS input volume, PulseAudio or
+//! PipeWire source mute), and inferred from that digital silence elsewhere,
+//! so the dictation can be paused and the overlay tell the user.
+
+use super::encode::CAPTURE_SAMPLE_RATE;
+
+/// Settings of the mute awareness
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct MuteAwareness {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// How often the OS mute state is read during a dictation
+    #[serde(default = "default_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    /// Run of exact zeros read as a muted microphone
+    #[serde(default = "default_digital_silence_ms")]
+    pub digital_silence_ms: u64,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_poll_interval_ms() -> u64 {
+    1000
+}
+
+fn default_digital_silence_ms() -> u64 {
+    500
+}
+
+impl Default for MuteAwareness {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            poll_interval_ms: default_poll_interval_ms(),
+            digital_silence_ms: default_digital_silence_ms(),
+        }
+    }
+}
+
+/// Whether the default input device is muted, `None` when the OS does not tell
+#[cfg(target_os = "macos")]
+pub fn system_input_muted() -> Option<bool> {
+    // The menu bar and Control Center mute set the input volume to 0
+    let output = std::process::Command::new("osascript")
+        .arg("-e")
+        .arg("input volume of (get volume settings)")
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    // "missing value" for devices without an input volume
+    let volume = String::from_utf8_lossy(&output.stdout).trim().parse::<u32>().ok()?;
+    Some(volume == 0)
+}
+
+/// Whether the default PulseAudio/PipeWire source is muted
+#[cfg(target_os = "linux")]
+pub fn system_input_muted() -> Option<bool> {
+    let output = std::process::Command::new("pactl")
+        .args(["get-source-mute", "@DEFAULT_SOURCE@"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    // "Mute: yes" / "Mute: no"
+    let state = String::from_utf8_lossy(&output.stdout);
+    let value = state.trim().strip_prefix("Mute:")?.trim().to_string();
+    Some(value == "yes")
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+pub fn system_input_muted() -> Option<bool> {
+    None
+}
+
+/// Mute state of the microphone during a dictation
+pub struct MuteDetector {
+    /// Samples of exact zeros since the last non-zero one
+    zero_run: usize,
+    limit_samples: usize,
+    /// Last state reported by the OS
+    system_muted: bool,
+    muted: bool,
+}
+
+impl MuteDetector {
+    pub fn new(settings: &MuteAwareness) -> Self {
+        Self {
+            zero_run: 0,
+            limit_samples: (settings.digital_silence_ms as usize).saturating_mul(CAPTURE_SAMPLE_RATE as usize) / 1000,
+            system_muted: false,
+            muted: false,
+        }
+    }
+
+    /// Record the state read from the OS, returning the new mute state if it changed
+    pub fn set_system_muted(&mut self, muted: bool) -> Option<bool> {
+        self.system_muted = muted;
+        self.update()
+    }
+
+    /// Feed microphone samples, returning the new mute state if it changed
+    pub fn push(&mut self, samples: &[f32]) -> Option<bool> {
+        match samples.iter().rposition(|&sample| sample != 0.0) {
+            Some(last) => self.zero_run = samples.len() - 1 - last,
+            None => self.zero_run = self.zero_run.saturating_add(samples.len()),
+        }
+        self.update()
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.muted
+    }
+
+    fn update(&mut self) -> Option<bool> {
+        let muted = self.system_muted || (self.limit_samples > 0 && self.zero_run >= self.limit_samples);
+        if muted == self.muted {
+            return None;
+        }
+        self.muted = muted;
+        Some(muted)
+    }
+}
@@ -0,0 +1,335 @@
+//! Audio capture from a network stream
+//!
+//! Receives 16-bit PCM over UDP, either as RTP packets (L16 payload, network
+//! byte order) or as raw little-endian datagrams, e.g. from a conferencing
+//! bridge or `ffmpeg -f rtp rtp://host:port`. The samples are delivered
+//! through the same callback contract as the microphone capture.
+//!
+//! Only one sender is heard: the configured one, or else the first datagram
+//! received. An RTP stream is also locked onto its first SSRC, and packets
+//! arriving late or twice are dropped.
+
+use super::encode::LinearResampler;
+use super::microphone::{stereo_to_mono, AudioCommand, AudioHandle, MicrophoneError};
+use std::net::{IpAddr, SocketAddr, UdpSocket};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Framing of the received datagrams
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NetworkProtocol {
+    /// RTP with an L16 payload (big-endian samples, RFC 3551)
+    Rtp,
+    /// Bare little-endian 16-bit samples
+    RawPcm,
+}
+
+/// Network stream used instead of the local microphone
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct NetworkAudio {
+    /// Local address to listen on, e.g. "0.0.0.0:5004" (empty = microphone)
+    #[serde(default)]
+    pub address: String,
+    #[serde(default = "default_protocol")]
+    pub protocol: NetworkProtocol,
+    #[serde(default = "default_sample_rate")]
+    pub sample_rate: u32,
+    #[serde(default = "default_channels")]
+    pub channels: u16,
+    /// Only sender accepted, as "ip" or "ip:port" (empty = first sender heard)
+    #[serde(default)]
+    pub sender: String,
+}
+
+fn default_protocol() -> NetworkProtocol {
+    NetworkProtocol::Rtp
+}
+
+fn default_sample_rate() -> u32 {
+    16000
+}
+
+fn default_channels() -> u16 {
+    1
+}
+
+impl Default for NetworkAudio {
+    fn default() -> Self {
+        Self {
+            address: String::new(),
+            protocol: default_protocol(),
+            sample_rate: default_sample_rate(),
+            channels: default_channels(),
+            sender: String::new(),
+        }
+    }
+}
+
+impl NetworkAudio {
+    pub fn is_enabled(&self) -> bool {
+        !self.address.trim().is_empty()
+    }
+
+    pub fn socket_addr(&self) -> Option<SocketAddr> {
+        self.address.trim().parse().ok()
+    }
+
+    /// Configured sender, with its port when given
+    pub fn sender_addr(&self) -> Option<(IpAddr, Option<u16>)> {
+        let sender = self.sender.trim();
+        match sender.parse::<SocketAddr>() {
+            Ok(addr) => Some((addr.ip(), Some(addr.port()))),
+            Err(_) => sender.parse().ok().map(|ip| (ip, None)),
+        }
+    }
+}
+
+/// Whether a datagram from `peer` comes from the expected sender
+fn from_sender(peer: SocketAddr, (ip, port): (IpAddr, Option<u16>)) -> bool {
+    peer.ip() == ip && port.is_none_or(|port| port == peer.port())
+}
+
+/// Largest datagram accepted (jumbo frames excluded)
+const MAX_DATAGRAM: usize = 65536;
+
+/// Fields of an RTP packet used to play it back
+struct RtpPacket<'a> {
+    sequence: u16,
+    ssrc: u32,
+    /// Without header, CSRCs, extension and padding
+    payload: &'a [u8],
+}
+
+fn parse_rtp(packet: &[u8]) -> Option<RtpPacket<'_>> {
+    if packet.len() < 12 || packet[0] >> 6 != 2 {
+        return None;
+    }
+    let csrc_count = (packet[0] & 0x0f) as usize;
+    let mut start = 12 + 4 * csrc_count;
+    if packet[0] & 0x10 != 0 {
+        let header = packet.get(start..start + 4)?;
+        let words = u16::from_be_bytes([header[2], header[3]]) as usize;
+        start += 4 + 4 * words;
+    }
+    let mut end = packet.len();
+    if packet[0] & 0x20 != 0 {
+        end = end.checked_sub(*packet.last()? as usize)?;
+    }
+    Some(RtpPacket {
+        sequence: u16::from_be_bytes([packet[2], packet[3]]),
+        ssrc: u32::from_be_bytes([packet[8], packet[9], packet[10], packet[11]]),
+        payload: packet.get(start..end)?,
+    })
+}
+
+/// Source and position of the RTP stream being played
+struct RtpStream {
+    ssrc: u32,
+    sequence: u16,
+}
+
+impl RtpStream {
+    /// Whether the packet continues the stream, moving the stream past it
+    ///
+    /// Packets of another source are dropped, and so are duplicates and
+    /// packets older than the last one played: their place has passed.
+    fn accept(&mut self, packet: &RtpPacket) -> bool {
+        if packet.ssrc != self.ssrc {
+            return false;
+        }
+        // Sequence numbers wrap, half the range ahead counts as newer
+        let ahead = packet.sequence.wrapping_sub(self.sequence);
+        if ahead == 0 || ahead >= 0x8000 {
+            return false;
+        }
+        self.sequence = packet.sequence;
+        true
+    }
+}
+
+fn decode_samples(bytes: &[u8], big_endian: bool) -> Vec<f32> {
+    bytes
+        .chunks_exact(2)
+        .map(|pair| {
+            let sample = if big_endian {
+                i16::from_be_bytes([pair[0], pair[1]])
+            } else {
+                i16::from_le_bytes([pair[0], pair[1]])
+            };
+            sample as f32 / i16::MAX as f32
+        })
+        .collect()
+}
+
+impl AudioHandle {
+    /// Start receiving a network stream in a dedicated thread
+    ///
+    /// The socket is bound before returning, so a busy port fails here.
+    pub fn start_network<F>(
+        source: &NetworkAudio,
+        target_sample_rate: u32,
+        sample_callback: F,
+    ) -> Result<Self, MicrophoneError>
+    where
+        F: Fn(Vec<f32>) + Send + 'static,
+    {
+        let address = source
+            .socket_addr()
+            .ok_or_else(|| MicrophoneError::ConfigError(format!("invalid address '{}'", source.address)))?;
+        let socket = UdpSocket::bind(address).map_err(|e| MicrophoneError::StreamError(e.to_string()))?;
+        // Lets the thread check for the stop command while no audio arrives
+        socket
+            .set_read_timeout(Some(Duration::from_millis(100)))
+            .map_err(|e| MicrophoneError::StreamError(e.to_string()))?;
+
+        tracing::info!(
+            "Network audio on udp://{} ({:?}, {}Hz {}ch -> {}Hz mono)",
+            address,
+            source.protocol,
+            source.sample_rate,
+            source.channels,
+            target_sample_rate
+        );
+
+        let source = source.clone();
+        Ok(Self::spawn(move |command_rx| {
+            run_network_capture(socket, source, target_sample_rate, sample_callback, command_rx)
+        }))
+    }
+}
+
+/// Receive datagrams until stopped (in a dedicated thread)
+fn run_network_capture<F>(
+    socket: UdpSocket,
+    source: NetworkAudio,
+    target_rate: u32,
+    sample_callback: F,
+    command_rx: mpsc::Receiver<AudioCommand>,
+) where
+    F: Fn(Vec<f32>) + Send + 'static,
+{
+    let mut resampler = (source.sample_rate != target_rate)
+        .then(|| LinearResampler::new(source.sample_rate, target_rate));
+    let mut buffer = vec![0u8; MAX_DATAGRAM];
+    let mut sender = source.sender_addr();
+    let mut stream: Option<RtpStream> = None;
+
+    loop {
+        match command_rx.try_recv() {
+            Ok(AudioCommand::Stop) | Err(mpsc::TryRecvError::Disconnected) => break,
+            Err(mpsc::TryRecvError::Empty) => {}
+        }
+
+        let (len, peer) = match socket.recv_from(&mut buffer) {
+            Ok(received) => received,
+            Err(e) if matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) => continue,
+            Err(e) => {
+                tracing::error!("Network audio error: {}", e);
+                break;
+            }
+        };
+
+        match sender {
+            Some(expected) if !from_sender(peer, expected) => {
+                tracing::debug!("Ignoring a datagram from {}, not the audio sender", peer);
+                continue;
+            }
+            Some(_) => {}
+            None => {
+                tracing::info!("Network audio locked onto {}", peer);
+                sender = Some((peer.ip(), Some(peer.port())));
+            }
+        }
+
+        let samples = match source.protocol {
+            NetworkProtocol::Rtp => match parse_rtp(&buffer[..len]) {
+                Some(packet) => {
+                    let stream = stream.get_or_insert(RtpStream {
+                        ssrc: packet.ssrc,
+                        sequence: packet.sequence.wrapping_sub(1),
+                    });
+                    if !stream.accept(&packet) {
+                        tracing::debug!("Dropping RTP packet {} of SSRC {:08x}", packet.sequence, packet.ssrc);
+                        continue;
+                    }
+                    decode_samples(packet.payload, true)
+                }
+                None => {
+                    tracing::debug!("Ignoring a {} byte datagram that is not RTP", len);
+                    continue;
+                }
+            },
+            NetworkProtocol::RawPcm => decode_samples(&buffer[..len], false),
+        };
+
+        let mono = stereo_to_mono(&samples, source.channels);
+        let resampled = match resampler {
+            Some(ref mut resampler) => {
+                let mut resampled = Vec::with_capacity(mono.len());
+                resampler.process(&mono, &mut resampled);
+                resampled
+            }
+            None => mono,
+        };
+        if !resampled.is_empty() {
+            sample_callback(resampled);
+        }
+    }
+
+    tracing::info!("Network audio stopped");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packet(sequence: u16, ssrc: u32) -> RtpPacket<'static> {
+        RtpPacket {
+            sequence,
+            ssrc,
+            payload: &[],
+        }
+    }
+
+    #[test]
+    fn rtp_stream_drops_late_duplicate_and_foreign_packets() {
+        let mut stream = RtpStream { ssrc: 7, sequence: 10 };
+        assert!(stream.accept(&packet(11, 7)));
+        assert!(!stream.accept(&packet(11, 7)));
+        assert!(!stream.accept(&packet(9, 7)));
+        assert!(!stream.accept(&packet(12, 8)));
+        // A lost packet leaves a gap, the next one still plays
+        assert!(stream.accept(&packet(13, 7)));
+    }
+
+    #[test]
+    fn rtp_stream_follows_sequence_wrap() {
+        let mut stream = RtpStream {
+            ssrc: 1,
+            sequence: u16::MAX,
+        };
+        assert!(stream.accept(&packet(0, 1)));
+        assert!(!stream.accept(&packet(u16::MAX, 1)));
+    }
+
+    #[test]
+    fn sender_filter_checks_the_port_only_when_given() {
+        let peer: SocketAddr = "192.168.1.20:5004".parse().unwrap();
+        let ip: IpAddr = "192.168.1.20".parse().unwrap();
+        assert!(from_sender(peer, (ip, None)));
+        assert!(from_sender(peer, (ip, Some(5004))));
+        assert!(!from_sender(peer, (ip, Some(5006))));
+        assert!(!from_sender(peer, ("192.168.1.21".parse().unwrap(), None)));
+    }
+
+    #[test]
+    fn parses_rtp_ids_and_payload() {
+        let mut datagram = vec![0x80, 96, 0x01, 0x02, 0, 0, 0, 0, 0xde, 0xad, 0xbe, 0xef];
+        datagram.extend_from_slice(&[0x12, 0x34]);
+        let packet = parse_rtp(&datagram).unwrap();
+        assert_eq!(packet.sequence, 0x0102);
+        assert_eq!(packet.ssrc, 0xdeadbeef);
+        assert_eq!(packet.payload, &[0x12, 0x34]);
+    }
+}
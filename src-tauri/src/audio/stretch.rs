@@ -0,0 +1,198 @@
+//! Speed normalization for fast talkers
+//!
+//! Transcription models drop words on very fast speech (200+ wpm). The
+//! speaking rate is estimated from the syllable nuclei of the energy
+//! envelope, and fast dictations are slowed down with WSOLA time-stretching
+//! (pitch is preserved) before the upload.
+
+use super::AudioAccumulator;
+
+/// Sample rate of the processed audio
+const SAMPLE_RATE: usize = 16000;
+
+/// Envelope frame: 10ms
+const ENVELOPE_FRAME: usize = SAMPLE_RATE / 100;
+
+/// Minimum spacing between two syllable nuclei: 100ms
+const MIN_NUCLEUS_SPACING: usize = 10;
+
+/// Syllable rate the auto mode slows speech down to
+const TARGET_SYLLABLE_RATE: f32 = 5.0;
+
+/// Upper bound of the auto factor, to keep the stretch mild
+const MAX_AUTO_FACTOR: f32 = 1.25;
+
+/// Longer dictations are sent as is (the stretch works in memory)
+const MAX_STRETCH_SAMPLES: usize = SAMPLE_RATE * 60 * 10;
+
+/// WSOLA window (40ms), output hop and alignment search radius
+const WSOLA_WINDOW: usize = 640;
+const WSOLA_HOP: usize = WSOLA_WINDOW / 2;
+const WSOLA_TOLERANCE: usize = 160;
+
+/// How dictation speed is normalized before upload
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum SpeedNormalization {
+    /// Audio sent as captured
+    #[default]
+    Off,
+    /// Slow down only speech faster than `threshold` syllables per second
+    Auto { threshold: f32 },
+    /// Always stretch by `factor` (1.1 = 10% slower)
+    Fixed { factor: f32 },
+}
+
+impl SpeedNormalization {
+    /// Stretch factor to apply to `samples` (1.0 = unchanged)
+    fn factor_for(&self, samples: &[f32]) -> f32 {
+        match *self {
+            SpeedNormalization::Off => 1.0,
+            SpeedNormalization::Fixed { factor } => factor,
+            SpeedNormalization::Auto { threshold } => {
+                let rate = syllable_rate(samples);
+                tracing::debug!("Estimated speaking rate: {:.1} syllables/s", rate);
+                if rate > threshold {
+                    (rate / TARGET_SYLLABLE_RATE).clamp(1.0, MAX_AUTO_FACTOR)
+                } else {
+                    1.0
+                }
+            }
+        }
+    }
+
+    /// Return the audio slowed down if needed
+    pub fn apply(&self, mut audio: AudioAccumulator) -> AudioAccumulator {
+        if *self == SpeedNormalization::Off || audio.len() > MAX_STRETCH_SAMPLES {
+            return audio;
+        }
+
+        let mut samples = Vec::with_capacity(audio.len());
+        if let Err(e) = audio.try_for_each_chunk(|chunk| {
+            samples.extend_from_slice(chunk);
+            Ok::<(), std::io::Error>(())
+        }) {
+            tracing::warn!("Cannot read audio for speed normalization: {}", e);
+            return audio;
+        }
+
+        let factor = self.factor_for(&samples);
+        if (factor - 1.0).abs() < 0.01 {
+            return audio;
+        }
+        tracing::info!("Fast speech, slowing audio down by {:.2}x", factor);
+
+        audio.clear();
+        audio.extend_from_slice(&time_stretch(&samples, factor));
+        audio
+    }
+}
+
+/// Estimate the speaking rate in syllables per second of voiced audio
+fn syllable_rate(samples: &[f32]) -> f32 {
+    let envelope: Vec<f32> = samples
+        .chunks_exact(ENVELOPE_FRAME)
+        .map(|frame| (frame.iter().map(|s| s * s).sum::<f32>() / ENVELOPE_FRAME as f32).sqrt())
+        .collect();
+    if envelope.len() < 3 {
+        return 0.0;
+    }
+
+    // Smooth over 50ms so each syllable gives a single peak
+    let smoothed: Vec<f32> = (0..envelope.len())
+        .map(|i| {
+            let window = &envelope[i.saturating_sub(2)..(i + 3).min(envelope.len())];
+            window.iter().sum::<f32>() / window.len() as f32
+        })
+        .collect();
+
+    let peak_level = smoothed.iter().copied().fold(0.0f32, f32::max);
+    let voiced_level = peak_level * 0.1;
+    let nucleus_level = peak_level * 0.25;
+
+    let voiced_frames = smoothed.iter().filter(|&&e| e > voiced_level).count();
+    if voiced_frames == 0 {
+        return 0.0;
+    }
+
+    let mut nuclei = 0;
+    let mut last_nucleus: Option<usize> = None;
+    for i in 1..smoothed.len() - 1 {
+        let is_peak = smoothed[i] > nucleus_level
+            && smoothed[i] >= smoothed[i - 1]
+            && smoothed[i] > smoothed[i + 1];
+        if is_peak && last_nucleus.map_or(true, |last| i - last >= MIN_NUCLEUS_SPACING) {
+            nuclei += 1;
+            last_nucleus = Some(i);
+        }
+    }
+
+    nuclei as f32 / (voiced_frames as f32 / 100.0)
+}
+
+/// WSOLA time-stretch: `factor` > 1 makes the audio longer (slower)
+fn time_stretch(samples: &[f32], factor: f32) -> Vec<f32> {
+    if samples.len() < WSOLA_WINDOW + 2 * WSOLA_TOLERANCE {
+        return samples.to_vec();
+    }
+
+    let window: Vec<f32> = (0..WSOLA_WINDOW)
+        .map(|i| 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / WSOLA_WINDOW as f32).cos())
+        .collect();
+    let input_hop = WSOLA_HOP as f32 / factor;
+    let output_len = (samples.len() as f32 * factor) as usize;
+
+    let mut output = vec![0.0f32; output_len + WSOLA_WINDOW];
+    let mut weights = vec![0.0f32; output_len + WSOLA_WINDOW];
+
+    // Segment following the previously copied one, the natural continuation
+    let mut previous_start = 0usize;
+    let mut written = 0usize;
+    let mut frame = 0usize;
+    loop {
+        let out_start = frame * WSOLA_HOP;
+        let nominal = (frame as f32 * input_hop) as usize;
+        if out_start >= output_len || nominal + WSOLA_WINDOW + WSOLA_TOLERANCE > samples.len() {
+            break;
+        }
+
+        // Pick the offset around `nominal` most similar to the continuation
+        let start = if frame == 0 {
+            0
+        } else {
+            let continuation = previous_start + WSOLA_HOP;
+            let from = nominal.saturating_sub(WSOLA_TOLERANCE);
+            let to = nominal + WSOLA_TOLERANCE;
+            (from..=to)
+                .filter(|&candidate| {
+                    candidate + WSOLA_WINDOW <= samples.len() && continuation + WSOLA_WINDOW <= samples.len()
+                })
+                .max_by(|&a, &b| {
+                    let score = |candidate: usize| -> f32 {
+                        (0..WSOLA_WINDOW)
+                            .step_by(4)
+                            .map(|i| samples[candidate + i] * samples[continuation + i])
+                            .sum()
+                    };
+                    score(a).total_cmp(&score(b))
+                })
+                .unwrap_or(nominal)
+        };
+
+        for i in 0..WSOLA_WINDOW {
+            output[out_start + i] += samples[start + i] * window[i];
+            weights[out_start + i] += window[i];
+        }
+        previous_start = start;
+        written = out_start + WSOLA_WINDOW;
+        frame += 1;
+    }
+
+    output.truncate(written.min(output_len));
+    for (sample, weight) in output.iter_mut().zip(&weights) {
+        if *weight > 1e-3 {
+            *sample /= weight;
+        }
+    }
+    output
+}
@@ -0,0 +1,64 @@
+//! Calendar settings and the meeting read from it
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How often the calendar is read while enabled
+pub const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Meeting detection from the local calendar
+///
+/// Off by default: the first read asks for the permission to read the
+/// calendars.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CalendarAwareness {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Record in meeting mode on its own during a meeting, instead of only
+    /// suggesting it
+    #[serde(default)]
+    pub auto_meeting_mode: bool,
+    /// System audio device captured in meeting mode, when
+    /// `system_audio_device` is not set
+    #[serde(default)]
+    pub meeting_audio_device: String,
+}
+
+/// Timed (not all-day) calendar event in progress
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct CalendarEvent {
+    pub title: String,
+    /// End of the event (milliseconds since Unix epoch)
+    pub ends_at: u64,
+}
+
+impl CalendarEvent {
+    /// Event titled `title`, ending in `minutes_left` minutes
+    pub fn ending_in(title: &str, minutes_left: u64) -> Self {
+        Self {
+            title: title.trim().to_string(),
+            ends_at: now_millis() + minutes_left * 60_000,
+        }
+    }
+
+    /// Not over yet (the calendar is only read once a minute)
+    pub fn is_ongoing(&self) -> bool {
+        now_millis() < self.ends_at
+    }
+
+    /// Title usable in a file name
+    pub fn file_name(&self) -> String {
+        let name: String = self
+            .title
+            .chars()
+            .map(|c| if c.is_alphanumeric() || matches!(c, ' ' | '-' | '_') { c } else { '-' })
+            .collect();
+        name.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
@@ -0,0 +1,11 @@
+//! Calendar awareness module
+//!
+//! Reads the meeting in progress from the local calendar (Calendar.app on
+//! macOS, Outlook on Windows), so a dictation started during a meeting can
+//! switch to meeting mode and name its transcript after the meeting.
+
+mod event;
+mod reader;
+
+pub use event::{CalendarAwareness, CalendarEvent, POLL_INTERVAL};
+pub use reader::current_meeting;
@@ -0,0 +1,160 @@
+//! Meeting in progress, read from the calendar store of the platform
+//!
+//! EventKit on macOS, Outlook on Windows. Both expand recurring events, so
+//! a weekly meeting is seen on each of its occurrences.
+
+use super::CalendarEvent;
+
+/// Events in progress in EventKit, one "title<TAB>minutes left" per line
+///
+/// The first read asks for the permission to read the calendars.
+#[cfg(target_os = "macos")]
+const CALENDAR_SCRIPT: &str = r#"
+use AppleScript version "2.5"
+use framework "Foundation"
+use framework "EventKit"
+use scripting additions
+
+set store to current application's EKEventStore's alloc()'s init()
+-- 0 = events; 0 = not asked yet, 3 = full access
+if (current application's EKEventStore's authorizationStatusForEntityType:0) as integer is 0 then
+    store's requestAccessToEntityType:0 completion:(missing value)
+    delay 1
+end if
+if (current application's EKEventStore's authorizationStatusForEntityType:0) as integer is not 3 then
+    error "Calendar access not granted"
+end if
+
+set now to current application's NSDate's |date|()
+set predicate to store's predicateForEventsWithStartDate:now endDate:(now's dateByAddingTimeInterval:1) calendars:(missing value)
+set output to ""
+repeat with ev in ((store's eventsMatchingPredicate:predicate) as list)
+    if not (ev's isAllDay() as boolean) then
+        set minutesLeft to ((ev's endDate()'s timeIntervalSinceDate:now) as real) div 60
+        set output to output & ((ev's title()) as text) & tab & minutesLeft & linefeed
+    end if
+end repeat
+return output
+"#;
+
+/// Meeting in progress in the local calendar, the first one if several overlap
+#[cfg(target_os = "macos")]
+pub fn current_meeting() -> Result<Option<CalendarEvent>, String> {
+    let output = std::process::Command::new("osascript")
+        .arg("-e")
+        .arg(CALENDAR_SCRIPT)
+        .output()
+        .map_err(|e| format!("osascript launch error: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("Calendar access error: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(parse_events(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Appointments of the default Outlook calendar over the next hour, one
+/// "title<TAB>start<TAB>end" per line (milliseconds since Unix epoch)
+#[cfg(target_os = "windows")]
+const OUTLOOK_SCRIPT: &str = r#"
+[Console]::OutputEncoding = [Text.Encoding]::UTF8
+$items = (New-Object -ComObject Outlook.Application).GetNamespace('MAPI').GetDefaultFolder(9).Items
+$items.IncludeRecurrences = $true
+$items.Sort('[Start]')
+$now = Get-Date
+$filter = "[Start] < '{0}' AND [End] > '{1}'" -f $now.AddHours(1).ToString('g'), $now.ToString('g')
+foreach ($item in $items.Restrict($filter)) {
+    if (-not $item.AllDayEvent) {
+        "{0}`t{1}`t{2}" -f $item.Subject, ([DateTimeOffset]$item.Start).ToUnixTimeMilliseconds(), ([DateTimeOffset]$item.End).ToUnixTimeMilliseconds()
+    }
+}
+"#;
+
+/// Age of the Outlook read before it is done again
+///
+/// Starting PowerShell and Outlook's COM server is slow and heavy, so the
+/// appointments of the next hour are kept and looked up on each poll. One
+/// added meanwhile is seen on the next read. Must stay under the hour read.
+#[cfg(target_os = "windows")]
+const OUTLOOK_REFRESH: std::time::Duration = std::time::Duration::from_secs(15 * 60);
+
+/// Appointment read from Outlook: title, start and end in milliseconds
+#[cfg(target_os = "windows")]
+type Appointment = (String, u64, u64);
+
+/// Last Outlook read and its time
+#[cfg(target_os = "windows")]
+static OUTLOOK_CACHE: std::sync::Mutex<Option<(std::time::Instant, Vec<Appointment>)>> = std::sync::Mutex::new(None);
+
+/// Meeting in progress in the local calendar, the first one if several overlap
+#[cfg(target_os = "windows")]
+pub fn current_meeting() -> Result<Option<CalendarEvent>, String> {
+    let mut cache = OUTLOOK_CACHE.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let fresh = cache
+        .as_ref()
+        .is_some_and(|(read_at, _)| read_at.elapsed() < OUTLOOK_REFRESH);
+    if !fresh {
+        // A failing Outlook is not relaunched on every poll either
+        let read = read_outlook();
+        *cache = Some((std::time::Instant::now(), read.clone().unwrap_or_default()));
+        read?;
+    }
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    let appointments = cache.as_ref().map(|(_, appointments)| appointments.as_slice()).unwrap_or_default();
+    Ok(appointments
+        .iter()
+        .find(|(_, start, end)| *start <= now && now < *end)
+        .map(|(title, _, end)| CalendarEvent {
+            title: title.clone(),
+            ends_at: *end,
+        }))
+}
+
+#[cfg(target_os = "windows")]
+fn read_outlook() -> Result<Vec<Appointment>, String> {
+    use std::os::windows::process::CommandExt;
+    // No console window flashing on each read
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    let output = std::process::Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", OUTLOOK_SCRIPT])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map_err(|e| format!("PowerShell launch error: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("Outlook access error: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(parse_appointments(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Valid "title<TAB>start<TAB>end" lines
+#[cfg(target_os = "windows")]
+fn parse_appointments(output: &str) -> Vec<Appointment> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.trim_end_matches('\r').rsplitn(3, '\t');
+            let end = fields.next()?.trim().parse::<u64>().ok()?;
+            let start = fields.next()?.trim().parse::<u64>().ok()?;
+            let title = fields.next()?.trim();
+            (!title.is_empty()).then(|| (title.to_string(), start, end))
+        })
+        .collect()
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn current_meeting() -> Result<Option<CalendarEvent>, String> {
+    Err("Calendar reading is only supported on macOS and Windows".to_string())
+}
+
+/// First valid "title<TAB>minutes left" line
+#[cfg(target_os = "macos")]
+fn parse_events(output: &str) -> Option<CalendarEvent> {
+    output.lines().find_map(|line| {
+        let (title, minutes) = line.trim_end_matches('\r').rsplit_once('\t')?;
+        let minutes = minutes.trim().parse::<u64>().ok()?;
+        (!title.trim().is_empty()).then(|| CalendarEvent::ending_in(title, minutes))
+    })
+}
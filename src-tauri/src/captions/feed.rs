@@ -0,0 +1,62 @@
+//! Throttled caption updates
+//!
+//! Streaming engines revise the partial transcript many times per second.
+//! Large text reflowing at that rate is hard to read, so partials are sent
+//! at most every `update_interval_ms`; finals always go through.
+
+use super::style::Captions;
+use super::window::CAPTIONS_LABEL;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+/// Characters of transcript sent to the window, enough for a few lines
+const MAX_CAPTION_CHARS: usize = 600;
+
+/// Publisher of the live transcript to the captions window, one per dictation
+pub struct CaptionFeed {
+    interval: Duration,
+    last_send: Option<Instant>,
+    last_text: String,
+}
+
+impl CaptionFeed {
+    pub fn new(config: &Captions) -> Self {
+        Self {
+            interval: Duration::from_millis(config.update_interval_ms),
+            last_send: None,
+            last_text: String::new(),
+        }
+    }
+
+    /// Show `text` if it changed and an update is due (`force` for finals)
+    pub fn push(&mut self, app: &AppHandle, text: &str, force: bool) {
+        if text == self.last_text {
+            return;
+        }
+        if !force && self.last_send.is_some_and(|last| last.elapsed() < self.interval) {
+            return;
+        }
+        let Some(window) = app.get_webview_window(CAPTIONS_LABEL) else {
+            return;
+        };
+
+        let js = format!(
+            "window.__captionsSetText && window.__captionsSetText({})",
+            serde_json::Value::from(tail(text, MAX_CAPTION_CHARS))
+        );
+        let _ = window.eval(&js);
+        self.last_text = text.to_string();
+        self.last_send = Some(Instant::now());
+    }
+}
+
+/// Last `max_chars` characters of `text`, starting on a word
+fn tail(text: &str, max_chars: usize) -> &str {
+    let count = text.chars().count();
+    if count <= max_chars {
+        return text;
+    }
+    let start = text.char_indices().nth(count - max_chars).map_or(0, |(i, _)| i);
+    let rest = &text[start..];
+    rest.find(char::is_whitespace).map_or(rest, |i| rest[i..].trim_start())
+}
@@ -0,0 +1,13 @@
+//! Live captions window
+//!
+//! Optional borderless, always-on-top window showing the live transcript in
+//! large text, as personal captions during calls. It has its own label and
+//! its own update throttling, separate from the overlay.
+
+mod feed;
+mod style;
+mod window;
+
+pub use feed::CaptionFeed;
+pub use style::Captions;
+pub use window::{hide, show};
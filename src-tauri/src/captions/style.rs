@@ -0,0 +1,63 @@
+//! Captions settings
+
+/// Settings of the captions window
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Captions {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Text size, in logical pixels
+    #[serde(default = "default_font_size")]
+    pub font_size: u32,
+    /// Lines of text kept on screen
+    #[serde(default = "default_max_lines")]
+    pub max_lines: u32,
+    /// Text color (CSS color)
+    #[serde(default = "default_text_color")]
+    pub text_color: String,
+    /// Background opacity, from 0 (transparent) to 1
+    #[serde(default = "default_background_opacity")]
+    pub background_opacity: f32,
+    /// Minimum delay between two caption updates, finals excepted
+    #[serde(default = "default_update_interval_ms")]
+    pub update_interval_ms: u64,
+}
+
+fn default_font_size() -> u32 {
+    32
+}
+
+fn default_max_lines() -> u32 {
+    2
+}
+
+fn default_text_color() -> String {
+    "#ffffff".to_string()
+}
+
+fn default_background_opacity() -> f32 {
+    0.7
+}
+
+fn default_update_interval_ms() -> u64 {
+    150
+}
+
+impl Default for Captions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            font_size: default_font_size(),
+            max_lines: default_max_lines(),
+            text_color: default_text_color(),
+            background_opacity: default_background_opacity(),
+            update_interval_ms: default_update_interval_ms(),
+        }
+    }
+}
+
+impl Captions {
+    /// Window height fitting `max_lines` lines of text, padding included
+    pub(super) fn window_height(&self) -> f64 {
+        f64::from(self.font_size) * 1.3 * f64::from(self.max_lines.max(1)) + 32.0
+    }
+}
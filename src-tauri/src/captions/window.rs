@@ -0,0 +1,84 @@
+//! Captions window management
+//!
+//! Created on first use rather than declared in tauri.conf.json, so users
+//! who never turn captions on do not pay for a third webview.
+
+use super::style::Captions;
+use tauri::{AppHandle, Manager, WebviewWindow};
+
+/// Label of the captions window
+pub(super) const CAPTIONS_LABEL: &str = "captions";
+
+/// Window width (logical pixels)
+const CAPTIONS_WIDTH: f64 = 960.0;
+
+/// Gap between the window and the bottom of the screen (logical pixels)
+const BOTTOM_MARGIN: f64 = 96.0;
+
+/// Get or create the captions window
+fn captions_window(app: &AppHandle, config: &Captions) -> Option<WebviewWindow> {
+    if let Some(window) = app.get_webview_window(CAPTIONS_LABEL) {
+        return Some(window);
+    }
+
+    tauri::WebviewWindowBuilder::new(app, CAPTIONS_LABEL, tauri::WebviewUrl::App("index.html".into()))
+        .title("Dictea")
+        .inner_size(CAPTIONS_WIDTH, config.window_height())
+        .resizable(false)
+        .decorations(false)
+        .transparent(true)
+        .shadow(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .visible(false)
+        .focused(false)
+        .build()
+        .map_err(|e| tracing::error!("Captions window creation error: {}", e))
+        .ok()
+}
+
+/// Place the window horizontally centered, near the bottom of its monitor
+fn place(window: &WebviewWindow, height: f64) {
+    let monitor = window
+        .cursor_position()
+        .ok()
+        .and_then(|cursor| window.monitor_from_point(cursor.x, cursor.y).ok().flatten())
+        .or_else(|| window.primary_monitor().ok().flatten());
+    let Some(monitor) = monitor else {
+        return;
+    };
+
+    let scale = monitor.scale_factor();
+    let x = monitor.position().x as f64 / scale;
+    let y = monitor.position().y as f64 / scale;
+    let width = monitor.size().width as f64 / scale;
+    let screen_height = monitor.size().height as f64 / scale;
+    let _ = window.set_position(tauri::LogicalPosition::new(
+        (x + (width - CAPTIONS_WIDTH) / 2.0) as i32,
+        (y + screen_height - height - BOTTOM_MARGIN) as i32,
+    ));
+}
+
+/// Show the captions window, cleared and styled after `config`
+pub fn show(app: &AppHandle, config: &Captions) {
+    let Some(window) = captions_window(app, config) else {
+        return;
+    };
+    let height = config.window_height();
+    let _ = window.set_size(tauri::LogicalSize::new(CAPTIONS_WIDTH, height));
+    place(&window, height);
+
+    let style = serde_json::to_string(config).unwrap_or_else(|_| "null".to_string());
+    let _ = window.eval(&format!(
+        "window.__captionsSetStyle && window.__captionsSetStyle({}); window.__captionsSetText && window.__captionsSetText(\"\")",
+        style
+    ));
+    let _ = window.show();
+}
+
+/// Hide the captions window, if it was ever opened
+pub fn hide(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(CAPTIONS_LABEL) {
+        let _ = window.hide();
+    }
+}
@@ -0,0 +1,15 @@
+//! Configuration module
+//!
+//! Loading, saving and validation of the user settings, and the
+//! organization policy locking some of them, and the shared profiles
+//! pulled from a team URL.
+
+mod policy;
+mod settings;
+mod sync;
+mod validation;
+
+pub use policy::Policy;
+pub use settings::{AppConfig, OverlayMonitor, OverlayPosition, PasteMethod, ShortcutMode, SpokenMarker, VadBackend};
+pub use sync::ProfileSync;
+pub use validation::FieldError;
@@ -0,0 +1,158 @@
+//! Organization-managed policy
+//!
+//! An administrator can provision a read-only JSON file of `AppConfig`
+//! fields, in a system-wide location the user cannot write to. Each field
+//! it contains is locked to its value: it overrides the user config when
+//! loading, and saving a different value is rejected.
+//!
+//! A policy file that exists but cannot be read fails closed: every setting
+//! is then locked, until the administrator fixes it.
+
+use super::{AppConfig, FieldError};
+use crate::i18n::Message;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// Overrides the policy location in development builds, a release build
+/// must not let the user point it to a file of their own
+#[cfg(any(test, debug_assertions))]
+const POLICY_ENV: &str = "DICTEA_POLICY_FILE";
+
+/// Locked settings provisioned by the organization
+#[derive(Debug, Clone, Default)]
+pub struct Policy {
+    path: PathBuf,
+    locked: serde_json::Map<String, serde_json::Value>,
+    /// Why the file could not be used, every setting is locked then
+    unreadable: Option<String>,
+}
+
+static POLICY: OnceLock<Option<Policy>> = OnceLock::new();
+
+/// System-wide policy file for this platform
+fn policy_path() -> PathBuf {
+    #[cfg(any(test, debug_assertions))]
+    if let Some(path) = std::env::var_os(POLICY_ENV) {
+        return PathBuf::from(path);
+    }
+    if cfg!(target_os = "windows") {
+        let program_data = std::env::var_os("ProgramData").unwrap_or_else(|| "C:\\ProgramData".into());
+        PathBuf::from(program_data).join("Dictea").join("policy.json")
+    } else if cfg!(target_os = "macos") {
+        PathBuf::from("/Library/Application Support/Dictea/policy.json")
+    } else {
+        PathBuf::from("/etc/dictea/policy.json")
+    }
+}
+
+impl Policy {
+    /// Policy locking every setting, for a file that cannot be used
+    fn unreadable(path: PathBuf, details: String) -> Self {
+        tracing::error!("Policy {} unusable, every setting is locked: {}", path.display(), details);
+        Self {
+            path,
+            locked: serde_json::Map::new(),
+            unreadable: Some(details),
+        }
+    }
+
+    /// Read and check the policy file, `None` if there is none
+    fn load() -> Option<Self> {
+        let path = policy_path();
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(e) => return Some(Self::unreadable(path, e.to_string())),
+        };
+        let locked = match serde_json::from_str::<serde_json::Value>(&content) {
+            Ok(serde_json::Value::Object(fields)) => fields,
+            Ok(_) => return Some(Self::unreadable(path, "not a JSON object".to_string())),
+            Err(e) => return Some(Self::unreadable(path, e.to_string())),
+        };
+
+        // Keep only the fields that make a valid config on their own, in
+        // their serialized form (nested defaults filled) so they compare equal
+        let defaults = serde_json::to_value(AppConfig::default()).unwrap_or_default();
+        let locked: serde_json::Map<_, _> = locked
+            .into_iter()
+            .filter_map(|(key, value)| {
+                if defaults.get(&key).is_none() {
+                    tracing::warn!("Policy field \"{}\" is not a setting, ignored", key);
+                    return None;
+                }
+                let mut single = defaults.clone();
+                single[key.as_str()] = value;
+                let normalized = serde_json::from_value::<AppConfig>(single)
+                    .and_then(serde_json::to_value)
+                    .map(|config| config[key.as_str()].clone());
+                match normalized {
+                    Ok(value) => Some((key, value)),
+                    Err(e) => {
+                        tracing::warn!("Policy field \"{}\" ignored: {}", key, e);
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        tracing::info!("Policy {} locks {:?}", path.display(), locked.keys().collect::<Vec<_>>());
+        Some(Self {
+            path,
+            locked,
+            unreadable: None,
+        })
+    }
+
+    /// Policy of this machine, read once
+    pub fn current() -> Option<&'static Self> {
+        POLICY.get_or_init(Self::load).as_ref()
+    }
+
+    /// Names of the locked fields
+    pub fn locked_fields(&self) -> Vec<String> {
+        if self.unreadable.is_some() {
+            return match serde_json::to_value(AppConfig::default()) {
+                Ok(serde_json::Value::Object(fields)) => fields.keys().cloned().collect(),
+                _ => Vec::new(),
+            };
+        }
+        self.locked.keys().cloned().collect()
+    }
+
+    /// `config` with the locked fields set to their policy value
+    pub fn apply(&self, config: AppConfig) -> AppConfig {
+        let Ok(mut merged) = serde_json::to_value(&config) else {
+            return config;
+        };
+        for (key, value) in &self.locked {
+            merged[key.as_str()] = value.clone();
+        }
+        serde_json::from_value(merged).unwrap_or(config)
+    }
+
+    /// Check that `config` keeps every locked field at its policy value
+    pub fn check(&self, config: &AppConfig) -> Result<(), Vec<FieldError>> {
+        if let Some(details) = &self.unreadable {
+            return Err(vec![FieldError::new(
+                "config",
+                Message::new("policy_unreadable")
+                    .arg("path", self.path.display())
+                    .arg("details", details),
+            )]);
+        }
+        let current = serde_json::to_value(config).unwrap_or_default();
+        let errors: Vec<FieldError> = self
+            .locked
+            .iter()
+            .filter(|(key, value)| current.get(key.as_str()) != Some(value))
+            .map(|(key, _)| {
+                FieldError::new(key, Message::new("locked_by_policy").arg("path", self.path.display()))
+            })
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
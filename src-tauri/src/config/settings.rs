@@ -0,0 +1,775 @@
+//! Persisted user settings
+
+use super::{FieldError, Policy, ProfileSync};
+use crate::analytics::SpeechAnalytics;
+use crate::calendar::CalendarAwareness;
+use crate::captions::Captions;
+use crate::history::HistorySync;
+use crate::i18n::Message;
+use crate::meeting::{Annotations, MeetingSummary};
+use crate::audio::{MuteAwareness, NetworkAudio, NoiseSuppression, SpeedNormalization, UploadCodec};
+use crate::control::{ControlSocket, GamepadTrigger, HidTrigger, LiveFeed, ShortcutGestures};
+use crate::output::{route, ChatTarget, EmailDraft, IssueTracker, NoteOutput, OutputAction, OutputRule, QuickNote, SearchLauncher, TaskCapture};
+use crate::paste::PasteKeys;
+use crate::postprocess::{LiveTranslation, ReformulationPrompts, StructuredOutput, TextReplacements};
+use crate::stt::{find_profile, AudioGuards, ConcurrencyLimits, EngineHealth, EngineRouting, GoogleSpeech, LocalWorker, SmartStop, SpeakerProfile, TranscriptCache};
+use crate::usage::CostBudget;
+use crate::vad::AutoStop;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+/// Which monitor(s) the overlay is shown on
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum OverlayMonitor {
+    /// Monitor under the mouse cursor
+    #[default]
+    FollowCursor,
+    /// Monitor holding the focused window of the frontmost app
+    FollowFocusedWindow,
+    /// Always the monitor with this name
+    Named { name: String },
+    /// Every monitor at once
+    All,
+}
+
+/// Where the overlay is placed on screen
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlayPosition {
+    /// Centered near the top of the selected monitor
+    #[default]
+    MonitorTop,
+    /// Just below the text caret of the focused app
+    NearCaret,
+}
+
+/// How the toggle shortcut drives the recording
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShortcutMode {
+    /// A press starts the recording, the next one stops and pastes
+    #[default]
+    Toggle,
+    /// Push-to-talk: records while the keys are held, pastes on release
+    Hold,
+}
+
+/// How the text is put into the frontmost app
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PasteMethod {
+    /// Copied to the clipboard, then the paste shortcut is simulated
+    #[default]
+    Clipboard,
+    /// Typed as keystrokes, for apps that block or mangle pastes (terminals, VMs, Citrix)
+    Type,
+}
+
+/// Voice activity detection backend
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VadBackend {
+    /// Frame energy against the silence threshold
+    #[default]
+    Energy,
+    /// WebRTC GMM detector (needs the vad-webrtc build feature)
+    #[serde(rename = "webrtc")]
+    WebRtc,
+    /// Silero neural detector (needs the vad-silero build feature and a model)
+    Silero,
+}
+
+/// Spoken phrase flagging the rest of the sentence, e.g. "action item:"
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SpokenMarker {
+    /// Phrase to detect (case-insensitive)
+    pub phrase: String,
+    /// Title of the list collecting the flagged sentences
+    pub title: String,
+}
+
+/// Application configuration
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AppConfig {
+    pub global_shortcut: String,
+    /// Shortcut cancelling the current recording
+    #[serde(default = "default_cancel_shortcut")]
+    pub cancel_shortcut: String,
+    /// Toggle on each press, or record while the global shortcut is held
+    #[serde(default)]
+    pub shortcut_mode: ShortcutMode,
+    pub openai_api_key: String,
+    pub output_language: String,
+    /// Translation shown and typed while speaking, pasted at the end, per profile
+    #[serde(default)]
+    pub live_translation: LiveTranslation,
+    /// Translate again a result detected in another language than `output_language`
+    #[serde(default = "default_true")]
+    pub enforce_output_language: bool,
+    /// Adapt the leading capital and space to the text before the caret
+    #[serde(default)]
+    pub smart_continuation: bool,
+    /// Shortcut deleting the last pasted dictation (empty = none)
+    #[serde(default)]
+    pub undo_shortcut: String,
+    /// Shortcut inverting `reformulate` for the next dictation (empty = none)
+    #[serde(default)]
+    pub reformulate_toggle_shortcut: String,
+    /// Shortcut transcribing the audio of the clipboard into it (empty = none)
+    #[serde(default)]
+    pub clipboard_audio_shortcut: String,
+    /// Shortcut locking the dictations to the frontmost window, or releasing it (empty = none)
+    #[serde(default)]
+    pub target_lock_shortcut: String,
+    /// Type the transcript while it is revised (streaming engines) instead of pasting it at the end
+    #[serde(default)]
+    pub live_paste: bool,
+    /// Minimum delay between two live corrections, finals excepted
+    #[serde(default = "default_live_paste_interval_ms")]
+    pub live_paste_interval_ms: u64,
+    /// Stream audio to the OpenAI Realtime API for live partials (stt_engine == "openai")
+    #[serde(default)]
+    pub openai_realtime: bool,
+    /// Reformulate text via GPT before pasting
+    #[serde(default)]
+    pub reformulate: bool,
+    /// Custom reformulation prompt and named presets
+    #[serde(default)]
+    pub reformulation: ReformulationPrompts,
+    /// Word replacements and spoken commands, one table per language
+    #[serde(default)]
+    pub text_replacements: TextReplacements,
+    /// STT engine: "openai", "voxtral", "gemini", "google", "groq", "assemblyai", "local" or "whisper-local"
+    #[serde(default = "default_stt_engine")]
+    pub stt_engine: String,
+    /// Mistral API key (used when stt_engine == "voxtral")
+    #[serde(default)]
+    pub mistral_api_key: String,
+    /// Gemini API key (used when stt_engine == "gemini")
+    #[serde(default)]
+    pub gemini_api_key: String,
+    /// Google Cloud Speech-to-Text credentials and model (used when stt_engine == "google")
+    #[serde(default)]
+    pub google_speech: GoogleSpeech,
+    /// Groq API key (used when stt_engine == "groq")
+    #[serde(default)]
+    pub groq_api_key: String,
+    /// AssemblyAI API key (used when stt_engine == "assemblyai")
+    #[serde(default)]
+    pub assemblyai_api_key: String,
+    /// Worker process running the local model (used when stt_engine == "local")
+    #[serde(default)]
+    pub local_worker: LocalWorker,
+    /// GGML/GGUF Whisper model (used when stt_engine == "whisper-local")
+    #[serde(default)]
+    pub whisper_model_path: String,
+    /// Local Whisper models adapted to a speaker (fine-tuned or LoRA merged)
+    #[serde(default)]
+    pub speaker_profiles: Vec<SpeakerProfile>,
+    /// Speaker profile of the local Whisper engine, empty = `whisper_model_path`
+    #[serde(default)]
+    pub speaker_profile: String,
+    /// Transcripts reused when the same audio is sent again (no second API call)
+    #[serde(default)]
+    pub transcript_cache: TranscriptCache,
+    /// Transcribe in the background during pauses, so stop returns sooner
+    #[serde(default)]
+    pub smart_stop: SmartStop,
+    /// Selected audio input device name (empty = system default)
+    #[serde(default)]
+    pub audio_device: String,
+    /// Keep the audio of each dictation on disk for playback
+    #[serde(default = "default_true")]
+    pub keep_recordings: bool,
+    /// Disk quota for saved recordings, in MB (0 = unlimited)
+    #[serde(default = "default_recordings_max_mb")]
+    pub recordings_max_mb: u64,
+    /// Saved recordings older than this are deleted, in days (0 = never)
+    #[serde(default = "default_recordings_max_age_days")]
+    pub recordings_max_age_days: u64,
+    /// Keep the text of each dictation in the history
+    #[serde(default = "default_true")]
+    pub keep_history: bool,
+    /// Dictations kept in the history, the oldest are dropped first (0 = unlimited)
+    #[serde(default = "default_history_max_entries")]
+    pub history_max_entries: usize,
+    /// End-to-end encrypted copy of the history shared between machines
+    #[serde(default)]
+    pub history_sync: HistorySync,
+    /// Monitor selection for the overlay
+    #[serde(default)]
+    pub overlay_monitor: OverlayMonitor,
+    /// Overlay placement on screen
+    #[serde(default)]
+    pub overlay_position: OverlayPosition,
+    /// Borderless window showing the live transcript in large text
+    #[serde(default)]
+    pub captions: Captions,
+    /// Seconds of dictation audio kept in RAM before spilling to disk (0 = never spill)
+    #[serde(default = "default_audio_memory_limit_secs")]
+    pub audio_memory_limit_secs: u64,
+    /// Pause the dictation and warn while the microphone is muted at the OS level
+    #[serde(default)]
+    pub mute_awareness: MuteAwareness,
+    /// Background noise removed from the microphone audio before transcription
+    #[serde(default)]
+    pub noise_suppression: NoiseSuppression,
+    /// Clipboard and paste shortcut, or typed keystrokes
+    #[serde(default)]
+    pub paste_method: PasteMethod,
+    /// Per-app paste methods, keyed by app name (case-insensitive)
+    #[serde(default)]
+    pub paste_method_apps: BTreeMap<String, PasteMethod>,
+    /// Key sequence simulated to paste, e.g. "Ctrl+Shift+V" (empty = Cmd+V / Ctrl+V)
+    #[serde(default)]
+    pub paste_shortcut: String,
+    /// Per-app paste key sequences, keyed by app name (case-insensitive)
+    #[serde(default)]
+    pub paste_shortcut_apps: BTreeMap<String, String>,
+    /// Keep the overlay visible for the whole capture, refusing to record without it
+    #[serde(default)]
+    pub require_recording_indicator: bool,
+    /// Spoken markers extracted into lists appended to the transcript
+    #[serde(default)]
+    pub spoken_markers: Vec<SpokenMarker>,
+    /// Layout of each marker list, with {title} and {items} placeholders
+    #[serde(default = "default_marker_section_template")]
+    pub marker_section_template: String,
+    /// Dictations shorter than this are not sent, in milliseconds (0 = no minimum)
+    #[serde(default = "default_min_audio_duration_ms")]
+    pub min_audio_duration_ms: u64,
+    /// Per-engine overrides of the minimum duration, keyed by engine name
+    #[serde(default)]
+    pub min_audio_duration_ms_by_engine: BTreeMap<String, u64>,
+    /// RMS level under which a dictation is considered silent (0 = disabled)
+    #[serde(default)]
+    pub silence_threshold: f32,
+    /// Stop and paste after a pause in the dictation
+    #[serde(default)]
+    pub auto_stop: AutoStop,
+    /// System audio device captured alongside the microphone (empty = meeting mode off)
+    #[serde(default)]
+    pub system_audio_device: String,
+    /// Network stream (RTP/UDP) transcribed instead of the microphone
+    #[serde(default)]
+    pub network_audio: NetworkAudio,
+    /// Label of the microphone speaker in meeting transcripts
+    #[serde(default = "default_meeting_label_me")]
+    pub meeting_label_me: String,
+    /// Label of the system audio speakers in meeting transcripts
+    #[serde(default = "default_meeting_label_them")]
+    pub meeting_label_them: String,
+    /// Points of a meeting flagged with a shortcut, written into its transcript
+    #[serde(default)]
+    pub annotations: Annotations,
+    /// TL;DR and action items appended to the transcript file once a meeting ends
+    #[serde(default)]
+    pub meeting_summary: MeetingSummary,
+    /// Meeting mode offered or started during the meetings of the local calendar
+    #[serde(default)]
+    pub calendar: CalendarAwareness,
+    /// Speech detector used for segmentation
+    #[serde(default)]
+    pub vad_backend: VadBackend,
+    /// WebRTC VAD aggressiveness, 0 (keeps most audio) to 3 (filters most noise)
+    #[serde(default = "default_vad_aggressiveness")]
+    pub vad_aggressiveness: u8,
+    /// Path of the Silero VAD ONNX model
+    #[serde(default)]
+    pub silero_model_path: String,
+    /// Slowing down of fast speech before upload
+    #[serde(default)]
+    pub speed_normalization: SpeedNormalization,
+    /// Also put the raw transcript in the secondary clipboard (primary selection / find pasteboard)
+    #[serde(default)]
+    pub raw_to_secondary_clipboard: bool,
+    /// Number of state events kept for webviews that attach late (0 = no replay)
+    #[serde(default = "default_event_history_size")]
+    pub event_history_size: usize,
+    /// Audio level updates sent to the overlay per second
+    #[serde(default = "default_level_update_fps")]
+    pub level_update_fps: u32,
+    /// Seconds a dictation may stay in transcription/processing before the
+    /// watchdog force-resets the pipeline (0 = off)
+    #[serde(default = "default_watchdog_timeout_secs")]
+    pub watchdog_timeout_secs: u64,
+    /// Dump provider requests (without keys) and responses to the log directory
+    #[serde(default)]
+    pub request_logging: bool,
+    /// Engine chosen by dictation length (short = fastest, long = cheapest)
+    #[serde(default)]
+    pub engine_routing: EngineRouting,
+    /// Upload sample rate per engine ("openai", "gemini"...), 16 kHz when not set
+    #[serde(default)]
+    pub upload_sample_rates: BTreeMap<String, u32>,
+    /// Upload codec per engine ("openai", "voxtral", "gemini"), WAV when not set
+    #[serde(default)]
+    pub upload_codecs: BTreeMap<String, UploadCodec>,
+    /// Transcription requests sent at once per provider, the others are queued
+    #[serde(default)]
+    pub request_limits: ConcurrencyLimits,
+    /// Background probes of the providers, shown in the engine settings
+    #[serde(default)]
+    pub engine_health: EngineHealth,
+    /// Monthly spend caps per provider
+    #[serde(default)]
+    pub cost_budget: CostBudget,
+    /// Words per minute and filler words of each dictation
+    #[serde(default)]
+    pub speech_analytics: SpeechAnalytics,
+    /// Team profile pulled on startup and on demand
+    #[serde(default)]
+    pub profile_sync: ProfileSync,
+    /// File receiving each final segment as it is transcribed (empty = off).
+    /// Supports `~`, `{date}`, `{time}`, `{profile}` and `{meeting}`.
+    #[serde(default)]
+    pub transcript_file: String,
+    /// Append finished dictations to a Markdown note (Obsidian daily note...)
+    #[serde(default)]
+    pub note_output: NoteOutput,
+    /// Dictate tasks straight into Todoist/TickTick with a dedicated shortcut
+    #[serde(default)]
+    pub task_capture: TaskCapture,
+    /// Slack/Teams webhooks receiving the processed text
+    #[serde(default)]
+    pub chat_targets: Vec<ChatTarget>,
+    /// Dictate emails into a mail draft with a dedicated shortcut
+    #[serde(default)]
+    pub email_draft: EmailDraft,
+    /// Where finished dictations go, first matching rule wins
+    #[serde(default)]
+    pub output_rules: Vec<OutputRule>,
+    /// Ask the chat model for a JSON object instead of prose
+    #[serde(default)]
+    pub structured_output: StructuredOutput,
+    /// Dictate GitHub/GitLab issues with a dedicated shortcut
+    #[serde(default)]
+    pub issue_tracker: IssueTracker,
+    /// Open dictations as a web search or app URI with a dedicated shortcut
+    #[serde(default)]
+    pub search_launcher: SearchLauncher,
+    /// Time-boxed voice notes, titled and kept in the history, with a dedicated shortcut
+    #[serde(default)]
+    pub quick_note: QuickNote,
+    /// Local WebSocket accepting commands and pushing state changes
+    #[serde(default)]
+    pub control_socket: ControlSocket,
+    /// Live transcript streamed to companion devices (SSE) and a webhook
+    #[serde(default)]
+    pub live_feed: LiveFeed,
+    /// Foot pedal (raw HID) starting/stopping the dictation
+    #[serde(default)]
+    pub hid_trigger: HidTrigger,
+    /// Gamepad button starting/stopping the dictation
+    #[serde(default)]
+    pub gamepad_trigger: GamepadTrigger,
+    /// Headset play/pause button (media keys) toggling the dictation
+    #[serde(default)]
+    pub media_key_trigger: bool,
+    /// Double press / hold gestures on the toggle shortcut
+    #[serde(default)]
+    pub shortcut_gestures: ShortcutGestures,
+}
+
+fn default_stt_engine() -> String {
+    "openai".to_string()
+}
+
+fn default_cancel_shortcut() -> String {
+    "CmdOrCtrl+Shift+C".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_live_paste_interval_ms() -> u64 {
+    300
+}
+
+fn default_recordings_max_mb() -> u64 {
+    500
+}
+
+fn default_recordings_max_age_days() -> u64 {
+    30
+}
+
+fn default_history_max_entries() -> usize {
+    5000
+}
+
+fn default_audio_memory_limit_secs() -> u64 {
+    300
+}
+
+fn default_min_audio_duration_ms() -> u64 {
+    1000
+}
+
+fn default_vad_aggressiveness() -> u8 {
+    2
+}
+
+fn default_meeting_label_me() -> String {
+    "Me".to_string()
+}
+
+fn default_meeting_label_them() -> String {
+    "Them".to_string()
+}
+
+fn default_event_history_size() -> usize {
+    50
+}
+
+fn default_level_update_fps() -> u32 {
+    20
+}
+
+fn default_watchdog_timeout_secs() -> u64 {
+    120
+}
+
+fn default_marker_section_template() -> String {
+    "{title}:\n{items}".to_string()
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            global_shortcut: "CmdOrCtrl+Shift+Space".to_string(),
+            cancel_shortcut: default_cancel_shortcut(),
+            shortcut_mode: ShortcutMode::default(),
+            openai_api_key: String::new(),
+            output_language: "auto".to_string(),
+            live_translation: LiveTranslation::default(),
+            enforce_output_language: true,
+            smart_continuation: false,
+            undo_shortcut: String::new(),
+            reformulate_toggle_shortcut: String::new(),
+            clipboard_audio_shortcut: String::new(),
+            target_lock_shortcut: String::new(),
+            live_paste: false,
+            live_paste_interval_ms: default_live_paste_interval_ms(),
+            openai_realtime: false,
+            reformulate: false,
+            reformulation: ReformulationPrompts::default(),
+            text_replacements: TextReplacements::default(),
+            stt_engine: "openai".to_string(),
+            mistral_api_key: String::new(),
+            gemini_api_key: String::new(),
+            google_speech: GoogleSpeech::default(),
+            groq_api_key: String::new(),
+            assemblyai_api_key: String::new(),
+            local_worker: LocalWorker::default(),
+            whisper_model_path: String::new(),
+            speaker_profiles: Vec::new(),
+            speaker_profile: String::new(),
+            transcript_cache: TranscriptCache::default(),
+            smart_stop: SmartStop::default(),
+            audio_device: String::new(),
+            keep_recordings: true,
+            recordings_max_mb: default_recordings_max_mb(),
+            recordings_max_age_days: default_recordings_max_age_days(),
+            keep_history: true,
+            history_max_entries: default_history_max_entries(),
+            history_sync: HistorySync::default(),
+            overlay_monitor: OverlayMonitor::default(),
+            overlay_position: OverlayPosition::default(),
+            captions: Captions::default(),
+            audio_memory_limit_secs: default_audio_memory_limit_secs(),
+            mute_awareness: MuteAwareness::default(),
+            noise_suppression: NoiseSuppression::default(),
+            paste_shortcut: String::new(),
+            paste_shortcut_apps: BTreeMap::new(),
+            paste_method: PasteMethod::default(),
+            paste_method_apps: BTreeMap::new(),
+            require_recording_indicator: false,
+            spoken_markers: Vec::new(),
+            marker_section_template: default_marker_section_template(),
+            min_audio_duration_ms: default_min_audio_duration_ms(),
+            min_audio_duration_ms_by_engine: BTreeMap::new(),
+            silence_threshold: 0.0,
+            auto_stop: AutoStop::default(),
+            system_audio_device: String::new(),
+            network_audio: NetworkAudio::default(),
+            meeting_label_me: default_meeting_label_me(),
+            meeting_label_them: default_meeting_label_them(),
+            annotations: Annotations::default(),
+            meeting_summary: MeetingSummary::default(),
+            calendar: CalendarAwareness::default(),
+            vad_backend: VadBackend::default(),
+            vad_aggressiveness: default_vad_aggressiveness(),
+            silero_model_path: String::new(),
+            speed_normalization: SpeedNormalization::default(),
+            raw_to_secondary_clipboard: false,
+            event_history_size: default_event_history_size(),
+            level_update_fps: default_level_update_fps(),
+            watchdog_timeout_secs: default_watchdog_timeout_secs(),
+            request_logging: false,
+            engine_routing: EngineRouting::default(),
+            upload_sample_rates: BTreeMap::new(),
+            upload_codecs: BTreeMap::new(),
+            request_limits: ConcurrencyLimits::default(),
+            engine_health: EngineHealth::default(),
+            cost_budget: CostBudget::default(),
+            speech_analytics: SpeechAnalytics::default(),
+            profile_sync: ProfileSync::default(),
+            transcript_file: String::new(),
+            note_output: NoteOutput::default(),
+            task_capture: TaskCapture::default(),
+            chat_targets: Vec::new(),
+            email_draft: EmailDraft::default(),
+            output_rules: Vec::new(),
+            structured_output: StructuredOutput::default(),
+            issue_tracker: IssueTracker::default(),
+            search_launcher: SearchLauncher::default(),
+            quick_note: QuickNote::default(),
+            control_socket: ControlSocket::default(),
+            live_feed: LiveFeed::default(),
+            hid_trigger: HidTrigger::default(),
+            gamepad_trigger: GamepadTrigger::default(),
+            media_key_trigger: false,
+            shortcut_gestures: ShortcutGestures::default(),
+        }
+    }
+}
+
+impl AppConfig {
+    /// Whether switching from `self` to `other` requires rebuilding the pipeline
+    /// (engine choice, API keys or audio input changed)
+    pub fn requires_pipeline_reset(&self, other: &AppConfig) -> bool {
+        self.stt_engine != other.stt_engine
+            || self.openai_api_key != other.openai_api_key
+            || self.openai_realtime != other.openai_realtime
+            || self.mistral_api_key != other.mistral_api_key
+            || self.gemini_api_key != other.gemini_api_key
+            || self.google_speech != other.google_speech
+            || self.groq_api_key != other.groq_api_key
+            || self.assemblyai_api_key != other.assemblyai_api_key
+            || self.local_worker != other.local_worker
+            || self.whisper_model_path != other.whisper_model_path
+            || self.speaker_profiles != other.speaker_profiles
+            || self.speaker_profile != other.speaker_profile
+            || self.transcript_cache != other.transcript_cache
+            || self.smart_stop != other.smart_stop
+            || self.audio_device != other.audio_device
+            || self.audio_memory_limit_secs != other.audio_memory_limit_secs
+            || self.engine_routing != other.engine_routing
+            || self.upload_sample_rates != other.upload_sample_rates
+            || self.upload_codecs != other.upload_codecs
+    }
+
+    /// Return a copy of this config with the fields of a partial JSON object applied
+    pub fn patched(&self, partial: &serde_json::Value) -> Result<Self, Vec<FieldError>> {
+        let fields = partial
+            .as_object()
+            .ok_or_else(|| vec![FieldError::new("config", Message::new("patch_not_object"))])?;
+
+        let current = serde_json::to_value(self)
+            .map_err(|e| vec![FieldError::new("config", Message::new("invalid_value").arg("details", e))])?;
+
+        let mut merged = current.clone();
+        let mut errors = Vec::new();
+        for (key, value) in fields {
+            if current.get(key).is_none() {
+                errors.push(FieldError::new(key, Message::new("unknown_field")));
+                continue;
+            }
+            // Check each field on its own so a type error points at the right input
+            let mut single = current.clone();
+            single[key] = value.clone();
+            if let Err(e) = serde_json::from_value::<AppConfig>(single) {
+                errors.push(FieldError::new(key, Message::new("invalid_value").arg("details", e)));
+                continue;
+            }
+            merged[key] = value.clone();
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        serde_json::from_value(merged)
+            .map_err(|e| vec![FieldError::new("config", Message::new("invalid_value").arg("details", e))])
+    }
+
+    /// Recordings quota in bytes (0 = unlimited)
+    pub fn recordings_max_bytes(&self) -> u64 {
+        self.recordings_max_mb * 1024 * 1024
+    }
+
+    /// Recordings max age in seconds (0 = never expire)
+    pub fn recordings_max_age_secs(&self) -> u64 {
+        self.recordings_max_age_days * 24 * 3600
+    }
+
+    /// In-memory audio limit in samples at 16kHz (0 = unlimited)
+    pub fn audio_memory_limit_samples(&self) -> usize {
+        (self.audio_memory_limit_secs * 16000) as usize
+    }
+
+    /// Duration and silence thresholds for the selected engine
+    pub fn audio_guards(&self) -> AudioGuards {
+        let min_duration_ms = self
+            .min_audio_duration_ms_by_engine
+            .get(&self.stt_engine)
+            .copied()
+            .unwrap_or(self.min_audio_duration_ms);
+        AudioGuards::new(min_duration_ms, self.silence_threshold)
+    }
+
+    /// Paste method for the given frontmost app
+    pub fn paste_method_for(&self, app_name: Option<&str>) -> PasteMethod {
+        app_name
+            .and_then(|name| {
+                self.paste_method_apps
+                    .iter()
+                    .find(|(app, _)| app.eq_ignore_ascii_case(name))
+                    .map(|(_, method)| *method)
+            })
+            .unwrap_or(self.paste_method)
+    }
+
+    /// Paste key sequence for the given frontmost app
+    pub fn paste_keys_for(&self, app_name: Option<&str>) -> PasteKeys {
+        let per_app = app_name.and_then(|name| {
+            self.paste_shortcut_apps
+                .iter()
+                .find(|(app, _)| app.eq_ignore_ascii_case(name))
+                .map(|(_, keys)| keys)
+        });
+        per_app
+            .or((!self.paste_shortcut.is_empty()).then_some(&self.paste_shortcut))
+            .and_then(|keys| PasteKeys::parse(keys).ok())
+            .unwrap_or_else(PasteKeys::platform_default)
+    }
+
+    /// Speaker profile of the local Whisper engine, if one is selected
+    pub fn active_speaker_profile(&self) -> Option<&SpeakerProfile> {
+        find_profile(&self.speaker_profiles, &self.speaker_profile)
+    }
+
+    /// Output actions for a dictation of `profile` ending in `app_name`
+    ///
+    /// Without a matching rule, the text goes to the enabled note and chat
+    /// targets, then is pasted.
+    pub fn output_actions(&self, profile: &str, app_name: Option<&str>) -> Vec<OutputAction> {
+        if let Some(actions) = route(&self.output_rules, profile, app_name) {
+            return actions.to_vec();
+        }
+        let mut actions = Vec::new();
+        if self.note_output.enabled {
+            actions.push(OutputAction::Note);
+        }
+        if !self.chat_targets.is_empty() {
+            actions.push(OutputAction::Chat);
+        }
+        actions.push(OutputAction::Paste { submit: false });
+        actions
+    }
+
+    /// Config file path
+    fn config_path(app: &AppHandle) -> PathBuf {
+        app.path()
+            .app_data_dir()
+            .unwrap_or_else(|_| PathBuf::from("."))
+            .join("config.json")
+    }
+
+    /// Backup of the last known good config
+    fn backup_path(path: &Path) -> PathBuf {
+        path.with_extension("json.bak")
+    }
+
+    /// Parse a config file
+    fn read_from(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path).map_err(|e| format!("Cannot read config: {}", e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Invalid config: {}", e))
+    }
+
+    /// Load config from disk, with the fields locked by the organization policy applied
+    pub fn load(app: &AppHandle) -> Self {
+        let config = Self::load_user(app);
+        match Policy::current() {
+            Some(policy) => policy.apply(config),
+            None => config,
+        }
+    }
+
+    /// Load the user config, falling back to the backup, then to defaults
+    fn load_user(app: &AppHandle) -> Self {
+        let path = Self::config_path(app);
+        if !path.exists() {
+            return Self::default();
+        }
+
+        match Self::read_from(&path) {
+            Ok(config) => {
+                tracing::info!("Config loaded from {}", path.display());
+                return config;
+            }
+            Err(e) => tracing::warn!("{}, trying backup", e),
+        }
+
+        let backup = Self::backup_path(&path);
+        match Self::read_from(&backup) {
+            Ok(config) => {
+                tracing::info!("Config restored from {}", backup.display());
+                config
+            }
+            Err(e) => {
+                tracing::warn!("Backup unusable ({}), using defaults", e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Save config to disk
+    ///
+    /// Writes to a temp file then renames it over config.json, so a crash
+    /// mid-write never leaves a truncated file. The previous config is kept
+    /// as config.json.bak.
+    pub fn save(&self, app: &AppHandle) {
+        let path = Self::config_path(app);
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        let json = match serde_json::to_string_pretty(self) {
+            Ok(json) => json,
+            Err(e) => {
+                tracing::error!("Config serialization error: {}", e);
+                return;
+            }
+        };
+
+        let tmp_path = path.with_extension("json.tmp");
+        let written = std::fs::File::create(&tmp_path).and_then(|mut file| {
+            use std::io::Write;
+            file.write_all(json.as_bytes())?;
+            file.sync_all()
+        });
+        if let Err(e) = written {
+            tracing::error!("Config save error: {}", e);
+            let _ = std::fs::remove_file(&tmp_path);
+            return;
+        }
+
+        // Only back up a config that still parses, so the backup stays usable
+        if Self::read_from(&path).is_ok() {
+            if let Err(e) = std::fs::copy(&path, Self::backup_path(&path)) {
+                tracing::warn!("Config backup error: {}", e);
+            }
+        }
+
+        match std::fs::rename(&tmp_path, &path) {
+            Ok(()) => tracing::info!("Config saved to {}", path.display()),
+            Err(e) => {
+                tracing::error!("Config save error: {}", e);
+                let _ = std::fs::remove_file(&tmp_path);
+            }
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! Shared profile sync
+//!
+//! A team publishes the settings it curates (spoken markers, output rules,
+//! structured output schema...) as a JSON object, at a URL or in a Git
+//! repository. Pulling it replaces those settings locally. Only the fields
+//! listed in `SHARED_FIELDS` are taken, so API keys and tokens always stay
+//! local. Output rules come without their webhook and file actions: a pulled
+//! profile must not send dictations to a URL or write outside the app.
+
+use super::{AppConfig, FieldError};
+use crate::i18n::Message;
+use crate::output::{OutputAction, OutputRule};
+use std::path::Path;
+
+/// Settings a shared profile may set, none of them holding secrets
+///
+/// A new setting a team may want to curate must be added here, nothing else
+/// is taken from the profile.
+pub const SHARED_FIELDS: &[&str] = &[
+    "spoken_markers",
+    "marker_section_template",
+    "structured_output",
+    "output_rules",
+    "paste_shortcut_apps",
+    "paste_method_apps",
+    "reformulation",
+    "text_replacements",
+];
+
+/// Where the shared profile is pulled from
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ProfileSync {
+    /// HTTPS URL of the JSON file, or Git repository URL (empty = off)
+    #[serde(default)]
+    pub url: String,
+    /// File holding the profile inside the Git repository
+    #[serde(default = "default_repo_file")]
+    pub repo_file: String,
+}
+
+fn default_repo_file() -> String {
+    "dictea-profiles.json".to_string()
+}
+
+impl Default for ProfileSync {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            repo_file: default_repo_file(),
+        }
+    }
+}
+
+impl ProfileSync {
+    /// Repositories are cloned with git, anything else is fetched over HTTP
+    pub fn is_git(&self) -> bool {
+        self.url.ends_with(".git") || self.url.starts_with("git@") || self.url.starts_with("ssh://")
+    }
+
+    /// The profile rewrites settings, so it is only pulled over HTTPS or SSH
+    pub fn is_valid_url(&self) -> bool {
+        self.url.starts_with("https://") || self.url.starts_with("git@") || self.url.starts_with("ssh://")
+    }
+
+    /// Download the shared profile, keeping a Git checkout in `checkout_dir`
+    pub async fn fetch(&self, checkout_dir: &Path) -> Result<serde_json::Value, String> {
+        let content = if self.is_git() {
+            let sync = self.clone();
+            let dir = checkout_dir.to_path_buf();
+            tokio::task::spawn_blocking(move || sync.pull_repo(&dir))
+                .await
+                .map_err(|e| e.to_string())??
+        } else {
+            let response = reqwest::get(&self.url).await.map_err(|e| e.to_string())?;
+            let status = response.status();
+            if !status.is_success() {
+                return Err(format!("HTTP {}", status));
+            }
+            response.text().await.map_err(|e| e.to_string())?
+        };
+        serde_json::from_str(&content).map_err(|e| format!("Invalid shared profile: {}", e))
+    }
+
+    /// Clone or fast-forward the repository, returning the profile file content
+    fn pull_repo(&self, dir: &Path) -> Result<String, String> {
+        let git = |args: &[&str], cwd: Option<&Path>| {
+            let mut command = std::process::Command::new("git");
+            command.args(args);
+            if let Some(cwd) = cwd {
+                command.current_dir(cwd);
+            }
+            let output = command.output().map_err(|e| format!("git launch error: {}", e))?;
+            if !output.status.success() {
+                return Err(format!("git error: {}", String::from_utf8_lossy(&output.stderr).trim()));
+            }
+            Ok(())
+        };
+
+        if dir.join(".git").exists() {
+            git(&["pull", "--ff-only", "--quiet"], Some(dir))?;
+        } else {
+            if let Some(parent) = dir.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            let dir = dir.to_string_lossy();
+            // `--` so a URL starting with a dash is not read as an option
+            git(&["clone", "--depth", "1", "--quiet", "--", &self.url, &dir], None)?;
+        }
+
+        // The file must stay inside the checkout
+        let file = Path::new(&self.repo_file);
+        if file.is_absolute() || file.components().any(|c| c == std::path::Component::ParentDir) {
+            return Err(format!("Invalid profile file: {}", self.repo_file));
+        }
+        std::fs::read_to_string(dir.join(file)).map_err(|e| format!("Cannot read {}: {}", self.repo_file, e))
+    }
+}
+
+impl AppConfig {
+    /// Apply the shareable fields of a pulled profile, returning the new config
+    /// and the fields it changed
+    pub fn with_shared_profile(&self, shared: &serde_json::Value) -> Result<(Self, Vec<String>), Vec<FieldError>> {
+        let fields = shared
+            .as_object()
+            .ok_or_else(|| vec![FieldError::new("profile_sync", Message::new("patch_not_object"))])?;
+
+        let current = serde_json::to_value(self).unwrap_or_default();
+        let mut patch = serde_json::Map::new();
+        for (key, value) in fields {
+            if !SHARED_FIELDS.contains(&key.as_str()) {
+                tracing::warn!("Shared profile field \"{}\" ignored, only shared settings are synced", key);
+                continue;
+            }
+            let value = if key == "output_rules" {
+                local_output_rules(value)?
+            } else {
+                value.clone()
+            };
+            if current.get(key) != Some(&value) {
+                patch.insert(key.clone(), value);
+            }
+        }
+
+        let changed = patch.keys().cloned().collect();
+        let config = self.patched(&serde_json::Value::Object(patch))?;
+        Ok((config, changed))
+    }
+}
+
+/// Shared output rules without the actions sending text out of the app
+///
+/// Webhook and file actions are dropped, and so are the rules left without
+/// any action.
+fn local_output_rules(value: &serde_json::Value) -> Result<serde_json::Value, Vec<FieldError>> {
+    let invalid = |e: serde_json::Error| {
+        vec![FieldError::new(
+            "output_rules",
+            Message::new("invalid_value").arg("details", e),
+        )]
+    };
+    let mut rules: Vec<OutputRule> = serde_json::from_value(value.clone()).map_err(invalid)?;
+    for rule in &mut rules {
+        rule.actions.retain(|action| {
+            let local = !matches!(action, OutputAction::Webhook { .. } | OutputAction::File { .. });
+            if !local {
+                tracing::warn!("Shared output rule action {:?} ignored, only local actions are synced", action);
+            }
+            local
+        });
+    }
+    rules.retain(|rule| !rule.actions.is_empty());
+    serde_json::to_value(rules).map_err(invalid)
+}
@@ -0,0 +1,660 @@
+//! Validation of the user settings
+//!
+//! Errors are reported per field so the settings UI can highlight the
+//! offending input.
+
+use super::{AppConfig, OverlayMonitor, VadBackend};
+use crate::output::{EmailMethod, OutputAction};
+use crate::audio::{SpeedNormalization, UPLOAD_SAMPLE_RATES};
+use crate::control::shortcut_conflicts;
+use crate::history::is_local_http;
+use crate::i18n::Message;
+use crate::paste::PasteKeys;
+use crate::stt::MIN_PROBE_INTERVAL_SECS;
+use tauri_plugin_global_shortcut::Shortcut;
+
+/// Supported STT engine names
+pub const STT_ENGINES: &[&str] = &["openai", "voxtral", "gemini", "google", "groq", "assemblyai", "local", "whisper-local"];
+
+/// Engines whose API takes compressed uploads
+pub const COMPRESSED_UPLOAD_ENGINES: &[&str] = &["openai", "voxtral", "gemini"];
+
+/// Validation error for a single config field
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FieldError {
+    /// Name of the offending field (as serialized in AppConfig)
+    pub field: String,
+    pub message: Message,
+}
+
+impl FieldError {
+    pub fn new(field: &str, message: Message) -> Self {
+        Self {
+            field: field.to_string(),
+            message,
+        }
+    }
+}
+
+/// Check that a language code is "auto" or an ISO 639 code
+fn is_valid_language_code(code: &str) -> bool {
+    code == "auto" || ((2..=3).contains(&code.len()) && code.chars().all(|c| c.is_ascii_lowercase()))
+}
+
+impl AppConfig {
+    /// Validate the config, returning every invalid field
+    pub fn validate(&self) -> Result<(), Vec<FieldError>> {
+        let mut errors = Vec::new();
+
+        let required_shortcuts = [
+            ("global_shortcut", &self.global_shortcut),
+            ("cancel_shortcut", &self.cancel_shortcut),
+        ];
+        for (field, shortcut) in required_shortcuts {
+            if let Err(e) = shortcut.parse::<Shortcut>() {
+                errors.push(FieldError::new(
+                    field,
+                    Message::new("invalid_shortcut").arg("value", shortcut).arg("details", e),
+                ));
+            }
+        }
+
+        // One combination cannot trigger two actions
+        for (binding, first) in shortcut_conflicts(self) {
+            errors.push(FieldError::new(
+                binding.action.field(),
+                Message::new("duplicate_shortcut").arg("value", &binding.keys).arg("other", first.field()),
+            ));
+        }
+
+        if !STT_ENGINES.contains(&self.stt_engine.as_str()) {
+            errors.push(FieldError::new(
+                "stt_engine",
+                Message::new("unknown_engine").arg("value", &self.stt_engine),
+            ));
+        }
+
+        let mut engines = vec![self.stt_engine.as_str()];
+        if self.engine_routing.enabled {
+            engines.extend([self.engine_routing.short_engine.as_str(), self.engine_routing.long_engine.as_str()]);
+        }
+        if engines.contains(&"local") && self.local_worker.command.trim().is_empty() {
+            errors.push(FieldError::new("local_worker", Message::new("local_worker_required")));
+        }
+        if engines.contains(&"google") {
+            if !self.google_speech.has_credentials() {
+                errors.push(FieldError::new("google_speech", Message::new("api_key_required_google")));
+            } else if self.google_speech.service_account_path.trim().is_empty()
+                && self.google_speech.project_id.trim().is_empty()
+            {
+                // Only a service account key names its project
+                errors.push(FieldError::new("google_speech", Message::new("google_project_required")));
+            }
+        }
+        if engines.contains(&"whisper-local")
+            && self.whisper_model_path.trim().is_empty()
+            && self.active_speaker_profile().is_none()
+        {
+            errors.push(FieldError::new("whisper_model_path", Message::new("whisper_model_required")));
+        }
+        for (index, profile) in self.speaker_profiles.iter().enumerate() {
+            let duplicate = self.speaker_profiles[..index].iter().any(|other| other.name == profile.name);
+            if profile.name.trim().is_empty() || profile.model_path.trim().is_empty() || duplicate {
+                errors.push(FieldError::new(
+                    "speaker_profiles",
+                    Message::new("invalid_speaker_profile").arg("value", &profile.name),
+                ));
+            }
+        }
+        if !self.speaker_profile.is_empty() && self.active_speaker_profile().is_none() {
+            errors.push(FieldError::new(
+                "speaker_profile",
+                Message::new("unknown_speaker_profile").arg("value", &self.speaker_profile),
+            ));
+        }
+
+        for engine in self.min_audio_duration_ms_by_engine.keys() {
+            if !STT_ENGINES.contains(&engine.as_str()) {
+                errors.push(FieldError::new(
+                    "min_audio_duration_ms_by_engine",
+                    Message::new("unknown_engine").arg("value", engine),
+                ));
+            }
+        }
+
+        if !(0.0..=1.0).contains(&self.silence_threshold) {
+            errors.push(FieldError::new(
+                "silence_threshold",
+                Message::new("invalid_silence_threshold").arg("value", self.silence_threshold),
+            ));
+        }
+
+        if self.auto_stop.enabled {
+            if !(0.0..=1.0).contains(&self.auto_stop.threshold) {
+                errors.push(FieldError::new(
+                    "auto_stop",
+                    Message::new("invalid_silence_threshold").arg("value", self.auto_stop.threshold),
+                ));
+            }
+            if !(500..=60_000).contains(&self.auto_stop.silence_ms) {
+                errors.push(FieldError::new(
+                    "auto_stop",
+                    Message::new("invalid_auto_stop_delay").arg("value", self.auto_stop.silence_ms),
+                ));
+            }
+        }
+
+        if !is_valid_language_code(&self.output_language) {
+            errors.push(FieldError::new(
+                "output_language",
+                Message::new("invalid_language").arg("value", &self.output_language),
+            ));
+        }
+        for language in self.live_translation.languages.values() {
+            if !is_valid_language_code(language) {
+                errors.push(FieldError::new(
+                    "live_translation",
+                    Message::new("invalid_language").arg("value", language),
+                ));
+            }
+        }
+
+        if !self.paste_shortcut.is_empty() {
+            if let Err(e) = PasteKeys::parse(&self.paste_shortcut) {
+                errors.push(FieldError::new(
+                    "paste_shortcut",
+                    Message::new("invalid_paste_shortcut")
+                        .arg("value", &self.paste_shortcut)
+                        .arg("details", e),
+                ));
+            }
+        }
+
+        for (app, keys) in &self.paste_shortcut_apps {
+            if let Err(e) = PasteKeys::parse(keys) {
+                errors.push(FieldError::new(
+                    "paste_shortcut_apps",
+                    Message::new("invalid_paste_shortcut")
+                        .arg("value", format!("{}: {}", app, keys))
+                        .arg("details", e),
+                ));
+            }
+        }
+
+        let reformulation = &self.reformulation;
+        for (i, preset) in reformulation.presets.iter().enumerate() {
+            if preset.name.trim().is_empty() || preset.prompt.trim().is_empty() {
+                errors.push(FieldError::new("reformulation", Message::new("preset_incomplete")));
+            } else if reformulation.presets[..i].iter().any(|other| other.name == preset.name) {
+                errors.push(FieldError::new(
+                    "reformulation",
+                    Message::new("duplicate_preset").arg("value", &preset.name),
+                ));
+            }
+        }
+        if !reformulation.active_preset.is_empty() && reformulation.preset(&reformulation.active_preset).is_none() {
+            errors.push(FieldError::new(
+                "reformulation",
+                Message::new("unknown_preset").arg("value", &reformulation.active_preset),
+            ));
+        }
+
+        for (language, table) in &self.text_replacements.tables {
+            if table.replacements.keys().chain(table.commands.keys()).any(|phrase| phrase.trim().is_empty()) {
+                errors.push(FieldError::new(
+                    "text_replacements",
+                    Message::new("replacement_phrase_required").arg("value", language),
+                ));
+            }
+        }
+
+        if self.spoken_markers.iter().any(|m| m.phrase.trim().trim_end_matches(':').trim().is_empty()) {
+            errors.push(FieldError::new("spoken_markers", Message::new("marker_phrase_required")));
+        }
+
+        if !self.marker_section_template.contains("{items}") {
+            errors.push(FieldError::new(
+                "marker_section_template",
+                Message::new("invalid_marker_template").arg("value", &self.marker_section_template),
+            ));
+        }
+
+        if self.network_audio.is_enabled() {
+            let network = &self.network_audio;
+            if network.socket_addr().is_none() {
+                errors.push(FieldError::new(
+                    "network_audio",
+                    Message::new("invalid_network_address").arg("value", &network.address),
+                ));
+            }
+            if !network.sender.trim().is_empty() && network.sender_addr().is_none() {
+                errors.push(FieldError::new(
+                    "network_audio",
+                    Message::new("invalid_network_sender").arg("value", &network.sender),
+                ));
+            }
+            if !(8000..=48000).contains(&network.sample_rate) || !(1..=2).contains(&network.channels) {
+                errors.push(FieldError::new(
+                    "network_audio",
+                    Message::new("invalid_network_format")
+                        .arg("rate", network.sample_rate)
+                        .arg("channels", network.channels),
+                ));
+            }
+        }
+
+        if self.vad_aggressiveness > 3 {
+            errors.push(FieldError::new(
+                "vad_aggressiveness",
+                Message::new("invalid_vad_aggressiveness").arg("value", self.vad_aggressiveness),
+            ));
+        }
+
+        if self.vad_backend == VadBackend::Silero && self.silero_model_path.trim().is_empty() {
+            errors.push(FieldError::new("silero_model_path", Message::new("vad_model_required")));
+        }
+
+        match self.speed_normalization {
+            SpeedNormalization::Fixed { factor } if !(1.0..=1.5).contains(&factor) => {
+                errors.push(FieldError::new(
+                    "speed_normalization",
+                    Message::new("invalid_speed_factor").arg("value", factor),
+                ));
+            }
+            SpeedNormalization::Auto { threshold } if threshold <= 0.0 => {
+                errors.push(FieldError::new(
+                    "speed_normalization",
+                    Message::new("invalid_speed_threshold").arg("value", threshold),
+                ));
+            }
+            _ => {}
+        }
+
+        if !(1..=60).contains(&self.level_update_fps) {
+            errors.push(FieldError::new(
+                "level_update_fps",
+                Message::new("invalid_level_update_fps").arg("value", self.level_update_fps),
+            ));
+        }
+
+        // The engines already wait up to 30s for a response
+        if self.watchdog_timeout_secs != 0 && self.watchdog_timeout_secs < 60 {
+            errors.push(FieldError::new(
+                "watchdog_timeout_secs",
+                Message::new("invalid_watchdog_timeout").arg("value", self.watchdog_timeout_secs),
+            ));
+        }
+
+        if self.engine_routing.enabled {
+            let routing = &self.engine_routing;
+            for engine in [&routing.short_engine, &routing.long_engine] {
+                if !STT_ENGINES.contains(&engine.as_str()) {
+                    errors.push(FieldError::new(
+                        "engine_routing",
+                        Message::new("unknown_engine").arg("value", engine),
+                    ));
+                }
+            }
+            if routing.short_max_secs >= routing.long_min_secs {
+                errors.push(FieldError::new(
+                    "engine_routing",
+                    Message::new("invalid_routing_thresholds")
+                        .arg("short", routing.short_max_secs)
+                        .arg("long", routing.long_min_secs),
+                ));
+            }
+        }
+
+        for (engine, rate) in &self.upload_sample_rates {
+            if !STT_ENGINES.contains(&engine.as_str()) {
+                errors.push(FieldError::new(
+                    "upload_sample_rates",
+                    Message::new("unknown_engine").arg("value", engine),
+                ));
+            }
+            if !UPLOAD_SAMPLE_RATES.contains(rate) {
+                errors.push(FieldError::new(
+                    "upload_sample_rates",
+                    Message::new("invalid_upload_sample_rate").arg("value", rate),
+                ));
+            }
+        }
+
+        for engine in self.upload_codecs.keys() {
+            if !COMPRESSED_UPLOAD_ENGINES.contains(&engine.as_str()) {
+                errors.push(FieldError::new(
+                    "upload_codecs",
+                    Message::new("unsupported_upload_codec").arg("value", engine),
+                ));
+            }
+        }
+
+        if self.engine_health.enabled && self.engine_health.interval_secs < MIN_PROBE_INTERVAL_SECS {
+            errors.push(FieldError::new(
+                "engine_health",
+                Message::new("invalid_health_interval").arg("value", self.engine_health.interval_secs),
+            ));
+        }
+
+        if self.smart_stop.enabled && !(300..=10_000).contains(&self.smart_stop.silence_ms) {
+            errors.push(FieldError::new(
+                "smart_stop",
+                Message::new("invalid_smart_stop_delay").arg("value", self.smart_stop.silence_ms),
+            ));
+        }
+
+        let cache = &self.transcript_cache;
+        if cache.enabled && (!(1..=10_000).contains(&cache.max_entries) || !(1..=720).contains(&cache.ttl_hours)) {
+            errors.push(FieldError::new(
+                "transcript_cache",
+                Message::new("invalid_cache_limits")
+                    .arg("entries", cache.max_entries)
+                    .arg("hours", cache.ttl_hours),
+            ));
+        }
+
+        if !(50..=5000).contains(&self.live_paste_interval_ms) {
+            errors.push(FieldError::new(
+                "live_paste_interval_ms",
+                Message::new("invalid_live_paste_interval").arg("value", self.live_paste_interval_ms),
+            ));
+        }
+
+        let optional_shortcuts = [
+            ("undo_shortcut", &self.undo_shortcut),
+            ("reformulate_toggle_shortcut", &self.reformulate_toggle_shortcut),
+            ("clipboard_audio_shortcut", &self.clipboard_audio_shortcut),
+            ("target_lock_shortcut", &self.target_lock_shortcut),
+            ("annotations", &self.annotations.shortcut),
+        ];
+        for (field, shortcut) in optional_shortcuts {
+            if shortcut.is_empty() {
+                continue;
+            }
+            if let Err(e) = shortcut.parse::<Shortcut>() {
+                errors.push(FieldError::new(
+                    field,
+                    Message::new("invalid_shortcut").arg("value", shortcut).arg("details", e),
+                ));
+            }
+        }
+
+        if !self.profile_sync.url.is_empty() && !self.profile_sync.is_valid_url() {
+            errors.push(FieldError::new(
+                "profile_sync",
+                Message::new("invalid_sync_url").arg("value", &self.profile_sync.url),
+            ));
+        }
+        if self.history_sync.enabled {
+            if !self.history_sync.is_valid_url() {
+                errors.push(FieldError::new(
+                    "history_sync",
+                    Message::new("invalid_history_sync_url").arg("value", &self.history_sync.url),
+                ));
+            }
+            if self.history_sync.passphrase.is_empty() {
+                errors.push(FieldError::new("history_sync", Message::new("history_passphrase_required")));
+            }
+        }
+
+        if self.cost_budget.enabled {
+            let budget = &self.cost_budget;
+            for (engine, cap) in &budget.monthly_usd {
+                if !STT_ENGINES.contains(&engine.as_str()) {
+                    errors.push(FieldError::new(
+                        "cost_budget",
+                        Message::new("unknown_engine").arg("value", engine),
+                    ));
+                }
+                if !cap.is_finite() || *cap < 0.0 {
+                    errors.push(FieldError::new(
+                        "cost_budget",
+                        Message::new("invalid_budget_cap").arg("engine", engine).arg("value", cap),
+                    ));
+                }
+            }
+            if !budget.fallback_engine.is_empty() && !STT_ENGINES.contains(&budget.fallback_engine.as_str()) {
+                errors.push(FieldError::new(
+                    "cost_budget",
+                    Message::new("unknown_engine").arg("value", &budget.fallback_engine),
+                ));
+            }
+        }
+
+        if self.note_output.enabled && self.note_output.path.trim().is_empty() {
+            errors.push(FieldError::new("note_output", Message::new("note_path_required")));
+        }
+
+        if self.task_capture.enabled {
+            if self.task_capture.api_token.trim().is_empty() {
+                errors.push(FieldError::new("task_capture", Message::new("task_token_required")));
+            }
+            if let Err(e) = self.task_capture.shortcut.parse::<Shortcut>() {
+                errors.push(FieldError::new(
+                    "task_capture",
+                    Message::new("invalid_shortcut")
+                        .arg("value", &self.task_capture.shortcut)
+                        .arg("details", e),
+                ));
+            }
+        }
+
+        if self.email_draft.enabled {
+            if self.email_draft.method == EmailMethod::Gmail && self.email_draft.gmail_access_token.trim().is_empty() {
+                errors.push(FieldError::new("email_draft", Message::new("gmail_token_required")));
+            }
+            if let Err(e) = self.email_draft.shortcut.parse::<Shortcut>() {
+                errors.push(FieldError::new(
+                    "email_draft",
+                    Message::new("invalid_shortcut")
+                        .arg("value", &self.email_draft.shortcut)
+                        .arg("details", e),
+                ));
+            }
+        }
+
+        for rule in &self.output_rules {
+            if rule.actions.is_empty() {
+                errors.push(FieldError::new("output_rules", Message::new("rule_actions_required")));
+            }
+            for action in &rule.actions {
+                match action {
+                    OutputAction::File { path } if path.trim().is_empty() => {
+                        errors.push(FieldError::new("output_rules", Message::new("file_path_required")));
+                    }
+                    OutputAction::Webhook { url } if !url.starts_with("https://") && !is_local_http(url) => {
+                        errors.push(FieldError::new(
+                            "output_rules",
+                            Message::new("invalid_webhook_url").arg("value", url),
+                        ));
+                    }
+                    OutputAction::Note if self.note_output.path.trim().is_empty() => {
+                        errors.push(FieldError::new("output_rules", Message::new("note_path_required")));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if self.structured_output.enabled {
+            if self.structured_output.schema.is_empty() {
+                errors.push(FieldError::new("structured_output", Message::new("schema_fields_required")));
+            }
+            if self.structured_output.schema.iter().any(|field| field.name.trim().is_empty()) {
+                errors.push(FieldError::new("structured_output", Message::new("schema_field_name_required")));
+            }
+        }
+
+        if self.issue_tracker.enabled {
+            let tracker = &self.issue_tracker;
+            for (i, profile) in tracker.profiles.iter().enumerate() {
+                if profile.name.trim().is_empty() {
+                    errors.push(FieldError::new("issue_tracker", Message::new("issue_profile_name_required")));
+                } else if tracker.profiles[..i].iter().any(|other| other.name == profile.name) {
+                    errors.push(FieldError::new(
+                        "issue_tracker",
+                        Message::new("duplicate_issue_profile").arg("value", &profile.name),
+                    ));
+                }
+                if profile.repository.trim().is_empty() {
+                    errors.push(FieldError::new(
+                        "issue_tracker",
+                        Message::new("issue_repository_required").arg("profile", &profile.name),
+                    ));
+                }
+                if profile.token.trim().is_empty() {
+                    errors.push(FieldError::new(
+                        "issue_tracker",
+                        Message::new("issue_token_required").arg("profile", &profile.name),
+                    ));
+                }
+            }
+            if tracker.profile().is_none() {
+                let message = if tracker.profiles.is_empty() {
+                    Message::new("issue_profile_required")
+                } else {
+                    Message::new("unknown_issue_profile").arg("value", &tracker.active_profile)
+                };
+                errors.push(FieldError::new("issue_tracker", message));
+            }
+            if let Err(e) = self.issue_tracker.shortcut.parse::<Shortcut>() {
+                errors.push(FieldError::new(
+                    "issue_tracker",
+                    Message::new("invalid_shortcut")
+                        .arg("value", &self.issue_tracker.shortcut)
+                        .arg("details", e),
+                ));
+            }
+        }
+
+        if self.quick_note.enabled {
+            if self.quick_note.max_secs == 0 {
+                errors.push(FieldError::new("quick_note", Message::new("invalid_quick_note_duration")));
+            }
+            if let Err(e) = self.quick_note.shortcut.parse::<Shortcut>() {
+                errors.push(FieldError::new(
+                    "quick_note",
+                    Message::new("invalid_shortcut")
+                        .arg("value", &self.quick_note.shortcut)
+                        .arg("details", e),
+                ));
+            }
+        }
+
+        let calendar = &self.calendar;
+        if calendar.enabled
+            && calendar.auto_meeting_mode
+            && calendar.meeting_audio_device.trim().is_empty()
+            && self.system_audio_device.is_empty()
+        {
+            errors.push(FieldError::new("calendar", Message::new("meeting_device_required")));
+        }
+
+        if self.search_launcher.enabled {
+            if !self.search_launcher.url_template.contains("{text}") {
+                errors.push(FieldError::new(
+                    "search_launcher",
+                    Message::new("search_template_text_required")
+                        .arg("value", &self.search_launcher.url_template)
+                        .arg("placeholder", "{text}"),
+                ));
+            }
+            if let Err(e) = self.search_launcher.shortcut.parse::<Shortcut>() {
+                errors.push(FieldError::new(
+                    "search_launcher",
+                    Message::new("invalid_shortcut")
+                        .arg("value", &self.search_launcher.shortcut)
+                        .arg("details", e),
+                ));
+            }
+        }
+
+        if self.control_socket.enabled && self.control_socket.port < 1024 {
+            errors.push(FieldError::new(
+                "control_socket",
+                Message::new("invalid_port").arg("value", self.control_socket.port),
+            ));
+        }
+
+        if self.control_socket.enabled && self.control_socket.token.trim().is_empty() {
+            errors.push(FieldError::new("control_socket", Message::new("control_socket_token_required")));
+        }
+
+        if self.live_feed.enabled {
+            let feed = &self.live_feed;
+            if feed.port < 1024 {
+                errors.push(FieldError::new("live_feed", Message::new("invalid_port").arg("value", feed.port)));
+            }
+            // Neither other devices nor web pages may read the dictations freely
+            if feed.token.trim().is_empty() {
+                errors.push(FieldError::new("live_feed", Message::new("live_feed_token_required")));
+            }
+            let url = &feed.webhook_url;
+            if !url.is_empty() && !url.starts_with("https://") && !is_local_http(url) {
+                errors.push(FieldError::new(
+                    "live_feed",
+                    Message::new("invalid_webhook_url").arg("value", url),
+                ));
+            }
+        }
+
+        if self.hid_trigger.enabled && (self.hid_trigger.vendor_id == 0 || self.hid_trigger.product_id == 0) {
+            errors.push(FieldError::new("hid_trigger", Message::new("hid_device_required")));
+        }
+
+        if self.gamepad_trigger.enabled && self.gamepad_trigger.button.trim().is_empty() {
+            errors.push(FieldError::new("gamepad_trigger", Message::new("gamepad_button_required")));
+        }
+
+        let gestures = &self.shortcut_gestures;
+        for delay in [gestures.double_press_ms, gestures.long_press_ms] {
+            if gestures.enabled && !(100..=2000).contains(&delay) {
+                errors.push(FieldError::new(
+                    "shortcut_gestures",
+                    Message::new("invalid_gesture_delay").arg("value", delay),
+                ));
+            }
+        }
+
+        for target in &self.chat_targets {
+            if !target.webhook_url.starts_with("https://") {
+                errors.push(FieldError::new(
+                    "chat_targets",
+                    Message::new("invalid_webhook_url").arg("value", &target.webhook_url),
+                ));
+            }
+        }
+
+        let captions = &self.captions;
+        if !(12..=128).contains(&captions.font_size) {
+            errors.push(FieldError::new(
+                "captions",
+                Message::new("invalid_caption_font_size").arg("value", captions.font_size),
+            ));
+        }
+        if !(1..=6).contains(&captions.max_lines) {
+            errors.push(FieldError::new(
+                "captions",
+                Message::new("invalid_caption_lines").arg("value", captions.max_lines),
+            ));
+        }
+        if !(0.0..=1.0).contains(&captions.background_opacity) {
+            errors.push(FieldError::new(
+                "captions",
+                Message::new("invalid_caption_opacity").arg("value", captions.background_opacity),
+            ));
+        }
+
+        if let OverlayMonitor::Named { ref name } = self.overlay_monitor {
+            if name.trim().is_empty() {
+                errors.push(FieldError::new("overlay_monitor", Message::new("monitor_name_required")));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
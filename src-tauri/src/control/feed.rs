@@ -0,0 +1,261 @@
+//! Live transcript feed for companion devices
+//!
+//! A minimal HTTP server streaming the transcript as Server-Sent Events on
+//! `/events`, with a page on `/` mirroring it (open it on a phone or tablet
+//! during a meeting). The same updates can be pushed to a webhook.
+
+use super::socket::{constant_time_eq, generate_token};
+use crate::output::post_json;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+
+/// Settings of the live feed
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LiveFeed {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_port")]
+    pub port: u16,
+    /// Shared secret expected as `?token=...`, generated when the feed is
+    /// enabled without one
+    #[serde(default)]
+    pub token: String,
+    /// Listen on every interface so devices on the local network can connect
+    #[serde(default)]
+    pub lan: bool,
+    /// URL receiving each update as a JSON POST (empty = none)
+    #[serde(default)]
+    pub webhook_url: String,
+}
+
+fn default_port() -> u16 {
+    7782
+}
+
+impl Default for LiveFeed {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: default_port(),
+            token: String::new(),
+            lan: false,
+            webhook_url: String::new(),
+        }
+    }
+}
+
+impl LiveFeed {
+    /// Generate a random token if the feed is enabled without one
+    ///
+    /// Returns whether the token changed, so the caller can save it.
+    pub fn ensure_token(&mut self) -> bool {
+        if !self.enabled || !self.token.trim().is_empty() {
+            return false;
+        }
+        self.token = generate_token();
+        true
+    }
+}
+
+/// Transcript change sent to the feed clients
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TranscriptUpdate {
+    Started,
+    /// Revised hypothesis of the segment being spoken
+    Partial { text: String },
+    /// Segment that will not change anymore
+    Final { text: String },
+    /// Whole transcript of the dictation that ended
+    Stopped { text: String },
+}
+
+impl TranscriptUpdate {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Started => "started",
+            Self::Partial { .. } => "partial",
+            Self::Final { .. } => "final",
+            Self::Stopped { .. } => "stopped",
+        }
+    }
+}
+
+/// Companion page, following `/events` with the token of its own URL
+const COMPANION_PAGE: &str = r#"<!DOCTYPE html>
+<html><head><meta charset="utf-8"><meta name="viewport" content="width=device-width, initial-scale=1">
+<title>Dictea</title>
+<style>body{font:20px/1.5 system-ui,sans-serif;margin:1.5em;background:#14141e;color:#eee}#partial{opacity:.6}</style>
+</head><body><p><span id="final"></span> <span id="partial"></span></p>
+<script>
+const finalText = document.getElementById("final"), partial = document.getElementById("partial");
+const events = new EventSource("/events" + location.search);
+events.addEventListener("started", () => { finalText.textContent = ""; partial.textContent = ""; });
+events.addEventListener("partial", (e) => { partial.textContent = JSON.parse(e.data).text; });
+events.addEventListener("final", (e) => { finalText.textContent += " " + JSON.parse(e.data).text; partial.textContent = ""; });
+events.addEventListener("stopped", (e) => { finalText.textContent = JSON.parse(e.data).text; partial.textContent = ""; });
+</script></body></html>"#;
+
+/// Interval of the comments keeping idle connections open
+const KEEPALIVE: std::time::Duration = std::time::Duration::from_secs(15);
+
+/// Accept clients until the listener fails
+pub async fn serve_feed(settings: LiveFeed, updates: broadcast::Sender<TranscriptUpdate>) -> std::io::Result<()> {
+    let host = if settings.lan { "0.0.0.0" } else { "127.0.0.1" };
+    let listener = TcpListener::bind((host, settings.port)).await?;
+    tracing::info!("Live feed listening on http://{}:{}", host, settings.port);
+
+    let token = Arc::new(settings.token);
+    let lan = settings.lan;
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                // Per-connection failures (reset, too many open files) must
+                // not take the feed down
+                tracing::warn!("Live feed accept error: {}", e);
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                continue;
+            }
+        };
+        let updates = updates.subscribe();
+        let token = token.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_client(stream, &token, lan, updates).await {
+                tracing::debug!("Live feed client {} disconnected: {}", peer, e);
+            }
+        });
+    }
+}
+
+/// POST every update to `url` until the feed closes
+pub async fn push_to_webhook(url: String, mut updates: broadcast::Receiver<TranscriptUpdate>) {
+    loop {
+        match updates.recv().await {
+            Ok(update) => {
+                let payload = serde_json::to_value(&update).unwrap_or_default();
+                if let Err(e) = post_json(&url, &payload).await {
+                    tracing::warn!("Live feed webhook error ({}): {}", url, e);
+                }
+            }
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                tracing::warn!("Live feed webhook too slow, {} updates skipped", skipped);
+            }
+            Err(broadcast::error::RecvError::Closed) => return,
+        }
+    }
+}
+
+/// Path and query of the request line ("GET /events?token=x HTTP/1.1")
+fn request_target(head: &str) -> Option<(&str, &str)> {
+    let mut parts = head.lines().next()?.split_whitespace();
+    if parts.next()? != "GET" {
+        return None;
+    }
+    let target = parts.next()?;
+    Some(target.split_once('?').unwrap_or((target, "")))
+}
+
+/// Whether the query carries the expected token
+fn authorized(query: &str, token: &str) -> bool {
+    if token.is_empty() {
+        return false;
+    }
+    query
+        .split('&')
+        .filter_map(|pair| pair.strip_prefix("token="))
+        .fold(false, |found, candidate| {
+            found | constant_time_eq(candidate.as_bytes(), token.as_bytes())
+        })
+}
+
+/// Whether the `Host` header names this machine
+///
+/// A page of another site whose name was rebound to 127.0.0.1 still sends
+/// its own name, so only `localhost` and IP literals are accepted; an IP on
+/// the local network is only valid with `lan`.
+fn allowed_host(head: &str, lan: bool) -> bool {
+    let Some(host) = head
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("host"))
+        .map(|(_, value)| value.trim())
+    else {
+        return false;
+    };
+    let host = match host.strip_prefix('[') {
+        Some(ipv6) => ipv6.split(']').next().unwrap_or_default(),
+        None => host.split(':').next().unwrap_or_default(),
+    };
+    if host.eq_ignore_ascii_case("localhost") {
+        return true;
+    }
+    match host.parse::<std::net::IpAddr>() {
+        Ok(ip) => ip.is_loopback() || lan,
+        Err(_) => false,
+    }
+}
+
+async fn handle_client(
+    mut stream: TcpStream,
+    token: &str,
+    lan: bool,
+    mut updates: broadcast::Receiver<TranscriptUpdate>,
+) -> Result<(), String> {
+    // Only the request head matters, GET requests have no body
+    let mut buffer = vec![0u8; 8192];
+    let mut len = 0;
+    while !buffer[..len].windows(4).any(|w| w == b"\r\n\r\n") {
+        if len == buffer.len() {
+            return Err("request head too large".to_string());
+        }
+        let read = stream.read(&mut buffer[len..]).await.map_err(|e| e.to_string())?;
+        if read == 0 {
+            return Err("connection closed".to_string());
+        }
+        len += read;
+    }
+    let head = String::from_utf8_lossy(&buffer[..len]);
+
+    let response = match request_target(&head) {
+        _ if !allowed_host(&head, lan) => "HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\n\r\n".to_string(),
+        Some((_, query)) if !authorized(query, token) => "HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\n\r\n".to_string(),
+        Some(("/", _)) => format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\n\r\n{}",
+            COMPANION_PAGE.len(),
+            COMPANION_PAGE
+        ),
+        Some(("/events", _)) => {
+            let headers = "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n";
+            stream.write_all(headers.as_bytes()).await.map_err(|e| e.to_string())?;
+            return stream_events(&mut stream, &mut updates).await;
+        }
+        _ => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n".to_string(),
+    };
+    stream.write_all(response.as_bytes()).await.map_err(|e| e.to_string())
+}
+
+async fn stream_events(
+    stream: &mut TcpStream,
+    updates: &mut broadcast::Receiver<TranscriptUpdate>,
+) -> Result<(), String> {
+    let mut keepalive = tokio::time::interval(KEEPALIVE);
+    loop {
+        let chunk = tokio::select! {
+            update = updates.recv() => match update {
+                Ok(update) => {
+                    let data = serde_json::to_string(&update).map_err(|e| e.to_string())?;
+                    format!("event: {}\ndata: {}\n\n", update.kind(), data)
+                }
+                // A missed partial is superseded by the next update
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return Ok(()),
+            },
+            _ = keepalive.tick() => ": keepalive\n\n".to_string(),
+        };
+        stream.write_all(chunk.as_bytes()).await.map_err(|e| e.to_string())?;
+    }
+}
@@ -0,0 +1,113 @@
+//! Shortcut gestures
+//!
+//! Gives the toggle shortcut gesture-level semantics instead of a 1:1
+//! mapping: a single press toggles, a double press cancels, and holding
+//! the keys records until they are released (push-to-talk).
+
+use super::ControlCommand;
+use std::future::Future;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::{timeout, Instant};
+
+/// Key transition reported by the global shortcut
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyEdge {
+    Pressed,
+    Released,
+}
+
+/// Timings of the gestures
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShortcutGestures {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Second press within this delay cancels the dictation
+    #[serde(default = "default_double_press_ms")]
+    pub double_press_ms: u64,
+    /// Holding longer than this records until release
+    #[serde(default = "default_long_press_ms")]
+    pub long_press_ms: u64,
+}
+
+fn default_double_press_ms() -> u64 {
+    400
+}
+
+fn default_long_press_ms() -> u64 {
+    500
+}
+
+impl Default for ShortcutGestures {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            double_press_ms: default_double_press_ms(),
+            long_press_ms: default_long_press_ms(),
+        }
+    }
+}
+
+/// Wait for `edge`, skipping the other one (key repeat...); `None` once closed
+async fn next(edges: &mut mpsc::UnboundedReceiver<KeyEdge>, edge: KeyEdge) -> Option<()> {
+    loop {
+        if edges.recv().await? == edge {
+            return Some(());
+        }
+    }
+}
+
+/// Turn shortcut transitions into commands until the channel closes
+///
+/// Starting is immediate (no added latency when idle). Stopping waits for
+/// the double-press delay, in case a second press turns it into a cancel.
+pub async fn recognize_gestures<R, RF, C>(
+    settings: ShortcutGestures,
+    mut edges: mpsc::UnboundedReceiver<KeyEdge>,
+    is_recording: R,
+    on_command: C,
+) where
+    R: Fn() -> RF,
+    RF: Future<Output = bool>,
+    C: Fn(ControlCommand),
+{
+    let double_press = Duration::from_millis(settings.double_press_ms);
+    let long_press = Duration::from_millis(settings.long_press_ms);
+
+    while next(&mut edges, KeyEdge::Pressed).await.is_some() {
+        let pressed_at = Instant::now();
+        let starting = !is_recording().await;
+
+        if starting {
+            on_command(ControlCommand::Start);
+            if next(&mut edges, KeyEdge::Released).await.is_none() {
+                return;
+            }
+            // Held: push-to-talk, the release ends the dictation
+            if pressed_at.elapsed() >= long_press {
+                on_command(ControlCommand::Stop);
+                continue;
+            }
+        } else if next(&mut edges, KeyEdge::Released).await.is_none() {
+            return;
+        }
+
+        // Tap: a second press right after cancels, otherwise the tap stands
+        let remaining = double_press.saturating_sub(pressed_at.elapsed());
+        match timeout(remaining, next(&mut edges, KeyEdge::Pressed)).await {
+            Ok(Some(())) => {
+                on_command(ControlCommand::Cancel);
+                if next(&mut edges, KeyEdge::Released).await.is_none() {
+                    return;
+                }
+            }
+            Ok(None) => return,
+            Err(_) => {
+                // A single tap while recording stops the dictation
+                if !starting {
+                    on_command(ControlCommand::Stop);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Media key trigger
+//!
+//! Headset buttons (Bluetooth AVRCP play/pause) reach the OS as media key
+//! events, delivered to the "now playing" app: MPRIS on Linux, the media
+//! remote on macOS, system media transport controls on Windows (souvlaki).
+//! Dictea registers as a paused player so play/pause toggles the dictation.
+//! Behind the `trigger-media` cargo feature; without it the trigger is ignored.
+
+use super::ControlCommand;
+
+#[cfg(feature = "trigger-media")]
+thread_local! {
+    /// Media session, alive as long as the app (dropping it unregisters the player)
+    static MEDIA_CONTROLS: std::cell::RefCell<Option<souvlaki::MediaControls>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Register the media session, to call from the main thread
+///
+/// `hwnd` is the main window handle, required on Windows.
+#[cfg(feature = "trigger-media")]
+pub fn listen_media_keys<F>(hwnd: Option<*mut std::ffi::c_void>, on_command: F)
+where
+    F: Fn(ControlCommand) + Send + 'static,
+{
+    use souvlaki::{MediaControlEvent, MediaControls, MediaMetadata, MediaPlayback, PlatformConfig};
+
+    let config = PlatformConfig {
+        dbus_name: "dictea",
+        display_name: "Dictea",
+        hwnd,
+    };
+    let registered = MediaControls::new(config).and_then(|mut controls| {
+        // Play, pause and play/pause all toggle: the player never reports "playing"
+        controls.attach(move |event| match event {
+            MediaControlEvent::Play | MediaControlEvent::Pause | MediaControlEvent::Toggle => {
+                on_command(ControlCommand::Toggle)
+            }
+            _ => {}
+        })?;
+        controls.set_metadata(MediaMetadata {
+            title: Some("Dictea"),
+            ..Default::default()
+        })?;
+        controls.set_playback(MediaPlayback::Paused { progress: None })?;
+        Ok(controls)
+    });
+
+    match registered {
+        Ok(controls) => {
+            tracing::info!("Media key trigger registered");
+            MEDIA_CONTROLS.with(|cell| *cell.borrow_mut() = Some(controls));
+        }
+        Err(e) => tracing::error!("Media key trigger unavailable: {:?}", e),
+    }
+}
+
+#[cfg(not(feature = "trigger-media"))]
+pub fn listen_media_keys<F>(_hwnd: Option<*mut std::ffi::c_void>, _on_command: F)
+where
+    F: Fn(ControlCommand) + Send + 'static,
+{
+    tracing::warn!("Media key trigger configured but not built in (feature trigger-media)");
+}
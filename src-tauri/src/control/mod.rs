@@ -0,0 +1,20 @@
+//! External control module
+//!
+//! Lets external devices and apps (Stream Deck plugins, foot pedals, headset
+//! buttons...)
+//! drive the dictation and follow its state, and companion devices
+//! mirror the live transcript.
+
+mod feed;
+mod gesture;
+mod media;
+mod shortcuts;
+mod socket;
+mod trigger;
+
+pub use feed::{push_to_webhook, serve_feed, LiveFeed, TranscriptUpdate};
+pub use gesture::{recognize_gestures, KeyEdge, ShortcutGestures};
+pub use media::listen_media_keys;
+pub use shortcuts::{shortcut_conflicts, shortcuts_changed, ShortcutAction, ShortcutBindings};
+pub use socket::{serve, ControlCommand, ControlSocket};
+pub use trigger::{spawn_gamepad_trigger, spawn_hid_trigger, GamepadTrigger, HidTrigger};
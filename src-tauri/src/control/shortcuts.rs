@@ -0,0 +1,168 @@
+//! Global shortcut bindings
+//!
+//! Maps each configured key combination to the action it triggers, so the
+//! shortcut handler can look actions up at runtime and the bindings can be
+//! replaced when the config changes, without restarting the app.
+
+use crate::config::{AppConfig, ShortcutMode};
+use tauri_plugin_global_shortcut::Shortcut;
+
+/// Action triggered by a global shortcut
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortcutAction {
+    /// Start, or stop and paste
+    Toggle,
+    /// Cancel the current recording
+    Cancel,
+    /// Dictation into the task manager
+    Task,
+    /// Dictation into an email draft
+    Email,
+    /// Dictation into an issue
+    Issue,
+    /// Dictation opened as a search
+    Search,
+    /// Time-boxed note kept in the history
+    QuickNote,
+    /// Delete the last pasted dictation
+    Undo,
+    /// Invert `reformulate` for the next dictation
+    ReformulateToggle,
+    /// Transcribe the audio of the clipboard into it
+    ClipboardAudio,
+    /// Lock the dictations to the frontmost window, or release it
+    TargetLock,
+    /// Flag the current point of the meeting being recorded
+    Annotate,
+}
+
+impl ShortcutAction {
+    /// Config field holding the shortcut, for error reporting
+    pub fn field(self) -> &'static str {
+        match self {
+            Self::Toggle => "global_shortcut",
+            Self::Cancel => "cancel_shortcut",
+            Self::Task => "task_capture",
+            Self::Email => "email_draft",
+            Self::Issue => "issue_tracker",
+            Self::Search => "search_launcher",
+            Self::QuickNote => "quick_note",
+            Self::Undo => "undo_shortcut",
+            Self::ReformulateToggle => "reformulate_toggle_shortcut",
+            Self::ClipboardAudio => "clipboard_audio_shortcut",
+            Self::TargetLock => "target_lock_shortcut",
+            Self::Annotate => "annotations",
+        }
+    }
+}
+
+/// Shortcut bound to an action
+#[derive(Debug, Clone)]
+pub struct ShortcutBinding {
+    pub shortcut: Shortcut,
+    pub action: ShortcutAction,
+    /// Key combination as written in the config
+    pub keys: String,
+}
+
+/// Shortcuts set in `config`, skipping empty, disabled and invalid ones
+fn configured(config: &AppConfig) -> Vec<ShortcutBinding> {
+    let candidates = [
+        (ShortcutAction::Toggle, true, &config.global_shortcut),
+        (ShortcutAction::Cancel, true, &config.cancel_shortcut),
+        (ShortcutAction::Task, config.task_capture.enabled, &config.task_capture.shortcut),
+        (ShortcutAction::Email, config.email_draft.enabled, &config.email_draft.shortcut),
+        (ShortcutAction::Issue, config.issue_tracker.enabled, &config.issue_tracker.shortcut),
+        (ShortcutAction::Search, config.search_launcher.enabled, &config.search_launcher.shortcut),
+        (ShortcutAction::QuickNote, config.quick_note.enabled, &config.quick_note.shortcut),
+        (ShortcutAction::Undo, true, &config.undo_shortcut),
+        (ShortcutAction::ReformulateToggle, true, &config.reformulate_toggle_shortcut),
+        (ShortcutAction::ClipboardAudio, true, &config.clipboard_audio_shortcut),
+        (ShortcutAction::TargetLock, true, &config.target_lock_shortcut),
+        (ShortcutAction::Annotate, true, &config.annotations.shortcut),
+    ];
+
+    candidates
+        .into_iter()
+        .filter(|(_, enabled, keys)| *enabled && !keys.trim().is_empty())
+        .filter_map(|(action, _, keys)| match keys.parse::<Shortcut>() {
+            Ok(shortcut) => Some(ShortcutBinding {
+                shortcut,
+                action,
+                keys: keys.clone(),
+            }),
+            Err(e) => {
+                tracing::warn!("Invalid {} shortcut {}: {}", action.field(), keys, e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Shortcuts whose combination is already bound to an earlier action
+pub fn shortcut_conflicts(config: &AppConfig) -> Vec<(ShortcutBinding, ShortcutAction)> {
+    let mut conflicts = Vec::new();
+    let mut bound: Vec<&ShortcutBinding> = Vec::new();
+    let configured = configured(config);
+    for binding in &configured {
+        if let Some(first) = bound.iter().find(|first| first.shortcut == binding.shortcut) {
+            conflicts.push((binding.clone(), first.action));
+        } else {
+            bound.push(binding);
+        }
+    }
+    conflicts
+}
+
+/// Active shortcuts of a config
+#[derive(Debug, Clone, Default)]
+pub struct ShortcutBindings {
+    bindings: Vec<ShortcutBinding>,
+    mode: ShortcutMode,
+}
+
+impl ShortcutBindings {
+    /// Active shortcuts of `config`, the first action keeping a combination used twice
+    pub fn from_config(config: &AppConfig) -> Self {
+        let mut bindings: Vec<ShortcutBinding> = Vec::new();
+        for binding in configured(config) {
+            if bindings.iter().any(|bound| bound.shortcut == binding.shortcut) {
+                tracing::warn!("Shortcut {} already bound, ignored for {}", binding.keys, binding.action.field());
+                continue;
+            }
+            bindings.push(binding);
+        }
+        Self {
+            bindings,
+            mode: config.shortcut_mode,
+        }
+    }
+
+    /// Action bound to `shortcut`
+    pub fn action(&self, shortcut: &Shortcut) -> Option<ShortcutAction> {
+        self.bindings
+            .iter()
+            .find(|binding| &binding.shortcut == shortcut)
+            .map(|binding| binding.action)
+    }
+
+    /// Toggle or push-to-talk behavior of the toggle shortcut
+    pub fn mode(&self) -> ShortcutMode {
+        self.mode
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ShortcutBinding> {
+        self.bindings.iter()
+    }
+}
+
+/// The shortcuts (or their mode) of `current` differ from the ones of `previous`
+pub fn shortcuts_changed(previous: &AppConfig, current: &AppConfig) -> bool {
+    let keys = |config: &AppConfig| -> Vec<(ShortcutAction, String)> {
+        ShortcutBindings::from_config(config)
+            .iter()
+            .map(|binding| (binding.action, binding.keys.clone()))
+            .collect()
+    };
+    keys(previous) != keys(current) || previous.shortcut_mode != current.shortcut_mode
+}
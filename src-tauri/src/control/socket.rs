@@ -0,0 +1,221 @@
+//! Local WebSocket control socket
+//!
+//! Listens on 127.0.0.1 only. Clients send `{"command": "toggle"}` (or
+//! `start`, `stop`, `cancel`) and receive every state change as it happens,
+//! starting with the current one, so a Stream Deck key can light up while
+//! recording.
+
+use crate::events::AppStatus;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::OsRng;
+use futures_util::{SinkExt, StreamExt};
+use std::sync::Arc;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, RwLock};
+use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tokio_tungstenite::tungstenite::http::StatusCode;
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+/// Settings of the control socket
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ControlSocket {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_port")]
+    pub port: u16,
+    /// Shared secret expected as `?token=...`, generated when the socket
+    /// is enabled without one
+    #[serde(default)]
+    pub token: String,
+}
+
+/// Bytes of randomness of a generated token
+const TOKEN_LEN: usize = 32;
+
+fn default_port() -> u16 {
+    7781
+}
+
+impl Default for ControlSocket {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: default_port(),
+            token: String::new(),
+        }
+    }
+}
+
+impl ControlSocket {
+    /// Generate a random token if the socket is enabled without one
+    ///
+    /// Returns whether the token changed, so the caller can save it.
+    pub fn ensure_token(&mut self) -> bool {
+        if !self.enabled || !self.token.trim().is_empty() {
+            return false;
+        }
+        self.token = generate_token();
+        true
+    }
+}
+
+/// Random hex token, shared by the control socket and the live feed
+pub(super) fn generate_token() -> String {
+    let mut bytes = [0u8; TOKEN_LEN];
+    OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Command received from a client
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ControlCommand {
+    /// Start if idle, stop and paste if recording
+    Toggle,
+    Start,
+    Stop,
+    Cancel,
+}
+
+#[derive(serde::Deserialize)]
+struct CommandRequest {
+    command: ControlCommand,
+}
+
+/// Accept clients until the listener cannot be bound
+///
+/// `status` is the current state, `updates` publishes every change, and
+/// `on_command` is called for each valid command.
+pub async fn serve<F>(
+    settings: ControlSocket,
+    status: Arc<RwLock<AppStatus>>,
+    updates: broadcast::Sender<AppStatus>,
+    on_command: F,
+) -> std::io::Result<()>
+where
+    F: Fn(ControlCommand) + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind(("127.0.0.1", settings.port)).await?;
+    tracing::info!("Control socket listening on ws://127.0.0.1:{}", settings.port);
+
+    let on_command = Arc::new(on_command);
+    let token = Arc::new(settings.token);
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                // Per-connection failures (reset, too many open files) must
+                // not take the socket down
+                tracing::warn!("Control socket accept error: {}", e);
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                continue;
+            }
+        };
+        let status = status.clone();
+        let updates = updates.subscribe();
+        let on_command = on_command.clone();
+        let token = token.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_client(stream, &token, status, updates, on_command.as_ref()).await {
+                tracing::debug!("Control client {} disconnected: {}", peer, e);
+            }
+        });
+    }
+}
+
+/// Whether the handshake request comes from a web page
+///
+/// Browsers always send `Origin` on a WebSocket handshake, native
+/// controllers don't: any site could otherwise drive the recorder.
+fn from_browser(request: &Request) -> bool {
+    request.headers().contains_key("origin")
+}
+
+/// Whether the handshake request carries the expected token
+fn authorized(request: &Request, token: &str) -> bool {
+    if token.is_empty() {
+        return false;
+    }
+    request
+        .uri()
+        .query()
+        .unwrap_or_default()
+        .split('&')
+        .filter_map(|pair| pair.strip_prefix("token="))
+        .fold(false, |found, candidate| {
+            found | constant_time_eq(candidate.as_bytes(), token.as_bytes())
+        })
+}
+
+/// Compare two secrets without an early exit on the first difference
+pub(super) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+async fn handle_client<F>(
+    stream: TcpStream,
+    token: &str,
+    status: Arc<RwLock<AppStatus>>,
+    mut updates: broadcast::Receiver<AppStatus>,
+    on_command: &F,
+) -> Result<(), String>
+where
+    F: Fn(ControlCommand),
+{
+    let check = |request: &Request, response: Response| -> Result<Response, ErrorResponse> {
+        let (status, reason) = if from_browser(request) {
+            (StatusCode::FORBIDDEN, "browser origin")
+        } else if !authorized(request, token) {
+            (StatusCode::UNAUTHORIZED, "invalid token")
+        } else {
+            return Ok(response);
+        };
+        let mut rejection = ErrorResponse::new(Some(reason.to_string()));
+        *rejection.status_mut() = status;
+        Err(rejection)
+    };
+    let socket = tokio_tungstenite::accept_hdr_async(stream, check)
+        .await
+        .map_err(|e| e.to_string())?;
+    let (mut sink, mut incoming) = socket.split();
+
+    let current = status.read().await.clone();
+    send_status(&mut sink, &current).await?;
+
+    loop {
+        tokio::select! {
+            update = updates.recv() => match update {
+                Ok(update) => send_status(&mut sink, &update).await?,
+                Err(broadcast::error::RecvError::Lagged(_)) => {
+                    // Only the latest state matters
+                    let current = status.read().await.clone();
+                    send_status(&mut sink, &current).await?;
+                }
+                Err(broadcast::error::RecvError::Closed) => return Ok(()),
+            },
+            message = incoming.next() => match message {
+                Some(Ok(WsMessage::Text(text))) => {
+                    match serde_json::from_str::<CommandRequest>(&text) {
+                        Ok(request) => on_command(request.command),
+                        Err(e) => tracing::warn!("Invalid control command {}: {}", text, e),
+                    }
+                }
+                Some(Ok(WsMessage::Close(_))) | None => return Ok(()),
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return Err(e.to_string()),
+            },
+        }
+    }
+}
+
+async fn send_status<S>(sink: &mut S, status: &AppStatus) -> Result<(), String>
+where
+    S: SinkExt<WsMessage> + Unpin,
+    S::Error: std::fmt::Display,
+{
+    let json = serde_json::to_string(status).map_err(|e| e.to_string())?;
+    sink.send(WsMessage::Text(json)).await.map_err(|e| e.to_string())
+}
@@ -0,0 +1,176 @@
+//! Hardware dictation triggers
+//!
+//! Foot pedals and gamepad buttons cannot be bound as keyboard shortcuts.
+//! Pedals are read as raw HID reports (hidapi), gamepads through gilrs.
+//! Each backend is a cargo feature; without it the trigger is ignored.
+
+use super::ControlCommand;
+
+/// What pressing the bound button does
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerMode {
+    /// Press to start, press again to stop
+    #[default]
+    Toggle,
+    /// Record while the button is held
+    PushToTalk,
+}
+
+impl TriggerMode {
+    /// Command for a button transition, if any
+    fn command(&self, pressed: bool) -> Option<ControlCommand> {
+        match (self, pressed) {
+            (TriggerMode::Toggle, true) => Some(ControlCommand::Toggle),
+            (TriggerMode::Toggle, false) => None,
+            (TriggerMode::PushToTalk, true) => Some(ControlCommand::Start),
+            (TriggerMode::PushToTalk, false) => Some(ControlCommand::Stop),
+        }
+    }
+}
+
+/// Foot pedal (or any HID device) bound to the dictation
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct HidTrigger {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub vendor_id: u16,
+    #[serde(default)]
+    pub product_id: u16,
+    /// Byte of the input report holding the button state
+    #[serde(default)]
+    pub report_byte: usize,
+    /// Bit(s) of that byte set while the button is down
+    #[serde(default = "default_button_mask")]
+    pub button_mask: u8,
+    #[serde(default)]
+    pub mode: TriggerMode,
+}
+
+fn default_button_mask() -> u8 {
+    0x01
+}
+
+impl Default for HidTrigger {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            vendor_id: 0,
+            product_id: 0,
+            report_byte: 0,
+            button_mask: default_button_mask(),
+            mode: TriggerMode::default(),
+        }
+    }
+}
+
+/// Gamepad button bound to the dictation
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct GamepadTrigger {
+    #[serde(default)]
+    pub enabled: bool,
+    /// gilrs button name ("South", "RightTrigger", "Start"...)
+    #[serde(default)]
+    pub button: String,
+    #[serde(default)]
+    pub mode: TriggerMode,
+}
+
+/// Listen to the configured pedal in a background thread
+#[cfg(feature = "trigger-hid")]
+pub fn spawn_hid_trigger<F>(settings: HidTrigger, on_command: F)
+where
+    F: Fn(ControlCommand) + Send + 'static,
+{
+    std::thread::spawn(move || {
+        let device = hidapi::HidApi::new()
+            .and_then(|api| api.open(settings.vendor_id, settings.product_id));
+        let device = match device {
+            Ok(device) => device,
+            Err(e) => {
+                tracing::error!(
+                    "HID trigger {:04x}:{:04x} unavailable: {}",
+                    settings.vendor_id,
+                    settings.product_id,
+                    e
+                );
+                return;
+            }
+        };
+        tracing::info!("HID trigger {:04x}:{:04x} opened", settings.vendor_id, settings.product_id);
+
+        let mut report = [0u8; 64];
+        let mut was_pressed = false;
+        loop {
+            let len = match device.read(&mut report) {
+                Ok(len) => len,
+                Err(e) => {
+                    tracing::error!("HID trigger read error: {}", e);
+                    return;
+                }
+            };
+            let Some(&byte) = report[..len].get(settings.report_byte) else {
+                continue;
+            };
+            let pressed = byte & settings.button_mask != 0;
+            if pressed != was_pressed {
+                was_pressed = pressed;
+                if let Some(command) = settings.mode.command(pressed) {
+                    on_command(command);
+                }
+            }
+        }
+    });
+}
+
+#[cfg(not(feature = "trigger-hid"))]
+pub fn spawn_hid_trigger<F>(_settings: HidTrigger, _on_command: F)
+where
+    F: Fn(ControlCommand) + Send + 'static,
+{
+    tracing::warn!("HID trigger configured but not built in (feature trigger-hid)");
+}
+
+/// Listen to the configured gamepad button in a background thread
+#[cfg(feature = "trigger-gamepad")]
+pub fn spawn_gamepad_trigger<F>(settings: GamepadTrigger, on_command: F)
+where
+    F: Fn(ControlCommand) + Send + 'static,
+{
+    use gilrs::{EventType, Gilrs};
+
+    std::thread::spawn(move || {
+        let mut gilrs = match Gilrs::new() {
+            Ok(gilrs) => gilrs,
+            Err(e) => {
+                tracing::error!("Gamepad trigger unavailable: {}", e);
+                return;
+            }
+        };
+        tracing::info!("Gamepad trigger listening for {}", settings.button);
+
+        let matches = |button: gilrs::Button| format!("{:?}", button).eq_ignore_ascii_case(&settings.button);
+        loop {
+            while let Some(event) = gilrs.next_event() {
+                let pressed = match event.event {
+                    EventType::ButtonPressed(button, _) if matches(button) => true,
+                    EventType::ButtonReleased(button, _) if matches(button) => false,
+                    _ => continue,
+                };
+                if let Some(command) = settings.mode.command(pressed) {
+                    on_command(command);
+                }
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+    });
+}
+
+#[cfg(not(feature = "trigger-gamepad"))]
+pub fn spawn_gamepad_trigger<F>(_settings: GamepadTrigger, _on_command: F)
+where
+    F: Fn(ControlCommand) + Send + 'static,
+{
+    tracing::warn!("Gamepad trigger configured but not built in (feature trigger-gamepad)");
+}
@@ -0,0 +1,126 @@
+//! Command error with a stable code
+
+use crate::config::FieldError;
+use crate::i18n::Message;
+use std::fmt;
+
+/// Error category, stable across releases
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// No API key for the selected provider
+    MissingKey,
+    /// The provider rejected the API key (401/403)
+    InvalidKey,
+    /// Microphone access refused by the OS
+    MicDenied,
+    /// Other audio capture or playback failure
+    Audio,
+    /// Provider unreachable (DNS, timeout, TLS...)
+    Network,
+    /// Rate limit or exhausted credits (429)
+    Quota,
+    /// Rejected configuration, see `fields`
+    InvalidConfig,
+    /// Unknown recording, device...
+    NotFound,
+    /// Anything else
+    Internal,
+}
+
+/// Error returned by every command
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DicteaError {
+    pub code: ErrorCode,
+    /// Localizable message to display
+    pub message: Message,
+    /// Raw error text (provider response, OS error...)
+    pub details: Option<String>,
+    /// Offending config fields, for `invalid_config`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldError>,
+}
+
+impl DicteaError {
+    pub fn new(code: ErrorCode, message: Message) -> Self {
+        let details = message.args.get("details").cloned();
+        Self {
+            code,
+            message,
+            details,
+            fields: Vec::new(),
+        }
+    }
+}
+
+/// Guess the category of a raw error text (provider or OS error)
+fn classify_details(details: &str) -> Option<ErrorCode> {
+    let lower = details.to_lowercase();
+    if lower.contains(" 429") || lower.contains("rate limit") || lower.contains("quota") {
+        Some(ErrorCode::Quota)
+    } else if lower.contains(" 401") || lower.contains(" 403") || lower.contains("invalid api key") {
+        Some(ErrorCode::InvalidKey)
+    } else if lower.contains("network error") || lower.contains("timed out") || lower.contains("dns") {
+        Some(ErrorCode::Network)
+    } else if lower.contains("permission") || lower.contains("denied") || lower.contains("not permitted") {
+        Some(ErrorCode::MicDenied)
+    } else {
+        None
+    }
+}
+
+impl From<Message> for DicteaError {
+    fn from(message: Message) -> Self {
+        let from_details = message.args.get("details").and_then(|d| classify_details(d));
+        let code = match message.key.as_str() {
+            key if key.starts_with("api_key_required") => ErrorCode::MissingKey,
+            "audio_error" => match from_details {
+                Some(ErrorCode::MicDenied) => ErrorCode::MicDenied,
+                _ => ErrorCode::Audio,
+            },
+            "playback_error" => ErrorCode::Audio,
+            "profile_sync_error" => ErrorCode::Network,
+            "history_sync_error" => from_details.unwrap_or(ErrorCode::Internal),
+            "local_worker_required"
+            | "whisper_model_required"
+            | "history_sync_unconfigured"
+            | "google_project_required" => ErrorCode::InvalidConfig,
+            "recording_not_found"
+            | "nothing_to_undo"
+            | "annotation_needs_meeting"
+            | "clipboard_no_audio"
+            | "unknown_speaker_profile"
+            | "unknown_issue_profile" => ErrorCode::NotFound,
+            "transcription_error" | "engine_error" | "error_generic" => match from_details {
+                Some(ErrorCode::MicDenied) | None => ErrorCode::Internal,
+                Some(code) => code,
+            },
+            _ => ErrorCode::Internal,
+        };
+        Self::new(code, message)
+    }
+}
+
+/// Untranslated error text
+impl From<String> for DicteaError {
+    fn from(details: String) -> Self {
+        Message::from(details).into()
+    }
+}
+
+/// Config rejected by validation
+impl From<Vec<FieldError>> for DicteaError {
+    fn from(fields: Vec<FieldError>) -> Self {
+        Self {
+            fields,
+            ..Self::new(ErrorCode::InvalidConfig, Message::new("invalid_config"))
+        }
+    }
+}
+
+/// Code and message, for logs
+impl fmt::Display for DicteaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{:?}] {}", self.code, self.message)
+    }
+}
@@ -0,0 +1,9 @@
+//! Errors returned by the Tauri commands
+//!
+//! Every command fails with a `DicteaError`: a stable code the frontend
+//! branches on to show the matching recovery UI, plus the localizable
+//! message and raw details for display and bug reports.
+
+mod command;
+
+pub use command::DicteaError;
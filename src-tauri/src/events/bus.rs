@@ -0,0 +1,123 @@
+//! Session-scoped event bus
+//!
+//! Each dictation opens a fresh broadcast channel and closes it when the
+//! session ends. Consumer tasks see `RecvError::Closed` and exit instead of
+//! waiting forever on a channel that outlives them.
+
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// Events queued per subscriber before the slowest one starts lagging
+const BUS_CAPACITY: usize = 100;
+
+/// Broadcast channel living for the duration of one session
+pub struct SessionBus<T: Clone> {
+    sender: Option<broadcast::Sender<T>>,
+}
+
+impl<T: Clone> SessionBus<T> {
+    /// Closed bus, call `open` when a session starts
+    pub fn new() -> Self {
+        Self { sender: None }
+    }
+
+    /// Start a new session, closing the previous one if still open
+    pub fn open(&mut self) {
+        self.close();
+        let (sender, _) = broadcast::channel(BUS_CAPACITY);
+        self.sender = Some(sender);
+    }
+
+    /// End the session: every subscriber gets `Closed` once drained
+    pub fn close(&mut self) {
+        if let Some(sender) = self.sender.take() {
+            tracing::debug!(
+                "Event bus closed, releasing {} subscriber(s)",
+                sender.receiver_count()
+            );
+        }
+    }
+
+    /// Subscribe to the current session, `None` if no session is open
+    pub fn subscribe(&self) -> Option<SessionReceiver<T>> {
+        self.sender.as_ref().map(|sender| SessionReceiver {
+            receiver: sender.subscribe(),
+        })
+    }
+
+    /// Publish to the current session, dropped if no session is open
+    pub fn publish(&self, event: T) {
+        if let Some(ref sender) = self.sender {
+            let _ = sender.send(event);
+        }
+    }
+
+    /// Number of live subscribers on the current session
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.as_ref().map_or(0, |s| s.receiver_count())
+    }
+}
+
+impl<T: Clone> Default for SessionBus<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Subscriber handle, yields events until its session is closed
+pub struct SessionReceiver<T: Clone> {
+    receiver: broadcast::Receiver<T>,
+}
+
+impl<T: Clone> SessionReceiver<T> {
+    /// Next event, `None` once the session has ended
+    pub async fn recv(&mut self) -> Option<T> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) => return Some(event),
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Event bus subscriber lagged, {} events skipped", skipped);
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn closing_releases_every_subscriber() {
+        let mut bus = SessionBus::<u32>::new();
+        for session in 0..3 {
+            bus.open();
+            let mut first = bus.subscribe().expect("session is open");
+            let mut second = bus.subscribe().expect("session is open");
+            assert_eq!(bus.subscriber_count(), 2);
+
+            bus.publish(session);
+            bus.close();
+            assert_eq!(bus.subscriber_count(), 0);
+
+            // Queued events are still delivered, then the session ends
+            assert_eq!(first.recv().await, Some(session));
+            assert_eq!(first.recv().await, None);
+            assert_eq!(second.recv().await, Some(session));
+            assert_eq!(second.recv().await, None);
+        }
+        assert!(bus.subscribe().is_none());
+    }
+
+    #[tokio::test]
+    async fn reopening_ends_the_previous_session() {
+        let mut bus = SessionBus::<u32>::new();
+        bus.open();
+        let mut stale = bus.subscribe().expect("session is open");
+        bus.open();
+        assert_eq!(bus.subscriber_count(), 0);
+
+        bus.publish(1);
+        assert_eq!(stale.recv().await, None);
+    }
+}
@@ -0,0 +1,73 @@
+//! Replay buffer of recent state events
+//!
+//! A webview that finishes loading after `recording_started` (or any other
+//! state event) has fired would render a stale state. The last events are
+//! kept in memory so late subscribers can catch up through `sync_state`.
+
+use std::collections::VecDeque;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Event as it was emitted to the frontend
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RecordedEvent {
+    /// Monotonic sequence number, to dedupe against live events
+    pub seq: u64,
+    /// Event name
+    pub event: String,
+    pub payload: serde_json::Value,
+    /// Emission time, in milliseconds since the Unix epoch
+    pub timestamp_ms: u64,
+}
+
+/// Bounded history of the last state events
+pub struct EventHistory {
+    events: VecDeque<RecordedEvent>,
+    capacity: usize,
+    next_seq: u64,
+}
+
+impl EventHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            events: VecDeque::with_capacity(capacity),
+            capacity,
+            next_seq: 0,
+        }
+    }
+
+    /// Change the number of kept events, dropping the oldest if needed
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.events.len() > capacity {
+            self.events.pop_front();
+        }
+    }
+
+    /// Record an event, returning its sequence number
+    pub fn record(&mut self, event: &str, payload: serde_json::Value) -> u64 {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        if self.capacity == 0 {
+            return seq;
+        }
+        if self.events.len() == self.capacity {
+            self.events.pop_front();
+        }
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        self.events.push_back(RecordedEvent {
+            seq,
+            event: event.to_string(),
+            payload,
+            timestamp_ms,
+        });
+        seq
+    }
+
+    /// Recorded events, oldest first
+    pub fn snapshot(&self) -> Vec<RecordedEvent> {
+        self.events.iter().cloned().collect()
+    }
+}
@@ -0,0 +1,11 @@
+//! Frontend events module
+//!
+//! Typed events pushed from the backend to the UI.
+
+mod bus;
+mod history;
+mod status;
+
+pub use bus::{SessionBus, SessionReceiver};
+pub use history::{EventHistory, RecordedEvent};
+pub use status::{AppStatus, DictationPhase, STATUS_EVENT};
@@ -0,0 +1,60 @@
+//! Dictation state machine, published on a single typed channel
+//!
+//! Replaces the legacy string events (`recording_started`,
+//! `processing_started`, `recording_stopped`, `config_error`...), which are
+//! still emitted for now.
+
+use crate::i18n::Message;
+
+/// Event carrying every state change
+pub const STATUS_EVENT: &str = "dictea://state";
+
+/// Dictation state
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum AppStatus {
+    /// Waiting for a dictation
+    #[default]
+    Idle,
+    /// Capturing audio
+    Recording { engine: String },
+    /// Audio captured, waiting for the STT engine
+    Transcribing,
+    /// Reformulating and/or translating the transcript
+    Processing { text: String },
+    /// Dictation ended without any recognized speech
+    NoSpeech,
+    /// Copying to the clipboard and simulating paste
+    Pasting { text: String },
+    /// Last action failed
+    Error { message: Message },
+}
+
+/// Coarse phase of the current dictation, for frontends that poll
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DictationPhase {
+    /// No dictation in progress
+    #[default]
+    Idle,
+    /// Capturing audio
+    Recording,
+    /// Waiting for the STT engine
+    Transcribing,
+    /// Reformulating and/or translating
+    Processing,
+    /// Delivering the text to the target application
+    Pasting,
+}
+
+impl From<&AppStatus> for DictationPhase {
+    fn from(status: &AppStatus) -> Self {
+        match status {
+            AppStatus::Recording { .. } => Self::Recording,
+            AppStatus::Transcribing => Self::Transcribing,
+            AppStatus::Processing { .. } => Self::Processing,
+            AppStatus::Pasting { .. } => Self::Pasting,
+            AppStatus::Idle | AppStatus::NoSpeech | AppStatus::Error { .. } => Self::Idle,
+        }
+    }
+}
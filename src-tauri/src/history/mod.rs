@@ -0,0 +1,12 @@
+//! Transcription history module
+//!
+//! Keeps every finished dictation (text, engine, duration, processing) on
+//! disk so it can be browsed, copied again or deleted later, and optionally
+//! syncs it, end-to-end encrypted, between machines.
+
+mod store;
+mod sync;
+
+pub use store::{HistoryEntry, HistoryStore};
+pub(crate) use sync::is_local_http;
+pub use sync::{sync_history, HistorySync, SyncReport};
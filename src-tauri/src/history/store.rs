@@ -0,0 +1,222 @@
+//! On-disk history of dictations
+//!
+//! One JSON object per line, appended as dictations finish, oldest first.
+//! Deleted ids are kept aside with their deletion time, for the sync.
+
+use super::sync::{merge, SyncedHistory, TOMBSTONE_TTL_MILLIS};
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Serializes the changes, dictations may finish concurrently
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// A finished dictation
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct HistoryEntry {
+    pub id: String,
+    /// End of the dictation (milliseconds since Unix epoch)
+    pub created_at: u64,
+    /// Engine that transcribed it
+    pub engine: String,
+    /// Recording time, pauses included
+    pub duration_secs: f64,
+    /// Transcript as returned by the engine
+    pub raw_text: String,
+    /// Text after reformulation/translation
+    pub text: String,
+    #[serde(default)]
+    pub reformulated: bool,
+    #[serde(default)]
+    pub translated: bool,
+    /// Saved recording of the dictation, if any
+    #[serde(default)]
+    pub recording_id: Option<String>,
+    /// Title generated for a quick note
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
+impl HistoryEntry {
+    /// Entry for a dictation ending now
+    pub fn new(engine: &str, duration_secs: f64, raw_text: &str, text: &str) -> Self {
+        let created_at = now_millis();
+        Self {
+            id: created_at.to_string(),
+            created_at,
+            engine: engine.to_string(),
+            duration_secs,
+            raw_text: raw_text.to_string(),
+            text: text.to_string(),
+            reformulated: false,
+            translated: false,
+            recording_id: None,
+            title: None,
+        }
+    }
+}
+
+/// JSON-lines file holding the history
+#[derive(Debug, Clone)]
+pub struct HistoryStore {
+    path: PathBuf,
+}
+
+impl HistoryStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Add a dictation, then drop the oldest ones past `max_entries` (0 = unlimited)
+    pub fn append(&self, entry: &HistoryEntry, max_entries: usize) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("History dir error: {}", e))?;
+        }
+        let line = serde_json::to_string(entry).map_err(|e| e.to_string())?;
+        let _lock = WRITE_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| format!("History open error: {}", e))?;
+        writeln!(file, "{}", line).map_err(|e| format!("History write error: {}", e))?;
+
+        if max_entries > 0 {
+            let entries = self.read();
+            if entries.len() > max_entries {
+                self.write(&entries[entries.len() - max_entries..])?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Saved dictations, most recent first (`limit` 0 = all)
+    pub fn list(&self, limit: usize) -> Vec<HistoryEntry> {
+        let mut entries = self.read();
+        entries.reverse();
+        if limit > 0 {
+            entries.truncate(limit);
+        }
+        entries
+    }
+
+    /// Delete one dictation
+    pub fn delete(&self, id: &str) -> Result<(), String> {
+        let _lock = WRITE_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let entries = self.read();
+        let kept: Vec<HistoryEntry> = entries.iter().filter(|entry| entry.id != id).cloned().collect();
+        if kept.len() == entries.len() {
+            return Err(format!("Unknown history entry: {}", id));
+        }
+        self.write(&kept)?;
+        self.add_deleted([id.to_string()])
+    }
+
+    /// Delete every dictation, returning how many were removed
+    pub fn clear(&self) -> Result<usize, String> {
+        let _lock = WRITE_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let entries = self.read();
+        match std::fs::remove_file(&self.path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(format!("History delete error: {}", e)),
+        }
+        self.add_deleted(entries.iter().map(|entry| entry.id.clone()))?;
+        Ok(entries.len())
+    }
+
+    /// Merge a synced copy into the history, returning the merged history
+    /// and how many entries it brought
+    pub fn merge_synced(&self, remote: SyncedHistory, max_entries: usize) -> Result<(SyncedHistory, usize), String> {
+        let _lock = WRITE_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let local = SyncedHistory {
+            entries: self.read(),
+            deleted: self.read_deleted(),
+        };
+        let known: std::collections::HashSet<String> = local.entries.iter().map(|entry| entry.id.clone()).collect();
+        let mut merged = merge(local, remote);
+        let now = now_millis();
+        if max_entries > 0 && merged.entries.len() > max_entries {
+            // Other machines drop them too instead of sending them back
+            let trimmed: Vec<HistoryEntry> = merged.entries.drain(..merged.entries.len() - max_entries).collect();
+            merged.deleted.extend(trimmed.into_iter().map(|entry| (entry.id, now)));
+        }
+        // A deletion must outlive the copies of the entry on machines that
+        // have not synced yet, however old the entry is
+        merged.deleted.retain(|_, at| now.saturating_sub(*at) < TOMBSTONE_TTL_MILLIS);
+        let pulled = merged.entries.iter().filter(|entry| !known.contains(&entry.id)).count();
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("History dir error: {}", e))?;
+        }
+        self.write(&merged.entries)?;
+        self.write_deleted(&merged.deleted)?;
+        Ok((merged, pulled))
+    }
+
+    /// File of the deleted ids, next to the history
+    fn deleted_path(&self) -> PathBuf {
+        self.path.with_extension("deleted.json")
+    }
+
+    /// Deleted ids, with the time of deletion
+    fn read_deleted(&self) -> BTreeMap<String, u64> {
+        std::fs::read_to_string(self.deleted_path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn write_deleted(&self, deleted: &BTreeMap<String, u64>) -> Result<(), String> {
+        let content = serde_json::to_string(deleted).map_err(|e| e.to_string())?;
+        std::fs::write(self.deleted_path(), content).map_err(|e| format!("History write error: {}", e))
+    }
+
+    /// Remember the deletion of `ids` now
+    fn add_deleted(&self, ids: impl IntoIterator<Item = String>) -> Result<(), String> {
+        let mut deleted = self.read_deleted();
+        let now = now_millis();
+        deleted.extend(ids.into_iter().map(|id| (id, now)));
+        self.write_deleted(&deleted)
+    }
+
+    /// Every entry, oldest first, skipping unreadable lines
+    fn read(&self) -> Vec<HistoryEntry> {
+        let Ok(content) = std::fs::read_to_string(&self.path) else {
+            return Vec::new();
+        };
+        content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| match serde_json::from_str(line) {
+                Ok(entry) => Some(entry),
+                Err(e) => {
+                    tracing::warn!("Unreadable history entry skipped: {}", e);
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Replace the file with `entries`, through a temporary file
+    fn write(&self, entries: &[HistoryEntry]) -> Result<(), String> {
+        let mut content = String::new();
+        for entry in entries {
+            content.push_str(&serde_json::to_string(entry).map_err(|e| e.to_string())?);
+            content.push('\n');
+        }
+        let temp = self.path.with_extension("jsonl.tmp");
+        std::fs::write(&temp, content).map_err(|e| format!("History write error: {}", e))?;
+        std::fs::rename(&temp, &self.path).map_err(|e| format!("History write error: {}", e))
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
@@ -0,0 +1,393 @@
+//! End-to-end encrypted sync of the history between machines
+//!
+//! The history is merged with a copy kept on a user-provided backend (a
+//! WebDAV file or an S3 object), then the merged history is written back.
+//! The copy is encrypted on this machine with a key derived from the
+//! passphrase (Argon2id, XChaCha20-Poly1305): the backend only stores
+//! ciphertext. Entries are merged by id, the most recent one winning a
+//! conflict, and deletions travel as tombstones so a dictation deleted on
+//! one machine does not come back from another.
+
+use super::store::{HistoryEntry, HistoryStore};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::XChaCha20Poly1305;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+/// Settings of the history sync
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct HistorySync {
+    #[serde(default)]
+    pub enabled: bool,
+    /// WebDAV file (https://..., http:// to localhost only) or S3 object (s3://bucket/key)
+    #[serde(default)]
+    pub url: String,
+    /// WebDAV user, or S3 access key id
+    #[serde(default)]
+    pub username: String,
+    /// WebDAV password, or S3 secret access key
+    #[serde(default)]
+    pub password: String,
+    /// Region of the S3 bucket
+    #[serde(default = "default_s3_region")]
+    pub s3_region: String,
+    /// S3-compatible endpoint (MinIO, R2...), empty = AWS
+    #[serde(default)]
+    pub s3_endpoint: String,
+    /// Encryption passphrase, never sent; the same on every machine
+    #[serde(default)]
+    pub passphrase: String,
+    /// Time between two background syncs
+    #[serde(default = "default_interval_mins")]
+    pub interval_mins: u64,
+}
+
+fn default_s3_region() -> String {
+    "us-east-1".to_string()
+}
+
+fn default_interval_mins() -> u64 {
+    15
+}
+
+impl Default for HistorySync {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: String::new(),
+            username: String::new(),
+            password: String::new(),
+            s3_region: default_s3_region(),
+            s3_endpoint: String::new(),
+            passphrase: String::new(),
+            interval_mins: default_interval_mins(),
+        }
+    }
+}
+
+impl HistorySync {
+    pub fn is_valid_url(&self) -> bool {
+        self.url.starts_with("https://")
+            || is_local_http(&self.url)
+            || self.url.strip_prefix("s3://").is_some_and(|path| path.contains('/'))
+    }
+}
+
+/// Whether `url` is plain HTTP to this machine, the only place the WebDAV
+/// password may travel in clear
+pub(crate) fn is_local_http(url: &str) -> bool {
+    let Some(rest) = url.strip_prefix("http://") else {
+        return false;
+    };
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    let host = match host.strip_prefix('[') {
+        Some(ipv6) => ipv6.split(']').next().unwrap_or_default(),
+        None => host.split(':').next().unwrap_or_default(),
+    };
+    host.eq_ignore_ascii_case("localhost") || host == "127.0.0.1" || host == "::1"
+}
+
+/// History as exchanged with the backend, before encryption
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct SyncedHistory {
+    /// Oldest first
+    pub entries: Vec<HistoryEntry>,
+    /// Deleted entry ids, with the time of deletion (milliseconds since Unix epoch)
+    #[serde(default)]
+    pub deleted: BTreeMap<String, u64>,
+}
+
+/// Time a deletion is kept: a machine coming back after longer than that
+/// may bring the deleted entries back
+pub(super) const TOMBSTONE_TTL_MILLIS: u64 = 180 * 24 * 3600 * 1000;
+
+/// Merge two copies of the history
+///
+/// An entry present on both sides with different contents keeps the most
+/// recent version; an entry deleted after it was created stays deleted.
+pub(super) fn merge(local: SyncedHistory, remote: SyncedHistory) -> SyncedHistory {
+    let mut deleted = local.deleted;
+    for (id, at) in remote.deleted {
+        let kept = deleted.entry(id).or_insert(at);
+        *kept = (*kept).max(at);
+    }
+
+    let mut entries: BTreeMap<String, HistoryEntry> = BTreeMap::new();
+    for entry in local.entries.into_iter().chain(remote.entries) {
+        match entries.get(&entry.id) {
+            Some(kept) if kept.created_at >= entry.created_at => {}
+            _ => {
+                entries.insert(entry.id.clone(), entry);
+            }
+        }
+    }
+    let mut entries: Vec<HistoryEntry> = entries
+        .into_values()
+        .filter(|entry| deleted.get(&entry.id).is_none_or(|&at| at < entry.created_at))
+        .collect();
+    entries.sort_by_key(|entry| entry.created_at);
+    SyncedHistory { entries, deleted }
+}
+
+/// Result of a sync
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SyncReport {
+    /// Entries brought from the other machines
+    pub pulled: usize,
+    /// Entries in the history after the merge
+    pub total: usize,
+}
+
+/// Header of the encrypted copy, followed by the salt, the nonce and the ciphertext
+const MAGIC: &[u8] = b"DICTEA-HISTORY-1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<chacha20poly1305::Key, String> {
+    let mut key = chacha20poly1305::Key::default();
+    argon2::Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("Key derivation error: {}", e))?;
+    Ok(key)
+}
+
+fn encrypt(plaintext: &[u8], passphrase: &str) -> Result<Vec<u8>, String> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let cipher = XChaCha20Poly1305::new(&derive_key(passphrase, &salt)?);
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| "History encryption error".to_string())?;
+    Ok([MAGIC, &salt, nonce.as_slice(), &ciphertext].concat())
+}
+
+fn decrypt(data: &[u8], passphrase: &str) -> Result<Vec<u8>, String> {
+    let body = data
+        .strip_prefix(MAGIC)
+        .filter(|body| body.len() > SALT_LEN + NONCE_LEN)
+        .ok_or_else(|| "The synced history is not a dictea history".to_string())?;
+    let (salt, rest) = body.split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    let cipher = XChaCha20Poly1305::new(&derive_key(passphrase, salt)?);
+    cipher
+        .decrypt(chacha20poly1305::XNonce::from_slice(nonce), ciphertext)
+        .map_err(|_| "Wrong passphrase, or the synced history is corrupted".to_string())
+}
+
+/// Merge the history with the synced copy, then upload the merged history
+pub async fn sync_history(store: &HistoryStore, settings: &HistorySync, max_entries: usize) -> Result<SyncReport, String> {
+    let client = reqwest::Client::new();
+    let remote = match download(&client, settings).await? {
+        Some(data) => {
+            let plaintext = decrypt(&data, &settings.passphrase)?;
+            serde_json::from_slice(&plaintext).map_err(|e| format!("Synced history read error: {}", e))?
+        }
+        None => SyncedHistory::default(),
+    };
+
+    let (merged, pulled) = store.merge_synced(remote, max_entries)?;
+    let plaintext = serde_json::to_vec(&merged).map_err(|e| e.to_string())?;
+    upload(&client, settings, encrypt(&plaintext, &settings.passphrase)?).await?;
+    Ok(SyncReport {
+        pulled,
+        total: merged.entries.len(),
+    })
+}
+
+/// Encrypted copy on the backend, `None` before the first sync
+async fn download(client: &reqwest::Client, settings: &HistorySync) -> Result<Option<Vec<u8>>, String> {
+    let response = request(client, settings, reqwest::Method::GET, Vec::new())?
+        .send()
+        .await
+        .map_err(|e| format!("Network error: {}", e))?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        return Err(format!("Sync backend HTTP {}", response.status()));
+    }
+    let bytes = response.bytes().await.map_err(|e| format!("Network error: {}", e))?;
+    Ok(Some(bytes.to_vec()))
+}
+
+async fn upload(client: &reqwest::Client, settings: &HistorySync, data: Vec<u8>) -> Result<(), String> {
+    let response = request(client, settings, reqwest::Method::PUT, data)?
+        .send()
+        .await
+        .map_err(|e| format!("Network error: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Sync backend HTTP {}", response.status()));
+    }
+    Ok(())
+}
+
+/// Request on the synced copy: basic auth for WebDAV, signed (SigV4) for S3
+fn request(
+    client: &reqwest::Client,
+    settings: &HistorySync,
+    method: reqwest::Method,
+    body: Vec<u8>,
+) -> Result<reqwest::RequestBuilder, String> {
+    let Some(object) = settings.url.strip_prefix("s3://") else {
+        let request = client.request(method, &settings.url);
+        let request = if settings.username.is_empty() {
+            request
+        } else {
+            request.basic_auth(&settings.username, Some(&settings.password))
+        };
+        return Ok(request.body(body));
+    };
+
+    let endpoint = if settings.s3_endpoint.is_empty() {
+        format!("https://s3.{}.amazonaws.com", settings.s3_region)
+    } else {
+        settings.s3_endpoint.trim_end_matches('/').to_string()
+    };
+    let path: String = object.split('/').map(|segment| format!("/{}", uri_encode(segment))).collect();
+    let url = reqwest::Url::parse(&format!("{}{}", endpoint, path)).map_err(|e| format!("Invalid sync URL: {}", e))?;
+    let host = match (url.host_str(), url.port()) {
+        (Some(host), Some(port)) => format!("{}:{}", host, port),
+        (Some(host), None) => host.to_string(),
+        (None, _) => return Err(format!("Invalid sync URL: {}", settings.url)),
+    };
+
+    let now = chrono::Utc::now();
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = now.format("%Y%m%d").to_string();
+    let payload_hash = hex(&Sha256::digest(&body));
+    let canonical = format!(
+        "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\nhost;x-amz-content-sha256;x-amz-date\n{}",
+        method,
+        url.path(),
+        host,
+        payload_hash,
+        amz_date,
+        payload_hash
+    );
+    let scope = format!("{}/{}/s3/aws4_request", date, settings.s3_region);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        hex(&Sha256::digest(canonical.as_bytes()))
+    );
+    let mut key = hmac_sha256(format!("AWS4{}", settings.password).as_bytes(), date.as_bytes());
+    for part in [settings.s3_region.as_str(), "s3", "aws4_request"] {
+        key = hmac_sha256(&key, part.as_bytes());
+    }
+    let signature = hex(&hmac_sha256(&key, string_to_sign.as_bytes()));
+    let authorization = format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature={}",
+        settings.username, scope, signature
+    );
+
+    Ok(client
+        .request(method, url)
+        .header("x-amz-date", amz_date)
+        .header("x-amz-content-sha256", payload_hash)
+        .header("authorization", authorization)
+        .body(body))
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).expect("HMAC takes keys of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Percent-encode a path segment as SigV4 expects
+fn uri_encode(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, created_at: u64, text: &str) -> HistoryEntry {
+        HistoryEntry {
+            id: id.to_string(),
+            created_at,
+            engine: "openai".to_string(),
+            duration_secs: 1.0,
+            raw_text: text.to_string(),
+            text: text.to_string(),
+            reformulated: false,
+            translated: false,
+            recording_id: None,
+            title: None,
+        }
+    }
+
+    fn history(entries: Vec<HistoryEntry>, deleted: &[(&str, u64)]) -> SyncedHistory {
+        SyncedHistory {
+            entries,
+            deleted: deleted.iter().map(|(id, at)| (id.to_string(), *at)).collect(),
+        }
+    }
+
+    #[test]
+    fn merge_orders_entries_and_keeps_the_most_recent_version() {
+        let local = history(vec![entry("a", 10, "a"), entry("c", 30, "old")], &[]);
+        let remote = history(vec![entry("b", 20, "b"), entry("c", 35, "new")], &[]);
+        let merged = merge(local, remote);
+        let ids: Vec<&str> = merged.entries.iter().map(|entry| entry.id.as_str()).collect();
+        assert_eq!(ids, ["a", "b", "c"]);
+        assert_eq!(merged.entries[2].text, "new");
+    }
+
+    #[test]
+    fn merge_applies_tombstones_from_both_sides() {
+        let local = history(vec![entry("a", 10, "a"), entry("b", 20, "b")], &[("c", 40)]);
+        let remote = history(vec![entry("b", 20, "b"), entry("c", 30, "c")], &[("a", 15)]);
+        let merged = merge(local, remote);
+        let ids: Vec<&str> = merged.entries.iter().map(|entry| entry.id.as_str()).collect();
+        assert_eq!(ids, ["b"]);
+        assert_eq!(merged.deleted.len(), 2);
+    }
+
+    #[test]
+    fn entry_recreated_after_its_deletion_is_kept() {
+        let local = history(vec![entry("a", 50, "again")], &[]);
+        let remote = history(Vec::new(), &[("a", 40)]);
+        let merged = merge(local, remote);
+        assert_eq!(merged.entries.len(), 1);
+        // The latest deletion time wins
+        let merged = merge(merged, history(Vec::new(), &[("a", 60)]));
+        assert!(merged.entries.is_empty());
+        assert_eq!(merged.deleted["a"], 60);
+    }
+
+    #[test]
+    fn encrypted_history_round_trips() {
+        let plaintext = br#"{"entries":[],"deleted":{}}"#;
+        let data = encrypt(plaintext, "correct horse").unwrap();
+        assert!(data.starts_with(MAGIC));
+        assert!(!data.windows(plaintext.len()).any(|window| window == plaintext));
+        assert_eq!(decrypt(&data, "correct horse").unwrap(), plaintext);
+        assert!(decrypt(&data, "wrong horse").is_err());
+    }
+
+    #[test]
+    fn tampered_history_is_rejected() {
+        let mut data = encrypt(b"history", "passphrase").unwrap();
+        let last = data.len() - 1;
+        data[last] ^= 1;
+        assert!(decrypt(&data, "passphrase").is_err());
+        assert!(decrypt(b"not a history", "passphrase").is_err());
+    }
+}
@@ -0,0 +1,46 @@
+//! Message key with arguments
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// User-facing message, localized by the frontend
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct Message {
+    /// Translation key (see ui/i18n/translations.ts)
+    pub key: String,
+    /// Values for the `{name}` placeholders of the translation
+    pub args: BTreeMap<String, String>,
+}
+
+impl Message {
+    pub fn new(key: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            args: BTreeMap::new(),
+        }
+    }
+
+    /// Add a placeholder value
+    pub fn arg(mut self, name: &str, value: impl ToString) -> Self {
+        self.args.insert(name.to_string(), value.to_string());
+        self
+    }
+}
+
+/// Untranslated error text (wrapped as `error_generic`)
+impl From<String> for Message {
+    fn from(details: String) -> Self {
+        Message::new("error_generic").arg("details", details)
+    }
+}
+
+/// Key and arguments, for logs
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key)?;
+        for (name, value) in &self.args {
+            write!(f, " {}={}", name, value)?;
+        }
+        Ok(())
+    }
+}
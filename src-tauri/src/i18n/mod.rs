@@ -0,0 +1,8 @@
+//! Localizable user-facing messages
+//!
+//! The backend returns a message key plus named arguments; the UI looks the
+//! key up in its translation tables (fr/en/...) and fills in `{name}` slots.
+
+mod message;
+
+pub use message::Message;
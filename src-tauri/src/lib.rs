@@ -2,109 +2,133 @@
 //!
 //! Global shortcut to activate voice dictation.
 
+mod analytics;
 mod audio;
+mod calendar;
+mod captions;
+mod config;
+mod control;
+mod error;
+mod events;
+mod history;
+mod i18n;
+mod meeting;
+mod output;
+mod overlay;
+mod paste;
 mod pipeline;
+mod postprocess;
+mod recordings;
+#[cfg(feature = "sim")]
+pub mod sim;
 mod stt;
-
-use audio::{AudioConfig, AudioHandle};
-use stt::{Language, GeminiEngine, GroqEngine, OpenAiEngine, VoxtralEngine, SttEngine, SttEvent};
+mod usage;
+mod vad;
+
+#[cfg(feature = "whisper-local")]
+pub use stt::{whisper_worker_main, WHISPER_WORKER_ARG};
+
+use analytics::{SpeechLog, SpeechTrends};
+use audio::{AudioConfig, AudioHandle, AudioSource, Denoiser, DualCapture, MuteDetector};
+use calendar::CalendarEvent;
+use captions::CaptionFeed;
+use config::{AppConfig, FieldError, PasteMethod, Policy, ShortcutMode};
+use control::{ControlCommand, KeyEdge, ShortcutAction, ShortcutBindings, TranscriptUpdate};
+use error::DicteaError;
+use events::{AppStatus, DictationPhase, EventHistory, RecordedEvent, SessionBus, SessionReceiver, STATUS_EVENT};
+use history::{HistoryEntry, HistoryStore, SyncReport};
+use i18n::Message;
+use meeting::{append_summary, chapters_srt, merge_turns, timestamp, Annotation, AnnotationFormat, MeetingTurns, SpeakerLabels, TurnTranscript};
+use output::{
+    note_title, CapturedTask, DraftedEmail, EmailMethod, Issue, OutputAction, TemplateContext, TranscriptJournal,
+    TITLE_PROMPT,
+};
+use paste::{LiveTyper, PasteKeys, PastedText, TargetWindow};
+use recordings::{PlaybackHandle, RecordingInfo, RecordingStore, RecordingWriter, StorageUsage};
+use stt::{
+    parse_response, AssemblyAiEngine, CacheStore, CachedEngine, ChatCompletionResponse, EngineFactory, EngineHealthReport,
+    GeminiEngine, GoogleSpeechEngine, GroqEngine, Language, LocalEngine, OpenAiEngine, OpenAiRealtimeEngine, RequestMetrics,
+    RoutedEngine, SpeculativeEngine, SttEngine, SttEvent, VoxtralEngine, MIN_PROBE_INTERVAL_SECS,
+};
 use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, Ordering};
+use usage::{BudgetStatus, ProviderUsage, UsageLedger};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Manager, State};
-use tokio::sync::{broadcast, mpsc, Mutex, RwLock};
+use tokio::sync::{mpsc, watch, Mutex, RwLock};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-/// Application configuration
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct AppConfig {
-    pub global_shortcut: String,
-    pub openai_api_key: String,
-    pub output_language: String,
-    /// Reformulate text via GPT before pasting
-    #[serde(default)]
-    pub reformulate: bool,
-    /// STT engine: "openai", "voxtral", "gemini", or "groq"
-    #[serde(default = "default_stt_engine")]
-    pub stt_engine: String,
-    /// Mistral API key (used when stt_engine == "voxtral")
-    #[serde(default)]
-    pub mistral_api_key: String,
-    /// Gemini API key (used when stt_engine == "gemini")
-    #[serde(default)]
-    pub gemini_api_key: String,
-    /// Groq API key (used when stt_engine == "groq")
-    #[serde(default)]
-    pub groq_api_key: String,
-    /// Selected audio input device name (empty = system default)
-    #[serde(default)]
-    pub audio_device: String,
-}
-
-fn default_stt_engine() -> String {
-    "openai".to_string()
-}
-
-impl Default for AppConfig {
-    fn default() -> Self {
-        Self {
-            global_shortcut: "CmdOrCtrl+Shift+Space".to_string(),
-            openai_api_key: String::new(),
-            output_language: "auto".to_string(),
-            reformulate: false,
-            stt_engine: "openai".to_string(),
-            mistral_api_key: String::new(),
-            gemini_api_key: String::new(),
-            groq_api_key: String::new(),
-            audio_device: String::new(),
-        }
-    }
-}
-
-impl AppConfig {
-    /// Config file path
-    fn config_path(app: &AppHandle) -> PathBuf {
-        app.path()
-            .app_data_dir()
-            .unwrap_or_else(|_| PathBuf::from("."))
-            .join("config.json")
-    }
-
-    /// Load config from disk, or return defaults
-    fn load(app: &AppHandle) -> Self {
-        let path = Self::config_path(app);
-        if path.exists() {
-            match std::fs::read_to_string(&path) {
-                Ok(content) => match serde_json::from_str(&content) {
-                    Ok(config) => {
-                        tracing::info!("Config loaded from {}", path.display());
-                        return config;
-                    }
-                    Err(e) => tracing::warn!("Invalid config, using defaults: {}", e),
-                },
-                Err(e) => tracing::warn!("Cannot read config: {}", e),
-            }
-        }
-        Self::default()
-    }
+/// Store for saved dictation recordings
+fn recording_store(app: &AppHandle) -> RecordingStore {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join("recordings");
+    RecordingStore::new(dir)
+}
 
-    /// Save config to disk
-    fn save(&self, app: &AppHandle) {
-        let path = Self::config_path(app);
-        if let Some(parent) = path.parent() {
-            let _ = std::fs::create_dir_all(parent);
-        }
-        match serde_json::to_string_pretty(self) {
-            Ok(json) => {
-                if let Err(e) = std::fs::write(&path, json) {
-                    tracing::error!("Config save error: {}", e);
-                } else {
-                    tracing::info!("Config saved to {}", path.display());
-                }
-            }
-            Err(e) => tracing::error!("Config serialization error: {}", e),
-        }
-    }
+/// History of the finished dictations
+fn history_store(app: &AppHandle) -> HistoryStore {
+    let path = app
+        .path()
+        .app_data_dir()
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join("history.jsonl");
+    HistoryStore::new(path)
+}
+
+/// Transcripts reused when the same audio is sent again
+fn transcript_cache_path(app: &AppHandle) -> PathBuf {
+    app.path()
+        .app_data_dir()
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join("transcript-cache.json")
+}
+
+/// File counting the audio sent to each provider per month
+fn usage_ledger_path(app: &AppHandle) -> PathBuf {
+    app.path()
+        .app_data_dir()
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join("usage.json")
+}
+
+/// Speaking rate and filler words of the analyzed dictations
+fn speech_log_path(app: &AppHandle) -> PathBuf {
+    app.path()
+        .app_data_dir()
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join("speech-stats.json")
+}
+
+/// Git checkout of the shared team profile
+fn shared_profile_dir(app: &AppHandle) -> PathBuf {
+    app.path()
+        .app_data_dir()
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join("shared-profile")
+}
+
+/// Directory receiving the provider request dumps
+fn request_log_dir(app: &AppHandle) -> PathBuf {
+    app.path()
+        .app_log_dir()
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join("requests")
+}
+
+/// Enable or disable the provider request dumps from config
+fn apply_request_logging(app: &AppHandle, config: &AppConfig) {
+    stt::set_request_logging(config.request_logging.then(|| request_log_dir(app)));
+}
+
+/// Apply the recordings quota and max age from config
+fn cleanup_recordings(app: &AppHandle, config: &AppConfig) {
+    recording_store(app).enforce_retention(
+        config.recordings_max_bytes(),
+        config.recordings_max_age_secs(),
+    );
 }
 
 /// Current transcription state
@@ -113,6 +137,22 @@ pub struct TranscriptionState {
     pub is_recording: bool,
     pub partial_text: String,
     pub final_text: String,
+    /// Id of the recording saved for the last dictation, if any
+    pub last_recording_id: Option<String>,
+    /// Engine that transcribed the last dictation
+    pub last_engine_id: Option<String>,
+    /// Transcription error of the current dictation, if any
+    pub error: Option<String>,
+    /// The engine heard nothing in the current dictation
+    pub no_speech: bool,
+    /// Phase of the current dictation, kept in sync by `set_status`
+    pub phase: DictationPhase,
+    /// When `phase` was entered (ms since the epoch)
+    pub phase_since_ms: u64,
+    /// When the last recording started (ms since the epoch)
+    pub recording_started_ms: Option<u64>,
+    /// Language requested for the current dictation (None = auto)
+    pub language: Option<String>,
 }
 
 impl Default for TranscriptionState {
@@ -121,58 +161,161 @@ impl Default for TranscriptionState {
             is_recording: false,
             partial_text: String::new(),
             final_text: String::new(),
+            last_recording_id: None,
+            last_engine_id: None,
+            error: None,
+            no_speech: false,
+            phase: DictationPhase::Idle,
+            phase_since_ms: 0,
+            recording_started_ms: None,
+            language: None,
+        }
+    }
+}
+
+impl TranscriptionState {
+    /// Final segments followed by the pending partial
+    fn current_text(&self) -> String {
+        let mut text = self.final_text.clone();
+        if !self.partial_text.is_empty() {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(&self.partial_text);
+        }
+        text
+    }
+
+    /// Enter `phase`, keeping its timestamp when it does not change
+    fn set_phase(&mut self, phase: DictationPhase) {
+        if phase == self.phase {
+            return;
         }
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        if phase == DictationPhase::Recording {
+            self.recording_started_ms = Some(now);
+        }
+        self.phase = phase;
+        self.phase_since_ms = now;
     }
 }
 
+/// Outcome of a dictation
+#[derive(Debug, Clone, Default, serde::Serialize)]
+struct DictationResult {
+    /// Transcript as returned by the STT engine
+    raw_text: String,
+    /// Transcript after reformulation/translation, as it would be pasted
+    text: String,
+    /// Nothing was heard during the dictation
+    no_speech: bool,
+    /// Saved recording of the dictation, if recordings are kept
+    recording_id: Option<String>,
+    /// Validated JSON object, in structured output mode
+    structured: Option<serde_json::Value>,
+    /// Title generated for a quick note
+    title: Option<String>,
+}
+
 /// Simplified transcription pipeline
 struct TranscriptionPipeline {
     engine: Box<dyn SttEngine>,
+    /// Engine id ("openai", "groq"...) the pipeline was built for
+    engine_id: String,
+    /// Samples handed to the engine this session, billed by the provider
+    billed_samples: usize,
     audio_handle: Option<AudioHandle>,
-    event_tx: broadcast::Sender<SttEvent>,
+    /// Microphone + system audio capture, in meeting mode
+    dual_capture: Option<DualCapture>,
+    /// Speech turns of both sources, transcribed separately on stop (meeting mode)
+    meeting: Option<(MeetingTurns, SpeakerLabels)>,
+    /// Points flagged during the meeting capture
+    annotations: Vec<Annotation>,
+    annotation_format: AnnotationFormat,
+    /// Engine events of the running session, closed when it stops
+    events: SessionBus<SttEvent>,
     is_running: bool,
+    /// Dictation (`dictation_seq`) whose audio the pipeline is capturing
+    session: u64,
+    /// Writes the session audio to disk when recordings are kept
+    recorder: Option<RecordingWriter>,
 }
 
 impl TranscriptionPipeline {
-    fn new(engine: Box<dyn SttEngine>) -> Self {
-        let (event_tx, _) = broadcast::channel(100);
+    fn new(engine: Box<dyn SttEngine>, engine_id: String) -> Self {
         Self {
             engine,
+            engine_id,
+            billed_samples: 0,
             audio_handle: None,
-            event_tx,
+            dual_capture: None,
+            meeting: None,
+            annotations: Vec::new(),
+            annotation_format: AnnotationFormat::default(),
+            events: SessionBus::new(),
             is_running: false,
+            session: 0,
+            recorder: None,
         }
     }
 
-    fn subscribe(&self) -> broadcast::Receiver<SttEvent> {
-        self.event_tx.subscribe()
+    fn subscribe(&self) -> Option<SessionReceiver<SttEvent>> {
+        self.events.subscribe()
     }
 
-    fn start(&mut self, language: Language) -> Result<(), String> {
+    fn start(&mut self, language: Language, session: u64) -> Result<(), String> {
         if self.is_running {
             return Ok(());
         }
 
+        // New session: drop audio and late events left by the previous one
+        self.engine.reset();
         self.engine.set_language(language);
+        self.billed_samples = 0;
+        self.annotations.clear();
+        self.events.open();
         self.is_running = true;
-        tracing::info!("Transcription started");
+        self.session = session;
+        tracing::info!("Transcription started (upload format {:?})", self.engine.audio_format());
         Ok(())
     }
 
+    /// Stop the capture and end the session's consumer tasks
+    ///
+    /// Quick, unlike `stop`: the audio captured so far stays in the engine.
+    fn stop_capture(&mut self) {
+        if let Some(mut handle) = self.audio_handle.take() {
+            handle.stop();
+        }
+        if let Some(mut capture) = self.dual_capture.take() {
+            capture.stop();
+        }
+        self.events.close();
+    }
+
     /// Stop the pipeline and return remaining events
+    ///
+    /// Blocks until the engine has transcribed the end of the audio (network
+    /// round trip for the HTTP engines).
     fn stop(&mut self) -> Vec<SttEvent> {
         let mut remaining = Vec::new();
         if !self.is_running {
             return remaining;
         }
 
-        if let Some(mut handle) = self.audio_handle.take() {
-            handle.stop();
-        }
+        self.stop_capture();
 
-        self.engine.flush();
-        while let Some(event) = self.engine.poll() {
-            remaining.push(event);
+        if let Some((turns, labels)) = self.meeting.take() {
+            let marks = std::mem::take(&mut self.annotations);
+            remaining.extend(self.transcribe_meeting(turns, &labels, &marks));
+        } else {
+            self.engine.flush();
+            while let Some(event) = self.engine.poll() {
+                remaining.push(event);
+            }
         }
 
         self.is_running = false;
@@ -180,23 +323,171 @@ impl TranscriptionPipeline {
         remaining
     }
 
-    fn process_audio(&mut self, samples: Vec<f32>) {
+    /// Audio seconds sent during the session, counted once
+    fn take_billed_secs(&mut self) -> f64 {
+        let billed = std::mem::take(&mut self.billed_samples);
+        billed.saturating_sub(self.engine.take_cached_samples()) as f64 / 16000.0
+    }
+
+    /// Finalize the session recording and return its id
+    fn finish_recording(&mut self) -> Option<String> {
+        let recorder = self.recorder.take()?;
+        match recorder.finish() {
+            Ok(id) => Some(id),
+            Err(e) => {
+                tracing::error!("{}", e);
+                None
+            }
+        }
+    }
+
+    /// Transcribe each meeting turn on its own and merge them into one labeled Final
+    fn transcribe_meeting(&mut self, turns: MeetingTurns, labels: &SpeakerLabels, marks: &[Annotation]) -> Vec<SttEvent> {
+        let mut events = Vec::new();
+        let mut transcripts = Vec::new();
+        let end_sample = turns.position();
+        let mut audio = turns.finish();
+        for turn in std::mem::take(&mut audio.turns) {
+            let engine = &mut self.engine;
+            let pushed = audio.try_for_each_chunk(&turn, |chunk| {
+                engine.push_audio(chunk);
+                Ok::<(), std::io::Error>(())
+            });
+            if let Err(e) = pushed {
+                tracing::error!("Meeting turn read error: {}", e);
+                self.engine.reset();
+                events.push(SttEvent::Error(format!("Meeting audio read error: {}", e)));
+                continue;
+            }
+            self.billed_samples += turn.sample_count();
+            self.engine.flush();
+            while let Some(event) = self.engine.poll() {
+                match event {
+                    SttEvent::Final(text) => transcripts.push(TurnTranscript {
+                        source: turn.source,
+                        start_sample: turn.start_sample,
+                        text,
+                    }),
+                    SttEvent::Error(message) => events.push(SttEvent::Error(message)),
+                    // Short or silent turns are expected in a conversation
+                    SttEvent::Partial(_) | SttEvent::NoSpeech | SttEvent::TooShort { .. } => {}
+                }
+            }
+        }
+
+        let transcript = match self.annotation_format {
+            AnnotationFormat::Inline => merge_turns(transcripts, labels, marks),
+            AnnotationFormat::Chapters => {
+                let transcript = merge_turns(transcripts, labels, &[]);
+                if transcript.is_empty() || marks.is_empty() {
+                    transcript
+                } else {
+                    format!("{}\n\n{}", transcript, chapters_srt(marks, end_sample))
+                }
+            }
+        };
+        if transcript.is_empty() {
+            events.push(SttEvent::NoSpeech);
+        } else {
+            events.push(SttEvent::Final(transcript));
+        }
+        events
+    }
+
+    fn process_audio(&mut self, source: AudioSource, samples: Vec<f32>) {
+        if let Some((ref mut turns, _)) = self.meeting {
+            // Only the microphone is kept in the saved recording
+            if source == AudioSource::Microphone {
+                if let Some(ref mut recorder) = self.recorder {
+                    recorder.write(&samples);
+                }
+            }
+            turns.push(source, &samples);
+            return;
+        }
+
+        if let Some(ref mut recorder) = self.recorder {
+            recorder.write(&samples);
+        }
+        self.billed_samples += samples.len();
         self.engine.push_audio(&samples);
         while let Some(event) = self.engine.poll() {
-            let _ = self.event_tx.send(event);
+            self.events.publish(event);
         }
     }
 }
 
+/// Output replacing the paste, chosen by the shortcut that started the dictation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DedicatedOutput {
+    /// Task manager (Todoist, TickTick)
+    Task,
+    /// Email draft
+    Email,
+    /// GitHub/GitLab issue
+    Issue,
+    /// Search URL or app URI
+    Search,
+    /// Titled note kept in the history
+    QuickNote,
+}
+
 /// Global application state
 pub struct AppState {
     config: Arc<RwLock<AppConfig>>,
     pipeline: Arc<Mutex<Option<TranscriptionPipeline>>>,
     transcription: Arc<RwLock<TranscriptionState>>,
-    /// Guard against double calls to stop_and_paste
+    /// Guard against double calls to stop_and_paste, held until the audio is transcribed
     stopping: Arc<AtomicBool>,
+    /// Finished dictations waiting to paste, in stop order
+    output_queue: Arc<pipeline::OutputQueue>,
+    /// Bumped by each started dictation, so a previous one still
+    /// processing leaves the state of the new one alone
+    dictation_seq: Arc<AtomicU64>,
+    /// Config changed during a dictation, rebuild the pipeline once it ends
+    pipeline_reset_pending: Arc<AtomicBool>,
+    /// Stopped pipelines waiting on their engine, out of `pipeline` meanwhile
+    flushing_pipelines: Arc<AtomicUsize>,
+    /// Current dictation state, mirrored on the STATUS_EVENT channel
+    status: Arc<RwLock<AppStatus>>,
+    /// Every state change, for the control socket clients
+    status_updates: tokio::sync::broadcast::Sender<AppStatus>,
+    /// Transcript changes, for the live feed clients
+    transcript_updates: tokio::sync::broadcast::Sender<TranscriptUpdate>,
     /// Mic preview handle for settings UI
     mic_preview: Arc<Mutex<Option<AudioHandle>>>,
+    /// Playback of a saved recording
+    playback: Arc<Mutex<Option<PlaybackHandle>>>,
+    /// Last state events, replayed to late-attaching webviews
+    history: Arc<std::sync::Mutex<EventHistory>>,
+    /// File receiving the final segments of the current dictation
+    journal: Arc<std::sync::Mutex<Option<TranscriptJournal>>>,
+    /// Output of the current dictation, when started with a dedicated shortcut
+    dedicated_output: Arc<std::sync::Mutex<Option<DedicatedOutput>>>,
+    /// Last pasted dictation, context for smart capitalization
+    last_paste: Arc<std::sync::Mutex<Option<PastedText>>>,
+    /// Window every dictation is pasted into, while the target is locked
+    target_lock: Arc<std::sync::Mutex<Option<TargetWindow>>>,
+    /// Transcript typed live into the target app, in live paste mode
+    live_typer: Arc<std::sync::Mutex<Option<LiveTyper>>>,
+    /// Next pasted dictation inverts the `reformulate` setting
+    flip_reformulate: Arc<AtomicBool>,
+    /// Registered global shortcuts and their actions
+    shortcuts: Arc<std::sync::Mutex<ShortcutBindings>>,
+    /// Meeting in progress in the local calendar, when calendar awareness is on
+    current_meeting: Arc<std::sync::Mutex<Option<CalendarEvent>>>,
+    /// Speaker profile of this session, over the configured one (not saved)
+    session_speaker: Arc<std::sync::Mutex<Option<String>>>,
+    /// Periodic tasks of the enabled background features
+    background_loops: Arc<std::sync::Mutex<BackgroundLoops>>,
+}
+
+/// Periodic background tasks, each running only while its feature is enabled
+#[derive(Default)]
+struct BackgroundLoops {
+    calendar: Option<tauri::async_runtime::JoinHandle<()>>,
+    engine_health: Option<tauri::async_runtime::JoinHandle<()>>,
+    history_sync: Option<tauri::async_runtime::JoinHandle<()>>,
 }
 
 impl AppState {
@@ -206,7 +497,28 @@ impl AppState {
             pipeline: Arc::new(Mutex::new(None)),
             transcription: Arc::new(RwLock::new(TranscriptionState::default())),
             stopping: Arc::new(AtomicBool::new(false)),
+            output_queue: pipeline::OutputQueue::new(),
+            dictation_seq: Arc::new(AtomicU64::new(0)),
+            pipeline_reset_pending: Arc::new(AtomicBool::new(false)),
+            flushing_pipelines: Arc::new(AtomicUsize::new(0)),
+            status: Arc::new(RwLock::new(AppStatus::default())),
+            status_updates: tokio::sync::broadcast::channel(16).0,
+            transcript_updates: tokio::sync::broadcast::channel(64).0,
             mic_preview: Arc::new(Mutex::new(None)),
+            playback: Arc::new(Mutex::new(None)),
+            history: Arc::new(std::sync::Mutex::new(EventHistory::new(
+                AppConfig::default().event_history_size,
+            ))),
+            journal: Arc::new(std::sync::Mutex::new(None)),
+            dedicated_output: Arc::new(std::sync::Mutex::new(None)),
+            last_paste: Arc::new(std::sync::Mutex::new(None)),
+            target_lock: Arc::new(std::sync::Mutex::new(None)),
+            live_typer: Arc::new(std::sync::Mutex::new(None)),
+            flip_reformulate: Arc::new(AtomicBool::new(false)),
+            shortcuts: Arc::new(std::sync::Mutex::new(ShortcutBindings::default())),
+            current_meeting: Arc::new(std::sync::Mutex::new(None)),
+            session_speaker: Arc::new(std::sync::Mutex::new(None)),
+            background_loops: Arc::new(std::sync::Mutex::new(BackgroundLoops::default())),
         }
     }
 }
@@ -217,12 +529,66 @@ impl Default for AppState {
     }
 }
 
+/// Emit a state event to the frontend, keeping it in the replay history
+fn emit_state_event<S: serde::Serialize + Clone>(app: &AppHandle, event: &str, payload: S) {
+    let value = serde_json::to_value(&payload).unwrap_or(serde_json::Value::Null);
+    if let Ok(mut history) = app.state::<AppState>().history.lock() {
+        history.record(event, value);
+    }
+    let _ = app.emit(event, payload);
+}
+
+/// Record the new dictation state and publish it to the frontend
+async fn set_status(app: &AppHandle, state: &AppState, status: AppStatus) {
+    state.transcription.write().await.set_phase(DictationPhase::from(&status));
+    *state.status.write().await = status.clone();
+    let _ = state.status_updates.send(status.clone());
+    emit_state_event(app, STATUS_EVENT, status);
+}
+
+/// Append a final segment to the transcript journal, if one is open
+fn journal_segment(journal: &std::sync::Mutex<Option<TranscriptJournal>>, text: &str) {
+    if let Ok(mut guard) = journal.lock() {
+        if let Some(ref mut journal) = *guard {
+            if let Err(e) = journal.append(text) {
+                tracing::error!("Transcript file write error ({}): {}", journal.path().display(), e);
+            }
+        }
+    }
+}
+
+/// Kind of dictation, exposed to output templates as `{profile}`
+fn dictation_profile(config: &AppConfig) -> &'static str {
+    if config.system_audio_device.is_empty() {
+        "dictation"
+    } else {
+        "meeting"
+    }
+}
+
+/// Close the transcript journal of the dictation that just ended
+fn close_journal(state: &AppState) {
+    finish_journal(state.journal.lock().ok().and_then(|mut guard| guard.take()));
+}
+
+/// Write the end of a transcript journal taken out of the state
+fn finish_journal(journal: Option<TranscriptJournal>) {
+    if let Some(journal) = journal {
+        if let Err(e) = journal.finish() {
+            tracing::error!("Transcript file write error: {}", e);
+        }
+    }
+}
+
 /// Hide overlay and refocus the previous app
 fn hide_overlay_and_refocus(app: &AppHandle) {
-    if let Some(overlay) = app.get_webview_window("overlay") {
-        let _ = overlay.hide();
-    }
+    overlay::hide_all(app);
+    captions::hide(app);
+    refocus_previous_app(app);
+}
 
+/// Give the focus back to the app used before dictea
+fn refocus_previous_app(app: &AppHandle) {
     // On macOS, hide the Tauri app to refocus the previous app
     #[cfg(target_os = "macos")]
     {
@@ -255,6 +621,12 @@ fn list_audio_devices() -> Vec<String> {
     AudioHandle::list_devices()
 }
 
+/// List devices usable as system audio source (meeting mode)
+#[tauri::command]
+fn list_system_audio_devices() -> Vec<String> {
+    AudioHandle::list_loopback_devices()
+}
+
 /// Stop mic preview (internal helper)
 async fn stop_mic_preview_internal(state: &AppState) {
     let mut preview = state.mic_preview.lock().await;
@@ -269,12 +641,13 @@ async fn start_mic_preview(
     app: AppHandle,
     state: State<'_, AppState>,
     device_name: String,
-) -> Result<(), String> {
+) -> Result<(), DicteaError> {
     stop_mic_preview_internal(&state).await;
 
     let audio_config = AudioConfig {
         target_sample_rate: 16000,
         device_name: if device_name.is_empty() { None } else { Some(device_name) },
+        loopback: false,
     };
 
     let app_handle = app.clone();
@@ -289,7 +662,7 @@ async fn start_mic_preview(
         let level = (rms * 50.0).min(1.0);
         let _ = app_handle.emit("mic_preview_level", level);
     })
-    .map_err(|e| e.to_string())?;
+    .map_err(|e| Message::new("audio_error").arg("details", e))?;
 
     let mut preview = state.mic_preview.lock().await;
     *preview = Some(audio_handle);
@@ -298,241 +671,1042 @@ async fn start_mic_preview(
 
 /// Stop mic level preview
 #[tauri::command]
-async fn stop_mic_preview(state: State<'_, AppState>) -> Result<(), String> {
+async fn stop_mic_preview(state: State<'_, AppState>) -> Result<(), DicteaError> {
     stop_mic_preview_internal(&state).await;
     Ok(())
 }
 
-/// Get configuration
+/// List connected monitor names (for the overlay monitor setting)
 #[tauri::command]
-async fn get_config(state: State<'_, AppState>) -> Result<AppConfig, String> {
-    let config = state.config.read().await;
-    Ok(config.clone())
+fn list_monitors(app: AppHandle) -> Vec<String> {
+    overlay::monitor_names(&app)
 }
 
-/// Update configuration
+/// List saved recordings, most recent first
 #[tauri::command]
-async fn set_config(app: AppHandle, state: State<'_, AppState>, config: AppConfig) -> Result<(), String> {
-    // Save to disk
-    config.save(&app);
-
-    let mut current = state.config.write().await;
-    *current = config;
+fn list_recordings(app: AppHandle) -> Vec<RecordingInfo> {
+    recording_store(&app).list()
+}
 
-    // Reset pipeline to use the new engine/model
-    let mut pipeline = state.pipeline.lock().await;
-    if let Some(ref mut p) = *pipeline {
-        p.stop();
-    }
-    *pipeline = None;
+/// Finished dictations, most recent first (all of them without `limit`)
+#[tauri::command]
+fn get_history(app: AppHandle, limit: Option<usize>) -> Vec<HistoryEntry> {
+    history_store(&app).list(limit.unwrap_or(0))
+}
 
-    Ok(())
+/// Delete a dictation from the history
+#[tauri::command]
+fn delete_history_entry(app: AppHandle, id: String) -> Result<(), DicteaError> {
+    Ok(history_store(&app).delete(&id)?)
 }
 
-/// Create the STT engine based on config
-fn create_engine(config: &AppConfig) -> Result<Box<dyn SttEngine>, String> {
-    match config.stt_engine.as_str() {
-        "gemini" => {
-            if config.gemini_api_key.is_empty() {
-                return Err("Gemini API key required".to_string());
-            }
-            let engine = GeminiEngine::load(&config.gemini_api_key)
-                .map_err(|e| format!("Gemini error: {}", e))?;
-            tracing::info!("Gemini STT engine initialized");
-            Ok(Box::new(engine))
-        }
-        "voxtral" => {
-            if config.mistral_api_key.is_empty() {
-                return Err("Mistral API key required".to_string());
-            }
-            let engine = VoxtralEngine::load(&config.mistral_api_key)
-                .map_err(|e| format!("Voxtral error: {}", e))?;
-            tracing::info!("Voxtral STT engine initialized");
-            Ok(Box::new(engine))
-        }
-        "groq" => {
-            if config.groq_api_key.is_empty() {
-                return Err("Groq API key required".to_string());
-            }
-            let engine = GroqEngine::load(&config.groq_api_key)
-                .map_err(|e| format!("Groq error: {}", e))?;
-            tracing::info!("Groq Whisper API engine initialized");
-            Ok(Box::new(engine))
-        }
-        _ => {
-            if config.openai_api_key.is_empty() {
-                return Err("OpenAI API key required".to_string());
-            }
-            let engine = OpenAiEngine::load(&config.openai_api_key)
-                .map_err(|e| format!("OpenAI error: {}", e))?;
-            tracing::info!("OpenAI Whisper API engine initialized");
-            Ok(Box::new(engine))
-        }
-    }
+/// Delete the whole history, returning how many dictations were removed
+#[tauri::command]
+fn clear_history(app: AppHandle) -> Result<usize, DicteaError> {
+    Ok(history_store(&app).clear()?)
 }
 
-/// Process text via chat API: reformulate and/or translate in a single call
-async fn process_text(text: &str, reformulate: bool, output_language: &str, config: &AppConfig) -> String {
-    // Determine API endpoint, model, and key based on engine
-    let (api_url, model, api_key) = match config.stt_engine.as_str() {
-        "gemini" => (
-            "https://generativelanguage.googleapis.com/v1beta/openai/chat/completions",
-            "gemini-2.5-flash-lite",
-            config.gemini_api_key.as_str(),
-        ),
-        "voxtral" => (
-            "https://api.mistral.ai/v1/chat/completions",
-            "mistral-small-latest",
-            config.mistral_api_key.as_str(),
-        ),
-        "groq" => (
-            "https://api.groq.com/openai/v1/chat/completions",
-            "llama-3.3-70b-versatile",
-            config.groq_api_key.as_str(),
-        ),
-        _ => (
-            "https://api.openai.com/v1/chat/completions",
-            "gpt-4o-mini",
-            config.openai_api_key.as_str(),
-        ),
-    };
+/// Disk usage of saved recordings against the configured quota
+#[tauri::command]
+async fn get_storage_usage(app: AppHandle, state: State<'_, AppState>) -> Result<StorageUsage, DicteaError> {
+    let max_bytes = state.config.read().await.recordings_max_bytes();
+    Ok(recording_store(&app).usage(max_bytes))
+}
 
-    if text.is_empty() || api_key.is_empty() {
-        return text.to_string();
-    }
+/// Audio sent and estimated spend per provider this month, with the caps
+#[tauri::command]
+async fn get_provider_usage(app: AppHandle, state: State<'_, AppState>) -> Result<Vec<ProviderUsage>, DicteaError> {
+    let caps = state.config.read().await.cost_budget.monthly_usd.clone();
+    Ok(UsageLedger::load(&usage_ledger_path(&app)).summary(&caps))
+}
 
-    let needs_translation = output_language != "auto";
+/// Speaking rate and filler words per day over the last `days` days (30 by
+/// default), with the latest dictations
+#[tauri::command]
+fn get_stats(app: AppHandle, days: Option<u32>) -> Result<SpeechTrends, DicteaError> {
+    Ok(SpeechLog::load(&speech_log_path(&app)).trends(days.unwrap_or(30), 20))
+}
 
-    if !reformulate && !needs_translation {
-        return text.to_string();
-    }
+/// Forget every cached transcript, returning how many were removed
+#[tauri::command]
+fn clear_transcript_cache(app: AppHandle) -> Result<usize, DicteaError> {
+    Ok(CacheStore::clear(&transcript_cache_path(&app))?)
+}
 
-    let lang_name = match output_language {
-        "fr" => "French",
-        "en" => "English",
-        "es" => "Spanish",
-        "de" => "German",
-        "it" => "Italian",
-        "pt" => "Portuguese",
-        other => other,
-    };
+/// Audio streams, session tasks and timers alive, all zero when idle (debug)
+#[tauri::command]
+fn get_resource_usage() -> pipeline::ResourceUsage {
+    pipeline::ResourceUsage::current()
+}
 
-    let system_prompt = match (reformulate, needs_translation) {
-        (true, true) => format!(
-            "Reformulate the following spoken text into clean written text, then translate it to {}. \
-            Fix grammar, punctuation, remove hesitations, repetitions and filler words. \
-            Keep the meaning and tone. Output ONLY the final translated result in {}. \
-            Do NOT include any preamble, explanation, label or prefix. \
-            Do NOT write \"Here's the translation\" or similar. Just the text.",
-            lang_name, lang_name
-        ),
-        (true, false) => "Reformulate the following spoken text into clean written text. \
-            Fix grammar, punctuation, remove hesitations, repetitions and filler words. \
-            Keep the meaning and tone. Preserve English words used intentionally \
-            (franglais, technical terms, dev/tech jargon like push, pull, merge, deploy, commit, build, etc.). \
-            Do not translate them. Output ONLY the reformulated text. \
-            Do NOT include any preamble, explanation or prefix.".to_string(),
-        (false, true) => format!(
-            "Translate the following text to {}. Output ONLY the translated text. \
-            Do NOT include any preamble, explanation, label or prefix like \"Here's the translation\". Just the text.",
-            lang_name
-        ),
-        _ => unreachable!(),
-    };
+/// Requests in flight and queued per provider, with their wait times
+#[tauri::command]
+fn get_request_metrics() -> Vec<RequestMetrics> {
+    stt::request_metrics()
+}
 
-    let mode_label = match (reformulate, needs_translation) {
-        (true, true) => "reformulate+translate",
-        (true, false) => "reformulate",
-        (false, true) => "translate",
-        _ => unreachable!(),
-    };
+/// Last health probe of every provider, probed now if `refresh` (or never probed)
+#[tauri::command]
+async fn get_engine_status(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    refresh: Option<bool>,
+) -> Result<Vec<EngineHealthReport>, DicteaError> {
+    if refresh.unwrap_or(false) || stt::engine_status().is_empty() {
+        let config = state.config.read().await.clone();
+        refresh_engine_status(&app, &config).await;
+    }
+    Ok(stt::engine_status())
+}
 
-    tracing::info!("Processing text ({}, model: {}): '{}'", mode_label, model, text);
+/// Probe the HTTP providers, publishing their status when one changed
+async fn refresh_engine_status(app: &AppHandle, config: &AppConfig) {
+    let providers = [
+        ("openai", config.openai_api_key.clone()),
+        ("groq", config.groq_api_key.clone()),
+        ("voxtral", config.mistral_api_key.clone()),
+        ("gemini", config.gemini_api_key.clone()),
+    ];
+    if stt::probe_engines(&providers, config.engine_health.slow_ms).await {
+        emit_state_event(app, "engine_status", stt::engine_status());
+    }
+}
 
-    let client = reqwest::Client::new();
-    let body = serde_json::json!({
-        "model": model,
-        "messages": [
-            {"role": "system", "content": system_prompt},
-            {"role": "user", "content": text}
-        ],
-        "temperature": 0.3
-    });
+/// Probe the providers every `interval_secs`, while the probes are enabled
+async fn engine_health_loop(app: AppHandle) {
+    let state = app.state::<AppState>();
+    loop {
+        let config = state.config.read().await.clone();
+        refresh_engine_status(&app, &config).await;
+        let interval = config.engine_health.interval_secs.max(MIN_PROBE_INTERVAL_SECS);
+        let _timer = pipeline::ResourceGuard::acquire(pipeline::Resource::Timer);
+        tokio::time::sleep(tokio::time::Duration::from_secs(interval)).await;
+    }
+}
 
-    match client
-        .post(api_url)
-        .header("Authorization", format!("Bearer {}", api_key))
-        .json(&body)
-        .send()
-        .await
-    {
-        Ok(response) => {
-            if let Ok(json) = response.json::<serde_json::Value>().await {
-                if let Some(result) = json["choices"][0]["message"]["content"].as_str() {
-                    let result = result.trim().to_string();
-                    tracing::info!("Processed ({}): '{}' -> '{}'", mode_label, text, result);
-                    return result;
-                }
-            }
-            tracing::error!("Error parsing chat response ({})", mode_label);
-            text.to_string()
-        }
-        Err(e) => {
-            tracing::error!("Chat {} error: {}", mode_label, e);
-            text.to_string()
-        }
+/// Stop recording playback (internal helper)
+async fn stop_playback_internal(state: &AppState) {
+    let mut playback = state.playback.lock().await;
+    if let Some(mut handle) = playback.take() {
+        handle.stop();
     }
 }
 
-/// Start recording
+/// Play back a saved recording
 #[tauri::command]
-async fn start_recording(
-    app: AppHandle,
+async fn play_recording(app: AppHandle, state: State<'_, AppState>, id: String) -> Result<(), DicteaError> {
+    stop_playback_internal(&state).await;
+
+    let path = recording_store(&app)
+        .path_for(&id)
+        .ok_or_else(|| Message::new("recording_not_found").arg("id", &id))?;
+
+    let app_handle = app.clone();
+    let finished_id = id.clone();
+    let handle = PlaybackHandle::start(&path, move || {
+        let _ = app_handle.emit("playback_finished", finished_id);
+    })
+    .map_err(|e| Message::new("playback_error").arg("details", e))?;
+
+    let mut playback = state.playback.lock().await;
+    *playback = Some(handle);
+    tracing::info!("Playing recording {}", id);
+    Ok(())
+}
+
+/// Stop recording playback
+#[tauri::command]
+async fn stop_playback(state: State<'_, AppState>) -> Result<(), DicteaError> {
+    stop_playback_internal(&state).await;
+    Ok(())
+}
+
+/// Get configuration
+#[tauri::command]
+async fn get_config(state: State<'_, AppState>) -> Result<AppConfig, DicteaError> {
+    let config = state.config.read().await;
+    Ok(config.clone())
+}
+
+/// Settings locked by the organization policy, to be shown read-only
+#[tauri::command]
+fn get_locked_fields() -> Vec<String> {
+    Policy::current().map(Policy::locked_fields).unwrap_or_default()
+}
+
+/// Validate, persist and publish a new config, returning the previous one
+///
+/// Changed shortcuts are registered before anything is saved: if the OS
+/// refuses one, the previous shortcuts are registered again and the config
+/// is left as it was.
+async fn store_config(
+    app: &AppHandle,
+    state: &AppState,
+    mut config: AppConfig,
+) -> Result<AppConfig, Vec<FieldError>> {
+    if let Some(policy) = Policy::current() {
+        policy.check(&config)?;
+    }
+    config.control_socket.ensure_token();
+    config.live_feed.ensure_token();
+    config.validate()?;
+
+    let mut current = state.config.write().await;
+    if control::shortcuts_changed(&current, &config) {
+        if let Err(errors) = register_shortcuts(app, &config) {
+            if let Err(restored) = register_shortcuts(app, &current) {
+                for error in restored {
+                    tracing::warn!("Shortcut {} not restored: {}", error.field, error.message);
+                }
+            }
+            return Err(errors);
+        }
+    }
+
+    // Save to disk
+    config.save(app);
+
+    if let Ok(mut history) = state.history.lock() {
+        history.set_capacity(config.event_history_size);
+    }
+    apply_request_logging(app, &config);
+    stt::set_concurrency_limits(&config.request_limits);
+    // Spawned loops read the config once the new one is in place
+    update_background_loops(app, Some(&current), &config);
+
+    Ok(std::mem::replace(&mut *current, config))
+}
+
+/// Replace the registered global shortcuts with the ones of `config`
+///
+/// Returns the shortcuts the OS refused (already taken by another app).
+fn register_shortcuts(app: &AppHandle, config: &AppConfig) -> Result<(), Vec<FieldError>> {
+    use tauri_plugin_global_shortcut::GlobalShortcutExt;
+
+    let bindings = ShortcutBindings::from_config(config);
+    let manager = app.global_shortcut();
+    if let Err(e) = manager.unregister_all() {
+        tracing::warn!("Cannot unregister the global shortcuts: {}", e);
+    }
+
+    let mut errors = Vec::new();
+    for binding in bindings.iter() {
+        match manager.register(binding.shortcut) {
+            Ok(()) => tracing::info!("{} registered: {}", binding.action.field(), binding.keys),
+            Err(e) => errors.push(FieldError::new(
+                binding.action.field(),
+                Message::new("shortcut_unavailable")
+                    .arg("value", &binding.keys)
+                    .arg("details", e),
+            )),
+        }
+    }
+    if let Ok(mut shortcuts) = app.state::<AppState>().shortcuts.lock() {
+        *shortcuts = bindings;
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Drop the pipeline so the next recording uses the new engine/model
+///
+/// If a dictation is in progress, or a stopped one still waits on its
+/// engine, the reset is deferred until it ends.
+async fn reset_pipeline(state: &AppState) {
+    if state.transcription.read().await.is_recording || state.flushing_pipelines.load(Ordering::SeqCst) > 0 {
+        state.pipeline_reset_pending.store(true, Ordering::SeqCst);
+        tracing::info!("Recording in progress, pipeline reset deferred");
+        return;
+    }
+
+    let mut pipeline = state.pipeline.lock().await;
+    if let Some(ref mut p) = *pipeline {
+        p.stop();
+    }
+    *pipeline = None;
+}
+
+/// Apply a pipeline reset deferred by a config change during a dictation
+///
+/// Kept for later while the next dictation already runs, or another stopped
+/// one still waits on its engine.
+async fn apply_deferred_pipeline_reset(state: &AppState) {
+    let mut pipeline = state.pipeline.lock().await;
+    if pipeline.as_ref().is_some_and(|p| p.is_running) || state.flushing_pipelines.load(Ordering::SeqCst) > 0 {
+        return;
+    }
+    if state.pipeline_reset_pending.swap(false, Ordering::SeqCst) {
+        tracing::info!("Applying deferred pipeline reset");
+        *pipeline = None;
+    }
+}
+
+/// Stop the capture of the running pipeline and take it out of the state,
+/// so the next dictation can start while its engine finishes
+async fn detach_pipeline(state: &AppState) -> Option<TranscriptionPipeline> {
+    let mut guard = state.pipeline.lock().await;
+    if !guard.as_ref().is_some_and(|p| p.is_running) {
+        return None;
+    }
+    let mut pipeline = guard.take()?;
+    pipeline.stop_capture();
+    state.flushing_pipelines.fetch_add(1, Ordering::SeqCst);
+    Some(pipeline)
+}
+
+/// Wait for the engine of a detached pipeline, outside of the pipeline lock,
+/// returning the remaining events, the saved recording and the engine id
+///
+/// The pipeline is put back for the next dictation unless one was created
+/// meanwhile or a reset is pending.
+async fn flush_pipeline(
+    app: &AppHandle,
+    state: &AppState,
+    mut pipeline: TranscriptionPipeline,
+) -> Result<(Vec<SttEvent>, Option<String>, String), String> {
+    let joined = tokio::task::spawn_blocking(move || {
+        let events = pipeline.stop();
+        (pipeline, events)
+    })
+    .await;
+
+    let result = match joined {
+        Ok((mut pipeline, events)) => {
+            record_usage(app, &pipeline.engine_id, pipeline.take_billed_secs());
+            let recording_id = pipeline.finish_recording();
+            let engine_id = pipeline.engine_id.clone();
+            let mut guard = state.pipeline.lock().await;
+            if guard.is_none() && !state.pipeline_reset_pending.load(Ordering::SeqCst) {
+                *guard = Some(pipeline);
+            }
+            Ok((events, recording_id, engine_id))
+        }
+        Err(e) => Err(format!("Transcription task failed: {}", e)),
+    };
+    state.flushing_pipelines.fetch_sub(1, Ordering::SeqCst);
+    apply_deferred_pipeline_reset(state).await;
+    result
+}
+
+/// Update configuration
+#[tauri::command]
+async fn set_config(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    config: AppConfig,
+) -> Result<(), DicteaError> {
+    let previous = store_config(&app, &state, config.clone()).await?;
+
+    if previous.requires_pipeline_reset(&config) {
+        reset_pipeline(&state).await;
+    }
+    Ok(())
+}
+
+/// Merge a partial JSON object into the current configuration
+///
+/// The pipeline is only reset when engine-affecting fields change.
+#[tauri::command]
+async fn patch_config(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    partial: serde_json::Value,
+) -> Result<AppConfig, DicteaError> {
+    let config = state.config.read().await.patched(&partial)?;
+    let previous = store_config(&app, &state, config.clone()).await?;
+
+    if previous.requires_pipeline_reset(&config) {
+        reset_pipeline(&state).await;
+    }
+    Ok(config)
+}
+
+/// Pull the shared team profile and apply it, returning the updated fields
+#[tauri::command]
+async fn sync_profiles(app: AppHandle, state: State<'_, AppState>) -> Result<Vec<String>, DicteaError> {
+    let config = state.config.read().await.clone();
+    if config.profile_sync.url.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let shared = config
+        .profile_sync
+        .fetch(&shared_profile_dir(&app))
+        .await
+        .map_err(|e| Message::new("profile_sync_error").arg("details", e))?;
+    let (config, changed) = config.with_shared_profile(&shared)?;
+    if !changed.is_empty() {
+        store_config(&app, &state, config).await?;
+        emit_state_event(&app, "profiles_synced", changed.clone());
+    }
+    tracing::info!("Shared profile synced, updated: {:?}", changed);
+    Ok(changed)
+}
+
+/// Merge the history with its encrypted copy shared between machines
+#[tauri::command]
+async fn sync_history(app: AppHandle, state: State<'_, AppState>) -> Result<SyncReport, DicteaError> {
+    let config = state.config.read().await.clone();
+    if !config.history_sync.is_valid_url() || config.history_sync.passphrase.is_empty() {
+        return Err(Message::new("history_sync_unconfigured").into());
+    }
+    Ok(run_history_sync(&app, &config).await?)
+}
+
+/// Sync the history, publishing the entries brought from the other machines
+async fn run_history_sync(app: &AppHandle, config: &AppConfig) -> Result<SyncReport, Message> {
+    let report = history::sync_history(&history_store(app), &config.history_sync, config.history_max_entries)
+        .await
+        .map_err(|e| Message::new("history_sync_error").arg("details", e))?;
+    tracing::info!("History synced: {} pulled, {} in total", report.pulled, report.total);
+    if report.pulled > 0 {
+        emit_state_event(app, "history_synced", report.clone());
+    }
+    Ok(report)
+}
+
+/// Sync the history every `interval_mins`, while the sync is enabled
+async fn history_sync_loop(app: AppHandle) {
+    let state = app.state::<AppState>();
+    loop {
+        let config = state.config.read().await.clone();
+        if let Err(e) = run_history_sync(&app, &config).await {
+            tracing::warn!("History sync failed: {}", e);
+        }
+        let interval = config.history_sync.interval_mins.max(1) * 60;
+        let _timer = pipeline::ResourceGuard::acquire(pipeline::Resource::Timer);
+        tokio::time::sleep(tokio::time::Duration::from_secs(interval)).await;
+    }
+}
+
+/// Read the meeting in progress from the local calendar, while enabled
+async fn calendar_loop(app: AppHandle) {
+    let state = app.state::<AppState>();
+    let mut last_error = None;
+    loop {
+        let meeting = match tokio::task::spawn_blocking(calendar::current_meeting).await {
+            Ok(Ok(meeting)) => {
+                last_error = None;
+                meeting
+            }
+            Ok(Err(e)) => {
+                // Logged once, not on every read
+                if last_error.as_ref() != Some(&e) {
+                    tracing::warn!("{}", e);
+                    last_error = Some(e);
+                }
+                None
+            }
+            Err(_) => None,
+        };
+        set_current_meeting(&state, meeting);
+        let _timer = pipeline::ResourceGuard::acquire(pipeline::Resource::Timer);
+        tokio::time::sleep(calendar::POLL_INTERVAL).await;
+    }
+}
+
+fn set_current_meeting(state: &AppState, meeting: Option<CalendarEvent>) {
+    if let Ok(mut current) = state.current_meeting.lock() {
+        let title = |meeting: &Option<CalendarEvent>| meeting.as_ref().map(|event| event.title.clone());
+        if title(&*current) != title(&meeting) {
+            tracing::info!("Calendar meeting: {:?}", meeting.as_ref().map(|event| &event.title));
+        }
+        *current = meeting;
+    }
+}
+
+/// Stop a background loop, if running
+fn stop_loop(task: &mut Option<tauri::async_runtime::JoinHandle<()>>) {
+    if let Some(task) = task.take() {
+        task.abort();
+    }
+}
+
+/// Start, stop or restart the background loops whose settings changed
+///
+/// `previous` is `None` at launch. The loop of a disabled feature is not
+/// spawned, so the idle app never wakes up for it.
+fn update_background_loops(app: &AppHandle, previous: Option<&AppConfig>, config: &AppConfig) {
+    let state = app.state::<AppState>();
+    let Ok(mut loops) = state.background_loops.lock() else {
+        return;
+    };
+    if previous.is_none_or(|previous| previous.calendar.enabled != config.calendar.enabled) {
+        stop_loop(&mut loops.calendar);
+        set_current_meeting(&state, None);
+        if config.calendar.enabled {
+            loops.calendar = Some(tauri::async_runtime::spawn(calendar_loop(app.clone())));
+        }
+    }
+    if previous.is_none_or(|previous| previous.engine_health != config.engine_health) {
+        stop_loop(&mut loops.engine_health);
+        if config.engine_health.enabled {
+            loops.engine_health = Some(tauri::async_runtime::spawn(engine_health_loop(app.clone())));
+        }
+    }
+    if previous.is_none_or(|previous| previous.history_sync != config.history_sync) {
+        stop_loop(&mut loops.history_sync);
+        if config.history_sync.enabled {
+            loops.history_sync = Some(tauri::async_runtime::spawn(history_sync_loop(app.clone())));
+        }
+    }
+}
+
+/// Turn the provider request dumps on or off, returning their directory
+#[tauri::command]
+async fn set_request_logging(app: AppHandle, state: State<'_, AppState>, enabled: bool) -> Result<String, DicteaError> {
+    let mut config = state.config.read().await.clone();
+    config.request_logging = enabled;
+    store_config(&app, &state, config).await?;
+    Ok(request_log_dir(&app).display().to_string())
+}
+
+/// Switch the reformulation prompt to the preset `name` (empty = custom or built-in prompt)
+#[tauri::command]
+async fn set_active_preset(app: AppHandle, state: State<'_, AppState>, name: String) -> Result<(), DicteaError> {
+    let mut config = state.config.read().await.clone();
+    config.reformulation.active_preset = name.trim().to_string();
+    store_config(&app, &state, config).await?;
+    tracing::info!("Active reformulation preset: {:?}", name.trim());
+    emit_state_event(&app, "preset_changed", name.trim());
+    Ok(())
+}
+
+/// Create the STT engine based on config, behind the length router if enabled
+fn create_engine(config: &AppConfig) -> Result<Box<dyn SttEngine>, Message> {
+    let default = create_named_engine(&config.stt_engine, config)?;
+    let routing = &config.engine_routing;
+    if !routing.enabled {
+        return Ok(default);
+    }
+
+    // A routed engine that cannot be created (no API key...) falls back to the default one
+    let routed = |name: &str| {
+        if name == config.stt_engine {
+            return None;
+        }
+        create_named_engine(name, config)
+            .map_err(|e| tracing::warn!("Routed engine {} unavailable: {}", name, e))
+            .ok()
+    };
+    let short = routed(&routing.short_engine);
+    let long = routed(&routing.long_engine);
+    Ok(Box::new(RoutedEngine::new(routing, default, short, long)))
+}
+
+/// Offline Whisper engine, when built with the `whisper-local` feature
+#[cfg(feature = "whisper-local")]
+fn create_whisper_local_engine(model_path: &str, prompt: &str) -> Result<Box<dyn SttEngine>, Message> {
+    let engine = stt::start_whisper_local(model_path.trim(), prompt)
+        .map_err(|e| Message::new("engine_error").arg("engine", "Whisper").arg("details", e))?;
+    tracing::info!("Local Whisper engine initialized");
+    Ok(Box::new(engine))
+}
+
+#[cfg(not(feature = "whisper-local"))]
+fn create_whisper_local_engine(_model_path: &str, _prompt: &str) -> Result<Box<dyn SttEngine>, Message> {
+    Err(Message::new("engine_error")
+        .arg("engine", "Whisper")
+        .arg("details", "not built in (feature whisper-local)"))
+}
+
+/// Settings of `engine` and of its routed engines changing the transcript
+/// of the same audio, part of the transcript cache key
+fn transcript_cache_settings(config: &AppConfig, engine: &str) -> String {
+    let mut engines = vec![engine];
+    if config.engine_routing.enabled {
+        engines.push(&config.engine_routing.short_engine);
+        engines.push(&config.engine_routing.long_engine);
+    }
+    let mut settings = Vec::new();
+    for name in engines {
+        let sample_rate = config.upload_sample_rates.get(name).copied().unwrap_or_default();
+        let codec = config.upload_codecs.get(name).copied().unwrap_or_default();
+        settings.push(format!("{}:{}:{:?}", name, sample_rate, codec));
+        if name == "google" {
+            settings.push(format!("{}:{}", config.google_speech.location, config.google_speech.model));
+        }
+    }
+    if let Some(profile) = config.active_speaker_profile() {
+        settings.push(profile.prompt.clone());
+    }
+    settings.join("|")
+}
+
+/// Create the STT engine `name` ("openai", "groq"...), uploading at its
+/// configured sample rate and codec
+fn create_named_engine(name: &str, config: &AppConfig) -> Result<Box<dyn SttEngine>, Message> {
+    let mut engine = create_provider_engine(name, config)?;
+    if let Some(&sample_rate) = config.upload_sample_rates.get(name) {
+        engine.set_upload_sample_rate(sample_rate);
+    }
+    if let Some(&codec) = config.upload_codecs.get(name) {
+        engine.set_upload_codec(codec);
+    }
+    Ok(engine)
+}
+
+/// Create the STT engine of provider `name`
+fn create_provider_engine(name: &str, config: &AppConfig) -> Result<Box<dyn SttEngine>, Message> {
+    // Simulation runs send the requests to the mock provider
+    #[cfg(feature = "sim")]
+    if let Some(engine) = sim::mock_engine(name) {
+        return Ok(engine);
+    }
+    match name {
+        "gemini" => {
+            if config.gemini_api_key.is_empty() {
+                return Err(Message::new("api_key_required_gemini"));
+            }
+            let engine = GeminiEngine::load(&config.gemini_api_key)
+                .map_err(|e| Message::new("engine_error").arg("engine", "Gemini").arg("details", e))?;
+            tracing::info!("Gemini STT engine initialized");
+            Ok(Box::new(engine))
+        }
+        "google" => {
+            if !config.google_speech.has_credentials() {
+                return Err(Message::new("api_key_required_google"));
+            }
+            let engine = GoogleSpeechEngine::start(&config.google_speech)
+                .map_err(|e| Message::new("engine_error").arg("engine", "Google").arg("details", e))?;
+            tracing::info!("Google Speech-to-Text engine initialized");
+            Ok(Box::new(engine))
+        }
+        "voxtral" => {
+            if config.mistral_api_key.is_empty() {
+                return Err(Message::new("api_key_required_mistral"));
+            }
+            let engine = VoxtralEngine::load(&config.mistral_api_key)
+                .map_err(|e| Message::new("engine_error").arg("engine", "Voxtral").arg("details", e))?;
+            tracing::info!("Voxtral STT engine initialized");
+            Ok(Box::new(engine))
+        }
+        "groq" => {
+            if config.groq_api_key.is_empty() {
+                return Err(Message::new("api_key_required_groq"));
+            }
+            let engine = GroqEngine::load(&config.groq_api_key)
+                .map_err(|e| Message::new("engine_error").arg("engine", "Groq").arg("details", e))?;
+            tracing::info!("Groq Whisper API engine initialized");
+            Ok(Box::new(engine))
+        }
+        "assemblyai" => {
+            if config.assemblyai_api_key.is_empty() {
+                return Err(Message::new("api_key_required_assemblyai"));
+            }
+            let engine = AssemblyAiEngine::load(&config.assemblyai_api_key)
+                .map_err(|e| Message::new("engine_error").arg("engine", "AssemblyAI").arg("details", e))?;
+            tracing::info!("AssemblyAI engine initialized");
+            Ok(Box::new(engine))
+        }
+        "whisper-local" => {
+            if let Some(profile) = config.active_speaker_profile() {
+                tracing::info!("Speaker profile {}", profile.name);
+                return create_whisper_local_engine(&profile.model_path, &profile.prompt);
+            }
+            if config.whisper_model_path.trim().is_empty() {
+                return Err(Message::new("whisper_model_required"));
+            }
+            create_whisper_local_engine(&config.whisper_model_path, "")
+        }
+        "local" => {
+            if config.local_worker.command.trim().is_empty() {
+                return Err(Message::new("local_worker_required"));
+            }
+            let engine = LocalEngine::start(&config.local_worker)
+                .map_err(|e| Message::new("engine_error").arg("engine", "Local").arg("details", e))?;
+            tracing::info!("{} engine initialized", engine.name());
+            Ok(Box::new(engine))
+        }
+        _ => {
+            if config.openai_api_key.is_empty() {
+                return Err(Message::new("api_key_required"));
+            }
+            if config.openai_realtime {
+                let engine = OpenAiRealtimeEngine::load(&config.openai_api_key)
+                    .map_err(|e| Message::new("engine_error").arg("engine", "OpenAI").arg("details", e))?;
+                tracing::info!("OpenAI Realtime engine initialized");
+                return Ok(Box::new(engine));
+            }
+            let engine = OpenAiEngine::load(&config.openai_api_key)
+                .map_err(|e| Message::new("engine_error").arg("engine", "OpenAI").arg("details", e))?;
+            tracing::info!("OpenAI Whisper API engine initialized");
+            Ok(Box::new(engine))
+        }
+    }
+}
+
+/// Chat completion endpoint, model and key matching the STT engine's provider
+fn chat_endpoint(config: &AppConfig) -> (&'static str, &'static str, &str) {
+    match config.stt_engine.as_str() {
+        "gemini" => (
+            "https://generativelanguage.googleapis.com/v1beta/openai/chat/completions",
+            "gemini-2.5-flash-lite",
+            config.gemini_api_key.as_str(),
+        ),
+        "voxtral" => (
+            "https://api.mistral.ai/v1/chat/completions",
+            "mistral-small-latest",
+            config.mistral_api_key.as_str(),
+        ),
+        "groq" => (
+            "https://api.groq.com/openai/v1/chat/completions",
+            "llama-3.3-70b-versatile",
+            config.groq_api_key.as_str(),
+        ),
+        _ => (
+            "https://api.openai.com/v1/chat/completions",
+            "gpt-4o-mini",
+            config.openai_api_key.as_str(),
+        ),
+    }
+}
+
+/// Run a single-turn chat completion on the engine's provider
+async fn chat_completion(config: &AppConfig, system_prompt: &str, text: &str) -> Result<String, String> {
+    let (api_url, model, api_key) = chat_endpoint(config);
+    if api_key.is_empty() {
+        return Err("No API key for the chat provider".to_string());
+    }
+
+    let client = reqwest::Client::new();
+    let body = serde_json::json!({
+        "model": model,
+        "messages": [
+            {"role": "system", "content": system_prompt},
+            {"role": "user", "content": text}
+        ],
+        "temperature": 0.3
+    });
+
+    let response = client
+        .post(api_url)
+        .header("Authorization", format!("Bearer {}", api_key))
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    let body = response.text().await.map_err(|e| e.to_string())?;
+    parse_response::<ChatCompletionResponse>("chat", &body)
+        .and_then(|parsed| parsed.into_content())
+        .map(|content| content.trim().to_string())
+        .map_err(|e| e.to_string())
+}
+
+/// Process text via chat API: reformulate and/or translate in a single call
+async fn process_text(text: &str, reformulate: bool, output_language: &str, config: &AppConfig) -> String {
+    let (_, model, api_key) = chat_endpoint(config);
+
+    if text.is_empty() || api_key.is_empty() {
+        return text.to_string();
+    }
+
+    let needs_translation = output_language != "auto";
+    let lang_name = match output_language {
+        "fr" => "French",
+        "en" => "English",
+        "es" => "Spanish",
+        "de" => "German",
+        "it" => "Italian",
+        "pt" => "Portuguese",
+        other => other,
+    };
+
+    let language = needs_translation.then_some(lang_name);
+    let Some(system_prompt) = config.reformulation.system_prompt(reformulate, language) else {
+        return text.to_string();
+    };
+
+    let mode_label = match (reformulate, needs_translation) {
+        (true, true) => "reformulate+translate",
+        (true, false) => "reformulate",
+        (false, true) => "translate",
+        _ => unreachable!(),
+    };
+
+    if reformulate && !config.reformulation.active_preset.is_empty() {
+        tracing::info!("Reformulation preset: {}", config.reformulation.active_preset);
+    }
+    tracing::info!("Processing text ({}, model: {}): '{}'", mode_label, model, text);
+
+    match chat_completion(config, &system_prompt, text).await {
+        Ok(result) => {
+            tracing::info!("Processed ({}): '{}' -> '{}'", mode_label, text, result);
+            result
+        }
+        Err(e) => {
+            tracing::error!("Chat {} error: {}", mode_label, e);
+            text.to_string()
+        }
+    }
+}
+
+/// Translate again a result that is not in `output_language` (hint ignored
+/// by the provider, chat call failed...), warning if it still is not
+async fn enforce_output_language(app: &AppHandle, text: String, config: &AppConfig) -> String {
+    let expected = config.output_language.as_str();
+    if expected == "auto" || !postprocess::is_detectable(expected) {
+        return text;
+    }
+    let Some(detected) = postprocess::detect_language(&text) else {
+        return text;
+    };
+    if detected == expected {
+        return text;
+    }
+
+    tracing::warn!("Result in '{}' instead of '{}', translating again", detected, expected);
+    let translated = process_text(&text, false, expected, config).await;
+    if postprocess::detect_language(&translated).is_some_and(|lang| lang != expected) {
+        let message = Message::new("language_mismatch")
+            .arg("detected", detected)
+            .arg("expected", expected);
+        let _ = app.emit("language_mismatch", message);
+    }
+    translated
+}
+
+/// Translate dictation `seq` while it is spoken, showing the translation on
+/// the overlay and typing it live in place of the transcript
+async fn translate_live(
+    app: AppHandle,
+    config: AppConfig,
+    language: String,
+    seq: u64,
+    mut updates: tokio::sync::broadcast::Receiver<TranscriptUpdate>,
+) {
+    let state = app.state::<AppState>();
+    let mut translation = postprocess::IncrementalTranslation::new(config.live_translation.interval_ms);
+    loop {
+        let mut update = match updates.recv().await {
+            Ok(update) => update,
+            Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(tokio::sync::broadcast::error::RecvError::Closed) => return,
+        };
+        // Only the latest hypothesis is worth translating
+        while matches!(update, TranscriptUpdate::Partial { .. }) {
+            match updates.try_recv() {
+                Ok(next) => update = next,
+                Err(_) => break,
+            }
+        }
+        let force = match update {
+            TranscriptUpdate::Started => continue,
+            TranscriptUpdate::Stopped { .. } => return,
+            TranscriptUpdate::Partial { text } => {
+                if !translation.wants_partial(&text) {
+                    continue;
+                }
+                let translated = process_text(&text, false, &language, &config).await;
+                translation.set_partial(&text, translated);
+                false
+            }
+            TranscriptUpdate::Final { text } => {
+                let translated = process_text(&text, false, &language, &config).await;
+                translation.commit(translated);
+                true
+            }
+        };
+        // A newer dictation owns the overlay and the live typer
+        if state.dictation_seq.load(Ordering::SeqCst) != seq {
+            return;
+        }
+
+        let text = translation.text();
+        let js = format!(
+            "window.__overlaySetText && window.__overlaySetText({})",
+            serde_json::Value::from(text.as_str())
+        );
+        overlay::eval_all(&app, &js);
+        if let Ok(mut guard) = state.live_typer.lock() {
+            if let Some(ref mut typer) = *guard {
+                if let Err(e) = typer.revise(&text, force) {
+                    tracing::error!("Live paste error: {}", e);
+                }
+            }
+        }
+        let _ = app.emit("translation_partial", text);
+    }
+}
+
+/// Start recording
+#[tauri::command]
+async fn start_recording(
+    app: AppHandle,
     state: State<'_, AppState>,
     language: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), DicteaError> {
+    // The previous dictation can still be processing, but its audio must be transcribed
+    if state.stopping.load(Ordering::SeqCst) {
+        tracing::warn!("start_recording skipped, the previous dictation is still being transcribed");
+        return Ok(());
+    }
+
     let lang = language
         .map(|l| Language::from_code(&l))
         .unwrap_or(Language::Auto);
 
-    let config = state.config.read().await.clone();
+    let mut config = state.config.read().await.clone();
+    if let Some(speaker) = state.session_speaker.lock().ok().and_then(|speaker| speaker.clone()) {
+        config.speaker_profile = speaker;
+    }
+
+    // During a calendar meeting, record in meeting mode or suggest it
+    let meeting = ongoing_meeting(&state);
+    if let Some(ref event) = meeting {
+        let dedicated = state.dedicated_output.lock().is_ok_and(|output| output.is_some());
+        if config.system_audio_device.is_empty() && !dedicated {
+            if config.calendar.auto_meeting_mode && !config.calendar.meeting_audio_device.is_empty() {
+                tracing::info!("Meeting in progress ({}), recording in meeting mode", event.title);
+                config.system_audio_device = config.calendar.meeting_audio_device.clone();
+            } else {
+                let message = Message::new("meeting_suggested").arg("title", &event.title);
+                emit_state_event(&app, "meeting_suggested", message);
+            }
+        }
+    }
 
     // Stop mic preview to avoid concurrent streams
     stop_mic_preview_internal(&state).await;
 
+    let engine_id = budgeted_engine(&app, &config);
+
     // Create pipeline if needed
     {
         let mut pipeline_guard = state.pipeline.lock().await;
+        if pipeline_guard
+            .as_ref()
+            .is_some_and(|p| !p.is_running && p.engine_id != engine_id)
+        {
+            tracing::info!("Switching pipeline to the {} engine", engine_id);
+            *pipeline_guard = None;
+        }
         if pipeline_guard.is_none() {
-            let engine = create_engine(&config)?;
-            *pipeline_guard = Some(TranscriptionPipeline::new(engine));
+            let engine_config = AppConfig {
+                stt_engine: engine_id.clone(),
+                ..config.clone()
+            };
+            let mut engine = create_engine(&engine_config)?;
+            // A streaming engine already transcribes while capturing and
+            // emits its finals before the whole audio is known
+            let streaming = engine.streams_partials();
+            if config.smart_stop.enabled && !streaming {
+                // Each background segment gets its own engine instance
+                let factory: EngineFactory = Arc::new(move || {
+                    create_engine(&engine_config)
+                        .map_err(|e| tracing::warn!("Smart stop engine unavailable: {}", e))
+                        .ok()
+                });
+                let vad = vad::create_detector(&config);
+                engine = Box::new(SpeculativeEngine::new(engine, factory, vad, &config.smart_stop));
+            }
+            // Local engines cost nothing to run again, and without history
+            // no transcript may stay on disk
+            let local = matches!(engine_id.as_str(), "local" | "whisper-local");
+            if config.transcript_cache.enabled && !streaming && !local && config.keep_history {
+                let cache = config.transcript_cache.clone();
+                let settings = transcript_cache_settings(&config, &engine_id);
+                engine = Box::new(CachedEngine::new(
+                    engine,
+                    &engine_id,
+                    settings,
+                    cache,
+                    transcript_cache_path(&app),
+                ));
+            }
+            engine.set_memory_limit(config.audio_memory_limit_samples());
+            *pipeline_guard = Some(TranscriptionPipeline::new(engine, engine_id.clone()));
         }
     }
 
     // Reset transcription state
+    let seq = state.dictation_seq.fetch_add(1, Ordering::SeqCst) + 1;
     {
         let mut trans = state.transcription.write().await;
         trans.is_recording = true;
         trans.partial_text.clear();
         trans.final_text.clear();
+        trans.last_recording_id = None;
+        trans.error = None;
+        trans.no_speech = false;
+        trans.language = (!matches!(lang, Language::Auto)).then(|| lang.code().to_string());
     }
 
     // Start the pipeline
     {
         let mut pipeline_guard = state.pipeline.lock().await;
         if let Some(ref mut pipeline) = *pipeline_guard {
-            pipeline.start(lang.clone())?;
+            pipeline.start(lang.clone(), seq)?;
+            pipeline.engine.set_guards(config.audio_guards());
+            pipeline.engine.set_speed_normalization(config.speed_normalization);
+
+            if config.keep_recordings {
+                match recording_store(&app).create() {
+                    Ok(recorder) => {
+                        tracing::info!("Saving recording {}", recorder.id());
+                        pipeline.recorder = Some(recorder);
+                    }
+                    Err(e) => tracing::error!("{}", e),
+                }
+            }
+
+            let mut receiver = pipeline
+                .subscribe()
+                .ok_or_else(|| Message::new("pipeline_session_closed"))?;
+            if !config.transcript_file.trim().is_empty() {
+                let context = TemplateContext::new(dictation_profile(&config))
+                    .with_meeting(meeting.as_ref().map(CalendarEvent::file_name));
+                match TranscriptJournal::open(&config.transcript_file, &context) {
+                    Ok(journal) => {
+                        tracing::info!("Streaming transcript to {}", journal.path().display());
+                        if let Ok(mut guard) = state.journal.lock() {
+                            *guard = Some(journal);
+                        }
+                    }
+                    Err(e) => tracing::error!("{}", e),
+                }
+            }
+
+            // Dedicated outputs are not pasted, nothing is typed live for them
+            let dedicated = state.dedicated_output.lock().is_ok_and(|output| output.is_some());
+            if let Ok(mut typer) = state.live_typer.lock() {
+                *typer = (config.live_paste && !dedicated).then(|| LiveTyper::new(config.live_paste_interval_ms));
+            }
+
+            // Live translation: the overlay and live paste show the translation instead
+            let translation_target = config.live_translation.target(dictation_profile(&config));
+            if let Some(language) = translation_target {
+                tracing::info!("Live translation to {}", language);
+                let updates = state.transcript_updates.subscribe();
+                tokio::spawn(translate_live(app.clone(), config.clone(), language.to_string(), seq, updates));
+            }
+            let translating = translation_target.is_some();
 
-            let mut receiver = pipeline.subscribe();
             let app_handle = app.clone();
             let transcription = state.transcription.clone();
+            let journal = state.journal.clone();
+            let live_typer = state.live_typer.clone();
+            let transcript_updates = state.transcript_updates.clone();
+            let mut caption_feed = config.captions.enabled.then(|| CaptionFeed::new(&config.captions));
 
+            // Exits when the session bus is closed by stop() or the pipeline is dropped
             tokio::spawn(async move {
-                while let Ok(event) = receiver.recv().await {
+                let _task = pipeline::ResourceGuard::acquire(pipeline::Resource::SessionTask);
+                while let Some(event) = receiver.recv().await {
                     let mut trans = transcription.write().await;
+                    let revision = match event {
+                        SttEvent::Partial(_) => Some(false),
+                        SttEvent::Final(_) => Some(true),
+                        _ => None,
+                    };
                     match event {
                         SttEvent::Partial(text) => {
                             trans.partial_text = text.clone();
+                            let _ = transcript_updates.send(TranscriptUpdate::Partial { text: text.clone() });
                             let _ = app_handle.emit("stt_partial", text);
                         }
                         SttEvent::Final(text) => {
@@ -541,13 +1715,50 @@ async fn start_recording(
                             }
                             trans.final_text.push_str(&text);
                             trans.partial_text.clear();
+                            journal_segment(&journal, &text);
+                            let _ = transcript_updates.send(TranscriptUpdate::Final { text: text.clone() });
                             let _ = app_handle.emit("stt_final", text);
                         }
+                        SttEvent::NoSpeech => {
+                            trans.no_speech = true;
+                        }
+                        SttEvent::TooShort { duration_ms, min_duration_ms } => {
+                            trans.no_speech = true;
+                            let _ = app_handle.emit("audio_too_short", too_short_message(duration_ms, min_duration_ms));
+                        }
+                        SttEvent::Error(message) => {
+                            trans.error = Some(message.clone());
+                            let _ = app_handle.emit("stt_error", message);
+                        }
+                    }
+
+                    // Live paste: correct the typed text, finals are never throttled
+                    if let Some(force) = revision {
+                        let target = trans.current_text();
+                        drop(trans);
+                        if let Some(ref mut feed) = caption_feed {
+                            feed.push(&app_handle, &target, force);
+                        }
+                        if translating {
+                            continue;
+                        }
+                        let js = format!(
+                            "window.__overlaySetText && window.__overlaySetText({})",
+                            serde_json::Value::from(target.as_str())
+                        );
+                        overlay::eval_all(&app_handle, &js);
+                        if let Ok(mut guard) = live_typer.lock() {
+                            if let Some(ref mut typer) = *guard {
+                                if let Err(e) = typer.revise(&target, force) {
+                                    tracing::error!("Live paste error: {}", e);
+                                }
+                            }
+                        }
                     }
                 }
             });
 
-            let (audio_tx, mut audio_rx) = mpsc::unbounded_channel::<Vec<f32>>();
+            let (audio_tx, mut audio_rx) = mpsc::unbounded_channel::<(AudioSource, Vec<f32>)>();
 
             let audio_config = AudioConfig {
                 target_sample_rate: 16000,
@@ -556,36 +1767,99 @@ async fn start_recording(
                 } else {
                     Some(config.audio_device.clone())
                 },
+                loopback: false,
             };
-            let audio_handle = AudioHandle::start(audio_config, move |samples| {
-                let _ = audio_tx.send(samples);
-            })
-            .map_err(|e| e.to_string())?;
 
-            pipeline.audio_handle = Some(audio_handle);
+            if config.network_audio.is_enabled() {
+                // Remote source (conferencing bridge, other machine) instead of the microphone
+                let sample_rate = audio_config.target_sample_rate;
+                let audio_handle = AudioHandle::start_network(&config.network_audio, sample_rate, move |samples| {
+                    let _ = audio_tx.send((AudioSource::Microphone, samples));
+                })
+                .map_err(|e| Message::new("audio_error").arg("details", e))?;
+                pipeline.audio_handle = Some(audio_handle);
+            } else if config.system_audio_device.is_empty() {
+                let audio_handle = AudioHandle::start(audio_config, move |samples| {
+                    let _ = audio_tx.send((AudioSource::Microphone, samples));
+                })
+                .map_err(|e| Message::new("audio_error").arg("details", e))?;
+                pipeline.audio_handle = Some(audio_handle);
+            } else {
+                // Meeting mode: the other participants are captured from the system audio
+                let system_config = AudioConfig {
+                    device_name: Some(config.system_audio_device.clone()),
+                    ..audio_config.clone()
+                };
+                let capture = DualCapture::start(audio_config, system_config, move |source, samples| {
+                    let _ = audio_tx.send((source, samples));
+                })
+                .map_err(|e| Message::new("audio_error").arg("details", e))?;
+                pipeline.dual_capture = Some(capture);
+                pipeline.meeting = Some((
+                    MeetingTurns::new(
+                        vad::create_detector(&config),
+                        vad::create_detector(&config),
+                        config.audio_memory_limit_samples(),
+                    ),
+                    SpeakerLabels {
+                        microphone: config.meeting_label_me.clone(),
+                        system: config.meeting_label_them.clone(),
+                    },
+                ));
+                pipeline.annotation_format = config.annotations.format;
+            }
 
             let pipeline_arc = state.pipeline.clone();
             let app_for_level = app.clone();
+            let mut level_meter = overlay::LevelMeter::new(config.level_update_fps, vad::create_detector(&config));
+            // Meetings are mostly listened to, pauses do not end them
+            let mut silence = (config.auto_stop.enabled && config.system_audio_device.is_empty())
+                .then(|| config.auto_stop.timer(&config));
+            let silence_ms = config.auto_stop.silence_ms;
+            let mut mute = config.mute_awareness.enabled.then(|| MuteDetector::new(&config.mute_awareness));
+            let mut denoiser = config.noise_suppression.enabled.then(Denoiser::new);
+            let (system_mute_tx, mut system_mute_rx) = watch::channel(false);
+            if mute.is_some() {
+                tokio::spawn(watch_system_mute(app.clone(), seq, config.mute_awareness.poll_interval_ms, system_mute_tx));
+            }
+            // Exits once the capture threads are stopped and drop their sender
             tokio::spawn(async move {
-                let mut last_level_send = std::time::Instant::now();
-                while let Some(samples) = audio_rx.recv().await {
-                    // Send audio level to overlay (~20fps)
-                    if last_level_send.elapsed().as_millis() > 50 {
-                        let rms = (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt();
-                        let level = (rms * 50.0).min(1.0); // normalize (mic levels are very low)
-                        if let Some(overlay) = app_for_level.get_webview_window("overlay") {
-                            let _ = overlay.eval(&format!(
-                                "window.__overlaySetLevel && window.__overlaySetLevel({})",
-                                level
-                            ));
+                let _task = pipeline::ResourceGuard::acquire(pipeline::Resource::SessionTask);
+                while let Some((source, samples)) = audio_rx.recv().await {
+                    // A muted microphone only brings silence, it is not accumulated
+                    if let (AudioSource::Microphone, Some(detector)) = (source, mute.as_mut()) {
+                        let mut changed = detector.push(&samples);
+                        if system_mute_rx.has_changed().unwrap_or(false) {
+                            let system_muted = *system_mute_rx.borrow_and_update();
+                            changed = detector.set_system_muted(system_muted).or(changed);
+                        }
+                        if let Some(muted) = changed {
+                            show_microphone_muted(&app_for_level, muted);
+                        }
+                        if detector.is_muted() {
+                            continue;
+                        }
+                    }
+
+                    let samples = match denoiser.as_mut() {
+                        Some(denoiser) if source == AudioSource::Microphone => denoiser.process(&samples),
+                        _ => samples,
+                    };
+
+                    // Send microphone level to the overlay, if visible and listening
+                    if source == AudioSource::Microphone {
+                        level_meter.push(&app_for_level, &samples);
+                        if silence.as_mut().is_some_and(|timer| timer.push(&samples)) {
+                            silence = None;
+                            tokio::spawn(auto_stop_recording(app_for_level.clone(), seq, silence_ms));
                         }
-                        last_level_send = std::time::Instant::now();
                     }
 
+                    // The pipeline may already capture the next dictation
                     let mut guard = pipeline_arc.lock().await;
                     if let Some(ref mut p) = *guard {
-                        if p.is_running {
-                            p.process_audio(samples);
+                        if p.is_running && p.session == seq {
+                            p.process_audio(source, samples);
                         } else {
                             break;
                         }
@@ -597,226 +1871,1200 @@ async fn start_recording(
         }
     }
 
-    // Show overlay on the screen where the mouse cursor is
-    if let Some(overlay) = app.get_webview_window("overlay") {
-        let monitor = overlay.cursor_position()
-            .ok()
-            .and_then(|cursor| overlay.monitor_from_point(cursor.x, cursor.y).ok().flatten())
-            .or_else(|| overlay.current_monitor().ok().flatten());
-
-        if let Some(monitor) = monitor {
-            let scale = monitor.scale_factor();
-            let pos = monitor.position();
-            let size = monitor.size();
-            let screen_x = pos.x as f64 / scale;
-            let screen_y = pos.y as f64 / scale;
-            let screen_width = size.width as f64 / scale;
-            let screen_height = size.height as f64 / scale;
-            let window_width = 140.0;
-            let x = (screen_x + (screen_width - window_width) / 2.0) as i32;
-            let y = (screen_y + screen_height * 0.15) as i32;
-            let _ = overlay.set_position(tauri::LogicalPosition::new(x, y));
-        }
-        let _ = overlay.show();
-    }
-
-    let _ = app.emit("recording_started", ());
-    if let Some(overlay) = app.get_webview_window("overlay") {
-        let _ = overlay.eval("window.__overlaySetProcessing && window.__overlaySetProcessing(false)");
-    }
-    tracing::info!("Recording started ({})", config.stt_engine);
-    Ok(())
+    // Show overlay near the caret or on the monitor(s) selected in config
+    overlay::show(&app, &config.overlay_position, &config.overlay_monitor);
+    if config.captions.enabled {
+        captions::show(&app, &config.captions);
+    }
+
+    if config.require_recording_indicator {
+        if !overlay::is_visible(&app) {
+            tracing::error!("Recording indicator could not be shown, capture aborted");
+            let _ = cancel_recording(app.clone(), state.clone()).await;
+            return Err(Message::new("indicator_unavailable").into());
+        }
+        overlay::set_capture_title(&app, true);
+        spawn_indicator_guard(app.clone(), state.transcription.clone(), config.clone());
+    }
+
+    set_status(&app, &state, AppStatus::Recording { engine: engine_id.clone() }).await;
+    emit_state_event(&app, "recording_started", ());
+    let _ = state.transcript_updates.send(TranscriptUpdate::Started);
+    overlay::eval_all(&app, "window.__overlaySetProcessing && window.__overlaySetProcessing(false)");
+    tracing::info!("Recording started ({})", engine_id);
+
+    let quick_note = state
+        .dedicated_output
+        .lock()
+        .is_ok_and(|output| *output == Some(DedicatedOutput::QuickNote));
+    if quick_note {
+        tokio::spawn(end_quick_note(app.clone(), seq, config.quick_note.max_secs));
+    }
+    Ok(())
+}
+
+/// Stop quick note `seq` once its `max_secs` are over, unless it was
+/// stopped in the meantime
+async fn end_quick_note(app: AppHandle, seq: u64, max_secs: u64) {
+    tokio::time::sleep(tokio::time::Duration::from_secs(max_secs)).await;
+    let state = app.state::<AppState>();
+    if state.dictation_seq.load(Ordering::SeqCst) != seq || !state.transcription.read().await.is_recording {
+        return;
+    }
+    tracing::info!("Quick note reached {} s, stopping it", max_secs);
+    emit_state_event(&app, "quick_note_time_up", max_secs);
+    if let Err(e) = stop_and_paste(app.clone(), state).await {
+        tracing::warn!("Quick note stop failed: {}", e);
+    }
+}
+
+/// Read the OS microphone mute state of dictation `seq` every `interval_ms`,
+/// until it stops
+async fn watch_system_mute(app: AppHandle, seq: u64, interval_ms: u64, muted: watch::Sender<bool>) {
+    let state = app.state::<AppState>();
+    loop {
+        if state.dictation_seq.load(Ordering::SeqCst) != seq || !state.transcription.read().await.is_recording {
+            break;
+        }
+        match tokio::task::spawn_blocking(audio::system_input_muted).await {
+            Ok(Some(system_muted)) => {
+                // The session task is gone
+                if muted.send(system_muted).is_err() {
+                    break;
+                }
+            }
+            // Not exposed by this OS, the digital silence is all there is
+            _ => break,
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(interval_ms.max(100))).await;
+    }
+}
+
+/// Tell the overlay and the listeners that the microphone was muted or unmuted
+fn show_microphone_muted(app: &AppHandle, muted: bool) {
+    tracing::info!("Microphone {}", if muted { "muted, dictation paused" } else { "unmuted" });
+    overlay::eval_all(app, &format!("window.__overlaySetMuted && window.__overlaySetMuted({})", muted));
+    emit_state_event(app, "microphone_muted", muted);
+}
+
+/// Stop and paste dictation `seq` after `silence_ms` of silence, unless it
+/// was stopped in the meantime
+async fn auto_stop_recording(app: AppHandle, seq: u64, silence_ms: u64) {
+    let state = app.state::<AppState>();
+    if state.dictation_seq.load(Ordering::SeqCst) != seq || !state.transcription.read().await.is_recording {
+        return;
+    }
+    tracing::info!("{} ms of silence, stopping the dictation", silence_ms);
+    let message = Message::new("recording_autostopped").arg("silence_secs", format!("{:.1}", silence_ms as f64 / 1000.0));
+    let js_message = serde_json::to_string(&message).unwrap_or_else(|_| "null".to_string());
+    overlay::eval_all(
+        &app,
+        &format!("window.__overlaySetAutoStopped && window.__overlaySetAutoStopped({})", js_message),
+    );
+    emit_state_event(&app, "recording_autostopped", message);
+    if let Err(e) = stop_and_paste(app.clone(), state).await {
+        tracing::warn!("Auto-stop failed: {}", e);
+    }
+}
+
+/// Calendar meeting in progress, if calendar awareness is on
+fn ongoing_meeting(state: &AppState) -> Option<CalendarEvent> {
+    let current = state.current_meeting.lock().ok()?;
+    current.as_ref().filter(|event| event.is_ongoing()).cloned()
+}
+
+/// Engine to dictate with: the configured one, or the budget fallback once
+/// its monthly cap is reached
+fn budgeted_engine(app: &AppHandle, config: &AppConfig) -> String {
+    let engine = config.stt_engine.clone();
+    let budget = &config.cost_budget;
+    if !budget.enabled {
+        return engine;
+    }
+
+    let mut ledger = UsageLedger::load(&usage_ledger_path(app));
+    match budget.status(&ledger, &engine) {
+        BudgetStatus::Within => engine,
+        BudgetStatus::Warning { spent, cap } => {
+            if ledger.mark_warned(&engine) {
+                if let Err(e) = ledger.save() {
+                    tracing::error!("{}", e);
+                }
+                let _ = app.emit("budget_warning", budget_message("budget_warning", &engine, spent, cap));
+            }
+            engine
+        }
+        BudgetStatus::Exceeded { spent, cap } => match budget.fallback_for(&ledger, &engine) {
+            Some(fallback) => {
+                tracing::warn!("Monthly budget of {} reached, dictating with {}", engine, fallback);
+                let message = budget_message("budget_fallback", &engine, spent, cap).arg("fallback", fallback);
+                let _ = app.emit("budget_exceeded", message);
+                fallback.to_string()
+            }
+            None => {
+                tracing::warn!("Monthly budget of {} reached, no fallback engine", engine);
+                let _ = app.emit("budget_exceeded", budget_message("budget_exceeded", &engine, spent, cap));
+                engine
+            }
+        },
+    }
+}
+
+fn budget_message(key: &str, engine: &str, spent: f64, cap: f64) -> Message {
+    Message::new(key)
+        .arg("engine", engine)
+        .arg("spent", format!("{:.2}", spent))
+        .arg("cap", format!("{:.2}", cap))
+}
+
+/// Add the audio of a finished session to the usage ledger
+fn record_usage(app: &AppHandle, engine: &str, secs: f64) {
+    if secs <= 0.0 {
+        return;
+    }
+    let mut ledger = UsageLedger::load(&usage_ledger_path(app));
+    ledger.record(engine, secs);
+    if let Err(e) = ledger.save() {
+        tracing::error!("{}", e);
+    }
+}
+
+/// Add a finished dictation to the history
+fn record_history(app: &AppHandle, config: &AppConfig, entry: &HistoryEntry) {
+    match history_store(app).append(entry, config.history_max_entries) {
+        Ok(()) => emit_state_event(app, "history_added", entry.id.clone()),
+        Err(e) => tracing::error!("{}", e),
+    }
+}
+
+/// Measure the spoken text of a dictation and add it to the speech stats
+fn record_speech_stats(
+    app: &AppHandle,
+    config: &AppConfig,
+    text: &str,
+    duration_secs: f64,
+    recording_id: Option<String>,
+) {
+    if !config.speech_analytics.enabled {
+        return;
+    }
+    let stats = config.speech_analytics.analyze(text, duration_secs, recording_id);
+    tracing::info!(
+        "Speech stats: {:.0} words/min, {} filler(s)",
+        stats.words_per_minute,
+        stats.filler_count()
+    );
+    let mut log = SpeechLog::load(&speech_log_path(app));
+    log.record(stats);
+    if let Err(e) = log.save() {
+        tracing::error!("{}", e);
+    }
+}
+
+/// Keep the overlay on screen until the dictation stops, cancelling it if
+/// the indicator cannot be shown again
+fn spawn_indicator_guard(
+    app: AppHandle,
+    transcription: Arc<RwLock<TranscriptionState>>,
+    config: AppConfig,
+) {
+    tokio::spawn(async move {
+        let _task = pipeline::ResourceGuard::acquire(pipeline::Resource::SessionTask);
+        let _timer = pipeline::ResourceGuard::acquire(pipeline::Resource::Timer);
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_millis(500));
+        loop {
+            interval.tick().await;
+            if !transcription.read().await.is_recording {
+                break;
+            }
+            if !overlay::ensure_visible(&app, &config.overlay_position, &config.overlay_monitor) {
+                tracing::error!("Recording indicator lost, cancelling capture");
+                let state = app.state::<AppState>();
+                let _ = cancel_recording(app.clone(), state).await;
+                break;
+            }
+        }
+    });
+}
+
+/// Explanation sent when a dictation is under the minimum duration
+fn too_short_message(duration_ms: u64, min_duration_ms: u64) -> Message {
+    Message::new("audio_too_short")
+        .arg("duration_ms", duration_ms)
+        .arg("min_duration_ms", min_duration_ms)
+}
+
+/// Dictation whose capture stopped, the end of its audio still to transcribe
+struct StoppingRecording {
+    /// `dictation_seq` of the dictation
+    seq: u64,
+    pipeline: Option<TranscriptionPipeline>,
+    /// Transcription state when the capture stopped
+    transcription: TranscriptionState,
+    journal: Option<TranscriptJournal>,
+}
+
+/// Transcript and details of a stopped dictation
+#[derive(Debug, Clone, Default)]
+struct StoppedRecording {
+    text: String,
+    recording_id: Option<String>,
+    engine_id: Option<String>,
+    error: Option<String>,
+    no_speech: bool,
+    /// When the recording started (ms since the epoch)
+    started_ms: Option<u64>,
+    /// Language requested for the dictation (None = auto)
+    language: Option<String>,
+}
+
+/// Stop the capture of the current dictation
+///
+/// Returns quickly: a new dictation may start while `finish_stop` waits
+/// for the engine.
+async fn begin_stop(app: &AppHandle, state: &AppState) -> StoppingRecording {
+    // A dedicated output only applies to the dictation it was started for
+    take_dedicated_output(state);
+
+    let seq = state.dictation_seq.load(Ordering::SeqCst);
+    let pipeline = detach_pipeline(state).await;
+    overlay::set_capture_title(app, false);
+
+    let transcription = {
+        let mut trans = state.transcription.write().await;
+        trans.is_recording = false;
+        trans.clone()
+    };
+    let journal = state.journal.lock().ok().and_then(|mut guard| guard.take());
+    StoppingRecording {
+        seq,
+        pipeline,
+        transcription,
+        journal,
+    }
+}
+
+/// Wait for the engine of a stopped dictation and return its transcript
+async fn finish_stop(app: &AppHandle, state: &AppState, stopping: StoppingRecording) -> Result<StoppedRecording, String> {
+    let StoppingRecording {
+        seq,
+        pipeline,
+        mut transcription,
+        mut journal,
+    } = stopping;
+
+    let meeting = pipeline.as_ref().is_some_and(|pipeline| pipeline.meeting.is_some());
+    let flushed = match pipeline {
+        Some(pipeline) => flush_pipeline(app, state, pipeline)
+            .await
+            .map(|(events, recording_id, engine_id)| (events, recording_id, Some(engine_id))),
+        None => Ok((Vec::new(), None, None)),
+    };
+    let (remaining_events, recording_id, engine_id) = match flushed {
+        Ok(flushed) => flushed,
+        Err(e) => {
+            finish_journal(journal);
+            return Err(e);
+        }
+    };
+
+    if let Some(ref id) = recording_id {
+        emit_state_event(app, "recording_saved", id.clone());
+        let config = state.config.read().await.clone();
+        cleanup_recordings(app, &config);
+    }
+
+    for event in remaining_events {
+        match event {
+            SttEvent::Partial(text) => {
+                transcription.partial_text = text;
+            }
+            SttEvent::Final(text) => {
+                if !transcription.final_text.is_empty() {
+                    transcription.final_text.push(' ');
+                }
+                transcription.final_text.push_str(&text);
+                transcription.partial_text.clear();
+                if let Some(ref mut journal) = journal {
+                    if let Err(e) = journal.append(&text) {
+                        tracing::error!("Transcript file write error ({}): {}", journal.path().display(), e);
+                    }
+                }
+                let _ = state.transcript_updates.send(TranscriptUpdate::Final { text });
+            }
+            SttEvent::NoSpeech => {
+                transcription.no_speech = true;
+            }
+            SttEvent::TooShort { duration_ms, min_duration_ms } => {
+                transcription.no_speech = true;
+                let _ = app.emit("audio_too_short", too_short_message(duration_ms, min_duration_ms));
+            }
+            SttEvent::Error(message) => {
+                let _ = app.emit("stt_error", message.clone());
+                transcription.error = Some(message);
+            }
+        }
+    }
+    let final_text = transcription.current_text().trim().to_string();
+    let transcript_path = journal.as_ref().map(|journal| journal.path().to_path_buf());
+    finish_journal(journal);
+
+    // A newer dictation owns the shared state once it started
+    if state.dictation_seq.load(Ordering::SeqCst) == seq {
+        let mut trans = state.transcription.write().await;
+        trans.partial_text = transcription.partial_text.clone();
+        trans.final_text = transcription.final_text.clone();
+        trans.error = transcription.error.clone();
+        trans.no_speech = transcription.no_speech;
+        trans.last_recording_id = recording_id.clone();
+        trans.last_engine_id = engine_id.clone();
+    }
+
+    emit_state_event(app, "recording_stopped", final_text.clone());
+    let _ = state.transcript_updates.send(TranscriptUpdate::Stopped { text: final_text.clone() });
+    tracing::info!("Recording stopped, text: {}", final_text);
+
+    if let Some(path) = transcript_path.filter(|_| meeting && !final_text.is_empty()) {
+        let config = state.config.read().await.clone();
+        if config.meeting_summary.enabled {
+            tauri::async_runtime::spawn(summarize_meeting(app.clone(), config, path, final_text.clone()));
+        }
+    }
+
+    Ok(StoppedRecording {
+        text: final_text,
+        recording_id,
+        engine_id,
+        error: transcription.error,
+        no_speech: transcription.no_speech,
+        started_ms: transcription.recording_started_ms,
+        language: transcription.language,
+    })
+}
+
+/// Append the summary of a finished meeting to its transcript file
+///
+/// Runs after the stop, the transcript is already pasted; progress is
+/// published as `meeting_summary` events.
+async fn summarize_meeting(app: AppHandle, config: AppConfig, path: PathBuf, transcript: String) {
+    emit_state_event(&app, "meeting_summary", Message::new("meeting_summary_started"));
+    let result = match chat_completion(&config, config.meeting_summary.system_prompt(), &transcript).await {
+        Ok(summary) => append_summary(&path, &summary),
+        Err(e) => Err(format!("Chat error: {}", e)),
+    };
+    let progress = match result {
+        Ok(()) => {
+            tracing::info!("Meeting summary appended to {}", path.display());
+            Message::new("meeting_summary_written").arg("path", path.display())
+        }
+        Err(e) => {
+            tracing::error!("Meeting summary error: {}", e);
+            Message::new("meeting_summary_failed").arg("details", e)
+        }
+    };
+    emit_state_event(&app, "meeting_summary", progress);
+}
+
+/// Stop the current dictation and wait for its transcript
+async fn stop_recording_internal(app: &AppHandle, state: &AppState) -> Result<StoppedRecording, String> {
+    let stopping = begin_stop(app, state).await;
+    finish_stop(app, state, stopping).await
+}
+
+/// Stop recording and return the text
+#[tauri::command]
+async fn stop_recording(app: AppHandle, state: State<'_, AppState>) -> Result<String, DicteaError> {
+    let stopped = stop_recording_internal(&app, &state).await?;
+    hide_overlay_and_refocus(&app);
+    set_status(&app, &state, AppStatus::Idle).await;
+    Ok(stopped.text)
+}
+
+/// Stop and paste text into the active application
+#[tauri::command]
+async fn stop_and_paste(app: AppHandle, state: State<'_, AppState>) -> Result<(), DicteaError> {
+    // Guard against double call
+    let Some(stop_guard) = pipeline::StopGuard::acquire(&state.stopping) else {
+        tracing::warn!("stop_and_paste already in progress, skipped");
+        return Ok(());
+    };
+    let seq = state.dictation_seq.load(Ordering::SeqCst);
+    let ticket = state.output_queue.ticket();
+
+    let result = do_stop_and_paste(app.clone(), state.clone(), stop_guard, ticket).await;
+
+    let status = match result {
+        Ok(ref status) => status.clone(),
+        Err(ref e) => AppStatus::Error { message: e.clone() },
+    };
+    set_dictation_status(&app, &state, seq, status).await;
+    result.map(|_| ()).map_err(DicteaError::from)
+}
+
+/// Publish the state of dictation `seq`, unless a newer dictation started
+/// while it was processing
+async fn set_dictation_status(app: &AppHandle, state: &AppState, seq: u64, status: AppStatus) {
+    if state.dictation_seq.load(Ordering::SeqCst) == seq {
+        set_status(app, state, status).await;
+    }
+}
+
+/// Hide the overlay of dictation `seq`, unless a newer dictation is using it
+fn hide_dictation_overlay(app: &AppHandle, state: &AppState, seq: u64) {
+    if state.dictation_seq.load(Ordering::SeqCst) == seq {
+        hide_overlay_and_refocus(app);
+    }
+}
+
+/// Stop, transcribe and post-process the current dictation, without pasting
+///
+/// `flip_reformulate` inverts the `reformulate` setting for this dictation.
+/// A `quick_note` is titled by the chat model and kept in the history even
+/// when `keep_history` is off.
+/// `stop_guard` is released as soon as the capture stops: the next
+/// dictation may start while this one is transcribed and post-processed.
+async fn transcribe_and_process(
+    app: &AppHandle,
+    state: &State<'_, AppState>,
+    flip_reformulate: bool,
+    quick_note: bool,
+    stop_guard: pipeline::StopGuard,
+) -> Result<DictationResult, Message> {
+    let seq = state.dictation_seq.load(Ordering::SeqCst);
+    let stop_requested = std::time::SystemTime::now();
+
+    // Switch immediately to processing mode
+    set_status(app, state, AppStatus::Transcribing).await;
+    overlay::eval_all(app, "window.__overlaySetProcessing && window.__overlaySetProcessing(true)");
+
+    // Stop recording WITHOUT hiding the overlay
+    let stopping = begin_stop(app, state).await;
+    drop(stop_guard);
+    let StoppedRecording {
+        text: raw_text,
+        recording_id,
+        engine_id,
+        error,
+        no_speech,
+        started_ms: recording_started_ms,
+        language,
+    } = finish_stop(app, state, stopping).await?;
+
+    if raw_text.is_empty() {
+        tracing::info!("No text transcribed");
+        hide_dictation_overlay(app, state, seq);
+        if let Some(details) = error {
+            return Err(Message::new("transcription_error").arg("details", details));
+        }
+        if no_speech {
+            emit_state_event(app, "no_speech", ());
+        }
+        return Ok(DictationResult {
+            no_speech,
+            recording_id,
+            ..Default::default()
+        });
+    }
+
+    // Signal to the frontend that we're entering processing mode
+    set_dictation_status(app, state, seq, AppStatus::Processing { text: raw_text.clone() }).await;
+    emit_state_event(app, "processing_started", ());
+
+    let mut config = state.config.read().await.clone();
+    // Live translated dictations are pasted translated
+    if let Some(language) = config.live_translation.target(dictation_profile(&config)) {
+        config.output_language = language.to_string();
+    }
+
+    // Fillers are measured on the spoken text, before reformulation drops them
+    let duration_secs = recording_started_ms.map_or(0.0, |started| {
+        let stopped = stop_requested
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        stopped.saturating_sub(started) as f64 / 1000.0
+    });
+    record_speech_stats(app, &config, &raw_text, duration_secs, recording_id.clone());
+
+    // Replacements and spoken commands of the requested language, else the detected one
+    let language = language.or_else(|| postprocess::detect_language(&raw_text).map(str::to_string));
+    let dictated = config.text_replacements.apply(&raw_text, language.as_deref());
+
+    // Structured mode: the chat model returns a JSON object instead of prose
+    let structured = if config.structured_output.enabled {
+        let settings = &config.structured_output;
+        structure_text(&dictated, &config, &settings.schema, settings.max_retries).await
+    } else {
+        None
+    };
+
+    let mut reformulated = false;
+    let mut translated = false;
+    let text = match structured {
+        Some(ref object) => serde_json::to_string_pretty(object).unwrap_or_default(),
+        None => {
+            // Reformulate and/or translate in a single chat API call
+            let reformulate = config.reformulate != flip_reformulate;
+            let mut text = process_text(&dictated, reformulate, &config.output_language, &config).await;
+            // Handed back untouched without a chat API key or on error
+            let processed = text != dictated;
+            reformulated = reformulate && processed;
+            translated = config.output_language != "auto" && processed;
+            if config.enforce_output_language {
+                text = enforce_output_language(app, text, &config).await;
+            }
+
+            // Lists of marked sentences ("action item: ..."), detected in the spoken text
+            let sections = postprocess::extract_markers(&raw_text, &config.spoken_markers);
+            postprocess::append_marker_sections(&text, &sections, &config.marker_section_template)
+        }
+    };
+
+    let title = if quick_note {
+        let reply = match chat_completion(&config, TITLE_PROMPT, &text).await {
+            Ok(reply) => Some(reply),
+            Err(e) => {
+                tracing::warn!("Note title error, using its first words: {}", e);
+                None
+            }
+        };
+        Some(note_title(reply.as_deref(), &text))
+    } else {
+        None
+    };
+
+    // Now hide the overlay
+    hide_dictation_overlay(app, state, seq);
+
+    if config.keep_history || quick_note {
+        let engine = engine_id.as_deref().unwrap_or_default();
+        let entry = HistoryEntry {
+            reformulated,
+            translated,
+            recording_id: recording_id.clone(),
+            title: title.clone(),
+            ..HistoryEntry::new(engine, duration_secs, &raw_text, &text)
+        };
+        record_history(app, &config, &entry);
+    }
+
+    Ok(DictationResult {
+        raw_text,
+        text,
+        no_speech: false,
+        recording_id,
+        structured,
+        title,
+    })
+}
+
+/// Ask the chat model for a JSON object following `schema`, retrying invalid replies
+///
+/// Returns `None` (plain processing) if no valid object could be obtained.
+async fn structure_text(
+    text: &str,
+    config: &AppConfig,
+    schema: &[postprocess::SchemaField],
+    max_retries: u32,
+) -> Option<serde_json::Value> {
+    let prompt = postprocess::structured_prompt(schema);
+    let mut rejection: Option<String> = None;
+
+    for attempt in 0..=max_retries {
+        let system_prompt = match rejection {
+            Some(ref error) => format!("{}\nYour previous reply was rejected: {}.", prompt, error),
+            None => prompt.clone(),
+        };
+        let reply = match chat_completion(config, &system_prompt, text).await {
+            Ok(reply) => reply,
+            Err(e) => {
+                tracing::error!("Structured output error: {}", e);
+                return None;
+            }
+        };
+        match postprocess::validate_structured(&reply, schema) {
+            Ok(object) => return Some(object),
+            Err(e) => {
+                tracing::warn!("Invalid structured reply (attempt {}): {}", attempt + 1, e);
+                rejection = Some(e);
+            }
+        }
+    }
+    tracing::error!("No valid structured reply, falling back to plain text");
+    None
+}
+
+/// State to settle in once a stop command is done
+fn settled_status(result: &Result<DictationResult, Message>) -> AppStatus {
+    match result {
+        Ok(dictation) if dictation.no_speech => AppStatus::NoSpeech,
+        Ok(_) => AppStatus::Idle,
+        Err(e) => AppStatus::Error { message: e.clone() },
+    }
+}
+
+/// Stop, transcribe and post-process, returning the text to the caller
+/// without touching the clipboard or simulating keys (dry-run)
+#[tauri::command]
+async fn stop_and_return(app: AppHandle, state: State<'_, AppState>) -> Result<DictationResult, DicteaError> {
+    // Guard against double call
+    let Some(stop_guard) = pipeline::StopGuard::acquire(&state.stopping) else {
+        tracing::warn!("stop_and_return skipped, a stop is already in progress");
+        return Ok(DictationResult::default());
+    };
+    let seq = state.dictation_seq.load(Ordering::SeqCst);
+
+    let result = transcribe_and_process(&app, &state, false, false, stop_guard).await;
+    set_dictation_status(&app, &state, seq, settled_status(&result)).await;
+    result.map_err(DicteaError::from)
+}
+
+/// Turn the dictation into a task and create it on the configured service
+async fn capture_task(config: &AppConfig, raw_text: &str) -> Result<(), Message> {
+    let task = match chat_completion(config, &CapturedTask::extraction_prompt(), raw_text).await {
+        Ok(reply) => CapturedTask::from_reply(&reply, raw_text),
+        Err(e) => {
+            tracing::warn!("Task extraction error, using the dictation as title: {}", e);
+            CapturedTask::untitled(raw_text)
+        }
+    };
+    config
+        .task_capture
+        .send(&task)
+        .await
+        .map_err(|e| Message::new("task_capture_error").arg("details", e))
+}
+
+/// Write an email from the dictation and open it as a draft
+async fn draft_email(app: &AppHandle, config: &AppConfig, raw_text: &str) -> Result<(), Message> {
+    use tauri_plugin_opener::OpenerExt;
+
+    let email = match chat_completion(config, DraftedEmail::extraction_prompt(), raw_text).await {
+        Ok(reply) => DraftedEmail::from_reply(&reply, raw_text),
+        Err(e) => {
+            tracing::warn!("Email drafting error, using the dictation as body: {}", e);
+            DraftedEmail::untitled(raw_text)
+        }
+    };
+    let settings = &config.email_draft;
+    match settings.method {
+        EmailMethod::Mailto => app
+            .opener()
+            .open_url(email.mailto_url(&settings.to), None::<&str>)
+            .map_err(|e| Message::new("email_draft_error").arg("details", e)),
+        EmailMethod::Gmail => email
+            .create_gmail_draft(&settings.to, &settings.gmail_access_token)
+            .await
+            .map_err(|e| Message::new("email_draft_error").arg("details", e)),
+    }
+}
+
+/// Turn the dictation into an issue and file it with the active tracker
+/// profile, returning its URL
+async fn file_issue(config: &AppConfig, raw_text: &str) -> Result<String, Message> {
+    let profile = config
+        .issue_tracker
+        .profile()
+        .ok_or_else(|| Message::new("unknown_issue_profile").arg("value", &config.issue_tracker.active_profile))?;
+    let settings = &config.structured_output;
+    let issue = structure_text(raw_text, config, &Issue::schema(), settings.max_retries)
+        .await
+        .and_then(|object| Issue::from_structured(&object))
+        .unwrap_or_else(|| Issue::untitled(raw_text));
+    profile
+        .create(&issue)
+        .await
+        .map_err(|e| Message::new("issue_creation_error").arg("details", e))
 }
 
-/// Stop recording (internal, without hiding overlay)
-async fn stop_recording_internal(app: AppHandle, state: State<'_, AppState>) -> Result<String, String> {
-    let remaining_events = {
-        let mut pipeline_guard = state.pipeline.lock().await;
-        if let Some(ref mut pipeline) = *pipeline_guard {
-            pipeline.stop()
-        } else {
-            Vec::new()
-        }
-    };
+/// Open the dictation as a search (or app URI) with its default handler
+fn launch_search(app: &AppHandle, config: &AppConfig, text: &str) -> Result<(), Message> {
+    use tauri_plugin_opener::OpenerExt;
 
-    let final_text = {
-        let mut trans = state.transcription.write().await;
-        trans.is_recording = false;
+    let url = config.search_launcher.url_for(text);
+    app.opener()
+        .open_url(&url, None::<&str>)
+        .map_err(|e| Message::new("search_launch_error").arg("details", e))?;
+    tracing::info!("Search launched: {}", url);
+    emit_state_event(app, "search_launched", url);
+    Ok(())
+}
 
-        for event in remaining_events {
-            match event {
-                SttEvent::Partial(text) => {
-                    trans.partial_text = text;
-                }
-                SttEvent::Final(text) => {
-                    if !trans.final_text.is_empty() {
-                        trans.final_text.push(' ');
-                    }
-                    trans.final_text.push_str(&text);
-                    trans.partial_text.clear();
-                }
-            }
+/// Rewrite the live-typed transcript into the final text, then Enter if `submit`
+fn finish_live_paste(state: &AppState, typer: &mut LiveTyper, text: &str, app_name: Option<&str>, submit: bool) {
+    if let Err(e) = typer.revise(text, true) {
+        tracing::error!("Live paste error: {}", e);
+    }
+    if submit {
+        if let Err(e) = paste::send_keys(&PasteKeys::submit()) {
+            tracing::error!("{}", e);
         }
+    }
+    if let Ok(mut last) = state.last_paste.lock() {
+        *last = Some(PastedText::new(app_name, typer.typed()));
+    }
+}
 
-        let mut text = trans.final_text.clone();
-        if !trans.partial_text.is_empty() {
-            if !text.is_empty() {
-                text.push(' ');
-            }
-            text.push_str(&trans.partial_text);
-        }
-        text.trim().to_string()
+/// Invert `reformulate` for the next pasted dictation only (or restore it),
+/// returning whether the override is armed
+#[tauri::command]
+async fn toggle_next_reformulate(app: AppHandle, state: State<'_, AppState>) -> Result<bool, DicteaError> {
+    let armed = !state.flip_reformulate.fetch_xor(true, Ordering::SeqCst);
+    let reformulate = state.config.read().await.reformulate;
+    let mode = match (armed, reformulate) {
+        (false, _) => None,
+        (true, true) => Some("raw"),
+        (true, false) => Some("processed"),
     };
-
-    let _ = app.emit("recording_stopped", final_text.clone());
-    tracing::info!("Recording stopped, text: {}", final_text);
-
-    Ok(final_text)
+    tracing::info!("Next dictation output override: {:?}", mode);
+    show_output_override(&app, mode);
+    Ok(armed)
 }
 
-/// Stop recording and return the text
+/// Flag the current point of the meeting being recorded, returning its "MM:SS" offset
 #[tauri::command]
-async fn stop_recording(app: AppHandle, state: State<'_, AppState>) -> Result<String, String> {
-    let text = stop_recording_internal(app.clone(), state).await?;
-    hide_overlay_and_refocus(&app);
-    Ok(text)
+async fn add_annotation(app: AppHandle, state: State<'_, AppState>, label: Option<String>) -> Result<String, DicteaError> {
+    let label = match label.map(|label| label.trim().to_string()).filter(|label| !label.is_empty()) {
+        Some(label) => label,
+        None => state.config.read().await.annotations.label.clone(),
+    };
+    let sample = {
+        let mut guard = state.pipeline.lock().await;
+        let pipeline = guard
+            .as_mut()
+            .filter(|pipeline| pipeline.is_running && pipeline.meeting.is_some())
+            .ok_or_else(|| Message::new("annotation_needs_meeting"))?;
+        let sample = pipeline.meeting.as_ref().map_or(0, |(turns, _)| turns.position());
+        pipeline.annotations.push(Annotation {
+            sample,
+            label: label.clone(),
+        });
+        sample
+    };
+    let time = timestamp(sample);
+    tracing::info!("Annotation at {}: {}", time, label);
+    emit_state_event(&app, "annotation_added", serde_json::json!({ "time": time, "label": label }));
+    Ok(time)
 }
 
-/// Stop and paste text into the active application
+/// Use speaker profile `name` for the dictations of this session, over the
+/// configured one (`None` = back to the configured one)
 #[tauri::command]
-async fn stop_and_paste(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
-    // Guard against double call
-    if state.stopping.swap(true, Ordering::SeqCst) {
-        tracing::warn!("stop_and_paste already in progress, skipped");
-        return Ok(());
+async fn set_session_speaker(app: AppHandle, state: State<'_, AppState>, name: Option<String>) -> Result<(), DicteaError> {
+    let name = name.filter(|name| !name.is_empty());
+    if let Some(ref name) = name {
+        let config = state.config.read().await;
+        if stt::find_profile(&config.speaker_profiles, name).is_none() {
+            return Err(Message::new("unknown_speaker_profile").arg("value", name).into());
+        }
     }
-
-    let result = do_stop_and_paste(app, state.clone()).await;
-
-    state.stopping.store(false, Ordering::SeqCst);
-    result
+    tracing::info!("Session speaker profile: {}", name.as_deref().unwrap_or("configured one"));
+    if let Ok(mut speaker) = state.session_speaker.lock() {
+        *speaker = name.clone();
+    }
+    // The local engine loads the model of the profile
+    reset_pipeline(&state).await;
+    emit_state_event(&app, "session_speaker", name);
+    Ok(())
 }
 
-async fn do_stop_and_paste(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
-    // Switch immediately to processing mode
-    if let Some(overlay) = app.get_webview_window("overlay") {
-        let _ = overlay.eval("window.__overlaySetProcessing && window.__overlaySetProcessing(true)");
+/// Lock the dictations to the frontmost window, or release the lock,
+/// returning the locked app
+#[tauri::command]
+async fn toggle_target_lock(app: AppHandle, state: State<'_, AppState>) -> Result<Option<String>, DicteaError> {
+    let released = state.target_lock.lock().ok().and_then(|mut lock| lock.take());
+    if released.is_some() {
+        tracing::info!("Dictation target unlocked");
+        emit_state_event(&app, "target_lock", Option::<String>::None);
+        return Ok(None);
     }
 
-    // Stop recording WITHOUT hiding the overlay
-    let text = stop_recording_internal(app.clone(), state.clone()).await?;
-
-    if text.is_empty() {
-        tracing::info!("No text to paste");
-        hide_overlay_and_refocus(&app);
-        return Ok(());
+    let target = tokio::task::spawn_blocking(TargetWindow::frontmost)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| Message::new("target_lock_unavailable"))?;
+    if target.app_name.as_deref().is_some_and(paste::is_own_app) {
+        return Err(Message::new("target_lock_own_window").into());
     }
+    let app_name = target.app_name.clone().unwrap_or_default();
+    tracing::info!("Dictation target locked to {}", app_name);
+    if let Ok(mut lock) = state.target_lock.lock() {
+        *lock = Some(target);
+    }
+    emit_state_event(&app, "target_lock", Some(app_name.clone()));
+    Ok(Some(app_name))
+}
 
-    // Signal to the frontend that we're entering processing mode
-    let _ = app.emit("processing_started", ());
-
-    let config = state.config.read().await.clone();
-
-    // Reformulate and/or translate in a single chat API call
-    let final_text = process_text(
-        &text,
-        config.reformulate,
-        &config.output_language,
-        &config,
-    ).await;
+/// Show (or clear) the output override of the next dictation on the overlay
+fn show_output_override(app: &AppHandle, mode: Option<&str>) {
+    let js_mode = mode.map_or("null".to_string(), |mode| format!("\"{}\"", mode));
+    overlay::eval_all(
+        app,
+        &format!("window.__overlaySetOutputOverride && window.__overlaySetOutputOverride({})", js_mode),
+    );
+    emit_state_event(app, "output_override", mode);
+}
 
-    // Now hide the overlay
-    hide_overlay_and_refocus(&app);
+/// End live typing for the current dictation, returning the typer
+fn take_live_typer(state: &AppState) -> Option<LiveTyper> {
+    state.live_typer.lock().ok().and_then(|mut guard| guard.take())
+}
 
-    tracing::info!("Copying text to clipboard: {}", final_text);
+/// Forget the dedicated output of the current dictation, returning it
+fn take_dedicated_output(state: &AppState) -> Option<DedicatedOutput> {
+    state.dedicated_output.lock().ok().and_then(|mut guard| guard.take())
+}
 
-    // Copy to clipboard
-    match arboard::Clipboard::new() {
-        Ok(mut clipboard) => {
-            if let Err(e) = clipboard.set_text(&final_text) {
-                tracing::error!("Clipboard copy error: {}", e);
-                return Err(format!("Copy error: {}", e));
+/// Stop, transcribe and paste, returning the state to settle in
+///
+/// The output waits for `ticket`, so dictations overlapping with the
+/// processing of this one paste after it.
+async fn do_stop_and_paste(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    stop_guard: pipeline::StopGuard,
+    ticket: pipeline::OutputTicket,
+) -> Result<AppStatus, Message> {
+    let seq = state.dictation_seq.load(Ordering::SeqCst);
+    let dedicated = take_dedicated_output(&state);
+    let flip_reformulate = state.flip_reformulate.swap(false, Ordering::SeqCst);
+    if flip_reformulate {
+        show_output_override(&app, None);
+    }
+    // Text typed live during the dictation, if any (taken now, the next
+    // dictation gets its own typer)
+    let mut live_typed = take_live_typer(&state).filter(|typer| !typer.typed().is_empty());
+    let quick_note = dedicated == Some(DedicatedOutput::QuickNote);
+    let dictation = transcribe_and_process(&app, &state, flip_reformulate, quick_note, stop_guard).await?;
+    if dictation.text.is_empty() {
+        return Ok(if dictation.no_speech { AppStatus::NoSpeech } else { AppStatus::Idle });
+    }
+    let final_text = dictation.text;
+    let config = state.config.read().await.clone();
+    ticket.wait_turn().await;
+
+    // Dictations started with a dedicated shortcut skip the clipboard
+    match dedicated {
+        Some(DedicatedOutput::Task) => {
+            capture_task(&config, &dictation.raw_text).await?;
+            emit_state_event(&app, "task_captured", final_text);
+            return Ok(AppStatus::Idle);
+        }
+        Some(DedicatedOutput::Email) => {
+            draft_email(&app, &config, &dictation.raw_text).await?;
+            return Ok(AppStatus::Idle);
+        }
+        Some(DedicatedOutput::Issue) => {
+            let url = file_issue(&config, &dictation.raw_text).await?;
+            // The issue link is left in the clipboard
+            if !url.is_empty() {
+                copy_to_clipboard(&config, &url, &url)?;
             }
-            tracing::info!("Text copied to clipboard");
+            emit_state_event(&app, "issue_created", url);
+            return Ok(AppStatus::Idle);
         }
-        Err(e) => {
-            tracing::error!("Clipboard creation error: {}", e);
-            return Err(format!("Clipboard error: {}", e));
+        Some(DedicatedOutput::Search) => {
+            launch_search(&app, &config, &final_text)?;
+            return Ok(AppStatus::Idle);
+        }
+        Some(DedicatedOutput::QuickNote) => {
+            // Already in the history, the inbox file is optional
+            let title = dictation.title.unwrap_or_default();
+            let context = TemplateContext::new(dictation_profile(&config));
+            config
+                .quick_note
+                .append(&title, &final_text, &context)
+                .map_err(|e| Message::new("quick_note_error").arg("details", e))?;
+            emit_state_event(&app, "quick_note_saved", title);
+            return Ok(AppStatus::Idle);
         }
+        None => {}
     }
 
     // Wait for focus to return to the previous app
     tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
 
-    // Simulate Cmd+V to paste
-    tracing::info!("Simulating Cmd+V...");
-
-    #[cfg(target_os = "macos")]
-    {
-        // Use osascript to paste - more reliable than enigo and no Accessibility permissions needed
-        let status = std::process::Command::new("osascript")
-            .arg("-e")
-            .arg("tell application \"System Events\" to keystroke \"v\" using command down")
-            .output();
+    // A locked target gets the dictation wherever the focus went
+    let locked = state.target_lock.lock().ok().and_then(|lock| lock.clone());
+    if let Some(target) = locked {
+        match target.focus() {
+            Ok(()) => tokio::time::sleep(tokio::time::Duration::from_millis(150)).await,
+            Err(e) => {
+                tracing::warn!("Locked target lost ({}), lock released", e);
+                if let Ok(mut lock) = state.target_lock.lock() {
+                    *lock = None;
+                }
+                emit_state_event(&app, "target_lock", Option::<String>::None);
+                let _ = app.emit("paste_blocked", Message::new("target_lock_lost"));
+            }
+        }
+    }
 
-        match status {
-            Ok(output) => {
-                if output.status.success() {
-                    tracing::info!("Cmd+V simulated via osascript");
+    let mut app_name = paste::frontmost_app();
+    if app_name.as_deref().is_some_and(paste::is_own_app) {
+        tracing::warn!("Dictea still in front, refocusing the previous app");
+        refocus_previous_app(&app);
+        tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
+        app_name = paste::frontmost_app();
+    }
+    // Keys sent now would land in dictea's own window
+    let self_focused = app_name.as_deref().is_some_and(paste::is_own_app);
+    let profile = dictation_profile(&config);
+    let context = TemplateContext::new(profile);
+    for action in config.output_actions(profile, app_name.as_deref()) {
+        match action {
+            OutputAction::Clipboard => {
+                copy_to_clipboard(&config, &final_text, &dictation.raw_text)?;
+            }
+            OutputAction::Paste { .. } if self_focused => {
+                tracing::warn!("Focus did not return from dictea, paste skipped (text is in clipboard)");
+                copy_to_clipboard(&config, &final_text, &dictation.raw_text)?;
+                let _ = app.emit("paste_blocked", Message::new("paste_blocked_self"));
+            }
+            OutputAction::Paste { submit } => {
+                // Already typed live, only rewrite it into the processed text
+                if let Some(ref mut typer) = live_typed {
+                    set_dictation_status(&app, &state, seq, AppStatus::Pasting { text: final_text.clone() }).await;
+                    finish_live_paste(&state, typer, &final_text, app_name.as_deref(), submit);
+                    continue;
+                }
+                let text = if config.smart_continuation {
+                    continued_text(&state, app_name.as_deref(), &final_text)
                 } else {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    tracing::error!("osascript error: {}", stderr);
-                    tracing::info!("Text is in clipboard, paste with Cmd+V");
+                    final_text.clone()
+                };
+                set_dictation_status(&app, &state, seq, AppStatus::Pasting { text: text.clone() }).await;
+                match config.paste_method_for(app_name.as_deref()) {
+                    PasteMethod::Clipboard => {
+                        copy_to_clipboard(&config, &text, &dictation.raw_text)?;
+                        paste_into(&app, &config, app_name.as_deref(), submit);
+                    }
+                    PasteMethod::Type => type_into(&app, &config, app_name.as_deref(), &text, &dictation.raw_text, submit)?,
+                }
+                if let Ok(mut last) = state.last_paste.lock() {
+                    *last = Some(PastedText::new(app_name.as_deref(), &text));
                 }
             }
-            Err(e) => {
-                tracing::error!("osascript launch error: {}", e);
-                tracing::info!("Text is in clipboard, paste with Cmd+V");
+            OutputAction::Note => {
+                if let Err(e) = config.note_output.append(&final_text, &context) {
+                    tracing::error!("{}", e);
+                }
+            }
+            OutputAction::File { path } => {
+                let written = TranscriptJournal::open(&path, &context).and_then(|mut file| {
+                    file.append(&final_text)
+                        .and_then(|_| file.finish())
+                        .map_err(|e| format!("Output file write error: {}", e))
+                });
+                if let Err(e) = written {
+                    tracing::error!("{}", e);
+                }
+            }
+            OutputAction::Webhook { url } => {
+                let payload = dictation
+                    .structured
+                    .clone()
+                    .unwrap_or_else(|| serde_json::json!({ "text": final_text }));
+                if let Err(e) = output::post_json(&url, &payload).await {
+                    tracing::error!("Webhook error ({}): {}", url, e);
+                }
+            }
+            OutputAction::Chat => {
+                for target in config.chat_targets.iter().filter(|t| t.applies_to(profile)) {
+                    if let Err(e) = target.post(&final_text).await {
+                        tracing::error!("Chat post error ({:?}): {}", target.service, e);
+                    }
+                }
             }
         }
     }
 
-    #[cfg(target_os = "windows")]
-    {
-        use enigo::{Enigo, Key, Keyboard, Settings};
-        match Enigo::new(&Settings::default()) {
-            Ok(mut enigo) => {
-                enigo.key(Key::Control, enigo::Direction::Press).ok();
-                enigo.key(Key::Unicode('v'), enigo::Direction::Click).ok();
-                enigo.key(Key::Control, enigo::Direction::Release).ok();
-                tracing::info!("Ctrl+V simulated via enigo");
-            }
-            Err(e) => {
-                tracing::error!("enigo error: {}", e);
-                tracing::info!("Text is in clipboard, paste with Ctrl+V");
-            }
+    Ok(AppStatus::Idle)
+}
+
+/// Adapt the capitalization and spacing of `text` to what precedes the caret,
+/// or to the previous dictation pasted in the same app
+fn continued_text(state: &AppState, app_name: Option<&str>, text: &str) -> String {
+    let preceding = overlay::text_before_caret(paste::CONTEXT_CHARS).or_else(|| {
+        let last = state.last_paste.lock().ok()?;
+        last.as_ref()?.preceding_for(app_name).map(str::to_string)
+    });
+    match preceding {
+        Some(preceding) => paste::continue_after(&preceding, text),
+        None => text.to_string(),
+    }
+}
+
+/// Copy the processed text to the clipboard (and the raw one to the secondary buffer)
+fn copy_to_clipboard(config: &AppConfig, text: &str, raw_text: &str) -> Result<(), Message> {
+    tracing::info!("Copying text to clipboard: {}", text);
+    if paste::is_wayland_session() && paste::has_wl_copy() {
+        // arboard only reaches the Wayland apps through XWayland
+        if let Err(e) = paste::wl_copy(text, false) {
+            tracing::error!("Clipboard copy error: {}", e);
+            return Err(Message::new("copy_error").arg("details", e));
+        }
+    } else {
+        let mut clipboard = arboard::Clipboard::new().map_err(|e| {
+            tracing::error!("Clipboard creation error: {}", e);
+            Message::new("clipboard_error").arg("details", e)
+        })?;
+        if let Err(e) = clipboard.set_text(text) {
+            tracing::error!("Clipboard copy error: {}", e);
+            return Err(Message::new("copy_error").arg("details", e));
+        }
+    }
+    tracing::info!("Text copied to clipboard");
+    if config.raw_to_secondary_clipboard {
+        match paste::copy_to_secondary(raw_text) {
+            Ok(()) => tracing::info!("Raw transcript copied to secondary clipboard"),
+            Err(e) => tracing::warn!("{}", e),
         }
     }
+    Ok(())
+}
 
-    #[cfg(target_os = "linux")]
-    {
-        let status = std::process::Command::new("xdotool")
-            .args(["key", "ctrl+v"])
-            .output();
+/// Simulate the paste shortcut configured for the frontmost app, then Enter if `submit`
+fn paste_into(app: &AppHandle, config: &AppConfig, app_name: Option<&str>, submit: bool) {
+    if paste::foreground_is_elevated() {
+        // Input to an elevated window is dropped, leave the text in the clipboard
+        let app_label = app_name.unwrap_or_default();
+        tracing::warn!("{} runs elevated, paste skipped (text is in clipboard)", app_label);
+        let _ = app.emit("paste_blocked", Message::new("paste_blocked_elevated").arg("app", app_label));
+        return;
+    }
+    if !paste::key_injection_available() {
+        tracing::warn!("Neither wtype nor ydotool in this Wayland session, paste skipped (text is in clipboard)");
+        let _ = app.emit("paste_blocked", Message::new("paste_tool_missing"));
+        return;
+    }
+    let keys = config.paste_keys_for(app_name);
+    tracing::info!("Simulating {} in {}...", keys, app_name.unwrap_or("unknown app"));
+    match paste::send_keys(&keys) {
+        Ok(()) => tracing::info!("{} simulated", keys),
+        Err(e) => {
+            tracing::error!("{}", e);
+            tracing::info!("Text is in clipboard, paste with {}", keys);
+            return;
+        }
+    }
+    if submit {
+        if let Err(e) = paste::send_keys(&PasteKeys::submit()) {
+            tracing::error!("{}", e);
+        }
+    }
+}
 
-        match status {
-            Ok(output) => {
-                if output.status.success() {
-                    tracing::info!("Ctrl+V simulated via xdotool");
-                } else {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    tracing::error!("xdotool error: {}", stderr);
-                    tracing::info!("Text is in clipboard, paste with Ctrl+V");
-                }
-            }
-            Err(e) => {
-                tracing::error!("xdotool launch error: {}", e);
-                tracing::info!("Text is in clipboard, paste with Ctrl+V");
-            }
+/// Type `text` into the frontmost app as keystrokes, then Enter if `submit`
+///
+/// The text is left in the clipboard when it cannot be typed.
+fn type_into(
+    app: &AppHandle,
+    config: &AppConfig,
+    app_name: Option<&str>,
+    text: &str,
+    raw_text: &str,
+    submit: bool,
+) -> Result<(), Message> {
+    if paste::foreground_is_elevated() {
+        // Input to an elevated window is dropped
+        let app_label = app_name.unwrap_or_default();
+        tracing::warn!("{} runs elevated, typing skipped (text is in clipboard)", app_label);
+        let _ = app.emit("paste_blocked", Message::new("paste_blocked_elevated").arg("app", app_label));
+        return copy_to_clipboard(config, text, raw_text);
+    }
+    if !paste::key_injection_available() {
+        tracing::warn!("Neither wtype nor ydotool in this Wayland session, typing skipped (text is in clipboard)");
+        let _ = app.emit("paste_blocked", Message::new("paste_tool_missing"));
+        return copy_to_clipboard(config, text, raw_text);
+    }
+    tracing::info!("Typing {} characters in {}...", text.chars().count(), app_name.unwrap_or("unknown app"));
+    if let Err(e) = paste::type_text(text) {
+        tracing::error!("{}", e);
+        return copy_to_clipboard(config, text, raw_text);
+    }
+    if config.raw_to_secondary_clipboard {
+        match paste::copy_to_secondary(raw_text) {
+            Ok(()) => tracing::info!("Raw transcript copied to secondary clipboard"),
+            Err(e) => tracing::warn!("{}", e),
+        }
+    }
+    if submit {
+        if let Err(e) = paste::send_keys(&PasteKeys::submit()) {
+            tracing::error!("{}", e);
+        }
+    }
+    Ok(())
+}
+
+/// Transcribe the audio file (or audio data) held by the clipboard, replacing
+/// it with the transcript
+#[tauri::command]
+async fn transcribe_clipboard(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    language: Option<String>,
+) -> Result<String, DicteaError> {
+    let config = state.config.read().await.clone();
+    let samples = tokio::task::spawn_blocking(paste::read_clipboard_audio)
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| Message::new("clipboard_audio_error").arg("details", e))?
+        .ok_or_else(|| Message::new("clipboard_no_audio"))?;
+    tracing::info!("Transcribing {:.1}s of clipboard audio", samples.len() as f64 / 16000.0);
+
+    let mut engine = create_engine(&config)?;
+    engine.set_language(language.as_deref().map_or(Language::Auto, Language::from_code));
+    let billed_secs = samples.len() as f64 / 16000.0;
+    // The HTTP engines block until the whole file is transcribed
+    let events = tokio::task::spawn_blocking(move || {
+        engine.push_audio(&samples);
+        engine.flush();
+        std::iter::from_fn(|| engine.poll()).collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    record_usage(&app, &config.stt_engine, billed_secs);
+
+    let mut texts = Vec::new();
+    for event in events {
+        match event {
+            SttEvent::Final(text) => texts.push(text),
+            SttEvent::Error(details) => return Err(Message::new("transcription_error").arg("details", details).into()),
+            SttEvent::Partial(_) | SttEvent::NoSpeech | SttEvent::TooShort { .. } => {}
         }
     }
+    let raw_text = texts.join(" ").trim().to_string();
+    if raw_text.is_empty() {
+        return Err(Message::new("clipboard_audio_silent").into());
+    }
+
+    let language = language.or_else(|| postprocess::detect_language(&raw_text).map(str::to_string));
+    let dictated = config.text_replacements.apply(&raw_text, language.as_deref());
+    let text = process_text(&dictated, config.reformulate, &config.output_language, &config).await;
+    copy_to_clipboard(&config, &text, &raw_text)?;
+    emit_state_event(&app, "clipboard_transcribed", text.clone());
+    Ok(text)
+}
+
+/// Delete the last pasted dictation, if the app it went to is still in front
+#[tauri::command]
+async fn undo_last_paste(app: AppHandle, state: State<'_, AppState>) -> Result<(), DicteaError> {
+    let app_name = paste::frontmost_app();
+    let count = state.last_paste.lock().ok().and_then(|mut last| {
+        let count = last.as_ref()?.undo_keystrokes(app_name.as_deref())?;
+        // A paste is only undone once
+        *last = None;
+        Some(count)
+    });
+    let Some(count) = count else {
+        return Err(Message::new("nothing_to_undo").into());
+    };
 
-    tracing::info!("Text pasted: {}", final_text);
+    tracing::info!("Undoing last paste in {}: {} characters", app_name.as_deref().unwrap_or("unknown app"), count);
+    paste::send_keys_times(&PasteKeys::backspace(), count)
+        .map_err(|e| Message::new("undo_error").arg("details", e))?;
+    emit_state_event(&app, "paste_undone", ());
     Ok(())
 }
 
@@ -824,14 +3072,67 @@ async fn do_stop_and_paste(app: AppHandle, state: State<'_, AppState>) -> Result
 #[tauri::command]
 async fn get_transcription_state(
     state: State<'_, AppState>,
-) -> Result<TranscriptionState, String> {
+) -> Result<TranscriptionState, DicteaError> {
     let trans = state.transcription.read().await;
     Ok(trans.clone())
 }
 
+/// Get the current dictation state (as last published on STATUS_EVENT)
+#[tauri::command]
+async fn get_status(state: State<'_, AppState>) -> Result<AppStatus, DicteaError> {
+    Ok(state.status.read().await.clone())
+}
+
+/// Session state for a webview that attached late
+#[derive(Debug, Clone, serde::Serialize)]
+struct SyncState {
+    status: AppStatus,
+    transcription: TranscriptionState,
+    /// Last state events, oldest first
+    events: Vec<RecordedEvent>,
+}
+
+/// Current session state plus the recent state events, so a webview that
+/// loaded after the fact can render correctly
+#[tauri::command]
+async fn sync_state(state: State<'_, AppState>) -> Result<SyncState, DicteaError> {
+    let status = state.status.read().await.clone();
+    let transcription = state.transcription.read().await.clone();
+    let events = state
+        .history
+        .lock()
+        .map(|history| history.snapshot())
+        .unwrap_or_default();
+    Ok(SyncState {
+        status,
+        transcription,
+        events,
+    })
+}
+
+/// Register the calling webview for audio level updates (or withdraw it)
+#[tauri::command]
+fn set_level_interest(window: tauri::WebviewWindow, interested: bool) {
+    overlay::set_level_interest(window.label(), interested);
+}
+
+/// Check the OS permissions needed to simulate the paste (macOS)
+#[tauri::command]
+async fn check_automation_permissions() -> Result<paste::PastePermissions, DicteaError> {
+    tokio::task::spawn_blocking(paste::check_paste_permissions)
+        .await
+        .map_err(|e| DicteaError::from(e.to_string()))
+}
+
+/// Open the System Settings pane granting `pane`
+#[tauri::command]
+fn open_permission_settings(pane: paste::PermissionPane) -> Result<(), DicteaError> {
+    paste::open_permission_settings(pane).map_err(DicteaError::from)
+}
+
 /// Toggle overlay (global shortcut)
 #[tauri::command]
-async fn toggle_overlay(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+async fn toggle_overlay(app: AppHandle, state: State<'_, AppState>) -> Result<(), DicteaError> {
     let is_recording = {
         let trans = state.transcription.read().await;
         trans.is_recording
@@ -841,16 +3142,80 @@ async fn toggle_overlay(app: AppHandle, state: State<'_, AppState>) -> Result<()
         stop_and_paste(app, state).await
     } else {
         let result = start_recording(app.clone(), state, None).await;
+        if result.is_err() {
+            take_dedicated_output(&app.state::<AppState>());
+        }
         if let Err(ref e) = result {
-            let _ = app.emit("config_error", e.clone());
+            let state = app.state::<AppState>();
+            set_status(&app, &state, AppStatus::Error { message: e.message.clone() }).await;
+            let _ = app.emit("config_error", e.message.clone());
         }
         result
     }
 }
 
+/// Toggle a dictation sent to `output` instead of pasted (dedicated shortcuts)
+async fn toggle_dedicated(app: AppHandle, state: State<'_, AppState>, output: DedicatedOutput) -> Result<(), DicteaError> {
+    let is_recording = state.transcription.read().await.is_recording;
+    if !is_recording {
+        if let Ok(mut dedicated) = state.dedicated_output.lock() {
+            *dedicated = Some(output);
+        }
+    }
+    toggle_overlay(app, state).await
+}
+
+/// Run a command received from an external controller (control socket...)
+async fn run_control_command(app: AppHandle, command: ControlCommand) {
+    let state = app.state::<AppState>();
+    let is_recording = state.transcription.read().await.is_recording;
+    let result = match command {
+        ControlCommand::Toggle => toggle_overlay(app.clone(), state).await,
+        ControlCommand::Start if !is_recording => toggle_overlay(app.clone(), state).await,
+        ControlCommand::Stop if is_recording => stop_and_paste(app.clone(), state).await,
+        ControlCommand::Cancel => cancel_recording(app.clone(), state).await,
+        _ => Ok(()),
+    };
+    if let Err(e) = result {
+        tracing::warn!("Control command {:?} failed: {}", command, e);
+    }
+}
+
+/// Force-reset a dictation stuck in transcription/processing
+///
+/// Drops the pipeline (and its engine) so the next dictation starts fresh,
+/// and releases the guards a wedged stop would otherwise hold forever.
+async fn recover_pipeline(app: &AppHandle, state: &AppState) {
+    tracing::error!("Dictation stuck in {:?}, resetting the pipeline", *state.status.read().await);
+
+    match state.pipeline.try_lock() {
+        Ok(mut pipeline) => *pipeline = None,
+        // The wedged stop still holds it, drop the pipeline once it returns
+        Err(_) => state.pipeline_reset_pending.store(true, Ordering::SeqCst),
+    }
+    // A pipeline still flushing must not be put back either
+    if state.flushing_pipelines.load(Ordering::SeqCst) > 0 {
+        state.pipeline_reset_pending.store(true, Ordering::SeqCst);
+    }
+    {
+        let mut trans = state.transcription.write().await;
+        trans.is_recording = false;
+        trans.partial_text.clear();
+        trans.final_text.clear();
+    }
+    take_dedicated_output(state);
+    close_journal(state);
+    state.stopping.store(false, Ordering::SeqCst);
+
+    hide_overlay_and_refocus(app);
+    let message = Message::new("pipeline_recovered");
+    set_status(app, state, AppStatus::Error { message: message.clone() }).await;
+    emit_state_event(app, "pipeline_recovered", message);
+}
+
 /// Cancel current recording (no paste)
 #[tauri::command]
-async fn cancel_recording(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+async fn cancel_recording(app: AppHandle, state: State<'_, AppState>) -> Result<(), DicteaError> {
     let is_recording = {
         let trans = state.transcription.read().await;
         trans.is_recording
@@ -860,14 +3225,31 @@ async fn cancel_recording(app: AppHandle, state: State<'_, AppState>) -> Result<
         return Ok(());
     }
 
-    // Stop pipeline without processing text
-    {
-        let mut pipeline_guard = state.pipeline.lock().await;
-        if let Some(ref mut pipeline) = *pipeline_guard {
-            pipeline.stop();
-        }
+    take_dedicated_output(&state);
+    // Segments already typed live stay in the target app
+    take_live_typer(&state);
+
+    // Stop the capture, the engine is flushed in the background
+    if let Some(pipeline) = detach_pipeline(&state).await {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let state = app.state::<AppState>();
+            // The audio was already sent on flush, it is billed all the same
+            match flush_pipeline(&app, &state, pipeline).await {
+                // A cancelled dictation is not kept
+                Ok((_, Some(id), _)) => {
+                    let _ = recording_store(&app).delete(&id);
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Cancelled dictation flush failed: {}", e),
+            }
+        });
     }
 
+    overlay::set_capture_title(&app, false);
+    // Segments already written stay in the transcript file
+    close_journal(&state);
+
     {
         let mut trans = state.transcription.write().await;
         trans.is_recording = false;
@@ -876,7 +3258,8 @@ async fn cancel_recording(app: AppHandle, state: State<'_, AppState>) -> Result<
     }
 
     hide_overlay_and_refocus(&app);
-    let _ = app.emit("recording_cancelled", ());
+    set_status(&app, &state, AppStatus::Idle).await;
+    emit_state_event(&app, "recording_cancelled", ());
     tracing::info!("Recording cancelled");
 
     Ok(())
@@ -884,6 +3267,20 @@ async fn cancel_recording(app: AppHandle, state: State<'_, AppState>) -> Result<
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    launch(|_| {})
+}
+
+/// Run the app driven by `simulation` instead of the user (feature `sim`)
+#[cfg(feature = "sim")]
+pub fn run_simulated(simulation: sim::Simulation) {
+    launch(move |app| sim::drive(app, simulation))
+}
+
+/// Build and run the app, handing it to `on_ready` once set up
+fn launch<F>(on_ready: F)
+where
+    F: FnOnce(&AppHandle) + Send + 'static,
+{
     tracing_subscriber::registry()
         .with(
             tracing_subscriber::EnvFilter::try_from_default_env()
@@ -903,67 +3300,330 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             get_config,
             set_config,
+            patch_config,
             list_audio_devices,
+            list_system_audio_devices,
+            list_monitors,
             start_mic_preview,
             stop_mic_preview,
             start_recording,
             stop_recording,
             stop_and_paste,
+            stop_and_return,
             get_transcription_state,
+            get_status,
+            sync_state,
+            set_level_interest,
+            check_automation_permissions,
+            open_permission_settings,
             toggle_overlay,
             cancel_recording,
+            list_recordings,
+            get_history,
+            delete_history_entry,
+            clear_history,
+            play_recording,
+            stop_playback,
+            get_storage_usage,
+            get_provider_usage,
+            get_stats,
+            undo_last_paste,
+            toggle_next_reformulate,
+            add_annotation,
+            transcribe_clipboard,
+            toggle_target_lock,
+            set_session_speaker,
+            get_locked_fields,
+            sync_profiles,
+            sync_history,
+            get_resource_usage,
+            get_request_metrics,
+            get_engine_status,
+            clear_transcript_cache,
+            set_request_logging,
+            set_active_preset,
         ])
-        .setup(|app| {
-            use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+        .setup(move |app| {
+            use tauri_plugin_global_shortcut::ShortcutState;
 
             // Load saved config
-            let saved_config = AppConfig::load(app.handle());
+            let mut saved_config = AppConfig::load(app.handle());
+            // Both must run, a `||` would skip the second
+            if saved_config.control_socket.ensure_token() | saved_config.live_feed.ensure_token() {
+                saved_config.save(app.handle());
+            }
             let state = app.state::<AppState>();
             let config = state.config.clone();
+            if let Ok(mut history) = state.history.lock() {
+                history.set_capacity(saved_config.event_history_size);
+            }
+            apply_request_logging(app.handle(), &saved_config);
+            stt::set_concurrency_limits(&saved_config.request_limits);
+            let saved_control_socket = saved_config.control_socket.clone();
+            let saved_live_feed = saved_config.live_feed.clone();
+            let saved_hid_trigger = saved_config.hid_trigger.clone();
+            let saved_gamepad_trigger = saved_config.gamepad_trigger.clone();
+            let saved_media_key_trigger = saved_config.media_key_trigger;
+            let saved_shortcut_gestures = saved_config.shortcut_gestures.clone();
+            let saved_profile_sync = saved_config.profile_sync.clone();
+            let launch_config = saved_config.clone();
             tauri::async_runtime::block_on(async {
                 let mut c = config.write().await;
                 *c = saved_config;
             });
 
-            let app_handle = app.handle().clone();
+            // Pull the shared team profile in the background
+            if !saved_profile_sync.url.is_empty() {
+                let handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let state = handle.state::<AppState>();
+                    if let Err(e) = sync_profiles(handle.clone(), state).await {
+                        tracing::warn!("Shared profile sync failed: {}", e);
+                    }
+                });
+            }
+
+            // Local WebSocket for Stream Deck plugins and other controllers
+            if saved_control_socket.enabled {
+                let status = state.status.clone();
+                let updates = state.status_updates.clone();
+                let control_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let on_command = move |command| {
+                        tauri::async_runtime::spawn(run_control_command(control_handle.clone(), command));
+                    };
+                    if let Err(e) = control::serve(saved_control_socket, status, updates, on_command).await {
+                        tracing::error!("Control socket error: {}", e);
+                    }
+                });
+            }
+
+            // Live transcript for companion devices (phone, tablet) and a webhook
+            if saved_live_feed.enabled {
+                if !saved_live_feed.webhook_url.is_empty() {
+                    let receiver = state.transcript_updates.subscribe();
+                    tauri::async_runtime::spawn(control::push_to_webhook(saved_live_feed.webhook_url.clone(), receiver));
+                }
+                let updates = state.transcript_updates.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = control::serve_feed(saved_live_feed, updates).await {
+                        tracing::error!("Live feed error: {}", e);
+                    }
+                });
+            }
+
+            // Foot pedal, gamepad and headset button triggers
+            if saved_hid_trigger.enabled {
+                let trigger_handle = app.handle().clone();
+                control::spawn_hid_trigger(saved_hid_trigger, move |command| {
+                    tauri::async_runtime::spawn(run_control_command(trigger_handle.clone(), command));
+                });
+            }
+            if saved_gamepad_trigger.enabled {
+                let trigger_handle = app.handle().clone();
+                control::spawn_gamepad_trigger(saved_gamepad_trigger, move |command| {
+                    tauri::async_runtime::spawn(run_control_command(trigger_handle.clone(), command));
+                });
+            }
+            if saved_media_key_trigger {
+                // The media session needs the main window on Windows
+                #[cfg(target_os = "windows")]
+                let hwnd = app
+                    .get_webview_window("main")
+                    .and_then(|window| window.hwnd().ok())
+                    .map(|hwnd| hwnd.0 as *mut std::ffi::c_void);
+                #[cfg(not(target_os = "windows"))]
+                let hwnd = None;
+                let trigger_handle = app.handle().clone();
+                control::listen_media_keys(hwnd, move |command| {
+                    tauri::async_runtime::spawn(run_control_command(trigger_handle.clone(), command));
+                });
+            }
+
+            // Toggle shortcut gestures: double press cancels, hold is push-to-talk
+            let gesture_edges = if saved_shortcut_gestures.enabled {
+                let (edges_tx, edges_rx) = tokio::sync::mpsc::unbounded_channel();
+                let gesture_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let is_recording = || async {
+                        let state = gesture_handle.state::<AppState>();
+                        let recording = state.transcription.read().await.is_recording;
+                        recording
+                    };
+                    let on_command = |command| {
+                        tauri::async_runtime::spawn(run_control_command(gesture_handle.clone(), command));
+                    };
+                    control::recognize_gestures(saved_shortcut_gestures, edges_rx, is_recording, on_command).await;
+                });
+                Some(edges_tx)
+            } else {
+                None
+            };
+
+            // Push-to-talk: edges handled in order so a quick release never
+            // overtakes the start it follows
+            let (hold_edges, mut hold_rx) = tokio::sync::mpsc::unbounded_channel::<KeyEdge>();
+            let hold_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                while let Some(edge) = hold_rx.recv().await {
+                    match edge {
+                        KeyEdge::Pressed => run_control_command(hold_handle.clone(), ControlCommand::Start).await,
+                        // The next dictation may start while this one is processed
+                        KeyEdge::Released => {
+                            tauri::async_runtime::spawn(run_control_command(hold_handle.clone(), ControlCommand::Stop));
+                        }
+                    }
+                }
+            });
 
-            let toggle_shortcut: Shortcut = "CmdOrCtrl+Shift+Space"
-                .parse()
-                .expect("Invalid shortcut");
-            let cancel_shortcut: Shortcut = "CmdOrCtrl+Shift+C"
-                .parse()
-                .expect("Invalid shortcut");
+            // Meeting in progress read from the local calendar, provider
+            // health probes and history sync between machines, restarted on
+            // config changes
+            update_background_loops(app.handle(), None, &launch_config);
+
+            // Watchdog recovering dictations stuck in transcription/processing,
+            // only polling while a dictation is busy
+            let watchdog_handle = app.handle().clone();
+            let mut status_updates = state.status_updates.subscribe();
+            tauri::async_runtime::spawn(async move {
+                let state = watchdog_handle.state::<AppState>();
+                let mut detector = pipeline::StuckDetector::default();
+                loop {
+                    let timeout_secs = state.config.read().await.watchdog_timeout_secs;
+                    let busy = timeout_secs > 0
+                        && matches!(
+                            *state.status.read().await,
+                            AppStatus::Transcribing | AppStatus::Processing { .. }
+                        );
+                    if detector.observe(busy, std::time::Duration::from_secs(timeout_secs)) {
+                        recover_pipeline(&watchdog_handle, &state).await;
+                        continue;
+                    }
+                    if busy {
+                        let _timer = pipeline::ResourceGuard::acquire(pipeline::Resource::Timer);
+                        tokio::time::sleep(pipeline::WATCHDOG_INTERVAL).await;
+                    } else if let Err(tokio::sync::broadcast::error::RecvError::Closed) = status_updates.recv().await {
+                        break;
+                    }
+                }
+            });
 
-            let toggle_sc = toggle_shortcut.clone();
-            let cancel_sc = cancel_shortcut.clone();
+            // Cleanup of saved recordings at launch, then after each saved dictation
+            let cleanup_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let state = cleanup_handle.state::<AppState>();
+                let config = state.config.read().await.clone();
+                cleanup_recordings(&cleanup_handle, &config);
+            });
 
             app.handle().plugin(
                 tauri_plugin_global_shortcut::Builder::new()
-                    .with_handler(move |_app, shortcut, event| {
-                        if event.state == ShortcutState::Pressed {
-                            let handle = app_handle.clone();
-                            if shortcut == &toggle_sc {
-                                tauri::async_runtime::spawn(async move {
-                                    let state = handle.state::<AppState>();
+                    .with_handler(move |app, shortcut, event| {
+                        let bound = app
+                            .state::<AppState>()
+                            .shortcuts
+                            .lock()
+                            .ok()
+                            .and_then(|shortcuts| Some((shortcuts.action(shortcut)?, shortcuts.mode())));
+                        let Some((action, mode)) = bound else {
+                            return;
+                        };
+                        let edge = match event.state {
+                            ShortcutState::Pressed => KeyEdge::Pressed,
+                            ShortcutState::Released => KeyEdge::Released,
+                        };
+                        // Push-to-talk replaces the gestures of the toggle shortcut
+                        if action == ShortcutAction::Toggle && mode == ShortcutMode::Hold {
+                            let _ = hold_edges.send(edge);
+                            return;
+                        }
+                        if let Some(ref edges) = gesture_edges {
+                            if action == ShortcutAction::Toggle {
+                                let _ = edges.send(edge);
+                                return;
+                            }
+                        }
+                        if event.state != ShortcutState::Pressed {
+                            return;
+                        }
+                        let handle = app.clone();
+                        tauri::async_runtime::spawn(async move {
+                            let state = handle.state::<AppState>();
+                            match action {
+                                ShortcutAction::Toggle => {
                                     let _ = toggle_overlay(handle.clone(), state).await;
-                                });
-                            } else if shortcut == &cancel_sc {
-                                tauri::async_runtime::spawn(async move {
-                                    let state = handle.state::<AppState>();
+                                }
+                                ShortcutAction::Cancel => {
                                     let _ = cancel_recording(handle.clone(), state).await;
-                                });
+                                }
+                                ShortcutAction::Task => {
+                                    let _ = toggle_dedicated(handle.clone(), state, DedicatedOutput::Task).await;
+                                }
+                                ShortcutAction::Email => {
+                                    let _ = toggle_dedicated(handle.clone(), state, DedicatedOutput::Email).await;
+                                }
+                                ShortcutAction::Issue => {
+                                    let _ = toggle_dedicated(handle.clone(), state, DedicatedOutput::Issue).await;
+                                }
+                                ShortcutAction::Search => {
+                                    let _ = toggle_dedicated(handle.clone(), state, DedicatedOutput::Search).await;
+                                }
+                                ShortcutAction::QuickNote => {
+                                    let _ = toggle_dedicated(handle.clone(), state, DedicatedOutput::QuickNote).await;
+                                }
+                                ShortcutAction::Undo => {
+                                    if let Err(e) = undo_last_paste(handle.clone(), state).await {
+                                        tracing::info!("Undo skipped: {}", e);
+                                    }
+                                }
+                                ShortcutAction::ReformulateToggle => {
+                                    let _ = toggle_next_reformulate(handle.clone(), state).await;
+                                }
+                                ShortcutAction::ClipboardAudio => {
+                                    if let Err(e) = transcribe_clipboard(handle.clone(), state, None).await {
+                                        tracing::warn!("Clipboard transcription failed: {}", e);
+                                        let _ = handle.emit("clipboard_audio_failed", e.message);
+                                    }
+                                }
+                                ShortcutAction::TargetLock => {
+                                    if let Err(e) = toggle_target_lock(handle.clone(), state).await {
+                                        tracing::warn!("Target lock failed: {}", e);
+                                        let _ = handle.emit("paste_blocked", e.message);
+                                    }
+                                }
+                                ShortcutAction::Annotate => {
+                                    if let Err(e) = add_annotation(handle.clone(), state, None).await {
+                                        tracing::info!("Annotation skipped: {}", e);
+                                    }
+                                }
                             }
-                        }
+                        });
                     })
                     .build(),
             )?;
 
-            app.global_shortcut().register(toggle_shortcut)?;
-            app.global_shortcut().register(cancel_shortcut)?;
-            tracing::info!("Global shortcuts registered: Cmd+Shift+Space (toggle), Cmd+Shift+C (cancel)");
+            // A shortcut taken by another app must not prevent the launch:
+            // it is reported, replayed to the settings window once it attaches
+            if let Err(errors) = register_shortcuts(app.handle(), &launch_config) {
+                for error in errors {
+                    tracing::warn!("Shortcut {} not registered: {}", error.field, error.message);
+                    emit_state_event(app.handle(), "shortcut_unavailable", error.message);
+                }
+            }
 
+            on_ready(app.handle());
             Ok(())
         })
+        .on_window_event(|window, event| {
+            // Overlay windows are reused across dictations and double as the
+            // recording indicator: hide instead of destroying them
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                if window.label().starts_with("overlay") {
+                    api.prevent_close();
+                    let _ = window.hide();
+                }
+            }
+        })
         .run(tauri::generate_context!())
         .expect("Error launching application");
 }
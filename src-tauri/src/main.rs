@@ -2,5 +2,22 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    // Hidden test harnesses, only in simulation builds: dictea --scenarios, dictea --soak [cycles]
+    #[cfg(feature = "sim")]
+    match dictea_lib::sim::Simulation::from_args(std::env::args().skip(1)) {
+        Some(Ok(simulation)) => return dictea_lib::run_simulated(simulation),
+        Some(Err(usage)) => {
+            eprintln!("{}", usage);
+            std::process::exit(2);
+        }
+        None => {}
+    }
+
+    // whisper.cpp runs in a child process of the app: dictea --whisper-worker <model> [prompt]
+    #[cfg(feature = "whisper-local")]
+    if std::env::args().nth(1).as_deref() == Some(dictea_lib::WHISPER_WORKER_ARG) {
+        std::process::exit(dictea_lib::whisper_worker_main(std::env::args().skip(2)));
+    }
+
     dictea_lib::run()
 }
@@ -0,0 +1,97 @@
+//! Session annotations
+//!
+//! A shortcut (or the command) flags a point of the meeting being recorded.
+//! Each marker keeps its offset in the capture, and ends up in the meeting
+//! transcript as an inline timestamped line or as an SRT chapter.
+
+/// Where the markers go in the meeting transcript
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnotationFormat {
+    /// "[MM:SS] label" lines between the speaker lines
+    #[default]
+    Inline,
+    /// SRT chapters appended after the transcript
+    Chapters,
+}
+
+/// Settings of the session annotations
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Annotations {
+    /// Global shortcut flagging the current point (empty = none)
+    #[serde(default)]
+    pub shortcut: String,
+    #[serde(default)]
+    pub format: AnnotationFormat,
+    /// Text of the markers added without one
+    #[serde(default = "default_label")]
+    pub label: String,
+}
+
+fn default_label() -> String {
+    "Flagged".to_string()
+}
+
+impl Default for Annotations {
+    fn default() -> Self {
+        Self {
+            shortcut: String::new(),
+            format: AnnotationFormat::default(),
+            label: default_label(),
+        }
+    }
+}
+
+/// Point flagged during the capture
+#[derive(Debug, Clone)]
+pub struct Annotation {
+    /// Offset since the start of the capture
+    pub sample: usize,
+    pub label: String,
+}
+
+/// Samples per second of the meeting capture
+const SAMPLE_RATE: usize = 16000;
+
+/// "MM:SS" offset of a sample, "H:MM:SS" past the first hour
+pub fn timestamp(sample: usize) -> String {
+    let secs = sample / SAMPLE_RATE;
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+/// "HH:MM:SS,mmm" SRT time of a sample
+fn srt_time(sample: usize) -> String {
+    let ms = sample * 1000 / SAMPLE_RATE;
+    format!(
+        "{:02}:{:02}:{:02},{:03}",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        ms % 1000
+    )
+}
+
+/// One SRT chapter per marker, running until the next one (or `end_sample`)
+pub fn chapters_srt(marks: &[Annotation], end_sample: usize) -> String {
+    let mut marks = marks.to_vec();
+    marks.sort_by_key(|mark| mark.sample);
+    marks
+        .iter()
+        .enumerate()
+        .map(|(i, mark)| {
+            let end = marks.get(i + 1).map_or(end_sample, |next| next.sample).max(mark.sample);
+            format!(
+                "{}\n{} --> {}\n{}\n",
+                i + 1,
+                srt_time(mark.sample),
+                srt_time(end),
+                mark.label
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,59 @@
+//! Labeled transcript assembly
+
+use super::annotations::{timestamp, Annotation};
+use crate::audio::AudioSource;
+
+/// Prefix written before each source's lines
+#[derive(Debug, Clone)]
+pub struct SpeakerLabels {
+    pub microphone: String,
+    pub system: String,
+}
+
+/// Transcript of one turn
+#[derive(Debug, Clone)]
+pub struct TurnTranscript {
+    pub source: AudioSource,
+    pub start_sample: usize,
+    pub text: String,
+}
+
+/// Interleave the turns by start time, one labeled line per speaker change
+///
+/// Inline markers are written as "[MM:SS] label" lines where they were flagged.
+pub fn merge_turns(mut turns: Vec<TurnTranscript>, labels: &SpeakerLabels, marks: &[Annotation]) -> String {
+    turns.retain(|turn| !turn.text.trim().is_empty());
+    turns.sort_by_key(|turn| turn.start_sample);
+    let mut marks = marks.to_vec();
+    marks.sort_by_key(|mark| mark.sample);
+    let mut marks = marks.into_iter().peekable();
+
+    // `None` source: marker line
+    let mut lines: Vec<(Option<AudioSource>, String)> = Vec::new();
+    for turn in turns {
+        while let Some(mark) = marks.next_if(|mark| mark.sample <= turn.start_sample) {
+            lines.push((None, format!("[{}] {}", timestamp(mark.sample), mark.label)));
+        }
+        match lines.last_mut() {
+            Some((Some(source), text)) if *source == turn.source => {
+                text.push(' ');
+                text.push_str(turn.text.trim());
+            }
+            _ => lines.push((Some(turn.source), turn.text.trim().to_string())),
+        }
+    }
+    lines.extend(marks.map(|mark| (None, format!("[{}] {}", timestamp(mark.sample), mark.label))));
+
+    lines
+        .into_iter()
+        .map(|(source, text)| {
+            let label = match source {
+                Some(AudioSource::Microphone) => &labels.microphone,
+                Some(AudioSource::System) => &labels.system,
+                None => return text,
+            };
+            format!("{}: {}", label, text)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,15 @@
+//! Meeting capture module
+//!
+//! Splits the microphone and system audio streams into speech turns and
+//! merges their transcripts into a single labeled conversation, with the
+//! points flagged during the capture and an optional summary footer.
+
+mod annotations;
+mod merge;
+mod summary;
+mod turns;
+
+pub use annotations::{chapters_srt, timestamp, Annotation, AnnotationFormat, Annotations};
+pub use merge::{merge_turns, SpeakerLabels, TurnTranscript};
+pub use summary::{append_summary, MeetingSummary};
+pub use turns::MeetingTurns;
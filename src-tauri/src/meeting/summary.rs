@@ -0,0 +1,51 @@
+//! Summary footer of meeting transcripts
+//!
+//! Once a meeting session ends, the chat model of the configured provider
+//! reads the merged transcript and writes a short TL;DR with the action
+//! items, appended under the transcript in its file.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+/// Instructions given to the chat model when no custom prompt is set
+const DEFAULT_PROMPT: &str = "You summarize meeting transcripts. Each line is a speaker turn, \
+prefixed with the speaker label. Reply in the language of the transcript, in Markdown, with \
+exactly two sections: \"## TL;DR\" (three sentences at most) and \"## Action items\" (a bullet \
+list, with the owner in bold when the transcript names one, or \"None\"). Do not invent \
+anything that was not said.";
+
+/// Settings of the meeting summary
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct MeetingSummary {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Instructions of the chat model, empty = TL;DR and action items
+    #[serde(default)]
+    pub prompt: String,
+}
+
+impl MeetingSummary {
+    /// System prompt of the summarization pass
+    pub fn system_prompt(&self) -> &str {
+        match self.prompt.trim() {
+            "" => DEFAULT_PROMPT,
+            prompt => prompt,
+        }
+    }
+}
+
+/// Append the summary to the transcript file, after a separator
+pub fn append_summary(path: &Path, summary: &str) -> Result<(), String> {
+    let summary = summary.trim();
+    if summary.is_empty() {
+        return Err("Empty summary".to_string());
+    }
+    let mut file = OpenOptions::new()
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Transcript file error ({}): {}", path.display(), e))?;
+    file.write_all(format!("\n---\n\n{}\n", summary).as_bytes())
+        .and_then(|_| file.sync_data())
+        .map_err(|e| format!("Transcript file write error ({}): {}", path.display(), e))
+}
@@ -0,0 +1,181 @@
+//! Speech turn segmentation
+//!
+//! Each source is cut into turns on silence, as told by the configured VAD.
+//! A turn keeps its start offset so turns from both sources can be
+//! interleaved. The speech of each source is kept in an `AudioAccumulator`,
+//! so a long meeting spills to disk instead of growing in RAM.
+
+use crate::audio::{AudioAccumulator, AudioSource};
+use crate::vad::VoiceActivityDetector;
+use std::ops::Range;
+
+/// Silence closing a turn: 800ms
+const HANGOVER_SAMPLES: usize = 16000 * 8 / 10;
+
+/// Contiguous speech of one source
+#[derive(Debug, Clone)]
+pub struct Turn {
+    pub source: AudioSource,
+    /// Offset of the first sample since the start of the capture
+    pub start_sample: usize,
+    /// Samples of the turn in the speech kept for its source
+    range: Range<usize>,
+}
+
+impl Turn {
+    pub fn sample_count(&self) -> usize {
+        self.range.len()
+    }
+}
+
+/// Cuts one source stream into turns
+pub struct TurnSegmenter {
+    source: AudioSource,
+    vad: Box<dyn VoiceActivityDetector>,
+    /// Samples consumed so far (full frames)
+    position: usize,
+    /// Incomplete frame carried over to the next push
+    pending: Vec<f32>,
+    /// Speech of every turn, back to back
+    speech: AudioAccumulator,
+    current: Option<Turn>,
+    /// Trailing silence of the current turn, kept only if speech resumes
+    silence: Vec<f32>,
+    turns: Vec<Turn>,
+}
+
+impl TurnSegmenter {
+    pub fn new(source: AudioSource, vad: Box<dyn VoiceActivityDetector>, memory_limit: usize) -> Self {
+        Self {
+            source,
+            pending: Vec::with_capacity(vad.frame_samples()),
+            vad,
+            position: 0,
+            speech: AudioAccumulator::new(memory_limit),
+            current: None,
+            silence: Vec::new(),
+            turns: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, pcm: &[f32]) {
+        let frame_samples = self.vad.frame_samples();
+        let mut pending = std::mem::take(&mut self.pending);
+        pending.extend_from_slice(pcm);
+        let frames = pending.len() / frame_samples;
+        for frame in pending.chunks_exact(frame_samples) {
+            if self.vad.is_speech(frame) {
+                let start = self.speech.len();
+                let turn = self.current.get_or_insert(Turn {
+                    source: self.source,
+                    start_sample: self.position,
+                    range: start..start,
+                });
+                // The pause was part of the turn
+                self.speech.extend_from_slice(&self.silence);
+                self.silence.clear();
+                self.speech.extend_from_slice(frame);
+                turn.range.end = self.speech.len();
+            } else if self.current.is_some() {
+                self.silence.extend_from_slice(frame);
+                if self.silence.len() >= HANGOVER_SAMPLES {
+                    self.close_turn();
+                }
+            }
+            self.position += frame_samples;
+        }
+        pending.drain(..frames * frame_samples);
+        self.pending = pending;
+    }
+
+    /// Move the current turn to the finished ones, without its trailing silence
+    fn close_turn(&mut self) {
+        if let Some(turn) = self.current.take() {
+            self.turns.push(turn);
+        }
+        self.silence.clear();
+    }
+
+    /// Samples received so far
+    fn position(&self) -> usize {
+        self.position + self.pending.len()
+    }
+
+    /// Close the capture and return every turn, in order, with their speech
+    pub fn finish(mut self) -> (Vec<Turn>, AudioAccumulator) {
+        self.close_turn();
+        (self.turns, self.speech)
+    }
+}
+
+/// Turn segmentation of both meeting sources
+pub struct MeetingTurns {
+    microphone: TurnSegmenter,
+    system: TurnSegmenter,
+}
+
+impl MeetingTurns {
+    /// One detector per source, as VADs carry state between frames
+    ///
+    /// Each source keeps up to `memory_limit` samples of speech in RAM
+    /// before spilling to disk (0 = never spill).
+    pub fn new(
+        microphone: Box<dyn VoiceActivityDetector>,
+        system: Box<dyn VoiceActivityDetector>,
+        memory_limit: usize,
+    ) -> Self {
+        tracing::info!("Meeting turn segmentation with {} VAD", microphone.name());
+        Self {
+            microphone: TurnSegmenter::new(AudioSource::Microphone, microphone, memory_limit),
+            system: TurnSegmenter::new(AudioSource::System, system, memory_limit),
+        }
+    }
+
+    pub fn push(&mut self, source: AudioSource, pcm: &[f32]) {
+        match source {
+            AudioSource::Microphone => self.microphone.push(pcm),
+            AudioSource::System => self.system.push(pcm),
+        }
+    }
+
+    /// Offset of the capture so far, as counted on the microphone
+    pub fn position(&self) -> usize {
+        self.microphone.position()
+    }
+
+    /// Close the capture of both sources
+    pub fn finish(self) -> MeetingAudio {
+        let (mut turns, microphone) = self.microphone.finish();
+        let (system_turns, system) = self.system.finish();
+        turns.extend(system_turns);
+        turns.sort_by_key(|turn| turn.start_sample);
+        MeetingAudio {
+            turns,
+            microphone,
+            system,
+        }
+    }
+}
+
+/// Turns of a finished meeting and the speech they point to
+pub struct MeetingAudio {
+    /// Turns of both sources, ordered by start time
+    pub turns: Vec<Turn>,
+    microphone: AudioAccumulator,
+    system: AudioAccumulator,
+}
+
+impl MeetingAudio {
+    /// Visit the samples of `turn` in order, in chunks
+    pub fn try_for_each_chunk<E, F>(&mut self, turn: &Turn, f: F) -> Result<(), E>
+    where
+        F: FnMut(&[f32]) -> Result<(), E>,
+        E: From<std::io::Error>,
+    {
+        let speech = match turn.source {
+            AudioSource::Microphone => &mut self.microphone,
+            AudioSource::System => &mut self.system,
+        };
+        speech.try_for_each_chunk_in(turn.range.clone(), f)
+    }
+}
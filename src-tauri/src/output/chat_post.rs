@@ -0,0 +1,50 @@
+//! Chat message output (Slack, Microsoft Teams)
+//!
+//! The processed text is posted to an incoming webhook, e.g. to dictate a
+//! standup update hands-free.
+
+/// Chat service behind a webhook
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChatService {
+    #[default]
+    Slack,
+    Teams,
+}
+
+/// Webhook receiving dictations
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ChatTarget {
+    #[serde(default)]
+    pub service: ChatService,
+    pub webhook_url: String,
+    /// Profiles posting to this target (empty = every dictation)
+    #[serde(default)]
+    pub profiles: Vec<String>,
+}
+
+impl ChatTarget {
+    /// Whether dictations of `profile` are posted here
+    pub fn applies_to(&self, profile: &str) -> bool {
+        self.profiles.is_empty() || self.profiles.iter().any(|p| p == profile)
+    }
+
+    /// Post `text` as a message
+    pub async fn post(&self, text: &str) -> Result<(), String> {
+        // Both Slack and Teams incoming webhooks accept a plain "text" payload
+        let body = serde_json::json!({ "text": text });
+        let response = reqwest::Client::new()
+            .post(&self.webhook_url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        let status = response.status();
+        if !status.is_success() {
+            let details = response.text().await.unwrap_or_default();
+            return Err(format!("HTTP {}: {}", status, details));
+        }
+        tracing::info!("Dictation posted to {:?}", self.service);
+        Ok(())
+    }
+}
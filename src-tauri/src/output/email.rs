@@ -0,0 +1,144 @@
+//! Email draft output (mailto, Gmail)
+//!
+//! The chat model turns the dictation into a subject and a body, which are
+//! opened as a prefilled draft in the default mail client (mailto URL) or
+//! saved as a Gmail draft through the Gmail API.
+
+use crate::postprocess::extract_json_object;
+use base64::Engine;
+
+/// How the draft is created
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmailMethod {
+    /// Open a mailto URL in the default mail client
+    #[default]
+    Mailto,
+    /// Create a draft with the Gmail API
+    Gmail,
+}
+
+/// Settings of the email draft mode
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct EmailDraft {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub method: EmailMethod,
+    /// Default recipient (may be empty)
+    #[serde(default)]
+    pub to: String,
+    /// OAuth access token with the gmail.compose scope
+    #[serde(default)]
+    pub gmail_access_token: String,
+    /// Global shortcut starting/stopping an email dictation
+    #[serde(default = "default_email_shortcut")]
+    pub shortcut: String,
+}
+
+fn default_email_shortcut() -> String {
+    "CmdOrCtrl+Shift+M".to_string()
+}
+
+impl Default for EmailDraft {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            method: EmailMethod::default(),
+            to: String::new(),
+            gmail_access_token: String::new(),
+            shortcut: default_email_shortcut(),
+        }
+    }
+}
+
+/// Email written from a dictation
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+pub struct DraftedEmail {
+    #[serde(default)]
+    pub subject: String,
+    pub body: String,
+}
+
+impl DraftedEmail {
+    /// System prompt asking the chat model for an email as JSON
+    pub fn extraction_prompt() -> &'static str {
+        "Write an email from the following dictated text. Fix grammar and punctuation, \
+        remove hesitations and filler words, keep the language, meaning and tone of the text. \
+        Reply with ONLY a JSON object: {\"subject\": \"short subject\", \"body\": \"email body\"}."
+    }
+
+    /// Parse the chat model reply, falling back to the whole text as body
+    pub fn from_reply(reply: &str, text: &str) -> Self {
+        let json = extract_json_object(reply).unwrap_or(reply);
+        match serde_json::from_str::<DraftedEmail>(json) {
+            Ok(email) if !email.body.trim().is_empty() => email,
+            _ => {
+                tracing::warn!("Unexpected email drafting reply: {}", reply);
+                Self::untitled(text)
+            }
+        }
+    }
+
+    /// Email without subject, the dictation as body
+    pub fn untitled(text: &str) -> Self {
+        Self {
+            subject: String::new(),
+            body: text.trim().to_string(),
+        }
+    }
+
+    /// mailto URL prefilling the draft
+    pub fn mailto_url(&self, to: &str) -> String {
+        format!(
+            "mailto:{}?subject={}&body={}",
+            percent_encode(to.trim()),
+            percent_encode(&self.subject),
+            percent_encode(&self.body)
+        )
+    }
+
+    /// Save the email as a Gmail draft
+    pub async fn create_gmail_draft(&self, to: &str, access_token: &str) -> Result<(), String> {
+        let mut message = String::new();
+        if !to.trim().is_empty() {
+            message.push_str(&format!("To: {}\r\n", to.trim()));
+        }
+        // RFC 2047 encoded subject, the body is sent as UTF-8
+        let subject = base64::engine::general_purpose::STANDARD.encode(self.subject.as_bytes());
+        message.push_str(&format!("Subject: =?UTF-8?B?{}?=\r\n", subject));
+        message.push_str("Content-Type: text/plain; charset=\"UTF-8\"\r\n\r\n");
+        message.push_str(&self.body.replace('\n', "\r\n"));
+
+        let raw = base64::engine::general_purpose::URL_SAFE.encode(message.as_bytes());
+        let body = serde_json::json!({ "message": { "raw": raw } });
+        let response = reqwest::Client::new()
+            .post("https://gmail.googleapis.com/gmail/v1/users/me/drafts")
+            .bearer_auth(access_token)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        let status = response.status();
+        if !status.is_success() {
+            let details = response.text().await.unwrap_or_default();
+            return Err(format!("HTTP {}: {}", status, details));
+        }
+        tracing::info!("Gmail draft created: {}", self.subject);
+        Ok(())
+    }
+}
+
+/// Percent-encode a mailto component (RFC 6068: spaces as %20, not +)
+pub(super) fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'@' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
@@ -0,0 +1,195 @@
+//! Issue tracker output (GitHub, GitLab)
+//!
+//! The dictation goes through the structured output mode with an issue
+//! schema (title, body, labels), then is filed through the tracker's API.
+//! Each tracker profile ("work", "oss"...) carries its own repository and
+//! token, issues go to the active one.
+
+use crate::postprocess::{FieldKind, SchemaField};
+use serde_json::Value;
+
+/// Issue tracker API
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueProvider {
+    #[default]
+    #[serde(rename = "github")]
+    GitHub,
+    #[serde(rename = "gitlab")]
+    GitLab,
+}
+
+/// Tracker, repository and credentials issues are filed with
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct IssueProfile {
+    pub name: String,
+    #[serde(default)]
+    pub provider: IssueProvider,
+    /// "owner/repo" on GitHub, project path or id on GitLab
+    #[serde(default)]
+    pub repository: String,
+    /// Personal access token allowed to create issues
+    #[serde(default)]
+    pub token: String,
+    /// GitLab instance (self-hosted), ignored for GitHub
+    #[serde(default = "default_gitlab_url")]
+    pub gitlab_url: String,
+    /// Labels added to every issue
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+/// Settings of the issue dictation mode
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct IssueTracker {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub profiles: Vec<IssueProfile>,
+    /// Name of the profile issues are filed with (empty = the first one)
+    #[serde(default)]
+    pub active_profile: String,
+    /// Global shortcut starting/stopping an issue dictation
+    #[serde(default = "default_issue_shortcut")]
+    pub shortcut: String,
+}
+
+fn default_gitlab_url() -> String {
+    "https://gitlab.com".to_string()
+}
+
+fn default_issue_shortcut() -> String {
+    "CmdOrCtrl+Shift+I".to_string()
+}
+
+impl Default for IssueTracker {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            profiles: Vec::new(),
+            active_profile: String::new(),
+            shortcut: default_issue_shortcut(),
+        }
+    }
+}
+
+impl IssueTracker {
+    /// Profile issues are filed with, if any
+    pub fn profile(&self) -> Option<&IssueProfile> {
+        match self.active_profile.trim() {
+            "" => self.profiles.first(),
+            name => self.profiles.iter().find(|profile| profile.name == name),
+        }
+    }
+}
+
+/// Issue written from a dictation
+#[derive(Debug, Clone, PartialEq)]
+pub struct Issue {
+    pub title: String,
+    pub body: String,
+    pub labels: Vec<String>,
+}
+
+impl Issue {
+    /// Structured output schema of an issue
+    pub fn schema() -> Vec<SchemaField> {
+        let field = |name: &str, kind, description: &str, required| SchemaField {
+            name: name.to_string(),
+            kind,
+            description: description.to_string(),
+            required,
+        };
+        vec![
+            field("title", FieldKind::String, "concise issue title", true),
+            field(
+                "body",
+                FieldKind::String,
+                "issue description in Markdown (context, steps to reproduce, expected and actual behavior when mentioned)",
+                true,
+            ),
+            field("labels", FieldKind::StringList, "labels such as bug, enhancement, documentation", false),
+        ]
+    }
+
+    /// Build the issue from an object validated against `schema()`
+    pub fn from_structured(object: &Value) -> Option<Self> {
+        let text = |name: &str| object.get(name).and_then(Value::as_str).map(str::trim);
+        let labels = object
+            .get("labels")
+            .and_then(Value::as_array)
+            .map(|labels| labels.iter().filter_map(Value::as_str).map(str::to_string).collect())
+            .unwrap_or_default();
+        Some(Self {
+            title: text("title").filter(|title| !title.is_empty())?.to_string(),
+            body: text("body").unwrap_or_default().to_string(),
+            labels,
+        })
+    }
+
+    /// Issue titled after the dictation's first line, the whole text as body
+    pub fn untitled(text: &str) -> Self {
+        let title: String = text.lines().next().unwrap_or_default().chars().take(80).collect();
+        Self {
+            title: title.trim().to_string(),
+            body: text.trim().to_string(),
+            labels: Vec::new(),
+        }
+    }
+}
+
+impl IssueProfile {
+    /// File the issue, returning its web URL
+    pub async fn create(&self, issue: &Issue) -> Result<String, String> {
+        let mut labels = self.labels.clone();
+        labels.extend(issue.labels.iter().cloned());
+        labels.sort();
+        labels.dedup();
+
+        let client = reqwest::Client::new();
+        let (request, url_field) = match self.provider {
+            IssueProvider::GitHub => (
+                client
+                    .post(format!("https://api.github.com/repos/{}/issues", self.repository.trim()))
+                    .bearer_auth(&self.token)
+                    .header("Accept", "application/vnd.github+json")
+                    // GitHub rejects requests without a user agent
+                    .header("User-Agent", "dictea")
+                    .json(&serde_json::json!({
+                        "title": issue.title,
+                        "body": issue.body,
+                        "labels": labels,
+                    })),
+                "html_url",
+            ),
+            IssueProvider::GitLab => (
+                client
+                    .post(format!(
+                        "{}/api/v4/projects/{}/issues",
+                        self.gitlab_url.trim_end_matches('/'),
+                        self.repository.trim().replace('/', "%2F")
+                    ))
+                    .header("PRIVATE-TOKEN", &self.token)
+                    .json(&serde_json::json!({
+                        "title": issue.title,
+                        "description": issue.body,
+                        "labels": labels.join(","),
+                    })),
+                "web_url",
+            ),
+        };
+
+        let response = request.send().await.map_err(|e| e.to_string())?;
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            return Err(format!("HTTP {}: {}", status, body));
+        }
+        let url = serde_json::from_str::<Value>(&body)
+            .ok()
+            .and_then(|created| created.get(url_field).and_then(Value::as_str).map(str::to_string))
+            .unwrap_or_default();
+        tracing::info!("Issue created: {} {}", issue.title, url);
+        Ok(url)
+    }
+}
@@ -0,0 +1,70 @@
+//! Live transcript journal
+//!
+//! Final segments are appended to a file as soon as the engine returns
+//! them, independently of the paste. The file doubles as a dictation log
+//! and a safety net if the paste target loses the text.
+
+use super::template::TemplateContext;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Append-only file receiving the segments of the current dictation
+pub struct TranscriptJournal {
+    file: File,
+    path: PathBuf,
+    /// No segment written yet for this dictation
+    first_segment: bool,
+}
+
+impl TranscriptJournal {
+    /// Open (or create) the file designated by `template`
+    pub fn open(template: &str, context: &TemplateContext) -> Result<Self, String> {
+        let path = context.render_path(template);
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Transcript dir error: {}", e))?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| format!("Transcript file error ({}): {}", path.display(), e))?;
+        Ok(Self {
+            file,
+            path,
+            first_segment: true,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append a final segment and flush it to disk right away
+    pub fn append(&mut self, segment: &str) -> std::io::Result<()> {
+        let segment = segment.trim();
+        if segment.is_empty() {
+            return Ok(());
+        }
+        // Each dictation starts its own paragraph, its segments follow each other
+        if self.first_segment {
+            if self.file.metadata()?.len() > 0 {
+                self.file.write_all(b"\n")?;
+            }
+            self.first_segment = false;
+        } else {
+            self.file.write_all(b" ")?;
+        }
+        self.file.write_all(segment.as_bytes())?;
+        self.file.sync_data()
+    }
+
+    /// Terminate the dictation's paragraph
+    pub fn finish(mut self) -> std::io::Result<()> {
+        if !self.first_segment {
+            self.file.write_all(b"\n")?;
+            self.file.sync_data()?;
+        }
+        Ok(())
+    }
+}
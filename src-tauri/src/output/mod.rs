@@ -0,0 +1,27 @@
+//! Output targets module
+//!
+//! Where dictations go besides the clipboard/paste.
+
+mod chat_post;
+mod email;
+mod issue;
+mod journal;
+mod note;
+mod quick_note;
+mod rules;
+mod search;
+mod task;
+mod template;
+mod webhook;
+
+pub use chat_post::ChatTarget;
+pub use email::{DraftedEmail, EmailDraft, EmailMethod};
+pub use issue::{Issue, IssueTracker};
+pub use journal::TranscriptJournal;
+pub use note::NoteOutput;
+pub use quick_note::{note_title, QuickNote, TITLE_PROMPT};
+pub use rules::{route, OutputAction, OutputRule};
+pub use search::SearchLauncher;
+pub use task::{CapturedTask, TaskCapture};
+pub use template::TemplateContext;
+pub use webhook::post_json;
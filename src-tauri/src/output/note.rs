@@ -0,0 +1,78 @@
+//! Markdown note output (Obsidian daily note...)
+//!
+//! Finished dictations are appended as entries to a Markdown file whose
+//! path is a template, e.g. `~/vault/Daily/{date}.md`.
+
+use super::template::TemplateContext;
+use std::fs::OpenOptions;
+use std::io::Write;
+
+/// Settings of the note output
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct NoteOutput {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Path template of the note (`{date}`, `{time}`, `{profile}`, `~`)
+    #[serde(default)]
+    pub path: String,
+    /// Entry template, also gets `{text}` and `{tags}`
+    #[serde(default = "default_entry_template")]
+    pub entry_template: String,
+    /// Tags added to each entry (without the leading `#`)
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+fn default_entry_template() -> String {
+    "- {time} {text} {tags}".to_string()
+}
+
+impl Default for NoteOutput {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: String::new(),
+            entry_template: default_entry_template(),
+            tags: Vec::new(),
+        }
+    }
+}
+
+impl NoteOutput {
+    /// Append `text` as a new entry of the note
+    pub fn append(&self, text: &str, context: &TemplateContext) -> Result<(), String> {
+        let path = context.render_path(&self.path);
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Note dir error: {}", e))?;
+        }
+
+        let tags = self
+            .tags
+            .iter()
+            .map(|tag| tag.trim().trim_start_matches('#'))
+            .filter(|tag| !tag.is_empty())
+            .map(|tag| format!("#{}", tag))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let entry = context.render(&self.entry_template, &[("text", text.trim()), ("tags", &tags)]);
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| format!("Note file error ({}): {}", path.display(), e))?;
+        // Entries start on their own line even if the note was edited by hand
+        let needs_newline = file.metadata().map(|m| m.len() > 0).unwrap_or(false)
+            && !std::fs::read(&path).map(|bytes| bytes.ends_with(b"\n")).unwrap_or(true);
+        let mut content = String::new();
+        if needs_newline {
+            content.push('\n');
+        }
+        content.push_str(entry.trim_end());
+        content.push('\n');
+        file.write_all(content.as_bytes())
+            .map_err(|e| format!("Note write error ({}): {}", path.display(), e))?;
+        tracing::info!("Dictation appended to {}", path.display());
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+//! Quick note mode (voice memo inbox)
+//!
+//! A dictation started with its own shortcut is stopped after at most
+//! `max_secs`, titled by the chat model and kept in the history (plus an
+//! optional Markdown inbox) instead of being pasted.
+
+use super::template::TemplateContext;
+use std::fs::OpenOptions;
+use std::io::Write;
+
+/// Settings of the quick note mode
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct QuickNote {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Global shortcut starting/stopping a quick note
+    #[serde(default = "default_quick_note_shortcut")]
+    pub shortcut: String,
+    /// The recording stops on its own after this long
+    #[serde(default = "default_max_secs")]
+    pub max_secs: u64,
+    /// Markdown file also receiving the notes (`{date}`, `{profile}`, `~`;
+    /// empty = history only)
+    #[serde(default)]
+    pub path: String,
+}
+
+fn default_quick_note_shortcut() -> String {
+    "CmdOrCtrl+Shift+N".to_string()
+}
+
+fn default_max_secs() -> u64 {
+    60
+}
+
+impl Default for QuickNote {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            shortcut: default_quick_note_shortcut(),
+            max_secs: default_max_secs(),
+            path: String::new(),
+        }
+    }
+}
+
+/// Words kept when no title could be generated
+const FALLBACK_TITLE_WORDS: usize = 6;
+
+/// System prompt asking the chat model for the title of a note
+pub const TITLE_PROMPT: &str = "Give a short title (at most 8 words) to the following voice note, \
+    in the language of the note. Output ONLY the title, without quotes or final punctuation.";
+
+/// Clean the chat model reply into a one-line title, falling back to the
+/// first words of the note
+pub fn note_title(reply: Option<&str>, text: &str) -> String {
+    let title = reply
+        .and_then(|reply| reply.lines().map(str::trim).find(|line| !line.is_empty()))
+        .map(|line| {
+            line.trim_start_matches('#')
+                .trim()
+                .trim_matches(|c| matches!(c, '"' | '\'' | '*' | '\u{201c}' | '\u{201d}'))
+                .trim_end_matches('.')
+                .trim()
+                .to_string()
+        })
+        .filter(|title| !title.is_empty());
+    title.unwrap_or_else(|| {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let mut title = words[..words.len().min(FALLBACK_TITLE_WORDS)].join(" ");
+        if words.len() > FALLBACK_TITLE_WORDS {
+            title.push('\u{2026}');
+        }
+        title
+    })
+}
+
+impl QuickNote {
+    /// Append the note to the Markdown inbox, if one is set
+    pub fn append(&self, title: &str, text: &str, context: &TemplateContext) -> Result<(), String> {
+        if self.path.trim().is_empty() {
+            return Ok(());
+        }
+        let path = context.render_path(&self.path);
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Note dir error: {}", e))?;
+        }
+        let entry = context.render("## {title}\n_{date} {time}_\n\n{text}\n\n", &[("title", title), ("text", text.trim())]);
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| format!("Note file error ({}): {}", path.display(), e))?;
+        file.write_all(entry.as_bytes())
+            .map_err(|e| format!("Note write error ({}): {}", path.display(), e))?;
+        tracing::info!("Quick note appended to {}", path.display());
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+//! Output routing rules
+//!
+//! Declarative rules choosing what happens to a finished dictation, e.g.
+//! "meeting → write a file; Slack → paste + Enter; anything else →
+//! clipboard only". Rules are evaluated in order after post-processing and
+//! the first matching one wins.
+
+/// What to do with the processed text
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum OutputAction {
+    /// Copy to the clipboard only
+    Clipboard,
+    /// Copy and simulate the paste shortcut, then Enter if `submit`
+    Paste {
+        #[serde(default)]
+        submit: bool,
+    },
+    /// Append to the configured Markdown note
+    Note,
+    /// Append to a file (path template: `~`, `{date}`, `{time}`, `{profile}`)
+    File { path: String },
+    /// Post to the chat webhooks of the profile
+    Chat,
+    /// POST the structured object (or `{"text": ...}`) to a URL
+    Webhook { url: String },
+}
+
+/// Conditions of a rule, every condition set must match
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RuleCondition {
+    /// Dictation profile ("dictation", "meeting")
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// Frontmost application name (case-insensitive)
+    #[serde(default)]
+    pub app: Option<String>,
+}
+
+/// Actions run when a condition matches
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct OutputRule {
+    /// Always matches when empty
+    #[serde(default)]
+    pub when: RuleCondition,
+    pub actions: Vec<OutputAction>,
+}
+
+impl RuleCondition {
+    fn matches(&self, profile: &str, app: Option<&str>) -> bool {
+        let profile_ok = self.profile.as_deref().map_or(true, |p| p == profile);
+        let app_ok = self
+            .app
+            .as_deref()
+            .map_or(true, |expected| app.is_some_and(|app| app.eq_ignore_ascii_case(expected)));
+        profile_ok && app_ok
+    }
+}
+
+/// Actions of the first rule matching the dictation, if any
+pub fn route<'a>(rules: &'a [OutputRule], profile: &str, app: Option<&str>) -> Option<&'a [OutputAction]> {
+    rules
+        .iter()
+        .find(|rule| rule.when.matches(profile, app))
+        .map(|rule| rule.actions.as_slice())
+}
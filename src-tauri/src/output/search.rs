@@ -0,0 +1,47 @@
+//! Search launcher output
+//!
+//! The dictation is not pasted but filled into a URL template (web search,
+//! app URI such as `obsidian://search?query={text}`), which is opened with
+//! its default handler.
+
+use super::email::percent_encode;
+
+/// Settings of the search launcher mode
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SearchLauncher {
+    #[serde(default)]
+    pub enabled: bool,
+    /// URL opened, `{text}` standing for the dictation
+    #[serde(default = "default_url_template")]
+    pub url_template: String,
+    /// Global shortcut starting/stopping a search dictation
+    #[serde(default = "default_search_shortcut")]
+    pub shortcut: String,
+}
+
+fn default_url_template() -> String {
+    "https://www.google.com/search?q={text}".to_string()
+}
+
+fn default_search_shortcut() -> String {
+    "CmdOrCtrl+Shift+K".to_string()
+}
+
+impl Default for SearchLauncher {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url_template: default_url_template(),
+            shortcut: default_search_shortcut(),
+        }
+    }
+}
+
+impl SearchLauncher {
+    /// URL to open for `text`, URL-encoded into the template
+    pub fn url_for(&self, text: &str) -> String {
+        // The final period added by the STT engine is not part of the query
+        let query = text.trim().trim_end_matches('.').trim_end();
+        self.url_template.replace("{text}", &percent_encode(query))
+    }
+}
@@ -0,0 +1,147 @@
+//! Task capture output (Todoist, TickTick)
+//!
+//! The dictation is turned into a task title plus an optional due date by
+//! the chat model, then created through the task manager's REST API
+//! instead of being pasted.
+
+use crate::postprocess::extract_json_object;
+use chrono::{Local, NaiveDate};
+
+/// Task manager receiving captured tasks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskService {
+    #[default]
+    Todoist,
+    #[serde(rename = "ticktick")]
+    TickTick,
+}
+
+/// Settings of the task capture mode
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TaskCapture {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub service: TaskService,
+    /// API token of the service
+    #[serde(default)]
+    pub api_token: String,
+    /// Project receiving the tasks (empty = inbox)
+    #[serde(default)]
+    pub project_id: String,
+    /// Global shortcut starting/stopping a task dictation
+    #[serde(default = "default_task_shortcut")]
+    pub shortcut: String,
+}
+
+fn default_task_shortcut() -> String {
+    "CmdOrCtrl+Shift+T".to_string()
+}
+
+impl Default for TaskCapture {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            service: TaskService::default(),
+            api_token: String::new(),
+            project_id: String::new(),
+            shortcut: default_task_shortcut(),
+        }
+    }
+}
+
+/// Task extracted from a dictation
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+pub struct CapturedTask {
+    pub title: String,
+    /// Due date, YYYY-MM-DD
+    #[serde(default)]
+    pub due_date: Option<String>,
+}
+
+impl CapturedTask {
+    /// System prompt asking the chat model for a task as JSON
+    pub fn extraction_prompt() -> String {
+        let today = Local::now().date_naive();
+        format!(
+            "Turn the following dictated text into a task for a to-do list. \
+            Today is {} ({}). Reply with ONLY a JSON object: \
+            {{\"title\": \"short imperative task title, in the language of the text\", \
+            \"due_date\": \"YYYY-MM-DD\" or null}}. \
+            Only set due_date when the text mentions a deadline or a day (tomorrow, next Friday...), \
+            and remove that mention from the title.",
+            today.format("%Y-%m-%d"),
+            today.format("%A")
+        )
+    }
+
+    /// Parse the chat model reply, falling back to the whole text as title
+    pub fn from_reply(reply: &str, text: &str) -> Self {
+        let json = extract_json_object(reply).unwrap_or(reply);
+        match serde_json::from_str::<CapturedTask>(json) {
+            Ok(task) if !task.title.trim().is_empty() => Self {
+                title: task.title.trim().to_string(),
+                due_date: task
+                    .due_date
+                    .filter(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok()),
+            },
+            _ => {
+                tracing::warn!("Unexpected task extraction reply: {}", reply);
+                Self::untitled(text)
+            }
+        }
+    }
+
+    /// Task titled with the dictation as is
+    pub fn untitled(text: &str) -> Self {
+        Self {
+            title: text.trim().to_string(),
+            due_date: None,
+        }
+    }
+}
+
+impl TaskCapture {
+    /// Create the task on the configured service
+    pub async fn send(&self, task: &CapturedTask) -> Result<(), String> {
+        let (url, body) = match self.service {
+            TaskService::Todoist => {
+                let mut body = serde_json::json!({ "content": task.title });
+                if let Some(ref due) = task.due_date {
+                    body["due_date"] = due.clone().into();
+                }
+                if !self.project_id.is_empty() {
+                    body["project_id"] = self.project_id.clone().into();
+                }
+                ("https://api.todoist.com/rest/v2/tasks", body)
+            }
+            TaskService::TickTick => {
+                let mut body = serde_json::json!({ "title": task.title });
+                if let Some(ref due) = task.due_date {
+                    body["dueDate"] = format!("{}T00:00:00+0000", due).into();
+                    body["isAllDay"] = true.into();
+                }
+                if !self.project_id.is_empty() {
+                    body["projectId"] = self.project_id.clone().into();
+                }
+                ("https://api.ticktick.com/open/v1/task", body)
+            }
+        };
+
+        let response = reqwest::Client::new()
+            .post(url)
+            .bearer_auth(&self.api_token)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        let status = response.status();
+        if !status.is_success() {
+            let details = response.text().await.unwrap_or_default();
+            return Err(format!("HTTP {}: {}", status, details));
+        }
+        tracing::info!("Task created on {:?}: {}", self.service, task.title);
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+//! Output templates
+//!
+//! Placeholders shared by output paths and entries: `{date}` (YYYY-MM-DD),
+//! `{time}` (HH:MM), both in local time, `{profile}` and `{meeting}` (title
+//! of the calendar meeting in progress, the profile outside meetings).
+
+use chrono::{DateTime, Local};
+use std::path::PathBuf;
+
+/// Values substituted in output templates
+pub struct TemplateContext {
+    pub now: DateTime<Local>,
+    /// Kind of dictation ("dictation", "meeting"...)
+    pub profile: String,
+    /// Calendar meeting the dictation belongs to, if any
+    pub meeting: Option<String>,
+}
+
+impl TemplateContext {
+    pub fn new(profile: &str) -> Self {
+        Self {
+            now: Local::now(),
+            profile: profile.to_string(),
+            meeting: None,
+        }
+    }
+
+    /// Name the output after `meeting`
+    pub fn with_meeting(mut self, meeting: Option<String>) -> Self {
+        self.meeting = meeting;
+        self
+    }
+
+    /// Substitute the placeholders, then the `extra` (name, value) pairs
+    pub fn render(&self, template: &str, extra: &[(&str, &str)]) -> String {
+        let mut rendered = template
+            .replace("{date}", &self.now.format("%Y-%m-%d").to_string())
+            .replace("{time}", &self.now.format("%H:%M").to_string())
+            .replace("{profile}", &self.profile)
+            .replace("{meeting}", self.meeting.as_deref().unwrap_or(&self.profile));
+        for (name, value) in extra {
+            rendered = rendered.replace(&format!("{{{}}}", name), value);
+        }
+        rendered
+    }
+
+    /// Render a path template, expanding a leading `~` to the home directory
+    pub fn render_path(&self, template: &str) -> PathBuf {
+        let expanded = self.render(template.trim(), &[]);
+        let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"));
+        match (expanded.strip_prefix('~'), home) {
+            (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with(['/', '\\']) => {
+                PathBuf::from(home).join(rest.trim_start_matches(['/', '\\']))
+            }
+            _ => PathBuf::from(expanded),
+        }
+    }
+}
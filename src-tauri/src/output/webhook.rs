@@ -0,0 +1,23 @@
+//! Generic JSON webhook output
+//!
+//! Hands the dictation to downstream tooling: the structured object when
+//! the structured output mode is on, `{"text": ...}` otherwise.
+
+use serde_json::Value;
+
+/// POST `payload` to `url`
+pub async fn post_json(url: &str, payload: &Value) -> Result<(), String> {
+    let response = reqwest::Client::new()
+        .post(url)
+        .json(payload)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    let status = response.status();
+    if !status.is_success() {
+        let details = response.text().await.unwrap_or_default();
+        return Err(format!("HTTP {}: {}", status, details));
+    }
+    tracing::info!("Dictation sent to webhook {}", url);
+    Ok(())
+}
@@ -0,0 +1,215 @@
+//! Text caret of the focused application
+//!
+//! Its bounds are used to show the overlay where the dictated text will
+//! land, and the text right before it to continue the sentence.
+
+/// Caret rectangle, in logical points on macOS and physical pixels elsewhere
+#[derive(Debug, Clone, Copy)]
+pub struct CaretRect {
+    pub x: f64,
+    pub y: f64,
+    pub height: f64,
+}
+
+#[cfg(target_os = "macos")]
+mod ax {
+    use super::CaretRect;
+    use core_foundation_sys::base::{CFRange, CFRelease, CFTypeRef};
+    use core_foundation_sys::string::{
+        kCFStringEncodingUTF8, CFStringCreateWithCString, CFStringGetCString, CFStringRef,
+    };
+    use std::ffi::{c_char, c_void, CStr, CString};
+
+    type AXUIElementRef = CFTypeRef;
+    const AX_ERROR_SUCCESS: i32 = 0;
+    const AX_VALUE_CG_RECT_TYPE: u32 = 3;
+    const AX_VALUE_CF_RANGE_TYPE: u32 = 4;
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct CGRect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    }
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXUIElementCreateSystemWide() -> AXUIElementRef;
+        fn AXUIElementCopyAttributeValue(
+            element: AXUIElementRef,
+            attribute: CFStringRef,
+            value: *mut CFTypeRef,
+        ) -> i32;
+        fn AXUIElementCopyParameterizedAttributeValue(
+            element: AXUIElementRef,
+            attribute: CFStringRef,
+            parameter: CFTypeRef,
+            value: *mut CFTypeRef,
+        ) -> i32;
+        fn AXValueGetValue(value: CFTypeRef, value_type: u32, value_ptr: *mut c_void) -> bool;
+        fn AXValueCreate(value_type: u32, value_ptr: *const c_void) -> CFTypeRef;
+    }
+
+    /// Owned CoreFoundation object, released on drop
+    struct Owned(CFTypeRef);
+
+    impl Drop for Owned {
+        fn drop(&mut self) {
+            // SAFETY: the pointer comes from a Create/Copy call and is non-null
+            unsafe { CFRelease(self.0) }
+        }
+    }
+
+    fn cf_string(value: &str) -> Option<Owned> {
+        let c_value = CString::new(value).ok()?;
+        // SAFETY: `c_value` is a valid NUL-terminated string
+        let string = unsafe {
+            CFStringCreateWithCString(std::ptr::null(), c_value.as_ptr(), kCFStringEncodingUTF8)
+        };
+        (!string.is_null()).then(|| Owned(string as CFTypeRef))
+    }
+
+    fn copy_attribute(element: CFTypeRef, name: &str) -> Option<Owned> {
+        let attribute = cf_string(name)?;
+        let mut value: CFTypeRef = std::ptr::null();
+        // SAFETY: `element` and `attribute` are valid CF objects
+        let err = unsafe { AXUIElementCopyAttributeValue(element, attribute.0 as CFStringRef, &mut value) };
+        (err == AX_ERROR_SUCCESS && !value.is_null()).then(|| Owned(value))
+    }
+
+    fn copy_parameterized(element: CFTypeRef, name: &str, parameter: CFTypeRef) -> Option<Owned> {
+        let attribute = cf_string(name)?;
+        let mut value: CFTypeRef = std::ptr::null();
+        // SAFETY: all arguments are valid CF objects
+        let err = unsafe {
+            AXUIElementCopyParameterizedAttributeValue(element, attribute.0 as CFStringRef, parameter, &mut value)
+        };
+        (err == AX_ERROR_SUCCESS && !value.is_null()).then(|| Owned(value))
+    }
+
+    fn focused_element() -> Option<Owned> {
+        // SAFETY: returns a new reference, released by `Owned`
+        let system = Owned(unsafe { AXUIElementCreateSystemWide() });
+        copy_attribute(system.0, "AXFocusedUIElement")
+    }
+
+    /// Bounds of the selected text range in the focused element
+    pub fn caret_rect() -> Option<CaretRect> {
+        let focused = focused_element()?;
+        let range = copy_attribute(focused.0, "AXSelectedTextRange")?;
+        let bounds = copy_parameterized(focused.0, "AXBoundsForRange", range.0)?;
+
+        let mut rect = CGRect::default();
+        // SAFETY: `rect` matches the CGRect layout expected for this value type
+        let ok = unsafe {
+            AXValueGetValue(bounds.0, AX_VALUE_CG_RECT_TYPE, &mut rect as *mut CGRect as *mut c_void)
+        };
+        // Some apps report an empty rect at the origin when they have no caret
+        if !ok || (rect.x == 0.0 && rect.y == 0.0 && rect.height == 0.0) {
+            return None;
+        }
+        Some(CaretRect {
+            x: rect.x,
+            y: rect.y,
+            height: rect.height,
+        })
+    }
+
+    /// Text of the focused element right before the selection
+    pub fn text_before_caret(max_chars: usize) -> Option<String> {
+        let focused = focused_element()?;
+        let selection = copy_attribute(focused.0, "AXSelectedTextRange")?;
+        let mut range = CFRange { location: 0, length: 0 };
+        // SAFETY: `range` matches the CFRange layout expected for this value type
+        let ok = unsafe {
+            AXValueGetValue(selection.0, AX_VALUE_CF_RANGE_TYPE, &mut range as *mut CFRange as *mut c_void)
+        };
+        if !ok || range.location < 0 {
+            return None;
+        }
+        if range.location == 0 {
+            return Some(String::new());
+        }
+
+        let start = range.location.saturating_sub(max_chars as isize).max(0);
+        let before = CFRange {
+            location: start,
+            length: range.location - start,
+        };
+        // SAFETY: `before` outlives the call, the AXValue copies it
+        let before = unsafe { AXValueCreate(AX_VALUE_CF_RANGE_TYPE, &before as *const CFRange as *const c_void) };
+        if before.is_null() {
+            return None;
+        }
+        let before = Owned(before);
+        let text = copy_parameterized(focused.0, "AXStringForRange", before.0)?;
+
+        let mut buffer = vec![0 as c_char; max_chars * 4 + 1];
+        // SAFETY: `buffer` is writable for its whole length, NUL-terminated on success
+        let ok = unsafe {
+            CFStringGetCString(text.0 as CFStringRef, buffer.as_mut_ptr(), buffer.len() as isize, kCFStringEncodingUTF8)
+        };
+        if ok == 0 {
+            return None;
+        }
+        // SAFETY: CFStringGetCString wrote a NUL-terminated string
+        let text = unsafe { CStr::from_ptr(buffer.as_ptr()) };
+        Some(text.to_string_lossy().into_owned())
+    }
+}
+
+/// Caret of the focused application via the Accessibility API
+#[cfg(target_os = "macos")]
+pub fn caret_rect() -> Option<CaretRect> {
+    ax::caret_rect()
+}
+
+/// Up to `max_chars` UTF-16 units before the caret via the Accessibility API,
+/// empty at the start of the field
+#[cfg(target_os = "macos")]
+pub fn text_before_caret(max_chars: usize) -> Option<String> {
+    ax::text_before_caret(max_chars)
+}
+
+/// Not exposed without UI Automation, the previous dictation is used instead
+#[cfg(not(target_os = "macos"))]
+pub fn text_before_caret(_max_chars: usize) -> Option<String> {
+    None
+}
+
+/// Caret of the foreground thread via GetGUIThreadInfo
+#[cfg(target_os = "windows")]
+pub fn caret_rect() -> Option<CaretRect> {
+    use windows_sys::Win32::Foundation::POINT;
+    use windows_sys::Win32::Graphics::Gdi::ClientToScreen;
+    use windows_sys::Win32::UI::WindowsAndMessaging::{GetGUIThreadInfo, GUITHREADINFO};
+
+    // SAFETY: GUITHREADINFO is plain data, cbSize is set before the call
+    unsafe {
+        let mut info: GUITHREADINFO = std::mem::zeroed();
+        info.cbSize = std::mem::size_of::<GUITHREADINFO>() as u32;
+        if GetGUIThreadInfo(0, &mut info) == 0 || info.hwndCaret.is_null() {
+            return None;
+        }
+
+        let mut point = POINT {
+            x: info.rcCaret.left,
+            y: info.rcCaret.top,
+        };
+        if ClientToScreen(info.hwndCaret, &mut point) == 0 {
+            return None;
+        }
+        Some(CaretRect {
+            x: point.x as f64,
+            y: point.y as f64,
+            height: (info.rcCaret.bottom - info.rcCaret.top) as f64,
+        })
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn caret_rect() -> Option<CaretRect> {
+    None
+}
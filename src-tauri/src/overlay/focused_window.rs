@@ -0,0 +1,89 @@
+//! Geometry of the frontmost application window
+//!
+//! Used to show the overlay on the monitor the user is working on.
+
+/// Parse "x, y, width, height" into the center point
+#[cfg(target_os = "macos")]
+fn center_from_bounds(bounds: &str) -> Option<(f64, f64)> {
+    let values: Vec<f64> = bounds
+        .trim()
+        .split(',')
+        .filter_map(|v| v.trim().parse().ok())
+        .collect();
+    match values.as_slice() {
+        [x, y, width, height] => Some((x + width / 2.0, y + height / 2.0)),
+        _ => None,
+    }
+}
+
+/// Center of the frontmost window (logical points)
+#[cfg(target_os = "macos")]
+pub fn frontmost_window_center() -> Option<(f64, f64)> {
+    let output = std::process::Command::new("osascript")
+        .arg("-e")
+        .arg(
+            "tell application \"System Events\" to tell (first application process whose frontmost is true) \
+            to get (position of front window) & (size of front window)",
+        )
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        tracing::debug!("osascript error: {}", String::from_utf8_lossy(&output.stderr));
+        return None;
+    }
+    center_from_bounds(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Center of the foreground window (physical pixels)
+#[cfg(target_os = "windows")]
+pub fn frontmost_window_center() -> Option<(f64, f64)> {
+    use windows_sys::Win32::Foundation::RECT;
+    use windows_sys::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowRect};
+
+    let mut rect = RECT {
+        left: 0,
+        top: 0,
+        right: 0,
+        bottom: 0,
+    };
+    // SAFETY: plain Win32 calls, `rect` outlives the call
+    let ok = unsafe {
+        let hwnd = GetForegroundWindow();
+        !hwnd.is_null() && GetWindowRect(hwnd, &mut rect) != 0
+    };
+    if !ok {
+        return None;
+    }
+    Some((
+        (rect.left + rect.right) as f64 / 2.0,
+        (rect.top + rect.bottom) as f64 / 2.0,
+    ))
+}
+
+/// Center of the active window via xdotool (physical pixels)
+#[cfg(target_os = "linux")]
+pub fn frontmost_window_center() -> Option<(f64, f64)> {
+    let output = std::process::Command::new("xdotool")
+        .args(["getactivewindow", "getwindowgeometry", "--shell"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let value = |key: &str| -> Option<f64> {
+        stdout
+            .lines()
+            .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+            .and_then(|v| v.trim().parse().ok())
+    };
+    let (x, y) = (value("X")?, value("Y")?);
+    let (width, height) = (value("WIDTH")?, value("HEIGHT")?);
+    Some((x + width / 2.0, y + height / 2.0))
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn frontmost_window_center() -> Option<(f64, f64)> {
+    None
+}
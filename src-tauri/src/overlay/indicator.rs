@@ -0,0 +1,42 @@
+//! Recording indicator enforcement
+//!
+//! Some workplaces require a visible indicator for any audio-capturing
+//! tool. When enabled in config, the overlay is kept on screen for as long
+//! as the microphone is open, and the main window title flags the capture.
+
+use super::window::{overlay_windows, show};
+use crate::config::{OverlayMonitor, OverlayPosition};
+use tauri::{AppHandle, Manager};
+
+/// Label of the settings window
+const MAIN_LABEL: &str = "main";
+
+/// Title of the settings window (as declared in tauri.conf.json)
+const MAIN_TITLE: &str = "Dictea - Parameters";
+
+/// Title of the settings window while capturing
+const RECORDING_TITLE: &str = "\u{25CF} Recording - Dictea";
+
+/// Whether at least one overlay window is on screen
+pub fn is_visible(app: &AppHandle) -> bool {
+    overlay_windows(app)
+        .iter()
+        .any(|window| window.is_visible().unwrap_or(false))
+}
+
+/// Show the overlay again if it was hidden, returning whether it is visible
+pub fn ensure_visible(app: &AppHandle, position: &OverlayPosition, mode: &OverlayMonitor) -> bool {
+    if is_visible(app) {
+        return true;
+    }
+    tracing::warn!("Overlay hidden while recording, showing it again");
+    show(app, position, mode);
+    is_visible(app)
+}
+
+/// Flag the capture in the window title, which also shows in the taskbar/dock menu
+pub fn set_capture_title(app: &AppHandle, capturing: bool) {
+    if let Some(window) = app.get_webview_window(MAIN_LABEL) {
+        let _ = window.set_title(if capturing { RECORDING_TITLE } else { MAIN_TITLE });
+    }
+}
@@ -0,0 +1,116 @@
+//! Audio level updates pushed to the overlay
+//!
+//! Each update is a JS `eval` per window, so they are only sent to overlay
+//! webviews that are on screen and have registered interest, at the rate
+//! set in config. The level only moves while the speech detector hears
+//! speech: background noise leaves the indicator at rest.
+
+use super::window::overlay_windows;
+use crate::vad::VoiceActivityDetector;
+use std::collections::HashSet;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+/// Labels of the webviews currently rendering the level meter
+fn interested() -> &'static Mutex<HashSet<String>> {
+    static INTERESTED: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+    INTERESTED.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+/// Register (or withdraw) a webview's interest in level updates
+pub fn set_level_interest(label: &str, interested_in: bool) {
+    if let Ok(mut labels) = interested().lock() {
+        if interested_in {
+            labels.insert(label.to_string());
+        } else {
+            labels.remove(label);
+        }
+    }
+}
+
+/// Speech keeps the level up this long (samples at 16kHz), so the
+/// indicator does not drop between two words
+const SPEECH_HOLD_SAMPLES: usize = 16000 * 3 / 10;
+
+/// Throttled publisher of the microphone level
+pub struct LevelMeter {
+    interval: Duration,
+    last_send: Option<Instant>,
+    detector: Box<dyn VoiceActivityDetector>,
+    /// Samples waiting to fill a detector frame
+    pending: Vec<f32>,
+    /// Samples analysed since the last speech frame
+    since_speech: usize,
+}
+
+impl LevelMeter {
+    /// Publisher sending at most `fps` updates per second, gated by `detector`
+    pub fn new(fps: u32, detector: Box<dyn VoiceActivityDetector>) -> Self {
+        Self {
+            interval: Duration::from_millis(1000 / u64::from(fps.max(1))),
+            last_send: None,
+            detector,
+            pending: Vec::new(),
+            since_speech: usize::MAX,
+        }
+    }
+
+    /// Run the detector on `samples`, whole frames only
+    fn detect(&mut self, samples: &[f32]) {
+        self.pending.extend_from_slice(samples);
+        let frame_len = self.detector.frame_samples();
+        let frames = self.pending.len() / frame_len;
+        for frame in self.pending.chunks_exact(frame_len) {
+            if self.detector.is_speech(frame) {
+                self.since_speech = 0;
+            } else {
+                self.since_speech = self.since_speech.saturating_add(frame_len);
+            }
+        }
+        self.pending.drain(..frames * frame_len);
+    }
+
+    /// Publish the level of `samples` if an update is due and someone is watching
+    pub fn push(&mut self, app: &AppHandle, samples: &[f32]) {
+        if samples.is_empty() {
+            return;
+        }
+        // Nobody watching: the detector is not run either
+        if !interested().lock().is_ok_and(|labels| !labels.is_empty()) {
+            self.pending.clear();
+            self.since_speech = usize::MAX;
+            return;
+        }
+        self.detect(samples);
+        if self.last_send.is_some_and(|last| last.elapsed() < self.interval) {
+            return;
+        }
+
+        let targets: Vec<_> = {
+            let Ok(labels) = interested().lock() else { return };
+            overlay_windows(app)
+                .into_iter()
+                .filter(|window| labels.contains(window.label()))
+                .filter(|window| {
+                    window.is_visible().unwrap_or(false) && !window.is_minimized().unwrap_or(false)
+                })
+                .collect()
+        };
+        if targets.is_empty() {
+            return;
+        }
+
+        let level = if self.since_speech < SPEECH_HOLD_SAMPLES {
+            let rms = (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt();
+            (rms * 50.0).min(1.0) // normalize (mic levels are very low)
+        } else {
+            0.0
+        };
+        let js = format!("window.__overlaySetLevel && window.__overlaySetLevel({})", level);
+        for window in targets {
+            let _ = window.eval(&js);
+        }
+        self.last_send = Some(Instant::now());
+    }
+}
@@ -0,0 +1,16 @@
+//! Overlay window module
+//!
+//! Placement of the floating overlay and the JS bridge used to drive it,
+//! including the throttled audio level feed.
+
+mod caret;
+mod focused_window;
+mod indicator;
+mod level;
+mod placement;
+mod window;
+
+pub use caret::text_before_caret;
+pub use indicator::{ensure_visible, is_visible, set_capture_title};
+pub use level::{set_level_interest, LevelMeter};
+pub use window::{eval_all, hide_all, monitor_names, show};
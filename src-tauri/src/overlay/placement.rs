@@ -0,0 +1,114 @@
+//! Overlay placement across monitors
+
+use super::caret::CaretRect;
+use super::focused_window;
+use crate::config::OverlayMonitor;
+use tauri::{Monitor, WebviewWindow};
+
+/// Overlay window size (logical pixels, see tauri.conf.json)
+pub const OVERLAY_WIDTH: f64 = 140.0;
+pub const OVERLAY_HEIGHT: f64 = 44.0;
+
+/// Monitor under the mouse cursor, or the overlay's current one
+fn cursor_monitor(window: &WebviewWindow) -> Option<Monitor> {
+    window
+        .cursor_position()
+        .ok()
+        .and_then(|cursor| window.monitor_from_point(cursor.x, cursor.y).ok().flatten())
+        .or_else(|| window.current_monitor().ok().flatten())
+}
+
+/// Whether a point lies on a monitor
+///
+/// macOS reports window geometry in logical points, other platforms in
+/// physical pixels.
+fn monitor_contains(monitor: &Monitor, x: f64, y: f64) -> bool {
+    let scale = if cfg!(target_os = "macos") {
+        monitor.scale_factor()
+    } else {
+        1.0
+    };
+    let left = monitor.position().x as f64 / scale;
+    let top = monitor.position().y as f64 / scale;
+    let width = monitor.size().width as f64 / scale;
+    let height = monitor.size().height as f64 / scale;
+    x >= left && x < left + width && y >= top && y < top + height
+}
+
+/// Monitor holding the focused window of the frontmost application
+fn focused_window_monitor(window: &WebviewWindow) -> Option<Monitor> {
+    let (x, y) = focused_window::frontmost_window_center()?;
+    window
+        .available_monitors()
+        .ok()?
+        .into_iter()
+        .find(|monitor| monitor_contains(monitor, x, y))
+}
+
+/// Monitors the overlay should be shown on
+pub fn target_monitors(window: &WebviewWindow, mode: &OverlayMonitor) -> Vec<Monitor> {
+    let monitor = match mode {
+        OverlayMonitor::FollowCursor => cursor_monitor(window),
+        OverlayMonitor::FollowFocusedWindow => {
+            focused_window_monitor(window).or_else(|| cursor_monitor(window))
+        }
+        OverlayMonitor::Named { name } => window
+            .available_monitors()
+            .ok()
+            .and_then(|monitors| {
+                monitors
+                    .into_iter()
+                    .find(|monitor| monitor.name().map(|n| n == name).unwrap_or(false))
+            })
+            .or_else(|| {
+                tracing::warn!("Monitor '{}' not found, following cursor", name);
+                cursor_monitor(window)
+            }),
+        OverlayMonitor::All => return window.available_monitors().unwrap_or_default(),
+    };
+    monitor.into_iter().collect()
+}
+
+/// Place the overlay horizontally centered, near the top of a monitor
+pub fn center_on(window: &WebviewWindow, monitor: &Monitor) {
+    let scale = monitor.scale_factor();
+    let pos = monitor.position();
+    let size = monitor.size();
+    let screen_x = pos.x as f64 / scale;
+    let screen_y = pos.y as f64 / scale;
+    let screen_width = size.width as f64 / scale;
+    let screen_height = size.height as f64 / scale;
+    let x = (screen_x + (screen_width - OVERLAY_WIDTH) / 2.0) as i32;
+    let y = (screen_y + screen_height * 0.15) as i32;
+    let _ = window.set_position(tauri::LogicalPosition::new(x, y));
+}
+
+/// Gap between the caret and the overlay (logical pixels)
+const CARET_GAP: f64 = 8.0;
+
+/// Place the overlay just below the text caret, horizontally centered on it
+pub fn place_near_caret(window: &WebviewWindow, caret: &CaretRect) {
+    if cfg!(target_os = "macos") {
+        let x = caret.x - OVERLAY_WIDTH / 2.0;
+        let y = caret.y + caret.height + CARET_GAP;
+        let _ = window.set_position(tauri::LogicalPosition::new(x as i32, y as i32));
+    } else {
+        let scale = window
+            .monitor_from_point(caret.x, caret.y)
+            .ok()
+            .flatten()
+            .map(|monitor| monitor.scale_factor())
+            .unwrap_or(1.0);
+        let x = caret.x - OVERLAY_WIDTH * scale / 2.0;
+        let y = caret.y + caret.height + CARET_GAP * scale;
+        let _ = window.set_position(tauri::PhysicalPosition::new(x as i32, y as i32));
+    }
+}
+
+/// Names of the monitors known to a window
+pub fn monitor_names(window: &WebviewWindow) -> Vec<String> {
+    window
+        .available_monitors()
+        .map(|monitors| monitors.iter().filter_map(|m| m.name().cloned()).collect())
+        .unwrap_or_default()
+}
@@ -0,0 +1,101 @@
+//! Overlay windows management
+//!
+//! The "overlay" window is declared in tauri.conf.json. In "all monitors"
+//! mode, clones labeled "overlay-N" are created on demand.
+
+use super::{caret, placement};
+use crate::config::{OverlayMonitor, OverlayPosition};
+use tauri::{AppHandle, Manager, WebviewWindow};
+
+/// Label of the overlay window (clones use it as prefix)
+const OVERLAY_LABEL: &str = "overlay";
+
+/// All overlay windows (main one and clones)
+pub(super) fn overlay_windows(app: &AppHandle) -> Vec<WebviewWindow> {
+    app.webview_windows()
+        .into_iter()
+        .filter(|(label, _)| label.starts_with(OVERLAY_LABEL))
+        .map(|(_, window)| window)
+        .collect()
+}
+
+/// Get or create the overlay clone for the monitor at `index`
+fn clone_window(app: &AppHandle, index: usize) -> Option<WebviewWindow> {
+    let label = format!("{}-{}", OVERLAY_LABEL, index);
+    if let Some(window) = app.get_webview_window(&label) {
+        return Some(window);
+    }
+
+    tauri::WebviewWindowBuilder::new(app, &label, tauri::WebviewUrl::App("index.html".into()))
+        .title("Dictea")
+        .inner_size(placement::OVERLAY_WIDTH, placement::OVERLAY_HEIGHT)
+        .resizable(false)
+        .decorations(false)
+        .transparent(true)
+        .shadow(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .visible(false)
+        .focused(false)
+        .build()
+        .map_err(|e| tracing::error!("Overlay clone creation error: {}", e))
+        .ok()
+}
+
+/// Run a JS snippet in every overlay window
+pub fn eval_all(app: &AppHandle, js: &str) {
+    for window in overlay_windows(app) {
+        let _ = window.eval(js);
+    }
+}
+
+/// Hide every overlay window
+pub fn hide_all(app: &AppHandle) {
+    for window in overlay_windows(app) {
+        let _ = window.hide();
+    }
+}
+
+/// Show the overlay near the caret, or on the monitor(s) selected by `mode`
+///
+/// Near-caret positioning falls back to the monitor placement when the
+/// focused app does not expose its caret bounds.
+pub fn show(app: &AppHandle, position: &OverlayPosition, mode: &OverlayMonitor) {
+    let Some(overlay) = app.get_webview_window(OVERLAY_LABEL) else {
+        return;
+    };
+
+    if *position == OverlayPosition::NearCaret {
+        if let Some(caret) = caret::caret_rect() {
+            placement::place_near_caret(&overlay, &caret);
+            let _ = overlay.show();
+            return;
+        }
+        tracing::debug!("Caret bounds unavailable, using monitor placement");
+    }
+
+    let monitors = placement::target_monitors(&overlay, mode);
+    if monitors.is_empty() {
+        let _ = overlay.show();
+        return;
+    }
+
+    for (index, monitor) in monitors.iter().enumerate() {
+        let window = if index == 0 {
+            Some(overlay.clone())
+        } else {
+            clone_window(app, index)
+        };
+        if let Some(window) = window {
+            placement::center_on(&window, monitor);
+            let _ = window.show();
+        }
+    }
+}
+
+/// Names of the connected monitors
+pub fn monitor_names(app: &AppHandle) -> Vec<String> {
+    app.get_webview_window(OVERLAY_LABEL)
+        .map(|overlay| placement::monitor_names(&overlay))
+        .unwrap_or_default()
+}
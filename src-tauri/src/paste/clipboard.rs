@@ -0,0 +1,126 @@
+//! Audio held by the clipboard
+//!
+//! A voice message saved from a messenger is copied either as a file (its
+//! path or file:// URI, a Finder file reference on macOS) or, on Linux, as
+//! raw audio data offered under an `audio/*` type.
+
+use crate::audio::{is_audio_file, read_audio_file};
+use std::path::PathBuf;
+
+/// Decode the audio of the clipboard into 16kHz mono samples, `None` when it holds none
+pub fn read_clipboard_audio() -> Result<Option<Vec<f32>>, String> {
+    if let Some(path) = clipboard_audio_path() {
+        tracing::info!("Clipboard audio file: {}", path.display());
+        return read_audio_file(&path).map(Some);
+    }
+    let Some((bytes, extension)) = clipboard_audio_data() else {
+        return Ok(None);
+    };
+    tracing::info!("Clipboard audio data: {} bytes", bytes.len());
+    // ffmpeg reads from a file, so it can seek in formats that need it
+    let path = std::env::temp_dir().join(format!("dictea-clipboard.{}", extension));
+    std::fs::write(&path, bytes).map_err(|e| format!("Clipboard audio write error: {}", e))?;
+    let samples = read_audio_file(&path);
+    let _ = std::fs::remove_file(&path);
+    samples.map(Some)
+}
+
+/// Audio file referenced by the clipboard text (path or file:// URI, one per line)
+fn clipboard_audio_path() -> Option<PathBuf> {
+    let text = arboard::Clipboard::new().and_then(|mut clipboard| clipboard.get_text()).ok();
+    text.iter()
+        .flat_map(|text| text.lines())
+        .map(|line| line.trim().trim_matches(|c| c == '"' || c == '\''))
+        .filter_map(|line| match line.strip_prefix("file://") {
+            Some(uri) => percent_decode(uri),
+            None => Some(line.to_string()),
+        })
+        .map(PathBuf::from)
+        .find(|path| path.is_file() && is_audio_file(path))
+        .or_else(finder_file)
+}
+
+/// Decode the %XX escapes of a file URI path
+fn percent_decode(uri: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(uri.len());
+    let mut rest = uri.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' && tail.len() >= 2 {
+            let hex = std::str::from_utf8(&tail[..2]).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// File copied in the Finder, whose name only is offered as text
+#[cfg(target_os = "macos")]
+fn finder_file() -> Option<PathBuf> {
+    let output = std::process::Command::new("osascript")
+        .arg("-e")
+        .arg("POSIX path of (the clipboard as \u{ab}class furl\u{bb})")
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let path = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    (path.is_file() && is_audio_file(&path)).then_some(path)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn finder_file() -> Option<PathBuf> {
+    None
+}
+
+/// Audio data offered by the clipboard owner, with the extension of its type
+#[cfg(target_os = "linux")]
+fn clipboard_audio_data() -> Option<(Vec<u8>, &'static str)> {
+    use std::process::Command;
+
+    // wl-paste in a Wayland session, xclip under X11
+    let read = |target: Option<&str>| {
+        let mut command = if super::wayland::is_wayland_session() {
+            let mut command = Command::new("wl-paste");
+            match target {
+                Some(target) => command.args(["--no-newline", "--type", target]),
+                None => command.arg("--list-types"),
+            };
+            command
+        } else {
+            let mut command = Command::new("xclip");
+            command.args(["-selection", "clipboard", "-o", "-t", target.unwrap_or("TARGETS")]);
+            command
+        };
+        let output = command.output().ok()?;
+        output.status.success().then_some(output.stdout)
+    };
+    let targets = String::from_utf8(read(None)?).ok()?;
+    let mime = targets.lines().map(str::trim).find(|target| target.starts_with("audio/"))?;
+    let bytes = read(Some(mime)).filter(|bytes| !bytes.is_empty())?;
+    Some((bytes, audio_extension(mime)))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn clipboard_audio_data() -> Option<(Vec<u8>, &'static str)> {
+    None
+}
+
+/// File extension of an audio MIME type, for ffmpeg to pick the demuxer
+#[cfg(target_os = "linux")]
+fn audio_extension(mime: &str) -> &'static str {
+    match mime.split(';').next().unwrap_or(mime) {
+        "audio/ogg" => "ogg",
+        "audio/opus" => "opus",
+        "audio/mpeg" | "audio/mp3" => "mp3",
+        "audio/mp4" | "audio/x-m4a" | "audio/aac" => "m4a",
+        "audio/wav" | "audio/x-wav" | "audio/wave" => "wav",
+        "audio/flac" | "audio/x-flac" => "flac",
+        "audio/webm" => "webm",
+        _ => "audio",
+    }
+}
@@ -0,0 +1,91 @@
+//! Smart capitalization when appending at the caret
+//!
+//! Adapts the start of the transcript to the text it lands after: a new
+//! sentence starts with a capital, the rest of a sentence goes on in
+//! lowercase, and a space separates it from the previous word.
+
+use std::time::{Duration, Instant};
+
+/// Previous dictations older than this no longer count as context
+const SESSION_WINDOW: Duration = Duration::from_secs(5 * 60);
+
+/// Characters read before the caret
+pub const CONTEXT_CHARS: usize = 16;
+
+/// Last text pasted, used as context when the caret cannot be read
+#[derive(Debug, Clone)]
+pub struct PastedText {
+    app: Option<String>,
+    text: String,
+    at: Instant,
+}
+
+impl PastedText {
+    pub fn new(app: Option<&str>, text: &str) -> Self {
+        Self {
+            app: app.map(str::to_string),
+            text: text.to_string(),
+            at: Instant::now(),
+        }
+    }
+
+    /// The pasted text, if it was recently pasted into `app`
+    pub fn preceding_for(&self, app: Option<&str>) -> Option<&str> {
+        (self.app.as_deref() == app && self.at.elapsed() < SESSION_WINDOW).then_some(self.text.as_str())
+    }
+
+    /// Backspaces deleting the pasted text, if it was recently pasted into `app`
+    pub fn undo_keystrokes(&self, app: Option<&str>) -> Option<usize> {
+        self.preceding_for(app).map(|text| text.chars().count())
+    }
+}
+
+/// Whether the next word starts a new sentence after `preceding`
+fn starts_sentence(preceding: &str) -> bool {
+    // A line break ends the sentence even without punctuation
+    preceding.ends_with('\n')
+        || preceding
+            .trim_end()
+            .chars()
+            .next_back()
+            .map_or(true, |c| matches!(c, '.' | '!' | '?' | '…'))
+}
+
+/// A capitalized word that should stay so mid-sentence (acronym, "I", "I'm")
+fn keeps_capital(word: &str) -> bool {
+    let letters: Vec<char> = word.chars().take_while(|c| c.is_alphabetic()).collect();
+    (letters.len() == 1 && letters[0] == 'I') || letters.iter().skip(1).any(|c| c.is_uppercase())
+}
+
+/// `text` with its first letter case changed by `f`
+fn with_first_letter(text: &str, f: impl Fn(char) -> String) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => f(first) + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+/// Adapt the start of `text` to the `preceding` text ("" = start of the field)
+pub fn continue_after(preceding: &str, text: &str) -> String {
+    let text = text.trim_start();
+    let adapted = if starts_sentence(preceding) {
+        with_first_letter(text, |c| c.to_uppercase().collect())
+    } else if keeps_capital(text.split_whitespace().next().unwrap_or_default()) {
+        text.to_string()
+    } else {
+        with_first_letter(text, |c| c.to_lowercase().collect())
+    };
+
+    // Glue to the previous word unless already spaced or opening a bracket/quote
+    let needs_space = preceding
+        .chars()
+        .next_back()
+        .is_some_and(|c| !c.is_whitespace() && !matches!(c, '(' | '[' | '{' | '"' | '\'' | '«' | '“'));
+    let attaches = adapted.starts_with(['.', ',', ';', '!', '?', ')']);
+    if needs_space && !attaches {
+        format!(" {}", adapted)
+    } else {
+        adapted
+    }
+}
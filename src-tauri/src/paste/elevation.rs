@@ -0,0 +1,64 @@
+//! Elevated target detection (Windows)
+//!
+//! User Interface Privilege Isolation drops input sent by an unelevated
+//! process to an elevated window (admin terminal...), so the simulated
+//! paste would silently do nothing. Such targets get the clipboard only.
+
+/// Whether the foreground window runs with a higher integrity than Dictea
+#[cfg(target_os = "windows")]
+pub fn foreground_is_elevated() -> bool {
+    use windows_sys::Win32::System::Threading::{GetCurrentProcess, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
+    use windows_sys::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+    // SAFETY: plain Win32 calls, handles are closed by process_is_elevated
+    unsafe {
+        if process_is_elevated(GetCurrentProcess()) == Some(true) {
+            // Elevated ourselves: UIPI does not block anything
+            return false;
+        }
+        let hwnd = GetForegroundWindow();
+        if hwnd.is_null() {
+            return false;
+        }
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(hwnd, &mut pid);
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if process.is_null() {
+            return false;
+        }
+        let elevated = process_is_elevated(process);
+        windows_sys::Win32::Foundation::CloseHandle(process);
+        // A token we cannot even query belongs to a more privileged process
+        elevated.unwrap_or(true)
+    }
+}
+
+/// Read the elevation flag of a process token, `None` if access is denied
+#[cfg(target_os = "windows")]
+unsafe fn process_is_elevated(process: windows_sys::Win32::Foundation::HANDLE) -> Option<bool> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::Security::{GetTokenInformation, TokenElevation, TOKEN_ELEVATION, TOKEN_QUERY};
+    use windows_sys::Win32::System::Threading::OpenProcessToken;
+
+    let mut token = std::ptr::null_mut();
+    if OpenProcessToken(process, TOKEN_QUERY, &mut token) == 0 {
+        return None;
+    }
+    let mut elevation = TOKEN_ELEVATION { TokenIsElevated: 0 };
+    let mut len = 0u32;
+    let ok = GetTokenInformation(
+        token,
+        TokenElevation,
+        &mut elevation as *mut TOKEN_ELEVATION as *mut _,
+        std::mem::size_of::<TOKEN_ELEVATION>() as u32,
+        &mut len,
+    ) != 0;
+    CloseHandle(token);
+    ok.then_some(elevation.TokenIsElevated != 0)
+}
+
+/// No privilege isolation of input outside Windows
+#[cfg(not(target_os = "windows"))]
+pub fn foreground_is_elevated() -> bool {
+    false
+}
@@ -0,0 +1,84 @@
+//! Name of the frontmost application
+//!
+//! Used to pick a per-app paste shortcut, and to never paste into dictea's
+//! own windows.
+
+/// Process name of the frontmost app, e.g. "Terminal"
+#[cfg(target_os = "macos")]
+pub fn frontmost_app() -> Option<String> {
+    let output = std::process::Command::new("osascript")
+        .arg("-e")
+        .arg("tell application \"System Events\" to get name of first application process whose frontmost is true")
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        tracing::debug!("osascript error: {}", String::from_utf8_lossy(&output.stderr));
+        return None;
+    }
+    let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!name.is_empty()).then_some(name)
+}
+
+/// Executable name of the foreground window's process, without ".exe"
+#[cfg(target_os = "windows")]
+pub fn frontmost_app() -> Option<String> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    use windows_sys::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+    let mut buffer = [0u16; 260];
+    let mut len = buffer.len() as u32;
+    // SAFETY: plain Win32 calls, the process handle is closed before returning
+    let ok = unsafe {
+        let hwnd = GetForegroundWindow();
+        if hwnd.is_null() {
+            return None;
+        }
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(hwnd, &mut pid);
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if process.is_null() {
+            return None;
+        }
+        let ok = QueryFullProcessImageNameW(process, PROCESS_NAME_WIN32, buffer.as_mut_ptr(), &mut len) != 0;
+        CloseHandle(process);
+        ok
+    };
+    if !ok {
+        return None;
+    }
+
+    let path = String::from_utf16_lossy(&buffer[..len as usize]);
+    std::path::Path::new(&path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+}
+
+/// WM_CLASS of the active window via xdotool, e.g. "gnome-terminal-server"
+#[cfg(target_os = "linux")]
+pub fn frontmost_app() -> Option<String> {
+    let output = std::process::Command::new("xdotool")
+        .args(["getactivewindow", "getwindowclassname"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!name.is_empty()).then_some(name)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn frontmost_app() -> Option<String> {
+    None
+}
+
+/// `app_name` (as returned by `frontmost_app`) is dictea itself
+pub fn is_own_app(app_name: &str) -> bool {
+    let exe_name = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.file_stem().map(|stem| stem.to_string_lossy().into_owned()));
+    app_name.eq_ignore_ascii_case("dictea") || exe_name.is_some_and(|exe_name| app_name.eq_ignore_ascii_case(&exe_name))
+}
@@ -0,0 +1,350 @@
+//! Platform key injection
+//!
+//! Character keys are resolved against the keyboard layout of the target
+//! window so that "V" hits the right physical key on AZERTY, Dvorak, etc.
+
+use super::keys::{Key, Modifier, PasteKeys};
+use super::wayland;
+
+/// Whether keys can be simulated in this session (Wayland needs wtype or ydotool)
+pub fn key_injection_available() -> bool {
+    !wayland::is_wayland_session() || wayland::has_key_tool()
+}
+
+/// Simulate the key sequence once
+pub fn send_keys(keys: &PasteKeys) -> Result<(), String> {
+    send_keys_times(keys, 1)
+}
+
+/// Simulate the key sequence via System Events (layout-aware by design)
+#[cfg(target_os = "macos")]
+pub fn send_keys_times(keys: &PasteKeys, count: usize) -> Result<(), String> {
+    let modifiers: Vec<&str> = keys
+        .modifiers
+        .iter()
+        .map(|m| match m {
+            Modifier::Ctrl => "control down",
+            Modifier::Shift => "shift down",
+            Modifier::Alt => "option down",
+            Modifier::Meta => "command down",
+        })
+        .collect();
+    let action = match keys.key {
+        Key::Char(c) => {
+            let escaped = match c {
+                '"' | '\\' => format!("\\{}", c),
+                c => c.to_string(),
+            };
+            format!("keystroke \"{}\"", escaped)
+        }
+        // Help/Insert key
+        Key::Insert => "key code 114".to_string(),
+        Key::Enter => "key code 36".to_string(),
+        Key::Backspace => "key code 51".to_string(),
+    };
+    let keystroke = if modifiers.is_empty() {
+        action
+    } else {
+        format!("{} using {{{}}}", action, modifiers.join(", "))
+    };
+    // A single script for all repetitions, osascript is slow to start
+    let script = format!(
+        "tell application \"System Events\"\nrepeat {} times\n{}\nend repeat\nend tell",
+        count, keystroke
+    );
+
+    let output = std::process::Command::new("osascript")
+        .arg("-e")
+        .arg(script)
+        .output()
+        .map_err(|e| format!("osascript launch error: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("osascript error: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(())
+}
+
+/// Keyboard layout of the foreground window's thread
+///
+/// Dictea's own thread may use another layout than the app receiving the
+/// paste, so keys are resolved against the target's layout.
+#[cfg(target_os = "windows")]
+fn foreground_layout() -> windows_sys::Win32::UI::Input::KeyboardAndMouse::HKL {
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::GetKeyboardLayout;
+    use windows_sys::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+    // SAFETY: plain Win32 calls, thread 0 is the current thread
+    unsafe {
+        let hwnd = GetForegroundWindow();
+        let thread = if hwnd.is_null() {
+            0
+        } else {
+            GetWindowThreadProcessId(hwnd, std::ptr::null_mut())
+        };
+        GetKeyboardLayout(thread)
+    }
+}
+
+/// Physical key producing a character on a keyboard layout
+#[cfg(any(target_os = "windows", test))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LayoutKey {
+    scan: u16,
+    /// Shift, Ctrl and Alt the layout needs for the character (Ctrl+Alt = AltGr)
+    modifiers: Vec<Modifier>,
+}
+
+/// Resolve `c` to a physical key from the layout lookups
+///
+/// `vk_scan` is `VkKeyScanExW`: virtual key in the low byte, shift state in
+/// the high byte (1 = Shift, 2 = Ctrl, 4 = Alt), -1 when the layout has no
+/// key for the character. `vk_to_scan` is `MapVirtualKeyExW`, 0 when the
+/// virtual key has no scan code.
+#[cfg(any(target_os = "windows", test))]
+fn resolve_layout_key(c: char, vk_scan: impl Fn(u16) -> i16, vk_to_scan: impl Fn(u32) -> u32) -> Option<LayoutKey> {
+    let mut utf16 = [0u16; 2];
+    if c.encode_utf16(&mut utf16).len() != 1 {
+        return None;
+    }
+    let vk_scan = vk_scan(utf16[0]);
+    if vk_scan == -1 {
+        return None;
+    }
+    let [vk, shift_state] = (vk_scan as u16).to_le_bytes();
+    // Kana and the reserved bits have no modifier to press
+    if shift_state & !0b111 != 0 {
+        return None;
+    }
+    let modifiers = [(1, Modifier::Shift), (2, Modifier::Ctrl), (4, Modifier::Alt)]
+        .into_iter()
+        .filter(|(bit, _)| shift_state & bit != 0)
+        .map(|(_, modifier)| modifier)
+        .collect();
+    let scan = vk_to_scan(vk as u32);
+    (scan != 0).then_some(LayoutKey {
+        scan: scan as u16,
+        modifiers,
+    })
+}
+
+/// Physical key producing `c` on `layout`, if any
+#[cfg(target_os = "windows")]
+fn layout_key(c: char, layout: windows_sys::Win32::UI::Input::KeyboardAndMouse::HKL) -> Option<LayoutKey> {
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::{MapVirtualKeyExW, VkKeyScanExW, MAPVK_VK_TO_VSC};
+
+    // SAFETY: plain Win32 calls
+    resolve_layout_key(
+        c,
+        |unit| unsafe { VkKeyScanExW(unit, layout) },
+        |vk| unsafe { MapVirtualKeyExW(vk, MAPVK_VK_TO_VSC, layout) },
+    )
+}
+
+/// Simulate the key sequence via enigo
+///
+/// Character keys are sent as scan codes, so the physical key is the one
+/// the target's layout maps to the character (V is on the same key on
+/// QWERTY and AZERTY, but not on Dvorak), with the Shift or AltGr the
+/// layout needs for it.
+#[cfg(target_os = "windows")]
+pub fn send_keys_times(keys: &PasteKeys, count: usize) -> Result<(), String> {
+    use enigo::{Direction, Enigo, Keyboard, Settings};
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::VK_INSERT;
+
+    let mut enigo = Enigo::new(&Settings::default()).map_err(|e| format!("enigo error: {}", e))?;
+
+    let char_key = match keys.key {
+        Key::Char(c) => layout_key(c, foreground_layout()),
+        _ => None,
+    };
+    let mut held = keys.modifiers.clone();
+    for modifier in char_key.iter().flat_map(|key| &key.modifiers) {
+        if !held.contains(modifier) {
+            held.push(*modifier);
+        }
+    }
+    let modifiers: Vec<enigo::Key> = held
+        .iter()
+        .map(|m| match m {
+            Modifier::Ctrl => enigo::Key::Control,
+            Modifier::Shift => enigo::Key::Shift,
+            Modifier::Alt => enigo::Key::Alt,
+            Modifier::Meta => enigo::Key::Meta,
+        })
+        .collect();
+
+    for modifier in &modifiers {
+        enigo.key(*modifier, Direction::Press).ok();
+    }
+    let result = (0..count).try_for_each(|_| match keys.key {
+        Key::Char(c) => match char_key {
+            Some(ref key) => enigo.raw(key.scan, Direction::Click),
+            None => {
+                tracing::warn!("No key for '{}' on the active layout, sending it as unicode", c);
+                enigo.key(enigo::Key::Unicode(c), Direction::Click)
+            }
+        },
+        Key::Insert => enigo.key(enigo::Key::Other(VK_INSERT as u32), Direction::Click),
+        Key::Enter => enigo.key(enigo::Key::Return, Direction::Click),
+        Key::Backspace => enigo.key(enigo::Key::Backspace, Direction::Click),
+    });
+    for modifier in modifiers.iter().rev() {
+        enigo.key(*modifier, Direction::Release).ok();
+    }
+    result.map_err(|e| format!("enigo error: {}", e))
+}
+
+/// Simulate the key sequence via xdotool, which maps keysyms to the active layout
+#[cfg(target_os = "linux")]
+pub fn send_keys_times(keys: &PasteKeys, count: usize) -> Result<(), String> {
+    if wayland::is_wayland_session() {
+        return wayland::send_keys_times(keys, count);
+    }
+    let mut combo: Vec<String> = keys
+        .modifiers
+        .iter()
+        .map(|m| {
+            match m {
+                Modifier::Ctrl => "ctrl",
+                Modifier::Shift => "shift",
+                Modifier::Alt => "alt",
+                Modifier::Meta => "super",
+            }
+            .to_string()
+        })
+        .collect();
+    combo.push(match keys.key {
+        Key::Char(c) => c.to_string(),
+        Key::Insert => "Insert".to_string(),
+        Key::Enter => "Return".to_string(),
+        Key::Backspace => "BackSpace".to_string(),
+    });
+
+    let output = std::process::Command::new("xdotool")
+        .args(["key", "--clearmodifiers", "--repeat", &count.to_string(), &combo.join("+")])
+        .output()
+        .map_err(|e| format!("xdotool launch error: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("xdotool error: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn send_keys_times(_keys: &PasteKeys, _count: usize) -> Result<(), String> {
+    Err("Key simulation is not supported on this platform".to_string())
+}
+
+/// Type `text` as keystrokes via System Events
+#[cfg(target_os = "macos")]
+pub fn type_text(text: &str) -> Result<(), String> {
+    let escaped = text.replace('\\', "\\\\").replace('"', "\\\"");
+    let script = format!("tell application \"System Events\" to keystroke \"{}\"", escaped);
+    let output = std::process::Command::new("osascript")
+        .arg("-e")
+        .arg(script)
+        .output()
+        .map_err(|e| format!("osascript launch error: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("osascript error: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(())
+}
+
+/// Type `text` as unicode keystrokes via enigo
+#[cfg(target_os = "windows")]
+pub fn type_text(text: &str) -> Result<(), String> {
+    use enigo::{Enigo, Keyboard, Settings};
+
+    let mut enigo = Enigo::new(&Settings::default()).map_err(|e| format!("enigo error: {}", e))?;
+    enigo.text(text).map_err(|e| format!("enigo error: {}", e))
+}
+
+/// Type `text` via xdotool
+#[cfg(target_os = "linux")]
+pub fn type_text(text: &str) -> Result<(), String> {
+    if wayland::is_wayland_session() {
+        return wayland::type_text(text);
+    }
+    let output = std::process::Command::new("xdotool")
+        .args(["type", "--clearmodifiers", "--", text])
+        .output()
+        .map_err(|e| format!("xdotool launch error: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("xdotool error: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn type_text(_text: &str) -> Result<(), String> {
+    Err("Key simulation is not supported on this platform".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `VkKeyScanExW` and `MapVirtualKeyExW` of a layout, from its tables
+    fn resolve(c: char, chars: &[(char, i16)], scans: &[(u32, u32)]) -> Option<LayoutKey> {
+        resolve_layout_key(
+            c,
+            |unit| {
+                chars
+                    .iter()
+                    .find(|(c, _)| *c as u16 == unit)
+                    .map_or(-1, |(_, vk_scan)| *vk_scan)
+            },
+            |vk| scans.iter().find(|(v, _)| *v == vk).map_or(0, |(_, scan)| *scan),
+        )
+    }
+
+    fn key(scan: u16, modifiers: &[Modifier]) -> Option<LayoutKey> {
+        Some(LayoutKey {
+            scan,
+            modifiers: modifiers.to_vec(),
+        })
+    }
+
+    #[test]
+    fn azerty() {
+        // A is where QWERTY has Q, digits need Shift, @ is AltGr+0
+        let chars = [('a', 0x41), ('v', 0x56), ('1', 0x131), ('@', 0x630)];
+        let scans = [(0x41, 0x10), (0x56, 0x2f), (0x31, 0x02), (0x30, 0x0b)];
+        assert_eq!(resolve('a', &chars, &scans), key(0x10, &[]));
+        assert_eq!(resolve('v', &chars, &scans), key(0x2f, &[]));
+        assert_eq!(resolve('1', &chars, &scans), key(0x02, &[Modifier::Shift]));
+        assert_eq!(resolve('@', &chars, &scans), key(0x0b, &[Modifier::Ctrl, Modifier::Alt]));
+    }
+
+    #[test]
+    fn qwertz() {
+        // Y and Z are swapped, @ is AltGr+Q
+        let chars = [('z', 0x5a), ('y', 0x59), ('@', 0x651)];
+        let scans = [(0x5a, 0x15), (0x59, 0x2c), (0x51, 0x10)];
+        assert_eq!(resolve('z', &chars, &scans), key(0x15, &[]));
+        assert_eq!(resolve('y', &chars, &scans), key(0x2c, &[]));
+        assert_eq!(resolve('@', &chars, &scans), key(0x10, &[Modifier::Ctrl, Modifier::Alt]));
+    }
+
+    #[test]
+    fn dvorak() {
+        // V is where QWERTY has the period, E where it has D
+        let chars = [('v', 0x56), ('e', 0x45), ('V', 0x156)];
+        let scans = [(0x56, 0x34), (0x45, 0x20)];
+        assert_eq!(resolve('v', &chars, &scans), key(0x34, &[]));
+        assert_eq!(resolve('e', &chars, &scans), key(0x20, &[]));
+        assert_eq!(resolve('V', &chars, &scans), key(0x34, &[Modifier::Shift]));
+    }
+
+    #[test]
+    fn missing_keys() {
+        let chars = [('a', 0x41), ('\u{3042}', 0x841)];
+        let scans = [(0x41, 0x1e)];
+        // Not on the layout, no scan code, outside the BMP, kana shift state
+        assert_eq!(resolve('b', &chars, &scans), None);
+        assert_eq!(resolve('a', &chars, &[]), None);
+        assert_eq!(resolve('\u{1f600}', &chars, &scans), None);
+        assert_eq!(resolve('\u{3042}', &chars, &scans), None);
+    }
+}
@@ -0,0 +1,122 @@
+//! Paste key sequences
+//!
+//! Parsed from strings such as "Ctrl+V", "Ctrl+Shift+V" or "Shift+Insert".
+
+use std::fmt;
+
+/// Modifier held while the key is pressed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Modifier {
+    Ctrl,
+    Shift,
+    Alt,
+    /// Cmd on macOS, Windows/Super key elsewhere
+    Meta,
+}
+
+/// Key pressed once the modifiers are down
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    /// Character key, resolved against the active keyboard layout
+    Char(char),
+    Insert,
+    /// Enter/Return, to submit after pasting
+    Enter,
+    /// Deletes the pasted text on undo
+    Backspace,
+}
+
+/// Key combination simulated to paste
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasteKeys {
+    pub modifiers: Vec<Modifier>,
+    pub key: Key,
+}
+
+impl PasteKeys {
+    /// Cmd+V on macOS, Ctrl+V elsewhere
+    pub fn platform_default() -> Self {
+        let modifier = if cfg!(target_os = "macos") {
+            Modifier::Meta
+        } else {
+            Modifier::Ctrl
+        };
+        Self {
+            modifiers: vec![modifier],
+            key: Key::Char('v'),
+        }
+    }
+
+    /// Plain Enter, sent after the paste to submit a message
+    pub fn submit() -> Self {
+        Self {
+            modifiers: Vec::new(),
+            key: Key::Enter,
+        }
+    }
+
+    /// Plain Backspace, repeated to undo a paste
+    pub fn backspace() -> Self {
+        Self {
+            modifiers: Vec::new(),
+            key: Key::Backspace,
+        }
+    }
+
+    /// Parse a "Modifier+...+Key" sequence (case-insensitive)
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = value.split('+').map(str::trim).collect();
+        let (key, modifiers) = parts
+            .split_last()
+            .filter(|(key, _)| !key.is_empty())
+            .ok_or_else(|| "missing key".to_string())?;
+
+        let modifiers = modifiers
+            .iter()
+            .map(|m| match m.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => Ok(Modifier::Ctrl),
+                "shift" => Ok(Modifier::Shift),
+                "alt" | "option" => Ok(Modifier::Alt),
+                "cmd" | "command" | "meta" | "super" | "win" => Ok(Modifier::Meta),
+                "cmdorctrl" => Ok(Self::platform_default().modifiers[0]),
+                _ => Err(format!("unknown modifier \"{}\"", m)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let key = match key.to_ascii_lowercase().as_str() {
+            "insert" | "ins" => Key::Insert,
+            "enter" | "return" => Key::Enter,
+            "backspace" => Key::Backspace,
+            k => {
+                let mut chars = k.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if !c.is_whitespace() => Key::Char(c),
+                    _ => return Err(format!("unknown key \"{}\"", key)),
+                }
+            }
+        };
+
+        Ok(Self { modifiers, key })
+    }
+}
+
+impl fmt::Display for PasteKeys {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for modifier in &self.modifiers {
+            let name = match modifier {
+                Modifier::Ctrl => "Ctrl",
+                Modifier::Shift => "Shift",
+                Modifier::Alt => "Alt",
+                Modifier::Meta if cfg!(target_os = "macos") => "Cmd",
+                Modifier::Meta => "Super",
+            };
+            write!(f, "{}+", name)?;
+        }
+        match self.key {
+            Key::Char(c) => write!(f, "{}", c.to_ascii_uppercase()),
+            Key::Insert => write!(f, "Insert"),
+            Key::Enter => write!(f, "Enter"),
+            Key::Backspace => write!(f, "Backspace"),
+        }
+    }
+}
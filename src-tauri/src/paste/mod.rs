@@ -0,0 +1,30 @@
+//! Paste module
+//!
+//! Simulates the paste shortcut in the frontmost application once the
+//! transcript is in the clipboard, and fills the secondary clipboard. In
+//! live mode, types the transcript while it is being revised instead.
+//! Wayland sessions go through their own tools (wtype/ydotool, wl-copy).
+
+mod clipboard;
+mod continuation;
+mod elevation;
+mod frontmost;
+mod inject;
+mod keys;
+mod permissions;
+mod rewrite;
+mod secondary;
+mod target;
+mod wayland;
+
+pub use clipboard::read_clipboard_audio;
+pub use continuation::{continue_after, PastedText, CONTEXT_CHARS};
+pub use elevation::foreground_is_elevated;
+pub use frontmost::{frontmost_app, is_own_app};
+pub use inject::{key_injection_available, send_keys, send_keys_times, type_text};
+pub use keys::PasteKeys;
+pub use rewrite::LiveTyper;
+pub use permissions::{check_paste_permissions, open_permission_settings, PastePermissions, PermissionPane};
+pub use secondary::copy_to_secondary;
+pub use target::TargetWindow;
+pub use wayland::{copy as wl_copy, has_clipboard_tool as has_wl_copy, is_wayland_session};
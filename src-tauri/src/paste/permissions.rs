@@ -0,0 +1,94 @@
+//! Paste permissions probe (macOS)
+//!
+//! Simulating the paste goes through System Events, which needs the
+//! Automation permission, and the Accessibility permission for the
+//! keystroke itself. Without them the paste silently does nothing, so the
+//! UI checks them up front and links to the right System Settings pane.
+
+/// State of the permissions needed to paste
+#[derive(Debug, Clone, Copy, serde::Serialize)]
+pub struct PastePermissions {
+    /// Accessibility (keystroke injection)
+    pub accessibility: bool,
+    /// Automation of System Events
+    pub automation: bool,
+}
+
+/// System Settings pane to open
+#[derive(Debug, Clone, Copy, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionPane {
+    Accessibility,
+    Automation,
+}
+
+#[cfg(target_os = "macos")]
+#[link(name = "ApplicationServices", kind = "framework")]
+extern "C" {
+    fn AXIsProcessTrusted() -> bool;
+}
+
+/// AppleScript error when the user denied (or has not yet allowed) automation
+#[cfg(target_os = "macos")]
+const NOT_AUTHORIZED_ERROR: &str = "-1743";
+
+/// Check the paste permissions, without triggering a prompt for Accessibility
+#[cfg(target_os = "macos")]
+pub fn check_paste_permissions() -> PastePermissions {
+    let accessibility = unsafe { AXIsProcessTrusted() };
+
+    // A harmless System Events query: fails with -1743 when not authorized.
+    // On first run this also shows the consent dialog.
+    let automation = match std::process::Command::new("osascript")
+        .arg("-e")
+        .arg("tell application \"System Events\" to return name of first process")
+        .output()
+    {
+        Ok(output) if output.status.success() => true,
+        Ok(output) => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            tracing::warn!("System Events automation check failed: {}", stderr.trim());
+            !stderr.contains(NOT_AUTHORIZED_ERROR)
+        }
+        Err(e) => {
+            tracing::warn!("osascript launch error: {}", e);
+            false
+        }
+    };
+
+    PastePermissions {
+        accessibility,
+        automation,
+    }
+}
+
+/// Nothing to grant outside macOS
+#[cfg(not(target_os = "macos"))]
+pub fn check_paste_permissions() -> PastePermissions {
+    PastePermissions {
+        accessibility: true,
+        automation: true,
+    }
+}
+
+/// Open the System Settings pane where `pane` is granted
+#[cfg(target_os = "macos")]
+pub fn open_permission_settings(pane: PermissionPane) -> Result<(), String> {
+    let anchor = match pane {
+        PermissionPane::Accessibility => "Privacy_Accessibility",
+        PermissionPane::Automation => "Privacy_Automation",
+    };
+    std::process::Command::new("open")
+        .arg(format!(
+            "x-apple.systempreferences:com.apple.preference.security?{}",
+            anchor
+        ))
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Cannot open System Settings: {}", e))
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn open_permission_settings(_pane: PermissionPane) -> Result<(), String> {
+    Err("No permission settings on this platform".to_string())
+}
@@ -0,0 +1,89 @@
+//! Rewrite-in-place of a live-typed transcript
+//!
+//! With live paste, the hypothesis is typed into the target app while the
+//! user speaks. Each revision only erases and retypes the part that changed
+//! since the last one, and revisions are throttled so that a fast stream of
+//! partials does not turn into a storm of keystrokes.
+
+use super::inject::{send_keys_times, type_text};
+use super::keys::PasteKeys;
+use std::time::{Duration, Instant};
+
+/// Keystrokes turning the typed text into a new hypothesis
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Correction {
+    /// Characters erased from the end of the typed text
+    pub backspaces: usize,
+    /// Text typed after erasing
+    pub insert: String,
+}
+
+impl Correction {
+    /// Keep the common prefix of `typed` and `target`, retype the rest
+    pub fn between(typed: &str, target: &str) -> Self {
+        let common = typed
+            .char_indices()
+            .zip(target.chars())
+            .find(|((_, a), b)| a != b)
+            .map(|((i, _), _)| i)
+            .unwrap_or_else(|| typed.len().min(target.len()));
+        // `common` is a char boundary of `typed`, and of `target` as the prefixes are equal
+        Self {
+            backspaces: typed[common..].chars().count(),
+            insert: target[common..].to_string(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.backspaces == 0 && self.insert.is_empty()
+    }
+}
+
+/// Text typed so far in the target app during a live dictation
+#[derive(Debug)]
+pub struct LiveTyper {
+    typed: String,
+    min_interval: Duration,
+    last_update: Option<Instant>,
+}
+
+impl LiveTyper {
+    pub fn new(min_interval_ms: u64) -> Self {
+        Self {
+            typed: String::new(),
+            min_interval: Duration::from_millis(min_interval_ms),
+            last_update: None,
+        }
+    }
+
+    /// Text currently in the target app
+    pub fn typed(&self) -> &str {
+        &self.typed
+    }
+
+    /// Bring the typed text to `target`, unless the last revision is too
+    /// recent and `force` is not set (partials; finals always go through)
+    pub fn revise(&mut self, target: &str, force: bool) -> Result<(), String> {
+        let throttled = self.last_update.is_some_and(|at| at.elapsed() < self.min_interval);
+        if throttled && !force {
+            return Ok(());
+        }
+
+        let correction = Correction::between(&self.typed, target);
+        if correction.is_empty() {
+            return Ok(());
+        }
+        self.last_update = Some(Instant::now());
+
+        if correction.backspaces > 0 {
+            send_keys_times(&PasteKeys::backspace(), correction.backspaces)?;
+            let kept = self.typed.chars().count() - correction.backspaces;
+            self.typed = self.typed.chars().take(kept).collect();
+        }
+        if !correction.insert.is_empty() {
+            type_text(&correction.insert)?;
+            self.typed.push_str(&correction.insert);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+//! Secondary clipboard buffer
+//!
+//! Keeps the raw transcript retrievable next to the processed text that
+//! goes to the regular clipboard: the primary selection on Linux
+//! (middle-click), the find pasteboard on macOS.
+
+/// Put `text` in the secondary buffer of the platform
+#[cfg(target_os = "linux")]
+pub fn copy_to_secondary(text: &str) -> Result<(), String> {
+    use arboard::{Clipboard, LinuxClipboardKind, SetExtLinux};
+
+    if super::wayland::is_wayland_session() && super::wayland::has_clipboard_tool() {
+        return super::wayland::copy(text, true);
+    }
+    let text = text.to_string();
+    // The selection is served as long as we own it: wait in a thread until
+    // another app takes it over
+    std::thread::spawn(move || {
+        let result = Clipboard::new().and_then(|mut clipboard| {
+            clipboard
+                .set()
+                .clipboard(LinuxClipboardKind::Primary)
+                .wait()
+                .text(text)
+        });
+        if let Err(e) = result {
+            tracing::error!("Primary selection error: {}", e);
+        }
+    });
+    Ok(())
+}
+
+/// Put `text` in the secondary buffer of the platform
+#[cfg(target_os = "macos")]
+pub fn copy_to_secondary(text: &str) -> Result<(), String> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new("pbcopy")
+        .args(["-pboard", "find"])
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("pbcopy launch error: {}", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .map_err(|e| format!("pbcopy write error: {}", e))?;
+    }
+    let status = child.wait().map_err(|e| format!("pbcopy error: {}", e))?;
+    if !status.success() {
+        return Err(format!("pbcopy exited with {}", status));
+    }
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn copy_to_secondary(_text: &str) -> Result<(), String> {
+    Err("No secondary clipboard on this platform".to_string())
+}
@@ -0,0 +1,148 @@
+//! Dictation target lock
+//!
+//! The window in front when the lock is taken receives every dictation
+//! until the lock is released: it is brought back to the front before the
+//! paste, wherever the focus went in the meantime.
+
+use super::frontmost::frontmost_app;
+
+/// Window the dictations are pasted into
+#[derive(Debug, Clone)]
+pub struct TargetWindow {
+    /// Platform window id: process id on macOS, HWND on Windows, X11 window id on Linux
+    id: u64,
+    /// Window title when locked, to raise the right window of the app (macOS)
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    title: String,
+    /// Name of its app, as returned by `frontmost_app`
+    pub app_name: Option<String>,
+}
+
+impl TargetWindow {
+    /// The frontmost window, if the platform can refocus it later
+    pub fn frontmost() -> Option<Self> {
+        let (id, title) = frontmost_window()?;
+        Some(Self {
+            id,
+            title,
+            app_name: frontmost_app(),
+        })
+    }
+
+    /// Bring the window back to the front, failing when it is gone
+    pub fn focus(&self) -> Result<(), String> {
+        focus_window(self)
+    }
+}
+
+/// Process id and window title of the frontmost app
+#[cfg(target_os = "macos")]
+fn frontmost_window() -> Option<(u64, String)> {
+    let script = "tell application \"System Events\" to tell (first application process whose frontmost is true)\n\
+        set windowTitle to \"\"\n\
+        try\n\
+        set windowTitle to name of front window\n\
+        end try\n\
+        return (unix id as text) & linefeed & windowTitle\n\
+        end tell";
+    let output = std::process::Command::new("osascript").arg("-e").arg(script).output().ok()?;
+    if !output.status.success() {
+        tracing::debug!("osascript error: {}", String::from_utf8_lossy(&output.stderr));
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    let pid = lines.next()?.trim().parse().ok()?;
+    Some((pid, lines.next().unwrap_or_default().trim().to_string()))
+}
+
+/// Activate the process, then raise the locked window
+#[cfg(target_os = "macos")]
+fn focus_window(target: &TargetWindow) -> Result<(), String> {
+    let escaped = target.title.replace('\\', "\\\\").replace('"', "\\\"");
+    let script = format!(
+        "tell application \"System Events\" to tell (first application process whose unix id is {})\n\
+        set frontmost to true\n\
+        try\n\
+        perform action \"AXRaise\" of (first window whose name is \"{}\")\n\
+        end try\n\
+        end tell",
+        target.id, escaped
+    );
+    let output = std::process::Command::new("osascript")
+        .arg("-e")
+        .arg(script)
+        .output()
+        .map_err(|e| format!("osascript launch error: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("osascript error: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(())
+}
+
+/// Handle of the foreground window
+#[cfg(target_os = "windows")]
+fn frontmost_window() -> Option<(u64, String)> {
+    use windows_sys::Win32::UI::WindowsAndMessaging::GetForegroundWindow;
+
+    // SAFETY: plain Win32 call
+    let hwnd = unsafe { GetForegroundWindow() };
+    (!hwnd.is_null()).then(|| (hwnd as usize as u64, String::new()))
+}
+
+#[cfg(target_os = "windows")]
+fn focus_window(target: &TargetWindow) -> Result<(), String> {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{IsIconic, IsWindow, SetForegroundWindow, ShowWindow, SW_RESTORE};
+
+    let hwnd = target.id as usize as windows_sys::Win32::Foundation::HWND;
+    // SAFETY: plain Win32 calls, IsWindow tells whether the handle is still valid
+    unsafe {
+        if IsWindow(hwnd) == 0 {
+            return Err("The locked window was closed".to_string());
+        }
+        if IsIconic(hwnd) != 0 {
+            ShowWindow(hwnd, SW_RESTORE);
+        }
+        if SetForegroundWindow(hwnd) == 0 {
+            return Err("The locked window could not be brought to the front".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// X11 id of the active window (xdotool)
+#[cfg(target_os = "linux")]
+fn frontmost_window() -> Option<(u64, String)> {
+    // Wayland does not let a client pick the focused window
+    if super::wayland::is_wayland_session() {
+        return None;
+    }
+    let output = std::process::Command::new("xdotool").arg("getactivewindow").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let id = String::from_utf8_lossy(&output.stdout).trim().parse().ok()?;
+    Some((id, String::new()))
+}
+
+#[cfg(target_os = "linux")]
+fn focus_window(target: &TargetWindow) -> Result<(), String> {
+    let output = std::process::Command::new("xdotool")
+        .args(["windowactivate", "--sync", &target.id.to_string()])
+        .output()
+        .map_err(|e| format!("xdotool launch error: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("xdotool error: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+fn frontmost_window() -> Option<(u64, String)> {
+    None
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+fn focus_window(_target: &TargetWindow) -> Result<(), String> {
+    Err("Window focus is not supported on this platform".to_string())
+}
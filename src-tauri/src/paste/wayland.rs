@@ -0,0 +1,180 @@
+//! Wayland input and clipboard
+//!
+//! xdotool only reaches X11 (and XWayland) windows. In a Wayland session,
+//! keys are simulated with wtype (virtual keyboard protocol: wlroots, KDE)
+//! or ydotool (uinput: any compositor, needs ydotoold running), and the
+//! clipboard is set with wl-copy.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Whether the desktop session runs on Wayland
+#[cfg(target_os = "linux")]
+pub fn is_wayland_session() -> bool {
+    std::env::var("XDG_SESSION_TYPE").is_ok_and(|session| session.eq_ignore_ascii_case("wayland"))
+        || std::env::var_os("WAYLAND_DISPLAY").is_some_and(|display| !display.is_empty())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn is_wayland_session() -> bool {
+    false
+}
+
+/// `program` is an executable of the PATH
+fn on_path(program: &str) -> bool {
+    std::env::var_os("PATH").is_some_and(|paths| std::env::split_paths(&paths).any(|dir| dir.join(program).is_file()))
+}
+
+/// Key simulation tools installed, in order of preference
+fn key_tools() -> Vec<&'static str> {
+    ["wtype", "ydotool"].into_iter().filter(|tool| on_path(tool)).collect()
+}
+
+/// Whether wtype or ydotool is installed
+pub fn has_key_tool() -> bool {
+    !key_tools().is_empty()
+}
+
+/// Whether wl-copy is installed
+pub fn has_clipboard_tool() -> bool {
+    on_path("wl-copy")
+}
+
+/// Run the first tool that succeeds, `args` giving its arguments (`None` = cannot do it)
+#[cfg(target_os = "linux")]
+fn run_key_tool<F>(args: F) -> Result<(), String>
+where
+    F: Fn(&str) -> Option<Vec<String>>,
+{
+    let mut error = "Neither wtype nor ydotool is installed".to_string();
+    for tool in key_tools() {
+        let Some(args) = args(tool) else {
+            continue;
+        };
+        match Command::new(tool).args(&args).output() {
+            Ok(output) if output.status.success() => return Ok(()),
+            // wtype fails on compositors without the virtual keyboard protocol (GNOME)
+            Ok(output) => error = format!("{} error: {}", tool, String::from_utf8_lossy(&output.stderr).trim()),
+            Err(e) => error = format!("{} launch error: {}", tool, e),
+        }
+        tracing::warn!("{}", error);
+    }
+    Err(error)
+}
+
+/// Linux input event code of a key, at its US QWERTY position (ydotool)
+#[cfg(target_os = "linux")]
+fn key_code(key: super::keys::Key) -> Option<u16> {
+    use super::keys::Key;
+
+    const TOP_ROW: &str = "qwertyuiop";
+    const HOME_ROW: &str = "asdfghjkl";
+    const BOTTOM_ROW: &str = "zxcvbnm";
+    let code = match key {
+        Key::Char(c) => {
+            let c = c.to_ascii_lowercase();
+            [(TOP_ROW, 16), (HOME_ROW, 30), (BOTTOM_ROW, 44)]
+                .iter()
+                .find_map(|(row, first)| row.find(c).map(|i| first + i as u16))?
+        }
+        Key::Insert => 110,
+        Key::Enter => 28,
+        Key::Backspace => 14,
+    };
+    Some(code)
+}
+
+/// Simulate the key sequence via wtype, or ydotool for letters and special keys
+#[cfg(target_os = "linux")]
+pub fn send_keys_times(keys: &super::keys::PasteKeys, count: usize) -> Result<(), String> {
+    use super::keys::{Key, Modifier};
+
+    run_key_tool(|tool| match tool {
+        "wtype" => {
+            let mut args = Vec::new();
+            for modifier in &keys.modifiers {
+                args.push("-M".to_string());
+                args.push(wtype_modifier(*modifier).to_string());
+            }
+            for _ in 0..count {
+                match keys.key {
+                    // Typed with the modifiers held, from wtype's own keymap
+                    Key::Char(c) => args.push(c.to_string()),
+                    Key::Insert => args.extend(["-k".to_string(), "Insert".to_string()]),
+                    Key::Enter => args.extend(["-k".to_string(), "Return".to_string()]),
+                    Key::Backspace => args.extend(["-k".to_string(), "BackSpace".to_string()]),
+                }
+            }
+            for modifier in keys.modifiers.iter().rev() {
+                args.push("-m".to_string());
+                args.push(wtype_modifier(*modifier).to_string());
+            }
+            Some(args)
+        }
+        _ => {
+            let modifiers: Vec<u16> = keys
+                .modifiers
+                .iter()
+                .map(|m| match m {
+                    Modifier::Ctrl => 29,
+                    Modifier::Shift => 42,
+                    Modifier::Alt => 56,
+                    Modifier::Meta => 125,
+                })
+                .collect();
+            let key = key_code(keys.key)?;
+            let mut args = vec!["key".to_string()];
+            args.extend(modifiers.iter().map(|code| format!("{}:1", code)));
+            for _ in 0..count {
+                args.push(format!("{}:1", key));
+                args.push(format!("{}:0", key));
+            }
+            args.extend(modifiers.iter().rev().map(|code| format!("{}:0", code)));
+            Some(args)
+        }
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn wtype_modifier(modifier: super::keys::Modifier) -> &'static str {
+    use super::keys::Modifier;
+
+    match modifier {
+        Modifier::Ctrl => "ctrl",
+        Modifier::Shift => "shift",
+        Modifier::Alt => "alt",
+        Modifier::Meta => "logo",
+    }
+}
+
+/// Type `text` via wtype or ydotool
+#[cfg(target_os = "linux")]
+pub fn type_text(text: &str) -> Result<(), String> {
+    run_key_tool(|tool| match tool {
+        "wtype" => Some(vec!["--".to_string(), text.to_string()]),
+        _ => Some(vec!["type".to_string(), "--".to_string(), text.to_string()]),
+    })
+}
+
+/// Put `text` in the clipboard (or the primary selection) via wl-copy
+pub fn copy(text: &str, primary: bool) -> Result<(), String> {
+    let mut command = Command::new("wl-copy");
+    if primary {
+        command.arg("--primary");
+    }
+    // wl-copy serves the selection from a background process and returns
+    let mut child = command
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("wl-copy launch error: {}", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .map_err(|e| format!("wl-copy write error: {}", e))?;
+    }
+    let status = child.wait().map_err(|e| format!("wl-copy error: {}", e))?;
+    if !status.success() {
+        return Err(format!("wl-copy exited with {}", status));
+    }
+    Ok(())
+}
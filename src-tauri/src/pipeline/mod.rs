@@ -1,7 +1,13 @@
 //! Real-time processing pipeline module
 
 #[allow(dead_code)]
+mod queue;
 mod realtime;
+mod resources;
+mod watchdog;
 
 #[allow(unused_imports)]
 pub use realtime::{PipelineConfig, PipelineError, PipelineStatus, RealtimePipeline};
+pub use queue::{OutputQueue, OutputTicket, StopGuard};
+pub use resources::{Resource, ResourceGuard, ResourceUsage};
+pub use watchdog::{StuckDetector, WATCHDOG_INTERVAL};
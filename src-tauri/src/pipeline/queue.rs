@@ -0,0 +1,89 @@
+//! Ordered delivery of finished dictations
+//!
+//! A new dictation can start as soon as the previous one is transcribed,
+//! while that one is still being reformulated or translated. Each stopped
+//! dictation takes a ticket, and pastes wait for their turn so the texts
+//! land in the order they were spoken, whatever the processing times.
+
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::watch;
+
+/// Queue of the dictations waiting for their output
+pub struct OutputQueue {
+    next_ticket: AtomicU64,
+    /// Ticket allowed to deliver its output
+    serving: watch::Sender<u64>,
+    /// Tickets done before their turn (errors, empty dictations)
+    done_early: Mutex<BTreeSet<u64>>,
+}
+
+impl OutputQueue {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self {
+            next_ticket: AtomicU64::new(0),
+            serving: watch::Sender::new(0),
+            done_early: Mutex::new(BTreeSet::new()),
+        })
+    }
+
+    /// Take the next place in the queue (call in stop order)
+    pub fn ticket(self: &Arc<Self>) -> OutputTicket {
+        OutputTicket {
+            number: self.next_ticket.fetch_add(1, Ordering::SeqCst),
+            queue: Arc::clone(self),
+        }
+    }
+
+    /// Mark `number` as done and move on to the next ticket still pending
+    fn release(&self, number: u64) {
+        let mut done = self.done_early.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        done.insert(number);
+        self.serving.send_modify(|serving| {
+            while done.remove(serving) {
+                *serving += 1;
+            }
+        });
+    }
+}
+
+/// Place of a dictation in the output queue, released when dropped
+pub struct OutputTicket {
+    number: u64,
+    queue: Arc<OutputQueue>,
+}
+
+impl OutputTicket {
+    /// Wait until every dictation stopped before this one is delivered
+    pub async fn wait_turn(&self) {
+        let mut serving = self.queue.serving.subscribe();
+        if *serving.borrow() < self.number {
+            tracing::info!("Dictation output queued behind {} other(s)", self.number - *serving.borrow());
+        }
+        let _ = serving.wait_for(|serving| *serving >= self.number).await;
+    }
+}
+
+impl Drop for OutputTicket {
+    fn drop(&mut self) {
+        self.queue.release(self.number);
+    }
+}
+
+/// Held while a dictation is being stopped, released once its audio is
+/// transcribed so the next dictation can start
+pub struct StopGuard(Arc<AtomicBool>);
+
+impl StopGuard {
+    /// `None` if a stop is already in progress
+    pub fn acquire(stopping: &Arc<AtomicBool>) -> Option<Self> {
+        (!stopping.swap(true, Ordering::SeqCst)).then(|| Self(Arc::clone(stopping)))
+    }
+}
+
+impl Drop for StopGuard {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
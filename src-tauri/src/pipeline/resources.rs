@@ -0,0 +1,64 @@
+//! Accounting of the per-dictation resources
+//!
+//! Capture threads, session tasks and timers must all be gone once a
+//! dictation ends, so the idle app keeps no audio device open and never
+//! wakes up on its own. Each task and timer holds a guard while alive;
+//! get_resource_usage reports the counts to check it.
+
+use crate::audio::AudioHandle;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static SESSION_TASKS: AtomicUsize = AtomicUsize::new(0);
+static TIMERS: AtomicUsize = AtomicUsize::new(0);
+
+/// Kind of resource counted by a guard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resource {
+    /// Task living for a dictation (event consumer, audio forwarding...)
+    SessionTask,
+    /// Periodic wake-up (interval, polling sleep)
+    Timer,
+}
+
+/// Counts a resource as alive until dropped
+pub struct ResourceGuard(&'static AtomicUsize);
+
+impl ResourceGuard {
+    pub fn acquire(resource: Resource) -> Self {
+        let counter = match resource {
+            Resource::SessionTask => &SESSION_TASKS,
+            Resource::Timer => &TIMERS,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        Self(counter)
+    }
+}
+
+impl Drop for ResourceGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Result of the get_resource_usage command, all zero when idle
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+pub struct ResourceUsage {
+    /// Capture threads holding an audio device or socket
+    pub audio_streams: usize,
+    pub session_tasks: usize,
+    pub timers: usize,
+}
+
+impl ResourceUsage {
+    pub fn current() -> Self {
+        Self {
+            audio_streams: AudioHandle::open_streams(),
+            session_tasks: SESSION_TASKS.load(Ordering::Relaxed),
+            timers: TIMERS.load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.audio_streams == 0 && self.session_tasks == 0 && self.timers == 0
+    }
+}
@@ -0,0 +1,34 @@
+//! Stuck session detection
+//!
+//! A transcription thread that dies or hangs can leave a dictation in
+//! Transcribing/Processing forever, and every later shortcut press is then
+//! ignored. The watchdog samples the status periodically and reports a
+//! session busy for longer than the timeout, so it can be force-reset.
+
+use std::time::{Duration, Instant};
+
+/// Interval between two status samples
+pub const WATCHDOG_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Tracks how long the current session has been busy
+#[derive(Debug, Default)]
+pub struct StuckDetector {
+    busy_since: Option<Instant>,
+}
+
+impl StuckDetector {
+    /// Record a status sample, true once the session has been busy for `timeout`
+    pub fn observe(&mut self, busy: bool, timeout: Duration) -> bool {
+        if !busy {
+            self.busy_since = None;
+            return false;
+        }
+        let since = *self.busy_since.get_or_insert_with(Instant::now);
+        if since.elapsed() < timeout {
+            return false;
+        }
+        // Start over, the session is about to be reset
+        self.busy_since = None;
+        true
+    }
+}
@@ -0,0 +1,88 @@
+//! Lightweight language identification
+//!
+//! Counts the most frequent function words of each supported language. Good
+//! enough to tell whether a paragraph is in the expected language, without
+//! shipping a model; short or mixed texts are left undecided.
+
+/// Function words of each language, none shared with another list
+const STOPWORDS: &[(&str, &[&str])] = &[
+    (
+        "fr",
+        &[
+            "le", "les", "des", "est", "une", "et", "je", "vous", "nous", "pas", "pour", "dans",
+            "qui", "avec", "sur", "au", "ce", "mais", "sont", "cette",
+        ],
+    ),
+    (
+        "en",
+        &[
+            "the", "and", "is", "are", "you", "that", "this", "with", "for", "not", "have", "it",
+            "of", "to", "was", "we", "be", "will", "can", "they",
+        ],
+    ),
+    (
+        "es",
+        &[
+            "el", "los", "las", "es", "y", "por", "con", "del", "pero", "muy", "como", "yo",
+            "este", "esta", "son", "hay", "porque", "también", "usted", "se",
+        ],
+    ),
+    (
+        "de",
+        &[
+            "der", "die", "das", "und", "ist", "ich", "nicht", "mit", "sie", "ein", "eine", "zu",
+            "auf", "wir", "auch", "sind", "den", "dem", "von", "aber",
+        ],
+    ),
+    (
+        "it",
+        &[
+            "il", "gli", "di", "che", "è", "non", "per", "sono", "della", "ho", "anche", "questo",
+            "molto", "ma", "nel", "alla", "ci", "io", "lo", "perché",
+        ],
+    ),
+    (
+        "pt",
+        &[
+            "o", "os", "da", "do", "não", "que", "uma", "para", "com", "é", "eu", "mas", "muito",
+            "está", "você", "isso", "ao", "nos", "em", "um",
+        ],
+    ),
+];
+
+/// Minimum function words counted before deciding
+const MIN_HITS: usize = 4;
+
+/// Languages [`detect_language`] can recognize
+pub fn is_detectable(code: &str) -> bool {
+    STOPWORDS.iter().any(|(lang, _)| *lang == code)
+}
+
+/// Most likely language of `text`, if clear enough
+///
+/// The winner needs `MIN_HITS` function words and twice as many as the
+/// runner-up.
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .flat_map(|word| word.rsplit('\''))
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+
+    let mut scores: Vec<(&'static str, usize)> = STOPWORDS
+        .iter()
+        .map(|(lang, stopwords)| {
+            let hits = words
+                .iter()
+                .filter(|word| stopwords.contains(&word.as_str()))
+                .count();
+            (*lang, hits)
+        })
+        .collect();
+    scores.sort_by(|a, b| b.1.cmp(&a.1));
+
+    let (best, hits) = scores[0];
+    let runner_up = scores[1].1;
+    (hits >= MIN_HITS && hits >= runner_up * 2).then_some(best)
+}
@@ -0,0 +1,108 @@
+//! Live translation of the dictation being spoken
+//!
+//! With a streaming engine, the transcript is translated while it is
+//! spoken: each final segment is translated once and kept, and only the
+//! segment still being revised is translated again, at most every
+//! `interval_ms`. The translation replaces the transcript on the overlay
+//! and in live paste, and the final paste is the translation of the whole
+//! dictation.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+/// Settings of the live translation
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LiveTranslation {
+    /// Target language per dictation profile ("dictation", "meeting"), e.g. "en"
+    #[serde(default)]
+    pub languages: BTreeMap<String, String>,
+    /// Time between two translations of the segment being spoken
+    #[serde(default = "default_interval_ms")]
+    pub interval_ms: u64,
+}
+
+fn default_interval_ms() -> u64 {
+    1500
+}
+
+impl Default for LiveTranslation {
+    fn default() -> Self {
+        Self {
+            languages: BTreeMap::new(),
+            interval_ms: default_interval_ms(),
+        }
+    }
+}
+
+impl LiveTranslation {
+    /// Language the dictations of `profile` are translated to, if any
+    pub fn target(&self, profile: &str) -> Option<&str> {
+        self.languages
+            .get(profile)
+            .map(String::as_str)
+            .filter(|language| !language.is_empty() && *language != "auto")
+    }
+}
+
+/// Translation of a dictation, kept up to date while it is spoken
+pub struct IncrementalTranslation {
+    interval: Duration,
+    /// Translations of the final segments, in order
+    committed: Vec<String>,
+    /// Segment being spoken, as last sent for translation
+    partial: String,
+    /// Translation of `partial`, once received
+    partial_translation: String,
+    last_request: Option<Instant>,
+}
+
+impl IncrementalTranslation {
+    pub fn new(interval_ms: u64) -> Self {
+        Self {
+            interval: Duration::from_millis(interval_ms),
+            committed: Vec::new(),
+            partial: String::new(),
+            partial_translation: String::new(),
+            last_request: None,
+        }
+    }
+
+    /// Whether the partial `text` is due for a translation, recording the request
+    pub fn wants_partial(&mut self, text: &str) -> bool {
+        if text.trim().is_empty() || text == self.partial {
+            return false;
+        }
+        if self.last_request.is_some_and(|last| last.elapsed() < self.interval) {
+            return false;
+        }
+        self.partial = text.to_string();
+        self.last_request = Some(Instant::now());
+        true
+    }
+
+    /// Translation of the partial `source`, ignored if a final segment replaced it
+    pub fn set_partial(&mut self, source: &str, translation: String) {
+        if source == self.partial {
+            self.partial_translation = translation;
+        }
+    }
+
+    /// Keep the translation of a final segment; the partial one is over
+    pub fn commit(&mut self, translation: String) {
+        if !translation.trim().is_empty() {
+            self.committed.push(translation.trim().to_string());
+        }
+        self.partial.clear();
+        self.partial_translation.clear();
+    }
+
+    /// Translation so far
+    pub fn text(&self) -> String {
+        self.committed
+            .iter()
+            .map(String::as_str)
+            .chain(Some(self.partial_translation.trim()).filter(|text| !text.is_empty()))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
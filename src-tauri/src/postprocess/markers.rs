@@ -0,0 +1,91 @@
+//! Spoken markers
+//!
+//! Phrases such as "action item:" or "note to self:" flag the rest of the
+//! sentence. Flagged sentences are collected per marker and appended to the
+//! transcript as separate lists.
+
+use crate::config::SpokenMarker;
+
+/// Items collected for one marker
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkerSection {
+    pub title: String,
+    pub items: Vec<String>,
+}
+
+/// Split the transcript into sentences (end punctuation and line breaks)
+fn segments(text: &str) -> impl Iterator<Item = &str> {
+    text.split_inclusive(['.', '!', '?', '\n'])
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+/// Byte offset right after `phrase` in `segment` (ASCII case-insensitive,
+/// starting on a word boundary)
+fn find_phrase(segment: &str, phrase: &str) -> Option<usize> {
+    segment.char_indices().find_map(|(i, _)| {
+        let at_boundary = segment[..i]
+            .chars()
+            .next_back()
+            .map_or(true, |c| !c.is_alphanumeric());
+        let matches = segment
+            .get(i..i + phrase.len())
+            .is_some_and(|s| s.eq_ignore_ascii_case(phrase));
+        (at_boundary && matches).then_some(i + phrase.len())
+    })
+}
+
+/// Collect the sentences flagged by each marker, in transcript order
+///
+/// Markers without any item are left out.
+pub fn extract_markers(text: &str, markers: &[SpokenMarker]) -> Vec<MarkerSection> {
+    let mut sections: Vec<MarkerSection> = markers
+        .iter()
+        .map(|marker| MarkerSection {
+            title: marker.title.clone(),
+            items: Vec::new(),
+        })
+        .collect();
+
+    for segment in segments(text) {
+        for (marker, section) in markers.iter().zip(sections.iter_mut()) {
+            let phrase = marker.phrase.trim().trim_end_matches(':').trim_end();
+            if phrase.is_empty() {
+                continue;
+            }
+            let Some(end) = find_phrase(segment, phrase) else {
+                continue;
+            };
+            let item = segment[end..]
+                .trim_start_matches(|c: char| c.is_whitespace() || matches!(c, ':' | ',' | ';' | '-'))
+                .trim_end_matches(['.', '!', '?'])
+                .trim();
+            if !item.is_empty() {
+                section.items.push(item.to_string());
+            }
+            break;
+        }
+    }
+
+    sections.retain(|section| !section.items.is_empty());
+    sections
+}
+
+/// Append each section to the transcript using `template`
+///
+/// `{title}` is replaced by the marker title and `{items}` by one
+/// "- item" line per flagged sentence.
+pub fn append_marker_sections(text: &str, sections: &[MarkerSection], template: &str) -> String {
+    let mut output = text.to_string();
+    for section in sections {
+        let items = section
+            .items
+            .iter()
+            .map(|item| format!("- {}", item))
+            .collect::<Vec<_>>()
+            .join("\n");
+        output.push_str("\n\n");
+        output.push_str(&template.replace("{title}", &section.title).replace("{items}", &items));
+    }
+    output
+}
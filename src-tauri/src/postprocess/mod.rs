@@ -0,0 +1,22 @@
+//! Transcript post-processing module
+//!
+//! Local text transformations applied to the transcript before it is
+//! pasted (per-language replacements and spoken commands, markers), on
+//! top of the optional chat reformulation (built-in, custom or preset
+//! prompt), the structured (JSON) output mode and the live translation.
+
+mod language_id;
+mod live_translation;
+mod markers;
+mod prompts;
+mod replacements;
+mod structured;
+
+pub use language_id::{detect_language, is_detectable};
+pub use live_translation::{IncrementalTranslation, LiveTranslation};
+pub use markers::{append_marker_sections, extract_markers};
+pub use prompts::ReformulationPrompts;
+pub use replacements::TextReplacements;
+pub use structured::{
+    extract_json_object, structured_prompt, validate_structured, FieldKind, SchemaField, StructuredOutput,
+};
@@ -0,0 +1,119 @@
+//! Reformulation prompts
+//!
+//! The chat reformulation uses a built-in prompt unless the user wrote
+//! their own, or picked one of their named presets ("email", "slack",
+//! "commit message"...). Translation instructions are added on top of
+//! whichever prompt is in use.
+
+/// Named reformulation prompt
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PromptPreset {
+    pub name: String,
+    /// System prompt sent to the chat model
+    pub prompt: String,
+}
+
+/// Settings of the reformulation prompt
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ReformulationPrompts {
+    /// Prompt replacing the built-in one (empty = built-in)
+    #[serde(default)]
+    pub custom_prompt: String,
+    #[serde(default = "default_presets")]
+    pub presets: Vec<PromptPreset>,
+    /// Name of the preset in use (empty = custom or built-in prompt)
+    #[serde(default)]
+    pub active_preset: String,
+}
+
+fn default_presets() -> Vec<PromptPreset> {
+    let preset = |name: &str, prompt: &str| PromptPreset {
+        name: name.to_string(),
+        prompt: prompt.to_string(),
+    };
+    vec![
+        preset(
+            "email",
+            "Rewrite the following spoken text as the body of a professional email. \
+            Fix grammar and punctuation, remove hesitations and filler words, split it into short paragraphs. \
+            Keep the meaning and the language of the text. Do not add a subject line or a signature. \
+            Output ONLY the email body.",
+        ),
+        preset(
+            "slack",
+            "Rewrite the following spoken text as a short, friendly chat message. \
+            Fix grammar and punctuation, remove hesitations and filler words, keep it casual and concise. \
+            Keep the meaning and the language of the text. Output ONLY the message.",
+        ),
+        preset(
+            "commit message",
+            "Turn the following spoken text into a git commit message: an imperative subject line \
+            of at most 72 characters, then a blank line and a short body if the text gives details. \
+            Write it in English and keep technical terms as they are. Output ONLY the commit message.",
+        ),
+    ]
+}
+
+impl Default for ReformulationPrompts {
+    fn default() -> Self {
+        Self {
+            custom_prompt: String::new(),
+            presets: default_presets(),
+            active_preset: String::new(),
+        }
+    }
+}
+
+/// Built-in reformulation prompt
+const BUILTIN_PROMPT: &str = "Reformulate the following spoken text into clean written text. \
+    Fix grammar, punctuation, remove hesitations, repetitions and filler words. \
+    Keep the meaning and tone. Preserve English words used intentionally \
+    (franglais, technical terms, dev/tech jargon like push, pull, merge, deploy, commit, build, etc.). \
+    Do not translate them. Output ONLY the reformulated text. \
+    Do NOT include any preamble, explanation or prefix.";
+
+impl ReformulationPrompts {
+    pub fn preset(&self, name: &str) -> Option<&PromptPreset> {
+        self.presets.iter().find(|preset| preset.name == name)
+    }
+
+    /// Prompt of the active preset, else the custom prompt, if any
+    fn user_prompt(&self) -> Option<&str> {
+        self.preset(&self.active_preset)
+            .map(|preset| preset.prompt.as_str())
+            .or(Some(self.custom_prompt.as_str()))
+            .map(str::trim)
+            .filter(|prompt| !prompt.is_empty())
+    }
+
+    /// System prompt reformulating and/or translating to `language` (English
+    /// name, `None` = keep the language)
+    ///
+    /// Returns `None` when there is nothing to do.
+    pub fn system_prompt(&self, reformulate: bool, language: Option<&str>) -> Option<String> {
+        let prompt = match (reformulate, language, self.user_prompt()) {
+            (false, None, _) => return None,
+            (false, Some(lang), _) => format!(
+                "Translate the following text to {}. Output ONLY the translated text. \
+                Do NOT include any preamble, explanation, label or prefix like \"Here's the translation\". Just the text.",
+                lang
+            ),
+            (true, None, Some(prompt)) => prompt.to_string(),
+            (true, None, None) => BUILTIN_PROMPT.to_string(),
+            (true, Some(lang), Some(prompt)) => format!(
+                "{}\nThen translate the result to {}. Output ONLY the final translated result in {}. \
+                Do NOT include any preamble, explanation, label or prefix. Just the text.",
+                prompt, lang, lang
+            ),
+            (true, Some(lang), None) => format!(
+                "Reformulate the following spoken text into clean written text, then translate it to {}. \
+                Fix grammar, punctuation, remove hesitations, repetitions and filler words. \
+                Keep the meaning and tone. Output ONLY the final translated result in {}. \
+                Do NOT include any preamble, explanation, label or prefix. \
+                Do NOT write \"Here's the translation\" or similar. Just the text.",
+                lang, lang
+            ),
+        };
+        Some(prompt)
+    }
+}
@@ -0,0 +1,167 @@
+//! Per-language replacements and spoken commands
+//!
+//! Each language has its own table: words fixed as written ("dictea" ->
+//! "Dictea") and spoken punctuation/layout commands ("virgule" in French,
+//! "comma" in English). The table of the dictation language is used first,
+//! the default table fills in the phrases it does not define.
+
+use std::collections::BTreeMap;
+
+/// Table used for the phrases missing from the language table
+pub const DEFAULT_TABLE: &str = "default";
+
+/// Replacements and spoken commands of one language
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ReplacementTable {
+    /// Words or phrases replaced as written (case-insensitive, whole words)
+    #[serde(default)]
+    pub replacements: BTreeMap<String, String>,
+    /// Spoken punctuation and layout, attached to the previous word
+    #[serde(default)]
+    pub commands: BTreeMap<String, String>,
+}
+
+/// Settings of the replacements applied to the transcript
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TextReplacements {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Tables keyed by language code ("fr", "en"...) or `DEFAULT_TABLE`
+    #[serde(default = "default_tables")]
+    pub tables: BTreeMap<String, ReplacementTable>,
+}
+
+fn default_tables() -> BTreeMap<String, ReplacementTable> {
+    let commands = |entries: &[(&str, &str)]| ReplacementTable {
+        replacements: BTreeMap::new(),
+        commands: entries.iter().map(|(phrase, text)| (phrase.to_string(), text.to_string())).collect(),
+    };
+    BTreeMap::from([
+        (DEFAULT_TABLE.to_string(), ReplacementTable::default()),
+        (
+            "fr".to_string(),
+            // French typography: a space before the two-part marks
+            commands(&[
+                ("virgule", ","),
+                ("point", "."),
+                ("point virgule", " ;"),
+                ("deux points", " :"),
+                ("point d'interrogation", " ?"),
+                ("point d'exclamation", " !"),
+                ("à la ligne", "\n"),
+                ("nouvelle ligne", "\n"),
+                ("nouveau paragraphe", "\n\n"),
+            ]),
+        ),
+        (
+            "en".to_string(),
+            commands(&[
+                ("comma", ","),
+                ("period", "."),
+                ("full stop", "."),
+                ("semicolon", ";"),
+                ("colon", ":"),
+                ("question mark", "?"),
+                ("exclamation mark", "!"),
+                ("exclamation point", "!"),
+                ("new line", "\n"),
+                ("new paragraph", "\n\n"),
+            ]),
+        ),
+    ])
+}
+
+impl Default for TextReplacements {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            tables: default_tables(),
+        }
+    }
+}
+
+/// Phrase to look for, lowercased
+struct Entry<'a> {
+    phrase: Vec<char>,
+    text: &'a str,
+    command: bool,
+}
+
+impl TextReplacements {
+    /// Apply the table of `language` (ISO 639-1 code, `None` = unknown) on
+    /// top of the default table
+    pub fn apply(&self, text: &str, language: Option<&str>) -> String {
+        if !self.enabled {
+            return text.to_string();
+        }
+        let tables: Vec<&ReplacementTable> = [language.unwrap_or(DEFAULT_TABLE), DEFAULT_TABLE]
+            .iter()
+            .filter_map(|code| self.tables.get(*code))
+            .collect();
+
+        let mut entries: Vec<Entry> = Vec::new();
+        for table in tables {
+            let phrases = table
+                .replacements
+                .iter()
+                .map(|(phrase, text)| (phrase, text, false))
+                .chain(table.commands.iter().map(|(phrase, text)| (phrase, text, true)));
+            for (phrase, text, command) in phrases {
+                let phrase: Vec<char> = phrase.trim().chars().map(fold).collect();
+                // The language table wins over the default one
+                if !phrase.is_empty() && !entries.iter().any(|entry| entry.phrase == phrase) {
+                    entries.push(Entry { phrase, text, command });
+                }
+            }
+        }
+        if entries.is_empty() {
+            return text.to_string();
+        }
+        // Longest first, so "point d'interrogation" is not read as "point"
+        entries.sort_by(|a, b| b.phrase.len().cmp(&a.phrase.len()));
+
+        let chars: Vec<char> = text.chars().collect();
+        let folded: Vec<char> = chars.iter().copied().map(fold).collect();
+        let mut output = String::with_capacity(text.len());
+        let mut i = 0;
+        while i < chars.len() {
+            let word_start = i == 0 || !folded[i - 1].is_alphanumeric();
+            let matched = word_start
+                .then(|| {
+                    entries.iter().find(|entry| {
+                        let end = i + entry.phrase.len();
+                        end <= folded.len()
+                            && folded[i..end] == entry.phrase[..]
+                            && folded.get(end).map_or(true, |c| !c.is_alphanumeric())
+                    })
+                })
+                .flatten();
+
+            let Some(entry) = matched else {
+                output.push(chars[i]);
+                i += 1;
+                continue;
+            };
+            if entry.command {
+                output.truncate(output.trim_end_matches([' ', '\t']).len());
+            }
+            output.push_str(entry.text);
+            i += entry.phrase.len();
+            // A new line does not start with the space that followed the command
+            if entry.command && entry.text.ends_with('\n') {
+                while i < chars.len() && matches!(chars[i], ' ' | '\t') {
+                    i += 1;
+                }
+            }
+        }
+        output
+    }
+}
+
+/// Lowercase, with typographic apostrophes read as plain ones
+fn fold(c: char) -> char {
+    match c {
+        '\u{2019}' | '\u{02bc}' => '\'',
+        c => c.to_lowercase().next().unwrap_or(c),
+    }
+}
@@ -0,0 +1,155 @@
+//! Structured JSON output
+//!
+//! The chat model is asked for a JSON object following a user-defined
+//! schema (e.g. `{title, body, tags}`). Replies are validated before being
+//! handed to webhooks and integrations; invalid ones are retried with the
+//! validation error as feedback.
+
+use serde_json::{Map, Value};
+
+/// Type of a schema field
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldKind {
+    #[default]
+    String,
+    StringList,
+    Number,
+    Boolean,
+}
+
+impl FieldKind {
+    fn json_type(&self) -> &'static str {
+        match self {
+            FieldKind::String => "string",
+            FieldKind::StringList => "array of strings",
+            FieldKind::Number => "number",
+            FieldKind::Boolean => "boolean",
+        }
+    }
+
+    fn accepts(&self, value: &Value) -> bool {
+        match self {
+            FieldKind::String => value.is_string(),
+            FieldKind::StringList => value
+                .as_array()
+                .is_some_and(|items| items.iter().all(Value::is_string)),
+            FieldKind::Number => value.is_number(),
+            FieldKind::Boolean => value.is_boolean(),
+        }
+    }
+}
+
+/// Field of the expected JSON object
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SchemaField {
+    pub name: String,
+    #[serde(default)]
+    pub kind: FieldKind,
+    /// What the field holds, given to the model
+    #[serde(default)]
+    pub description: String,
+    #[serde(default = "default_required")]
+    pub required: bool,
+}
+
+fn default_required() -> bool {
+    true
+}
+
+/// Settings of the structured output mode
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct StructuredOutput {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_schema")]
+    pub schema: Vec<SchemaField>,
+    /// Extra attempts after an invalid reply
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+}
+
+fn default_schema() -> Vec<SchemaField> {
+    let field = |name: &str, kind, description: &str, required| SchemaField {
+        name: name.to_string(),
+        kind,
+        description: description.to_string(),
+        required,
+    };
+    vec![
+        field("title", FieldKind::String, "short summary", true),
+        field("body", FieldKind::String, "full content, cleaned up", true),
+        field("tags", FieldKind::StringList, "a few keywords", false),
+    ]
+}
+
+fn default_max_retries() -> u32 {
+    2
+}
+
+impl Default for StructuredOutput {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            schema: default_schema(),
+            max_retries: default_max_retries(),
+        }
+    }
+}
+
+/// System prompt asking for a JSON object following `schema`
+pub fn structured_prompt(schema: &[SchemaField]) -> String {
+    let fields: Vec<String> = schema
+        .iter()
+        .map(|field| {
+            format!(
+                "- \"{}\" ({}{}){}",
+                field.name,
+                field.kind.json_type(),
+                if field.required { "" } else { ", optional" },
+                if field.description.is_empty() {
+                    String::new()
+                } else {
+                    format!(": {}", field.description)
+                }
+            )
+        })
+        .collect();
+    format!(
+        "Turn the following dictated text into a JSON object with these fields:\n{}\n\
+        Fix grammar and punctuation, remove hesitations and filler words, keep the language of the text. \
+        Reply with ONLY the JSON object, no code fence, no explanation.",
+        fields.join("\n")
+    )
+}
+
+/// JSON object of a chat model reply, without the code fence or the
+/// sentence models sometimes wrap it in despite the prompt
+pub fn extract_json_object(reply: &str) -> Option<&str> {
+    reply
+        .find('{')
+        .zip(reply.rfind('}'))
+        .and_then(|(start, end)| reply.get(start..=end))
+}
+
+/// Parse and check a model reply against `schema`
+pub fn validate_structured(reply: &str, schema: &[SchemaField]) -> Result<Value, String> {
+    let json = extract_json_object(reply).ok_or_else(|| "no JSON object in the reply".to_string())?;
+    let mut object: Map<String, Value> =
+        serde_json::from_str(json).map_err(|e| format!("invalid JSON: {}", e))?;
+
+    for field in schema {
+        match object.get(&field.name) {
+            None | Some(Value::Null) if field.required => {
+                return Err(format!("missing field \"{}\"", field.name));
+            }
+            Some(value) if !value.is_null() && !field.kind.accepts(value) => {
+                return Err(format!("field \"{}\" must be a {}", field.name, field.kind.json_type()));
+            }
+            _ => {}
+        }
+    }
+    // Only the declared fields are handed over
+    object.retain(|name, _| schema.iter().any(|field| &field.name == name));
+    Ok(Value::Object(object))
+}
@@ -0,0 +1,9 @@
+//! Recording retention module
+//!
+//! Saves the raw audio of each dictation to disk and plays it back on demand.
+
+mod player;
+mod store;
+
+pub use player::{PlaybackError, PlaybackHandle};
+pub use store::{RecordingInfo, RecordingStore, RecordingWriter, StorageUsage};
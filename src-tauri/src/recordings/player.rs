@@ -0,0 +1,104 @@
+//! Playback of saved recordings
+//!
+//! Uses rodio in a dedicated thread (the output stream is not Send).
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::mpsc;
+use std::thread::{self, JoinHandle};
+use thiserror::Error;
+
+/// Playback errors
+#[derive(Error, Debug)]
+pub enum PlaybackError {
+    #[error("Recording not found: {0}")]
+    NotFound(String),
+
+    #[error("Decode error: {0}")]
+    DecodeError(String),
+}
+
+/// Commands to control the playback thread
+enum PlaybackCommand {
+    Stop,
+}
+
+/// Handle to control the playback of a recording
+pub struct PlaybackHandle {
+    command_tx: mpsc::Sender<PlaybackCommand>,
+    thread_handle: Option<JoinHandle<()>>,
+}
+
+impl PlaybackHandle {
+    /// Start playing a WAV file in a dedicated thread
+    ///
+    /// `on_finished` is called when playback ends on its own (not when stopped).
+    pub fn start<F>(path: &Path, on_finished: F) -> Result<Self, PlaybackError>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let file = File::open(path)
+            .map_err(|_| PlaybackError::NotFound(path.display().to_string()))?;
+        let source = rodio::Decoder::new(BufReader::new(file))
+            .map_err(|e| PlaybackError::DecodeError(e.to_string()))?;
+
+        let (command_tx, command_rx) = mpsc::channel();
+
+        let thread_handle = thread::spawn(move || {
+            let (_stream, stream_handle) = match rodio::OutputStream::try_default() {
+                Ok(output) => output,
+                Err(e) => {
+                    tracing::error!("Audio output error: {}", e);
+                    return;
+                }
+            };
+            let sink = match rodio::Sink::try_new(&stream_handle) {
+                Ok(sink) => sink,
+                Err(e) => {
+                    tracing::error!("Audio sink error: {}", e);
+                    return;
+                }
+            };
+            sink.append(source);
+            tracing::info!("Playback started");
+
+            loop {
+                match command_rx.recv_timeout(std::time::Duration::from_millis(100)) {
+                    Ok(PlaybackCommand::Stop) | Err(mpsc::RecvTimeoutError::Disconnected) => {
+                        sink.stop();
+                        tracing::info!("Playback stopped");
+                        return;
+                    }
+                    Err(mpsc::RecvTimeoutError::Timeout) => {
+                        if sink.empty() {
+                            break;
+                        }
+                    }
+                }
+            }
+
+            tracing::info!("Playback finished");
+            on_finished();
+        });
+
+        Ok(Self {
+            command_tx,
+            thread_handle: Some(thread_handle),
+        })
+    }
+
+    /// Stop playback
+    pub fn stop(&mut self) {
+        let _ = self.command_tx.send(PlaybackCommand::Stop);
+        if let Some(handle) = self.thread_handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for PlaybackHandle {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
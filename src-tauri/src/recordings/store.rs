@@ -0,0 +1,197 @@
+//! On-disk storage of dictation recordings
+//!
+//! Each recording is a 16kHz mono 16-bit WAV file named after its id.
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Metadata about a saved recording
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RecordingInfo {
+    pub id: String,
+    /// Creation time (milliseconds since Unix epoch)
+    pub created_at: u64,
+    pub duration_secs: f32,
+    pub size_bytes: u64,
+}
+
+/// Disk usage of the saved recordings
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct StorageUsage {
+    pub total_bytes: u64,
+    pub count: usize,
+    /// Configured quota (0 = unlimited)
+    pub max_bytes: u64,
+}
+
+/// Directory holding the saved recordings
+#[derive(Debug, Clone)]
+pub struct RecordingStore {
+    dir: PathBuf,
+}
+
+impl RecordingStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Path of the WAV file for a recording id
+    pub fn path_for(&self, id: &str) -> Option<PathBuf> {
+        // Ids are generated by us, reject anything that could escape the directory
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return None;
+        }
+        Some(self.dir.join(format!("{}.wav", id)))
+    }
+
+    /// Start a new recording and return its writer
+    pub fn create(&self) -> Result<RecordingWriter, String> {
+        std::fs::create_dir_all(&self.dir).map_err(|e| format!("Recordings dir error: {}", e))?;
+
+        let created_at = now_millis();
+        let id = created_at.to_string();
+        let path = self.dir.join(format!("{}.wav", id));
+        RecordingWriter::create(id, &path)
+    }
+
+    /// List saved recordings, most recent first
+    pub fn list(&self) -> Vec<RecordingInfo> {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+
+        let mut recordings: Vec<RecordingInfo> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| Self::info_for(&entry.path()))
+            .collect();
+        recordings.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        recordings
+    }
+
+    /// Delete a recording
+    pub fn delete(&self, id: &str) -> Result<(), String> {
+        let path = self
+            .path_for(id)
+            .ok_or_else(|| format!("Invalid recording id: {}", id))?;
+        std::fs::remove_file(&path).map_err(|e| format!("Recording delete error: {}", e))
+    }
+
+    /// Total disk usage of the saved recordings
+    pub fn usage(&self, max_bytes: u64) -> StorageUsage {
+        let recordings = self.list();
+        StorageUsage {
+            total_bytes: recordings.iter().map(|r| r.size_bytes).sum(),
+            count: recordings.len(),
+            max_bytes,
+        }
+    }
+
+    /// Delete recordings older than `max_age_secs`, then the oldest ones
+    /// until the total size fits in `max_bytes` (0 disables either limit).
+    ///
+    /// Returns the number of deleted recordings.
+    pub fn enforce_retention(&self, max_bytes: u64, max_age_secs: u64) -> usize {
+        let now = now_millis();
+        let mut deleted = 0;
+        let mut kept = Vec::new();
+
+        for recording in self.list() {
+            let age_secs = now.saturating_sub(recording.created_at) / 1000;
+            if max_age_secs > 0 && age_secs > max_age_secs {
+                if self.delete(&recording.id).is_ok() {
+                    deleted += 1;
+                }
+            } else {
+                kept.push(recording);
+            }
+        }
+
+        if max_bytes > 0 {
+            // Most recent first: keep while the running total fits
+            let mut total = 0u64;
+            for recording in kept {
+                total += recording.size_bytes;
+                if total > max_bytes && self.delete(&recording.id).is_ok() {
+                    deleted += 1;
+                }
+            }
+        }
+
+        if deleted > 0 {
+            tracing::info!("Recording cleanup: {} file(s) deleted", deleted);
+        }
+        deleted
+    }
+
+    /// Read the metadata of a recording file
+    fn info_for(path: &Path) -> Option<RecordingInfo> {
+        if path.extension().and_then(|e| e.to_str()) != Some("wav") {
+            return None;
+        }
+        let id = path.file_stem()?.to_str()?.to_string();
+        let created_at = id.parse::<u64>().ok()?;
+        let size_bytes = std::fs::metadata(path).ok()?.len();
+        let duration_secs = hound::WavReader::open(path)
+            .map(|reader| reader.duration() as f32 / reader.spec().sample_rate as f32)
+            .unwrap_or(0.0);
+
+        Some(RecordingInfo {
+            id,
+            created_at,
+            duration_secs,
+            size_bytes,
+        })
+    }
+}
+
+/// Streams dictation audio to a WAV file as it is captured
+pub struct RecordingWriter {
+    id: String,
+    writer: hound::WavWriter<BufWriter<File>>,
+}
+
+impl RecordingWriter {
+    fn create(id: String, path: &Path) -> Result<Self, String> {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 16000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let writer = hound::WavWriter::create(path, spec)
+            .map_err(|e| format!("Recording create error: {}", e))?;
+        Ok(Self { id, writer })
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Append PCM float32 samples (mono, 16kHz)
+    pub fn write(&mut self, samples: &[f32]) {
+        for &sample in samples {
+            let sample_i16 = (sample * 32767.0).clamp(-32768.0, 32767.0) as i16;
+            if let Err(e) = self.writer.write_sample(sample_i16) {
+                tracing::error!("Recording write error: {}", e);
+                return;
+            }
+        }
+    }
+
+    /// Finalize the WAV header and return the recording id
+    pub fn finish(self) -> Result<String, String> {
+        self.writer
+            .finalize()
+            .map_err(|e| format!("Recording finalize error: {}", e))?;
+        Ok(self.id)
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
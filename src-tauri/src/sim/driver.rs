@@ -0,0 +1,51 @@
+//! App launches driven by a simulation instead of the user
+
+use super::scenario::scenarios_main;
+use super::soak::{soak_main, DEFAULT_SOAK_CYCLES};
+use tauri::AppHandle;
+
+/// First argument running the dictations read on stdin
+pub const SCENARIOS_ARG: &str = "--scenarios";
+
+/// First argument running the soak test
+pub const SOAK_ARG: &str = "--soak";
+
+/// What the app does once launched by a simulation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Simulation {
+    /// `dictea --scenarios`: run the dictations read on stdin
+    Scenarios,
+    /// `dictea --soak [cycles]`: soak every HTTP engine
+    Soak { cycles: usize },
+}
+
+impl Simulation {
+    /// Simulation requested on the command line (without the program name),
+    /// `None` for a normal launch
+    pub fn from_args(mut args: impl Iterator<Item = String>) -> Option<Result<Self, String>> {
+        match args.next()?.as_str() {
+            SCENARIOS_ARG => Some(Ok(Simulation::Scenarios)),
+            SOAK_ARG => Some(match args.next().map(|arg| arg.parse::<usize>()) {
+                None => Ok(Simulation::Soak {
+                    cycles: DEFAULT_SOAK_CYCLES,
+                }),
+                Some(Ok(cycles)) if cycles > 0 => Ok(Simulation::Soak { cycles }),
+                Some(_) => Err(format!("usage: dictea {} [cycles]", SOAK_ARG)),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Run `simulation` in the background of the set up app, then exit the
+/// app with its exit code
+pub(crate) fn drive(app: &AppHandle, simulation: Simulation) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let code = match simulation {
+            Simulation::Scenarios => scenarios_main(&app).await,
+            Simulation::Soak { cycles } => soak_main(&app, cycles).await,
+        };
+        app.exit(code);
+    });
+}
@@ -0,0 +1,176 @@
+//! Deterministic audio fixtures
+//!
+//! Builds 16kHz mono PCM from a sequence of segments (silence, tones,
+//! speech-shaped noise) and reports where each one starts and ends, so VAD,
+//! segmentation, resampling and chunking can be checked against known
+//! boundaries. The same builder calls always produce the same samples.
+
+/// Sample rate of the generated audio
+pub const FIXTURE_SAMPLE_RATE: usize = 16000;
+
+/// Content of a segment
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SegmentKind {
+    /// Digital silence, or a noise floor when `level` > 0
+    Silence { level: f32 },
+    /// Pure sine
+    Tone { frequency: f32, amplitude: f32 },
+    /// Low-passed noise modulated at a syllable rate, close enough to speech
+    /// for energy-based detectors
+    Speech { amplitude: f32 },
+}
+
+impl SegmentKind {
+    /// Whether a voice detector is expected to fire on this segment
+    pub fn is_voiced(&self) -> bool {
+        !matches!(self, SegmentKind::Silence { .. })
+    }
+}
+
+/// Generated segment and its position in the fixture
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment {
+    pub kind: SegmentKind,
+    /// First sample of the segment
+    pub start: usize,
+    /// One past the last sample
+    pub end: usize,
+}
+
+impl Segment {
+    pub fn start_ms(&self) -> u64 {
+        (self.start * 1000 / FIXTURE_SAMPLE_RATE) as u64
+    }
+
+    pub fn end_ms(&self) -> u64 {
+        (self.end * 1000 / FIXTURE_SAMPLE_RATE) as u64
+    }
+}
+
+/// xorshift32, seeded so every run gives the same noise
+struct Noise(u32);
+
+impl Noise {
+    /// Uniform sample in [-1, 1]
+    fn next(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        (self.0 as f32 / u32::MAX as f32) * 2.0 - 1.0
+    }
+}
+
+/// Audio fixture builder
+pub struct AudioFixture {
+    samples: Vec<f32>,
+    segments: Vec<Segment>,
+    noise: Noise,
+}
+
+impl AudioFixture {
+    /// Empty fixture; `seed` drives the noise of the speech/noise-floor segments
+    pub fn new(seed: u32) -> Self {
+        Self {
+            samples: Vec::new(),
+            segments: Vec::new(),
+            noise: Noise(seed.max(1)),
+        }
+    }
+
+    /// Digital silence
+    pub fn silence(self, ms: u64) -> Self {
+        self.noise_floor(ms, 0.0)
+    }
+
+    /// Background noise at `level` RMS-ish, still expected to be unvoiced
+    pub fn noise_floor(mut self, ms: u64, level: f32) -> Self {
+        let len = samples_for(ms);
+        for _ in 0..len {
+            let sample = if level > 0.0 { self.noise.next() * level } else { 0.0 };
+            self.samples.push(sample);
+        }
+        self.push_segment(SegmentKind::Silence { level }, len)
+    }
+
+    /// Sine tone
+    pub fn tone(mut self, ms: u64, frequency: f32, amplitude: f32) -> Self {
+        let len = samples_for(ms);
+        let step = 2.0 * std::f32::consts::PI * frequency / FIXTURE_SAMPLE_RATE as f32;
+        self.samples.extend((0..len).map(|i| amplitude * (step * i as f32).sin()));
+        self.push_segment(SegmentKind::Tone { frequency, amplitude }, len)
+    }
+
+    /// Speech-shaped noise: low-passed under ~1kHz, 4 syllables per second
+    pub fn speech(mut self, ms: u64, amplitude: f32) -> Self {
+        let len = samples_for(ms);
+        let syllable = 2.0 * std::f32::consts::PI * 4.0 / FIXTURE_SAMPLE_RATE as f32;
+        let mut filtered = 0.0f32;
+        for i in 0..len {
+            filtered += 0.35 * (self.noise.next() - filtered);
+            // Never fully closed, so the segment stays voiced end to end
+            let envelope = 0.6 + 0.4 * (syllable * i as f32).sin().abs();
+            // The filter loses ~half the amplitude, compensate
+            self.samples.push((filtered * 2.0 * envelope * amplitude).clamp(-1.0, 1.0));
+        }
+        self.push_segment(SegmentKind::Speech { amplitude }, len)
+    }
+
+    fn push_segment(mut self, kind: SegmentKind, len: usize) -> Self {
+        let start = self.samples.len() - len;
+        self.segments.push(Segment {
+            kind,
+            start,
+            end: start + len,
+        });
+        self
+    }
+
+    /// Samples and the segments they were built from, in order
+    pub fn build(self) -> (Vec<f32>, Vec<Segment>) {
+        (self.samples, self.segments)
+    }
+
+    /// Samples only
+    pub fn samples(self) -> Vec<f32> {
+        self.samples
+    }
+}
+
+fn samples_for(ms: u64) -> usize {
+    ms as usize * FIXTURE_SAMPLE_RATE / 1000
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::vad::create_detector;
+
+    #[test]
+    fn energy_vad_finds_the_segment_boundaries() {
+        let (samples, segments) = AudioFixture::new(3)
+            .silence(400)
+            .speech(1000, 0.3)
+            .noise_floor(500, 0.002)
+            .tone(300, 220.0, 0.2)
+            .silence(300)
+            .build();
+        let mut vad = create_detector(&AppConfig::default());
+        let frame = vad.frame_samples();
+
+        let voiced: Vec<bool> = samples.chunks_exact(frame).map(|chunk| vad.is_speech(chunk)).collect();
+        for segment in &segments {
+            let frames = segment.start.div_ceil(frame)..segment.end / frame;
+            assert!(!frames.is_empty(), "{:?}", segment);
+            for i in frames {
+                assert_eq!(voiced[i], segment.kind.is_voiced(), "frame {} in {:?}", i, segment);
+            }
+        }
+
+        // First and last voiced frames land on the speech and tone boundaries
+        let first = voiced.iter().position(|&v| v).expect("speech detected") * frame;
+        let last = (voiced.iter().rposition(|&v| v).expect("speech detected") + 1) * frame;
+        assert!(first.abs_diff(segments[1].start) < frame, "{} vs {:?}", first, segments[1]);
+        assert!(last.abs_diff(segments[3].end) < frame, "{} vs {:?}", last, segments[3]);
+    }
+}
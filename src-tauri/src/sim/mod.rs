@@ -0,0 +1,26 @@
+//! Simulated providers for end-to-end tests (feature `sim`)
+//!
+//! A local HTTP server stands in for the OpenAI/Groq/Mistral/Gemini
+//! endpoints and answers with canned fixtures (success, 401, 429, malformed
+//! JSON). The real engines are pointed at it and driven with synthetic
+//! audio, so the whole request/response path runs without network or keys.
+//! The scenarios (`dictea --scenarios`) and the soak test (`dictea --soak`)
+//! run the app itself: dictations go through the recording commands, the
+//! scenarios report the state events they emitted and the soak test
+//! watches the dictation resources for leaks.
+
+mod driver;
+mod fixtures;
+mod provider;
+mod scenario;
+mod soak;
+
+pub use crate::pipeline::ResourceUsage;
+pub use driver::{Simulation, SCENARIOS_ARG, SOAK_ARG};
+pub use fixtures::{AudioFixture, Segment, SegmentKind, FIXTURE_SAMPLE_RATE};
+pub use provider::{Fixture, MockProvider, Provider, RecordedRequest};
+pub use scenario::{run_scenarios, Dictation, DictationOutcome};
+pub use soak::{SoakReport, DEFAULT_SOAK_CYCLES};
+
+pub(crate) use driver::drive;
+pub(crate) use scenario::mock_engine;
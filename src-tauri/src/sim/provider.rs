@@ -0,0 +1,194 @@
+//! Local HTTP server replaying fixtures
+
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+
+/// Provider whose API is emulated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Provider {
+    OpenAi,
+    Groq,
+    Mistral,
+    Gemini,
+}
+
+impl Provider {
+    pub const ALL: [Provider; 4] = [Provider::OpenAi, Provider::Groq, Provider::Mistral, Provider::Gemini];
+
+    /// Id of the provider's engine in the config (`stt_engine`)
+    pub fn engine_id(self) -> &'static str {
+        match self {
+            Provider::OpenAi => "openai",
+            Provider::Groq => "groq",
+            Provider::Mistral => "voxtral",
+            Provider::Gemini => "gemini",
+        }
+    }
+}
+
+/// Canned HTTP response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fixture {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Fixture {
+    /// Successful transcription, in the provider's response format
+    pub fn transcript(provider: Provider, text: &str) -> Self {
+        let body = match provider {
+            Provider::Gemini => serde_json::json!({
+                "candidates": [{ "content": { "parts": [{ "text": text }] } }]
+            }),
+            Provider::OpenAi | Provider::Groq | Provider::Mistral => serde_json::json!({ "text": text }),
+        };
+        Self {
+            status: 200,
+            body: body.to_string(),
+        }
+    }
+
+    /// Rejected API key
+    pub fn unauthorized() -> Self {
+        Self {
+            status: 401,
+            body: r#"{"error":{"message":"Incorrect API key provided","type":"invalid_request_error"}}"#.to_string(),
+        }
+    }
+
+    /// Rate limit reached
+    pub fn rate_limited() -> Self {
+        Self {
+            status: 429,
+            body: r#"{"error":{"message":"Rate limit reached","type":"rate_limit_error"}}"#.to_string(),
+        }
+    }
+
+    /// 200 with a body that is not the expected JSON
+    pub fn malformed() -> Self {
+        Self {
+            status: 200,
+            body: r#"{"text": "truncated"#.to_string(),
+        }
+    }
+}
+
+/// Request received by the mock
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    /// An API key was sent (Authorization or x-goog-api-key header)
+    pub authenticated: bool,
+    pub body_len: usize,
+}
+
+/// Local server answering every request with the next fixture
+///
+/// The last fixture is repeated once the queue is drained. The server runs
+/// until the process exits.
+pub struct MockProvider {
+    base_url: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl MockProvider {
+    /// Listen on a free local port
+    pub fn start(fixtures: Vec<Fixture>) -> std::io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let base_url = format!("http://{}", listener.local_addr()?);
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = Arc::clone(&requests);
+        let mut fixtures: VecDeque<Fixture> = fixtures.into();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let fixture = if fixtures.len() > 1 {
+                    fixtures.pop_front()
+                } else {
+                    fixtures.front().cloned()
+                };
+                let fixture = fixture.unwrap_or_else(|| Fixture {
+                    status: 500,
+                    body: "no fixture".to_string(),
+                });
+                if let Err(e) = serve(stream, &fixture, &recorded) {
+                    tracing::warn!("Mock provider connection error: {}", e);
+                }
+            }
+        });
+
+        Ok(Self { base_url, requests })
+    }
+
+    /// Root URL to give the engines (`http://127.0.0.1:port`)
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Requests received so far
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+/// Read one HTTP/1.1 request, record it and answer it with `fixture`
+fn serve(stream: TcpStream, fixture: &Fixture, recorded: &Mutex<Vec<RecordedRequest>>) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut content_length = 0usize;
+    let mut authenticated = false;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        match name.trim().to_ascii_lowercase().as_str() {
+            "content-length" => content_length = value.trim().parse().unwrap_or(0),
+            "authorization" | "x-goog-api-key" => authenticated = !value.trim().is_empty(),
+            _ => {}
+        }
+    }
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body)?;
+
+    // Recorded before answering, so the client never sees a response first
+    recorded.lock().unwrap_or_else(|e| e.into_inner()).push(RecordedRequest {
+        method,
+        path,
+        authenticated,
+        body_len: content_length,
+    });
+
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        fixture.status,
+        reason(fixture.status),
+        fixture.body.len(),
+        fixture.body
+    )?;
+    stream.flush()
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        401 => "Unauthorized",
+        429 => "Too Many Requests",
+        _ => "Internal Server Error",
+    }
+}
@@ -0,0 +1,265 @@
+//! Dictations run through the app against the mock provider
+//!
+//! The app started with `--scenarios` reads the dictations to run on stdin,
+//! as one JSON array. Each one goes through the real start_recording and
+//! stop_and_return commands: the audio arrives as a network stream, the
+//! engine of the provider sends its request to the mock, and the state
+//! events the app emitted are printed back on stdout.
+
+use super::driver::SCENARIOS_ARG;
+use super::fixtures::FIXTURE_SAMPLE_RATE;
+use super::provider::Provider;
+use crate::audio::{MuteAwareness, NetworkAudio, NetworkProtocol};
+use crate::config::AppConfig;
+use crate::events::STATUS_EVENT;
+use crate::stt::{GeminiEngine, GroqEngine, OpenAiEngine, SttEngine, VoxtralEngine};
+use crate::AppState;
+use std::io::{BufRead, BufReader, Write};
+use std::net::UdpSocket;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+/// Marks the stdout line carrying the outcomes, the logs share stdout
+const OUTCOME_PREFIX: &str = "sim-outcomes: ";
+
+/// Longest wait for the app to receive the audio of a dictation
+const CAPTURE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// State events kept, enough for the events of a whole dictation
+const EVENT_HISTORY_SIZE: usize = 200;
+
+/// Root URL of the mock provider the engines are created for (empty =
+/// the real providers)
+static API_BASE: std::sync::Mutex<String> = std::sync::Mutex::new(String::new());
+
+/// Dictation to run in the app
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Dictation {
+    pub provider: Provider,
+    /// Root URL of the mock provider
+    pub api_base: String,
+    /// 16kHz mono samples streamed to the app
+    pub audio: Vec<f32>,
+}
+
+/// What the app did with a dictation
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DictationOutcome {
+    /// State events emitted from the start to the end of the dictation, in
+    /// order, status changes as `state:<state>`
+    pub events: Vec<String>,
+    /// Text returned by stop_and_return
+    pub text: String,
+    /// Error returned by start_recording or stop_and_return
+    pub error: Option<String>,
+}
+
+/// Run `dictations` in the app launched by `app` (built with `--features
+/// sim`) and return their outcomes, in order
+///
+/// The app opens its windows as usual, it needs a display.
+pub fn run_scenarios(mut app: Command, dictations: &[Dictation]) -> std::io::Result<Vec<DictationOutcome>> {
+    let mut child = app
+        .arg(SCENARIOS_ARG)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    // Written aside, the app logs to stdout while it reads
+    let input = serde_json::to_vec(dictations)?;
+    let mut stdin = child.stdin.take().ok_or_else(|| std::io::Error::other("no stdin"))?;
+    let writer = std::thread::spawn(move || stdin.write_all(&input));
+
+    let stdout = child.stdout.take().ok_or_else(|| std::io::Error::other("no stdout"))?;
+    let mut outcomes = None;
+    for line in BufReader::new(stdout).lines() {
+        if let Some(json) = line?.strip_prefix(OUTCOME_PREFIX) {
+            outcomes = Some(serde_json::from_str(json)?);
+        }
+    }
+    let status = child.wait()?;
+    writer.join().unwrap_or_else(|_| Err(std::io::Error::other("stdin writer panicked")))?;
+    outcomes.ok_or_else(|| std::io::Error::other(format!("the app exited ({}) without outcomes", status)))
+}
+
+/// Entry point of `dictea --scenarios`: runs the dictations read on stdin
+/// and prints their outcomes, returning the exit code
+pub(super) async fn scenarios_main(app: &AppHandle) -> i32 {
+    let read = tauri::async_runtime::spawn_blocking(|| serde_json::from_reader(std::io::stdin().lock()));
+    let dictations: Vec<Dictation> = match read.await {
+        Ok(Ok(dictations)) => dictations,
+        Ok(Err(e)) => {
+            eprintln!("Invalid dictations on stdin: {}", e);
+            return 2;
+        }
+        Err(e) => {
+            eprintln!("Cannot read stdin: {}", e);
+            return 1;
+        }
+    };
+    let port = match free_udp_port() {
+        Ok(port) => port,
+        Err(e) => {
+            eprintln!("No UDP port for the audio stream: {}", e);
+            return 1;
+        }
+    };
+
+    let mut outcomes = Vec::new();
+    for dictation in &dictations {
+        use_provider(app, dictation.provider, &dictation.api_base, port).await;
+        outcomes.push(dictate(app, &dictation.audio, port).await);
+    }
+    match serde_json::to_string(&outcomes) {
+        Ok(json) => {
+            println!("{}{}", OUTCOME_PREFIX, json);
+            0
+        }
+        Err(e) => {
+            eprintln!("Cannot print the outcomes: {}", e);
+            1
+        }
+    }
+}
+
+/// Engine `name` pointed at the mock provider, `None` unless a simulation
+/// selected one
+pub(crate) fn mock_engine(name: &str) -> Option<Box<dyn SttEngine>> {
+    let api_base = API_BASE.lock().ok()?.clone();
+    if api_base.is_empty() {
+        return None;
+    }
+    let provider = Provider::ALL.into_iter().find(|provider| provider.engine_id() == name)?;
+    Some(provider_engine(provider, &api_base))
+}
+
+/// Real engine of `provider`, sending its requests to `api_base`
+fn provider_engine(provider: Provider, api_base: &str) -> Box<dyn SttEngine> {
+    let api_key = "sim-key".to_string();
+    match provider {
+        Provider::OpenAi => Box::new(OpenAiEngine::with_api_key(api_key).with_api_base(api_base)),
+        Provider::Groq => Box::new(GroqEngine::with_api_key(api_key).with_api_base(api_base)),
+        Provider::Mistral => Box::new(VoxtralEngine::with_api_key(api_key).with_api_base(api_base)),
+        Provider::Gemini => Box::new(GeminiEngine::with_api_key(api_key).with_api_base(api_base)),
+    }
+}
+
+/// Local UDP port free for the simulated network stream
+pub(super) fn free_udp_port() -> std::io::Result<u16> {
+    Ok(UdpSocket::bind("127.0.0.1:0")?.local_addr()?.port())
+}
+
+/// Dictate with the engine of `provider` pointed at `api_base`, the audio
+/// coming from the network stream on `port`
+///
+/// Everything writing to the app data or polling the OS is turned off.
+/// The config is only changed in memory, never saved.
+pub(super) async fn use_provider(app: &AppHandle, provider: Provider, api_base: &str, port: u16) {
+    if let Ok(mut base) = API_BASE.lock() {
+        *base = api_base.to_string();
+    }
+    let config = AppConfig {
+        stt_engine: provider.engine_id().to_string(),
+        network_audio: NetworkAudio {
+            address: format!("127.0.0.1:{}", port),
+            protocol: NetworkProtocol::RawPcm,
+            ..NetworkAudio::default()
+        },
+        keep_recordings: false,
+        keep_history: false,
+        mute_awareness: MuteAwareness {
+            enabled: false,
+            ..MuteAwareness::default()
+        },
+        event_history_size: EVENT_HISTORY_SIZE,
+        ..AppConfig::default()
+    };
+
+    let state = app.state::<AppState>();
+    if let Ok(mut history) = state.history.lock() {
+        history.set_capacity(config.event_history_size);
+    }
+    let previous = std::mem::replace(&mut *state.config.write().await, config.clone());
+    crate::update_background_loops(app, Some(&previous), &config);
+    // The next start creates the engine for the mock
+    crate::reset_pipeline(&state).await;
+}
+
+/// Dictate `audio` through start_recording and stop_and_return, streaming
+/// it to `port`
+pub(super) async fn dictate(app: &AppHandle, audio: &[f32], port: u16) -> DictationOutcome {
+    let state = app.state::<AppState>();
+    let first = next_event_seq(&state);
+
+    let mut outcome = DictationOutcome::default();
+    match crate::start_recording(app.clone(), state.clone(), None).await {
+        Ok(()) => {
+            if let Err(e) = stream_audio(audio, port) {
+                tracing::warn!("Simulated audio not sent: {}", e);
+            }
+            wait_for_capture(&state, audio.len()).await;
+            match crate::stop_and_return(app.clone(), state.clone()).await {
+                Ok(result) => outcome.text = result.text,
+                Err(e) => outcome.error = Some(e.message.to_string()),
+            }
+        }
+        Err(e) => outcome.error = Some(e.message.to_string()),
+    }
+    outcome.events = events_since(&state, first);
+    outcome
+}
+
+/// Send `audio` to the app as bare 16-bit PCM, in capture-sized datagrams
+fn stream_audio(audio: &[f32], port: u16) -> std::io::Result<()> {
+    let socket = UdpSocket::bind("127.0.0.1:0")?;
+    for chunk in audio.chunks(FIXTURE_SAMPLE_RATE / 10) {
+        let datagram: Vec<u8> = chunk
+            .iter()
+            .flat_map(|sample| ((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16).to_le_bytes())
+            .collect();
+        socket.send_to(&datagram, ("127.0.0.1", port))?;
+    }
+    Ok(())
+}
+
+/// Wait until the pipeline was handed `samples` samples, or the timeout
+async fn wait_for_capture(state: &AppState, samples: usize) {
+    let deadline = Instant::now() + CAPTURE_TIMEOUT;
+    while Instant::now() < deadline {
+        let captured = state
+            .pipeline
+            .lock()
+            .await
+            .as_ref()
+            .map_or(0, |pipeline| pipeline.billed_samples);
+        if captured >= samples {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    tracing::warn!("Simulated audio not fully captured after {:?}", CAPTURE_TIMEOUT);
+}
+
+/// Sequence number of the next state event
+fn next_event_seq(state: &AppState) -> u64 {
+    state
+        .history
+        .lock()
+        .ok()
+        .and_then(|history| history.snapshot().last().map(|event| event.seq + 1))
+        .unwrap_or(0)
+}
+
+/// State events recorded from sequence number `first` on
+fn events_since(state: &AppState, first: u64) -> Vec<String> {
+    let events = state.history.lock().map(|history| history.snapshot()).unwrap_or_default();
+    events
+        .into_iter()
+        .filter(|event| event.seq >= first)
+        .map(|event| match event.payload.get("state").and_then(|status| status.as_str()) {
+            Some(status) if event.event == STATUS_EVENT => format!("state:{}", status),
+            _ => event.event,
+        })
+        .collect()
+}
@@ -0,0 +1,206 @@
+//! Long-session leak detection
+//!
+//! Runs hundreds of dictations through the app (start_recording, network
+//! audio, stop_and_return) against the mock provider and compares the
+//! per-dictation resources (capture threads, session tasks, timers) held
+//! once warmed up with the ones held at the end. A subscriber task, level
+//! task or timer that outlives its dictation shows up as a count that
+//! grows with the number of cycles. Resident memory is reported alongside.
+
+use super::fixtures::AudioFixture;
+use super::provider::{Fixture, MockProvider, Provider};
+use super::scenario::{dictate, free_udp_port, use_provider};
+use crate::pipeline::{ResourceUsage, WATCHDOG_INTERVAL};
+use std::fmt;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+/// Cycles run by `--soak` when no count is given
+pub const DEFAULT_SOAK_CYCLES: usize = 300;
+
+/// Cycles run before the baseline is taken (connection pools, allocator
+/// arenas and lazy statics settle during the first dictations)
+const WARMUP_CYCLES: usize = 10;
+
+/// How long the tasks of the last dictation get to end: the watchdog
+/// holds its timer for one interval after the dictation is over
+const SETTLE_TIMEOUT: Duration = Duration::from_secs(WATCHDOG_INTERVAL.as_secs() + 2);
+
+/// Transcript returned by the mock for every dictation
+const TRANSCRIPT: &str = "Soak test";
+
+/// Outcome of a soak run
+#[derive(Debug, Clone)]
+pub struct SoakReport {
+    pub provider: Provider,
+    pub cycles: usize,
+    /// Cycles that did not end with the transcript of the mock
+    pub failures: usize,
+    pub elapsed: Duration,
+    /// Resources held after the warm-up cycles
+    pub baseline: ResourceUsage,
+    /// Resources held after the last cycle, once its tasks had time to end
+    pub last: ResourceUsage,
+    /// Resident memory after the warm-up cycles, where the platform exposes it
+    pub baseline_rss: Option<u64>,
+    /// Resident memory after the last cycle
+    pub last_rss: Option<u64>,
+}
+
+impl SoakReport {
+    /// Resources still held past the baseline, and memory grown by more
+    /// than `rss_tolerance` bytes
+    pub fn leaks(&self, rss_tolerance: u64) -> Vec<&'static str> {
+        let mut leaks = Vec::new();
+        if self.last.audio_streams > self.baseline.audio_streams {
+            leaks.push("audio streams");
+        }
+        if self.last.session_tasks > self.baseline.session_tasks {
+            leaks.push("session tasks");
+        }
+        if self.last.timers > self.baseline.timers {
+            leaks.push("timers");
+        }
+        let grown = self.last_rss.zip(self.baseline_rss).map(|(last, baseline)| last.saturating_sub(baseline));
+        if grown.is_some_and(|bytes| bytes > rss_tolerance) {
+            leaks.push("memory");
+        }
+        leaks
+    }
+}
+
+impl fmt::Display for SoakReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let megabytes = |bytes: Option<u64>| {
+            bytes
+                .map(|b| format!("{:.1} MB", b as f64 / 1e6))
+                .unwrap_or_else(|| "n/a".to_string())
+        };
+        writeln!(
+            f,
+            "{:?}: {} cycles in {:.1}s, {} failed",
+            self.provider,
+            self.cycles,
+            self.elapsed.as_secs_f32(),
+            self.failures
+        )?;
+        writeln!(
+            f,
+            "  audio streams: {} -> {}",
+            self.baseline.audio_streams, self.last.audio_streams
+        )?;
+        writeln!(
+            f,
+            "  session tasks: {} -> {}",
+            self.baseline.session_tasks, self.last.session_tasks
+        )?;
+        writeln!(f, "  timers:        {} -> {}", self.baseline.timers, self.last.timers)?;
+        write!(
+            f,
+            "  memory:        {} -> {}",
+            megabytes(self.baseline_rss),
+            megabytes(self.last_rss)
+        )
+    }
+}
+
+/// Run `cycles` dictations through the app with the engine of `provider`,
+/// the audio streamed to `port`
+pub(super) async fn run_soak(
+    app: &AppHandle,
+    provider: Provider,
+    cycles: usize,
+    port: u16,
+) -> std::io::Result<SoakReport> {
+    let mock = MockProvider::start(vec![Fixture::transcript(provider, TRANSCRIPT)])?;
+    use_provider(app, provider, mock.base_url(), port).await;
+    let audio = AudioFixture::new(11).silence(200).speech(1200, 0.3).silence(300).samples();
+
+    for _ in 0..WARMUP_CYCLES {
+        dictate(app, &audio, port).await;
+    }
+    let baseline = settle().await;
+    let baseline_rss = resident_memory();
+
+    let started = Instant::now();
+    let mut failures = 0;
+    for _ in 0..cycles {
+        let outcome = dictate(app, &audio, port).await;
+        if outcome.error.is_some() || outcome.text != TRANSCRIPT {
+            failures += 1;
+        }
+    }
+    let elapsed = started.elapsed();
+
+    let last = settle().await;
+    Ok(SoakReport {
+        provider,
+        cycles,
+        failures,
+        elapsed,
+        baseline,
+        last,
+        baseline_rss,
+        last_rss: resident_memory(),
+    })
+}
+
+/// Wait until no dictation resource is held anymore, or the timeout
+async fn settle() -> ResourceUsage {
+    let deadline = Instant::now() + SETTLE_TIMEOUT;
+    loop {
+        let usage = ResourceUsage::current();
+        if usage.is_idle() || Instant::now() >= deadline {
+            return usage;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+}
+
+/// Resident memory of the process (Linux only)
+fn resident_memory() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let kilobytes = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .split_whitespace()
+        .next()?
+        .parse::<u64>()
+        .ok()?;
+    Some(kilobytes * 1024)
+}
+
+/// Entry point of `dictea --soak [cycles]`: soaks every HTTP engine and
+/// prints the reports, returning the exit code (1 on leak or failure)
+pub(super) async fn soak_main(app: &AppHandle, cycles: usize) -> i32 {
+    let port = match free_udp_port() {
+        Ok(port) => port,
+        Err(e) => {
+            eprintln!("No UDP port for the audio stream: {}", e);
+            return 1;
+        }
+    };
+
+    let mut code = 0;
+    for provider in Provider::ALL {
+        match run_soak(app, provider, cycles, port).await {
+            Ok(report) => {
+                println!("{}", report);
+                // Allocator fragmentation is tolerated, a per-cycle leak is not
+                let leaks = report.leaks(cycles as u64 * 64 * 1024);
+                if !leaks.is_empty() {
+                    println!("  LEAK: {}", leaks.join(", "));
+                    code = 1;
+                }
+                if report.failures > 0 {
+                    code = 1;
+                }
+            }
+            Err(e) => {
+                eprintln!("{:?}: cannot start the mock provider: {}", provider, e);
+                code = 1;
+            }
+        }
+    }
+    code
+}
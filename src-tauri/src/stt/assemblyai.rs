@@ -0,0 +1,534 @@
+//! AssemblyAI transcription: realtime WebSocket, async endpoint on flush
+//!
+//! Audio is streamed as it is captured to the Universal Streaming API,
+//! whose turns are surfaced as `Partial` events while the user speaks. The
+//! dictation is also kept, and on flush it is sent once to the async
+//! transcription endpoint, more accurate than the realtime model: its text
+//! is the `Final`. If the async pass fails, the streamed turns are used.
+
+use super::engine::{Language, SttEngine, SttError, SttEvent};
+use super::guards::AudioGuards;
+use super::request_log::{record_exchange, RequestSummary};
+use super::worker::{RequestWorker, SharedEvents};
+use crate::audio::{encode_upload, AudioAccumulator, AudioFormat, SpeedNormalization};
+use futures_util::{SinkExt, StreamExt};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc as async_mpsc;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+/// Sample rate of the audio pushed to the engines, streamed as is (pcm16)
+const CAPTURE_RATE: u32 = 16000;
+
+/// Shortest audio message accepted by the streaming API (50 ms)
+const MIN_CHUNK_SAMPLES: usize = CAPTURE_RATE as usize / 20;
+
+/// Longest wait for the last turns after the end of the audio
+const STREAM_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Longest wait for the async transcript
+const POLISH_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Longest wait for the async pass on flush: provider queue, upload and polling
+const PENDING_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// Time between two polls of the async transcript
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Message from the engine to its session task
+enum Outgoing {
+    /// pcm16 chunk
+    Audio(Vec<u8>),
+    /// End of the dictation: close once the last turn is returned
+    Terminate,
+}
+
+/// Connection opened for one dictation
+struct StreamingSession {
+    outgoing: async_mpsc::UnboundedSender<Outgoing>,
+    /// Closed by the session task when it ends (locked only to be `Sync`)
+    events: Mutex<mpsc::Receiver<SttEvent>>,
+}
+
+impl StreamingSession {
+    /// Connect from a dedicated thread
+    fn open(url: String, api_key: String) -> Self {
+        let (outgoing, outgoing_rx) = async_mpsc::unbounded_channel();
+        let (events_tx, events) = mpsc::channel();
+        std::thread::spawn(move || {
+            let rt = match tokio::runtime::Runtime::new() {
+                Ok(rt) => rt,
+                Err(e) => {
+                    let _ = events_tx.send(SttEvent::Error(format!("Cannot start runtime: {}", e)));
+                    return;
+                }
+            };
+            if let Err(e) = rt.block_on(run_session(url, api_key, outgoing_rx, &events_tx)) {
+                tracing::error!("AssemblyAI streaming error: {}", e);
+                let _ = events_tx.send(SttEvent::Error(e.to_string()));
+            }
+        });
+        Self {
+            outgoing,
+            events: Mutex::new(events),
+        }
+    }
+}
+
+/// Drive the WebSocket until the dictation is terminated
+///
+/// Each turn is revised until its formatted version arrives; every update
+/// is sent as the `Partial` text of all the turns so far.
+async fn run_session(
+    url: String,
+    api_key: String,
+    mut outgoing: async_mpsc::UnboundedReceiver<Outgoing>,
+    events: &mpsc::Sender<SttEvent>,
+) -> Result<(), SttError> {
+    let network = |e: tokio_tungstenite::tungstenite::Error| SttError::InferenceError(format!("Network error: {}", e));
+
+    let mut request = url.into_client_request().map_err(network)?;
+    let authorization = HeaderValue::from_str(&api_key).map_err(|e| SttError::InferenceError(e.to_string()))?;
+    request.headers_mut().insert("Authorization", authorization);
+
+    let (socket, _) = tokio_tungstenite::connect_async(request).await.map_err(network)?;
+    let (mut sink, mut stream) = socket.split();
+    tracing::info!("AssemblyAI streaming session opened");
+
+    let mut turns: BTreeMap<u64, String> = BTreeMap::new();
+    let mut terminating = false;
+    loop {
+        tokio::select! {
+            message = outgoing.recv(), if !terminating => {
+                let message = match message {
+                    Some(Outgoing::Audio(audio)) => WsMessage::Binary(audio),
+                    // Engine reset or dropped: nothing left to wait for
+                    None => break,
+                    Some(Outgoing::Terminate) => {
+                        terminating = true;
+                        WsMessage::Text(serde_json::json!({ "type": "Terminate" }).to_string())
+                    }
+                };
+                sink.send(message).await.map_err(network)?;
+            }
+            message = stream.next() => {
+                let text = match message {
+                    Some(Ok(WsMessage::Text(text))) => text,
+                    Some(Ok(WsMessage::Close(frame))) => {
+                        // Closed by the server after the termination, or refused (bad key, no credit)
+                        if terminating {
+                            break;
+                        }
+                        let reason = frame.map(|frame| frame.reason.to_string()).unwrap_or_default();
+                        return Err(SttError::InferenceError(format!("AssemblyAI connection closed: {}", reason)));
+                    }
+                    None => {
+                        return Err(SttError::InferenceError("AssemblyAI connection closed".to_string()));
+                    }
+                    Some(Ok(_)) => continue,
+                    Some(Err(e)) => return Err(network(e)),
+                };
+                let event: serde_json::Value = match serde_json::from_str(&text) {
+                    Ok(event) => event,
+                    Err(e) => {
+                        tracing::warn!("Unreadable AssemblyAI event: {}", e);
+                        continue;
+                    }
+                };
+                match event["type"].as_str().unwrap_or_default() {
+                    "Turn" => {
+                        let order = event["turn_order"].as_u64().unwrap_or_default();
+                        let transcript = event["transcript"].as_str().unwrap_or_default().trim();
+                        turns.insert(order, transcript.to_string());
+                        let text = turns
+                            .values()
+                            .map(String::as_str)
+                            .filter(|turn| !turn.is_empty())
+                            .collect::<Vec<_>>()
+                            .join(" ");
+                        if !text.is_empty() {
+                            let _ = events.send(SttEvent::Partial(text));
+                        }
+                    }
+                    "Termination" => break,
+                    "Error" => {
+                        let reason = event["error"].as_str().unwrap_or("unknown error");
+                        return Err(SttError::InferenceError(format!("AssemblyAI error: {}", reason)));
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    let _ = sink.send(WsMessage::Close(None)).await;
+    tracing::info!("AssemblyAI streaming session closed");
+    Ok(())
+}
+
+/// STT engine streaming to AssemblyAI, polished by its async endpoint
+pub struct AssemblyAiEngine {
+    api_key: String,
+    language: Language,
+    /// Whole dictation, sent to the async endpoint on flush
+    audio_buffer: AudioAccumulator,
+    guards: AudioGuards,
+    /// Slowing down of fast speech, applied to the async upload only
+    speed: SpeedNormalization,
+    /// Connection of the current dictation, opened on the first samples
+    session: Option<StreamingSession>,
+    /// pcm16 not sent yet, shorter than the smallest accepted message
+    pending_audio: Vec<u8>,
+    /// Last text streamed in the current dictation
+    streamed: String,
+    /// Streamed events and guard results, ready to be consumed
+    events: VecDeque<SttEvent>,
+    /// Results of the async pass, tagged with their session generation
+    shared_events: SharedEvents,
+    /// Flag indicating an async pass is in progress
+    pending: Arc<AtomicBool>,
+    /// Current session generation, bumped on reset
+    generation: Arc<AtomicU64>,
+    http_client: reqwest::Client,
+}
+
+impl AssemblyAiEngine {
+    /// Realtime endpoint
+    const STREAMING_URL: &'static str = "wss://streaming.assemblyai.com/v3/ws";
+
+    /// Async API root
+    const API_BASE: &'static str = "https://api.assemblyai.com/v2";
+
+    /// Async transcription model
+    const MODEL: &'static str = "universal";
+
+    pub fn with_api_key(api_key: String) -> Self {
+        Self {
+            api_key,
+            language: Language::Auto,
+            audio_buffer: AudioAccumulator::default(),
+            guards: AudioGuards::default(),
+            speed: SpeedNormalization::default(),
+            session: None,
+            pending_audio: Vec::new(),
+            streamed: String::new(),
+            events: VecDeque::new(),
+            shared_events: Arc::new(Mutex::new(VecDeque::new())),
+            pending: Arc::new(AtomicBool::new(false)),
+            generation: Arc::new(AtomicU64::new(0)),
+            http_client: reqwest::Client::new(),
+        }
+    }
+
+    /// Session of the current dictation, connecting on first use
+    fn session(&mut self) -> &StreamingSession {
+        // The English model is the fastest, the multilingual one covers fr, es, de, it, pt
+        let model = match self.language {
+            Language::English => "universal-streaming-english",
+            _ => "universal-streaming-multilingual",
+        };
+        let url = format!(
+            "{}?sample_rate={}&encoding=pcm_s16le&format_turns=true&speech_model={}",
+            Self::STREAMING_URL,
+            CAPTURE_RATE,
+            model
+        );
+        let api_key = self.api_key.clone();
+        self.session.get_or_insert_with(|| StreamingSession::open(url, api_key))
+    }
+
+    /// Move the events received so far to the queue
+    fn drain_session(&mut self) {
+        let received: Vec<SttEvent> = match self.session.as_ref().and_then(|session| session.events.lock().ok()) {
+            Some(events) => events.try_iter().collect(),
+            None => return,
+        };
+        for event in received {
+            self.record(event);
+        }
+    }
+
+    /// Forget the current dictation, closing its connection
+    fn end_session(&mut self) {
+        // Dropping the sender ends the session task
+        self.session = None;
+        self.pending_audio.clear();
+        self.streamed.clear();
+        self.audio_buffer.clear();
+    }
+
+    fn record(&mut self, event: SttEvent) {
+        match event {
+            SttEvent::Partial(ref text) => self.streamed = text.clone(),
+            // The live text stops, the async pass on flush still transcribes the dictation
+            SttEvent::Error(e) => {
+                tracing::warn!("AssemblyAI stream error: {}", e);
+                return;
+            }
+            _ => {}
+        }
+        self.events.push_back(event);
+    }
+
+    /// Wait for the last turns of the stream
+    fn finish_stream(&mut self) {
+        let Some(session) = self.session.take() else {
+            return;
+        };
+        if !self.pending_audio.is_empty() {
+            let _ = session.outgoing.send(Outgoing::Audio(std::mem::take(&mut self.pending_audio)));
+        }
+        let _ = session.outgoing.send(Outgoing::Terminate);
+        let events = session.events.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let deadline = Instant::now() + STREAM_TIMEOUT;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match events.recv_timeout(remaining) {
+                Ok(SttEvent::Partial(text)) => self.streamed = text,
+                Ok(SttEvent::Error(e)) => tracing::warn!("AssemblyAI stream error: {}", e),
+                Ok(_) => {}
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    tracing::warn!("Timeout waiting for the last AssemblyAI turns");
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Transcribe the whole dictation with the async endpoint
+    async fn transcribe_async(
+        client: reqwest::Client,
+        api_key: String,
+        mut audio_data: AudioAccumulator,
+        language: Option<String>,
+    ) -> Result<String, SttError> {
+        let network = |e: reqwest::Error| SttError::InferenceError(format!("Network error: {}", e));
+        let (audio_bytes, _) = encode_upload(&mut audio_data, AudioFormat::WAV_16K)
+            .map_err(|e| SttError::InferenceError(e.to_string()))?;
+        let duration_secs = audio_data.len() as f32 / CAPTURE_RATE as f32;
+        let audio_size = audio_bytes.len();
+        tracing::info!("Sending to AssemblyAI: {:.1}s audio, {} bytes", duration_secs, audio_size);
+
+        let upload: serde_json::Value = client
+            .post(format!("{}/upload", Self::API_BASE))
+            .header("Authorization", &api_key)
+            .body(audio_bytes)
+            .send()
+            .await
+            .map_err(network)?
+            .json()
+            .await
+            .map_err(network)?;
+        let audio_url = upload["upload_url"]
+            .as_str()
+            .ok_or_else(|| SttError::InferenceError(format!("AssemblyAI upload error: {}", upload)))?;
+
+        let mut body = serde_json::json!({ "audio_url": audio_url, "speech_model": Self::MODEL });
+        match language.as_deref() {
+            Some(language) => body["language_code"] = serde_json::Value::from(language),
+            None => body["language_detection"] = serde_json::Value::from(true),
+        }
+        let url = format!("{}/transcript", Self::API_BASE);
+        let submitted: serde_json::Value = client
+            .post(&url)
+            .header("Authorization", &api_key)
+            .json(&body)
+            .send()
+            .await
+            .map_err(network)?
+            .json()
+            .await
+            .map_err(network)?;
+        let id = submitted["id"]
+            .as_str()
+            .ok_or_else(|| SttError::InferenceError(format!("AssemblyAI API error: {}", submitted)))?;
+
+        let deadline = Instant::now() + POLISH_TIMEOUT;
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            let response = client
+                .get(format!("{}/{}", url, id))
+                .header("Authorization", &api_key)
+                .send()
+                .await
+                .map_err(network)?;
+            let status = response.status();
+            let body = response.text().await.map_err(network)?;
+            let transcript: serde_json::Value = serde_json::from_str(&body)
+                .map_err(|e| SttError::InferenceError(format!("AssemblyAI API error {}: {}", status, e)))?;
+            match transcript["status"].as_str().unwrap_or_default() {
+                "completed" => {
+                    let request = RequestSummary {
+                        url: &url,
+                        model: Self::MODEL,
+                        language: language.as_deref(),
+                        audio_bytes: audio_size,
+                        duration_secs,
+                    };
+                    record_exchange("AssemblyAI", &request, status.as_u16(), &body);
+                    return Ok(transcript["text"].as_str().unwrap_or_default().trim().to_string());
+                }
+                "error" => {
+                    let reason = transcript["error"].as_str().unwrap_or("unknown error");
+                    return Err(SttError::InferenceError(format!("AssemblyAI error: {}", reason)));
+                }
+                _ if Instant::now() >= deadline => {
+                    return Err(SttError::InferenceError("AssemblyAI transcript timed out".to_string()));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Send the dictation to the async endpoint from a request thread
+    ///
+    /// If the async pass fails, the streamed transcript is the result.
+    fn polish(&mut self) {
+        let audio_data = self.audio_buffer.take();
+        let speed = self.speed;
+        let client = self.http_client.clone();
+        let api_key = self.api_key.clone();
+        let streamed = self.streamed.clone();
+        let language = match &self.language {
+            Language::Auto => None,
+            lang => Some(lang.code().to_string()),
+        };
+        let worker = RequestWorker::new("AssemblyAI", &self.shared_events, &self.pending, &self.generation);
+
+        worker.spawn(move || async move {
+            let audio_data = speed.apply(audio_data);
+            match Self::transcribe_async(client, api_key, audio_data, language).await {
+                Err(e) if !streamed.is_empty() => {
+                    tracing::warn!("AssemblyAI async pass failed, keeping the streamed transcript: {}", e);
+                    Ok(streamed)
+                }
+                result => result,
+            }
+        });
+    }
+
+    /// Wait for the current async pass to complete, including its polling
+    fn wait_for_pending(&self) {
+        let start = Instant::now();
+        while self.pending.load(Ordering::SeqCst) {
+            if start.elapsed() > PENDING_TIMEOUT {
+                tracing::warn!("Timeout waiting for AssemblyAI response");
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    }
+}
+
+impl SttEngine for AssemblyAiEngine {
+    fn load(api_key: &str) -> Result<Self, SttError> {
+        if api_key.is_empty() {
+            return Err(SttError::ModelNotFound(
+                "AssemblyAI API key required".to_string(),
+            ));
+        }
+
+        tracing::info!("Initializing AssemblyAI API");
+        Ok(Self::with_api_key(api_key.to_string()))
+    }
+
+    fn set_language(&mut self, language: Language) {
+        self.language = language.clone();
+        tracing::debug!("AssemblyAI language set: {:?}", language);
+    }
+
+    fn language(&self) -> &Language {
+        &self.language
+    }
+
+    fn push_audio(&mut self, pcm: &[f32]) {
+        if pcm.is_empty() {
+            return;
+        }
+        self.audio_buffer.extend_from_slice(pcm);
+        self.pending_audio.extend(
+            pcm.iter()
+                .flat_map(|&sample| ((sample * 32767.0).clamp(-32768.0, 32767.0) as i16).to_le_bytes()),
+        );
+        if self.pending_audio.len() >= MIN_CHUNK_SAMPLES * 2 {
+            let audio = std::mem::take(&mut self.pending_audio);
+            let _ = self.session().outgoing.send(Outgoing::Audio(audio));
+        }
+    }
+
+    fn set_memory_limit(&mut self, samples: usize) {
+        self.audio_buffer.set_memory_limit(samples);
+    }
+
+    fn set_guards(&mut self, guards: AudioGuards) {
+        self.guards = guards;
+    }
+
+    fn set_speed_normalization(&mut self, speed: SpeedNormalization) {
+        self.speed = speed;
+    }
+
+    fn poll(&mut self) -> Option<SttEvent> {
+        self.drain_session();
+        if let Some(event) = self.events.pop_front() {
+            return Some(event);
+        }
+        let current = self.generation.load(Ordering::SeqCst);
+        let mut events = self.shared_events.lock().ok()?;
+        while let Some((generation, event)) = events.pop_front() {
+            if generation == current {
+                return Some(event);
+            }
+            tracing::debug!("Dropping AssemblyAI event from stale session {}", generation);
+        }
+        None
+    }
+
+    fn flush(&mut self) {
+        tracing::info!(
+            "Flush AssemblyAI: {} samples ({:.1}s)",
+            self.audio_buffer.len(),
+            self.audio_buffer.len() as f32 / CAPTURE_RATE as f32
+        );
+        self.drain_session();
+        if let Some(event) = self.guards.check(&mut self.audio_buffer) {
+            self.events.push_back(event);
+            self.end_session();
+            return;
+        }
+
+        self.finish_stream();
+        self.polish();
+        self.wait_for_pending();
+        self.end_session();
+    }
+
+    fn reset(&mut self) {
+        self.end_session();
+        self.events.clear();
+        // Late responses from the previous session will be discarded
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        self.pending.store(false, Ordering::SeqCst);
+        if let Ok(mut events) = self.shared_events.lock() {
+            events.clear();
+        }
+        tracing::debug!("AssemblyAI engine reset");
+    }
+
+    fn streams_partials(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &str {
+        "AssemblyAI"
+    }
+
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
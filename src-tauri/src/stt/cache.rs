@@ -0,0 +1,313 @@
+//! Transcript cache keyed by the audio content
+//!
+//! Sending the exact same audio again (retry, re-import of a recording)
+//! returns the stored transcript instead of making a second billable call.
+//! The cache sits in front of batch engines: audio still goes to the engine
+//! as it arrives, and a hit discards it at flush instead of uploading it.
+//!
+//! Transcripts are kept on disk, so the cache is opt-in. The key covers the
+//! engine settings that change the transcript of the same audio.
+
+use super::engine::{Language, SttEngine, SttError, SttEvent};
+use super::guards::AudioGuards;
+use crate::audio::{AudioFormat, SpeedNormalization};
+use std::collections::{BTreeMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Size and age limits of the cache
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TranscriptCache {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Oldest entries are evicted past this count
+    #[serde(default = "default_max_entries")]
+    pub max_entries: usize,
+    #[serde(default = "default_ttl_hours")]
+    pub ttl_hours: u64,
+}
+
+fn default_enabled() -> bool {
+    false
+}
+
+fn default_max_entries() -> usize {
+    200
+}
+
+fn default_ttl_hours() -> u64 {
+    24
+}
+
+impl Default for TranscriptCache {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            max_entries: default_max_entries(),
+            ttl_hours: default_ttl_hours(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct CacheEntry {
+    text: String,
+    /// Unix time of the transcription
+    created: u64,
+}
+
+/// Cached transcripts persisted as JSON, keyed by audio hash
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct CacheStore {
+    #[serde(skip)]
+    path: PathBuf,
+    #[serde(default)]
+    entries: BTreeMap<String, CacheEntry>,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+impl CacheStore {
+    /// Read the cache, starting empty if missing or unreadable
+    pub fn load(path: &Path) -> Self {
+        let mut store: Self = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        store.path = path.to_path_buf();
+        store
+    }
+
+    fn save(&self) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Cache dir error: {}", e))?;
+        }
+        let content = serde_json::to_string(self).map_err(|e| e.to_string())?;
+        std::fs::write(&self.path, content).map_err(|e| format!("Transcript cache write error: {}", e))
+    }
+
+    /// Remove every entry, returning how many there were
+    pub fn clear(path: &Path) -> Result<usize, String> {
+        let count = Self::load(path).entries.len();
+        match std::fs::remove_file(path) {
+            Ok(()) => Ok(count),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(0),
+            Err(e) => Err(format!("Cannot clear the transcript cache: {}", e)),
+        }
+    }
+
+    fn get(&self, key: &str, limits: &TranscriptCache) -> Option<&str> {
+        let entry = self.entries.get(key)?;
+        let fresh = now_secs().saturating_sub(entry.created) < limits.ttl_hours * 3600;
+        fresh.then_some(entry.text.as_str())
+    }
+
+    fn insert(&mut self, key: String, text: String, limits: &TranscriptCache) {
+        let created = now_secs();
+        self.entries.insert(key, CacheEntry { text, created });
+
+        let ttl_secs = limits.ttl_hours * 3600;
+        self.entries.retain(|_, entry| created.saturating_sub(entry.created) < ttl_secs);
+        while self.entries.len() > limits.max_entries {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.created)
+                .map(|(key, _)| key.clone());
+            match oldest {
+                Some(key) => self.entries.remove(&key),
+                None => break,
+            };
+        }
+    }
+}
+
+/// FNV-1a over the samples, stable across builds (unlike `DefaultHasher`)
+#[derive(Debug, Clone, Copy)]
+struct AudioHash {
+    state: u64,
+    samples: usize,
+}
+
+impl AudioHash {
+    const OFFSET: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    fn new() -> Self {
+        Self {
+            state: Self::OFFSET,
+            samples: 0,
+        }
+    }
+
+    fn update(&mut self, pcm: &[f32]) {
+        for sample in pcm {
+            self.update_bytes(&sample.to_bits().to_le_bytes());
+        }
+        self.samples += pcm.len();
+    }
+
+    fn update_bytes(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.state = (self.state ^ byte as u64).wrapping_mul(Self::PRIME);
+        }
+    }
+
+    /// Key of the audio for one engine, language and engine settings
+    fn key(&self, engine: &str, language: &Language, settings: &str) -> String {
+        let mut settings_hash = Self::new();
+        settings_hash.update_bytes(settings.as_bytes());
+        format!(
+            "{}/{}/{:016x}/{}/{:016x}",
+            engine,
+            language.code(),
+            settings_hash.state,
+            self.samples,
+            self.state
+        )
+    }
+}
+
+/// Engine wrapper answering repeated audio from the cache
+pub struct CachedEngine {
+    inner: Box<dyn SttEngine>,
+    engine_id: String,
+    /// Upload format, prompt... of the engine, see `CachedEngine::new`
+    settings: String,
+    /// Changes the audio actually sent, so the transcript too
+    speed: SpeedNormalization,
+    limits: TranscriptCache,
+    path: PathBuf,
+    hash: AudioHash,
+    /// Events of the last flush, from the cache or the engine
+    events: VecDeque<SttEvent>,
+    /// Samples answered from the cache since the last call to `take_cached_samples`
+    cached_samples: usize,
+}
+
+impl CachedEngine {
+    /// `settings` describes everything besides the audio and language that
+    /// changes the transcript (upload codec and rate, prompt...): a transcript
+    /// is only reused for the same settings.
+    pub fn new(
+        inner: Box<dyn SttEngine>,
+        engine_id: &str,
+        settings: String,
+        limits: TranscriptCache,
+        path: PathBuf,
+    ) -> Self {
+        Self {
+            inner,
+            engine_id: engine_id.to_string(),
+            settings,
+            speed: SpeedNormalization::default(),
+            limits,
+            path,
+            hash: AudioHash::new(),
+            events: VecDeque::new(),
+            cached_samples: 0,
+        }
+    }
+}
+
+impl SttEngine for CachedEngine {
+    fn load(_model_path: &str) -> Result<Self, SttError> {
+        Err(SttError::ModelNotFound("the cache wraps an existing engine".to_string()))
+    }
+
+    fn set_language(&mut self, language: Language) {
+        self.inner.set_language(language);
+    }
+
+    fn language(&self) -> &Language {
+        self.inner.language()
+    }
+
+    fn push_audio(&mut self, pcm: &[f32]) {
+        self.hash.update(pcm);
+        self.inner.push_audio(pcm);
+    }
+
+    fn set_memory_limit(&mut self, samples: usize) {
+        self.inner.set_memory_limit(samples);
+    }
+
+    fn set_guards(&mut self, guards: AudioGuards) {
+        self.inner.set_guards(guards);
+    }
+
+    fn set_speed_normalization(&mut self, speed: SpeedNormalization) {
+        self.speed = speed;
+        self.inner.set_speed_normalization(speed);
+    }
+
+    fn audio_format(&self) -> AudioFormat {
+        self.inner.audio_format()
+    }
+
+    fn poll(&mut self) -> Option<SttEvent> {
+        self.events.pop_front().or_else(|| self.inner.poll())
+    }
+
+    fn flush(&mut self) {
+        let hash = std::mem::replace(&mut self.hash, AudioHash::new());
+        if hash.samples == 0 {
+            self.inner.flush();
+            return;
+        }
+        let settings = format!("{}|{:?}", self.settings, self.speed);
+        let key = hash.key(&self.engine_id, self.inner.language(), &settings);
+        let mut store = CacheStore::load(&self.path);
+
+        if let Some(text) = store.get(&key, &self.limits) {
+            tracing::info!("Transcript served from cache ({:.1}s audio)", hash.samples as f32 / 16000.0);
+            // Drops the buffered audio without sending it
+            self.inner.reset();
+            self.events.push_back(SttEvent::Final(text.to_string()));
+            self.cached_samples += hash.samples;
+            return;
+        }
+
+        self.inner.flush();
+        while let Some(event) = self.inner.poll() {
+            self.events.push_back(event);
+        }
+
+        // Only a clean transcript is worth reusing, errors are retried
+        let mut finals = Vec::new();
+        for event in &self.events {
+            match event {
+                SttEvent::Final(text) => finals.push(text.as_str()),
+                SttEvent::Partial(_) => {}
+                _ => return,
+            }
+        }
+        if finals.is_empty() {
+            return;
+        }
+        store.insert(key, finals.join(" "), &self.limits);
+        if let Err(e) = store.save() {
+            tracing::warn!("{}", e);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.hash = AudioHash::new();
+        self.events.clear();
+        self.inner.reset();
+    }
+
+    fn take_cached_samples(&mut self) -> usize {
+        std::mem::take(&mut self.cached_samples)
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+}
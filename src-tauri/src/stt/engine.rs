@@ -1,5 +1,7 @@
 //! Main trait for STT engines
 
+use super::guards::AudioGuards;
+use crate::audio::{AudioFormat, SpeedNormalization, UploadCodec};
 use thiserror::Error;
 
 /// Events emitted by the STT engine
@@ -10,6 +12,12 @@ pub enum SttEvent {
     Partial(String),
     /// Final transcription (definitive)
     Final(String),
+    /// The audio holds no speech (silence, music)
+    NoSpeech,
+    /// The audio is shorter than the configured minimum, nothing was sent
+    TooShort { duration_ms: u64, min_duration_ms: u64 },
+    /// Transcription failed (network, API or unexpected response)
+    Error(String),
 }
 
 /// Supported languages for transcription
@@ -106,6 +114,30 @@ pub trait SttEngine: Send + Sync {
     /// Samples must be PCM float32, mono, 16kHz.
     fn push_audio(&mut self, pcm: &[f32]);
 
+    /// Set how many samples are kept in RAM before spilling to disk
+    fn set_memory_limit(&mut self, samples: usize);
+
+    /// Set the duration and silence thresholds checked before sending audio
+    fn set_guards(&mut self, guards: AudioGuards);
+
+    /// Set how fast speech is slowed down before sending audio
+    fn set_speed_normalization(&mut self, speed: SpeedNormalization);
+
+    /// Format the engine wants its audio uploaded in
+    fn audio_format(&self) -> AudioFormat {
+        AudioFormat::WAV_16K
+    }
+
+    /// Set the sample rate the audio is resampled to before upload
+    ///
+    /// Ignored by the engines whose API only takes one format.
+    fn set_upload_sample_rate(&mut self, _sample_rate: u32) {}
+
+    /// Set the codec the audio is compressed with before upload
+    ///
+    /// Ignored by the engines whose API only takes WAV.
+    fn set_upload_codec(&mut self, _codec: UploadCodec) {}
+
     /// Retrieve the next transcription event
     ///
     /// Returns `None` if no event is available.
@@ -117,6 +149,18 @@ pub trait SttEngine: Send + Sync {
     /// Reset the engine state
     fn reset(&mut self);
 
+    /// Transcripts arrive while audio is still pushed (live `Partial` and
+    /// `Final` events), not only on flush
+    fn streams_partials(&self) -> bool {
+        false
+    }
+
+    /// Samples answered without calling the provider since the last call
+    /// (transcript cache), not billed
+    fn take_cached_samples(&mut self) -> usize {
+        0
+    }
+
     /// Return the engine name
     fn name(&self) -> &str;
 
@@ -4,85 +4,92 @@
 //! Uses the multimodal generateContent API with base64-encoded audio.
 
 use super::engine::{Language, SttEngine, SttError, SttEvent};
+use super::guards::AudioGuards;
+use super::request_log::{record_exchange, RequestSummary};
+use super::responses::{parse_response, GeminiResponse};
+use super::worker::RequestWorker;
+use crate::audio::{encode_upload, AudioAccumulator, AudioFormat, SpeedNormalization, UploadCodec};
 use base64::Engine as _;
 use std::collections::VecDeque;
-use std::io::Cursor;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 
 /// STT engine based on Gemini (Google AI)
 pub struct GeminiEngine {
     api_key: String,
     language: Language,
-    /// Accumulates all audio until flush
-    audio_buffer: Vec<f32>,
-    /// Events ready to be consumed
-    shared_events: Arc<Mutex<VecDeque<SttEvent>>>,
+    /// Accumulates all audio until flush (spilled to disk when long)
+    audio_buffer: AudioAccumulator,
+    /// Duration and silence thresholds checked before sending
+    guards: AudioGuards,
+    /// Slowing down of fast speech before sending
+    speed: SpeedNormalization,
+    /// Upload format, 16kHz WAV unless another sample rate is configured
+    format: AudioFormat,
+    /// Events ready to be consumed, tagged with their session generation
+    shared_events: Arc<Mutex<VecDeque<(u64, SttEvent)>>>,
     /// Flag indicating a request is in progress
     pending: Arc<AtomicBool>,
+    /// Current session generation, bumped on reset
+    generation: Arc<AtomicU64>,
+    /// API root, overridden to target a local mock provider
+    api_base: String,
     http_client: reqwest::Client,
 }
 
 impl GeminiEngine {
+    /// Transcription model
+    const MODEL: &'static str = "gemini-2.5-flash";
+
+    /// Production API root
+    const API_BASE: &'static str = "https://generativelanguage.googleapis.com/v1beta";
+
     /// Create a new instance with an API key
     pub fn with_api_key(api_key: String) -> Self {
         Self {
             api_key,
             language: Language::Auto,
-            audio_buffer: Vec::new(),
+            audio_buffer: AudioAccumulator::default(),
+            guards: AudioGuards::default(),
+            speed: SpeedNormalization::default(),
+            format: AudioFormat::WAV_16K,
             shared_events: Arc::new(Mutex::new(VecDeque::new())),
             pending: Arc::new(AtomicBool::new(false)),
+            generation: Arc::new(AtomicU64::new(0)),
+            api_base: Self::API_BASE.to_string(),
             http_client: reqwest::Client::new(),
         }
     }
 
-    /// Convert f32 samples to WAV bytes
-    fn samples_to_wav(samples: &[f32]) -> Result<Vec<u8>, SttError> {
-        let spec = hound::WavSpec {
-            channels: 1,
-            sample_rate: 16000,
-            bits_per_sample: 16,
-            sample_format: hound::SampleFormat::Int,
-        };
-
-        let mut cursor = Cursor::new(Vec::new());
-        {
-            let mut writer = hound::WavWriter::new(&mut cursor, spec)
-                .map_err(|e| SttError::InferenceError(format!("WAV error: {}", e)))?;
-
-            for &sample in samples {
-                let sample_i16 = (sample * 32767.0).clamp(-32768.0, 32767.0) as i16;
-                writer
-                    .write_sample(sample_i16)
-                    .map_err(|e| SttError::InferenceError(format!("WAV write error: {}", e)))?;
-            }
-
-            writer
-                .finalize()
-                .map_err(|e| SttError::InferenceError(format!("WAV finalize error: {}", e)))?;
-        }
-
-        Ok(cursor.into_inner())
+    /// Send the requests to `api_base` instead of the provider (simulation)
+    #[cfg_attr(not(feature = "sim"), allow(dead_code))]
+    pub fn with_api_base(mut self, api_base: &str) -> Self {
+        self.api_base = api_base.trim_end_matches('/').to_string();
+        self
     }
 
     /// Run inference via the Gemini generateContent API
     async fn transcribe_async(
         client: reqwest::Client,
         api_key: String,
-        audio_data: Vec<f32>,
+        api_base: String,
+        mut audio_data: AudioAccumulator,
+        format: AudioFormat,
         language: Option<String>,
     ) -> Result<String, SttError> {
-        let wav_data = Self::samples_to_wav(&audio_data)?;
-        let audio_base64 = base64::engine::general_purpose::STANDARD.encode(&wav_data);
+        let (audio_bytes, format) = encode_upload(&mut audio_data, format)
+            .map_err(|e| SttError::InferenceError(e.to_string()))?;
+        let audio_base64 = base64::engine::general_purpose::STANDARD.encode(&audio_bytes);
 
         let duration_secs = audio_data.len() as f32 / 16000.0;
         tracing::info!(
-            "Sending to Gemini: {:.1}s audio, {} bytes WAV",
+            "Sending to Gemini: {:.1}s audio, {} bytes",
             duration_secs,
-            wav_data.len()
+            audio_bytes.len()
         );
+        let audio_size = audio_bytes.len();
 
-        let prompt = match language {
+        let prompt = match language.as_deref() {
             Some(lang) => format!(
                 "Transcribe this audio exactly as spoken in {}. Return only the transcription, nothing else.",
                 lang
@@ -95,7 +102,7 @@ impl GeminiEngine {
                 "parts": [
                     {
                         "inline_data": {
-                            "mime_type": "audio/wav",
+                            "mime_type": format.mime_type(),
                             "data": audio_base64
                         }
                     },
@@ -106,9 +113,7 @@ impl GeminiEngine {
             }]
         });
 
-        let url = format!(
-            "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.5-flash:generateContent"
-        );
+        let url = format!("{}/models/{}:generateContent", api_base, Self::MODEL);
 
         let response = client
             .post(&url)
@@ -119,27 +124,30 @@ impl GeminiEngine {
             .await
             .map_err(|e| SttError::InferenceError(format!("Network error: {}", e)))?;
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| SttError::InferenceError(format!("Network error: {}", e)))?;
+        let request = RequestSummary {
+            url: &url,
+            model: Self::MODEL,
+            language: language.as_deref(),
+            audio_bytes: audio_size,
+            duration_secs,
+        };
+        record_exchange("Gemini", &request, status.as_u16(), &body);
+
+        if !status.is_success() {
             return Err(SttError::InferenceError(format!(
                 "Gemini API error {}: {}",
-                status, error_text
+                status, body
             )));
         }
 
-        let json: serde_json::Value = response
-            .json()
-            .await
-            .map_err(|e| SttError::InferenceError(format!("JSON parse error: {}", e)))?;
-
-        let text = json["candidates"][0]["content"]["parts"][0]["text"]
-            .as_str()
-            .unwrap_or("")
-            .trim()
-            .to_string();
+        let parsed: GeminiResponse = parse_response("Gemini", &body)?;
 
-        Ok(text)
+        Ok(parsed.into_text()?.trim().to_string())
     }
 
     /// Send all accumulated audio buffer to the API
@@ -148,49 +156,33 @@ impl GeminiEngine {
             return;
         }
 
-        // Ignore if less than 1 second of audio
-        if self.audio_buffer.len() < 16000 {
-            tracing::debug!(
-                "Audio too short ({} samples), skipped",
-                self.audio_buffer.len()
-            );
+        // Ignore audio too short or too quiet to hold speech
+        if let Some(event) = self.guards.check(&mut self.audio_buffer) {
             self.audio_buffer.clear();
+            if let Ok(mut events) = self.shared_events.lock() {
+                events.push_back((self.generation.load(Ordering::SeqCst), event));
+            }
             return;
         }
 
-        let audio_data = std::mem::take(&mut self.audio_buffer);
+        let audio_data = self.audio_buffer.take();
+        let speed = self.speed;
+        let format = self.format;
         let client = self.http_client.clone();
         let api_key = self.api_key.clone();
+        let api_base = self.api_base.clone();
         let language = match &self.language {
             Language::Auto => None,
             lang => Some(lang.code().to_string()),
         };
-        let shared_events = Arc::clone(&self.shared_events);
-        let pending = Arc::clone(&self.pending);
-
-        pending.store(true, Ordering::SeqCst);
+        let worker = RequestWorker::new("Gemini", &self.shared_events, &self.pending, &self.generation);
 
         let duration = audio_data.len() as f32 / 16000.0;
         tracing::info!("Gemini transcription of {:.1}s audio...", duration);
 
-        std::thread::spawn(move || {
-            let rt = tokio::runtime::Runtime::new().unwrap();
-            rt.block_on(async {
-                match Self::transcribe_async(client, api_key, audio_data, language).await {
-                    Ok(text) => {
-                        if !text.is_empty() {
-                            tracing::info!("Gemini result: {}", text);
-                            if let Ok(mut events) = shared_events.lock() {
-                                events.push_back(SttEvent::Final(text));
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        tracing::error!("Gemini error: {}", e);
-                    }
-                }
-                pending.store(false, Ordering::SeqCst);
-            });
+        worker.spawn(move || {
+            let audio_data = speed.apply(audio_data);
+            Self::transcribe_async(client, api_key, api_base, audio_data, format, language)
         });
     }
 
@@ -232,12 +224,40 @@ impl SttEngine for GeminiEngine {
         self.audio_buffer.extend_from_slice(pcm);
     }
 
+    fn set_memory_limit(&mut self, samples: usize) {
+        self.audio_buffer.set_memory_limit(samples);
+    }
+
+    fn set_guards(&mut self, guards: AudioGuards) {
+        self.guards = guards;
+    }
+
+    fn set_speed_normalization(&mut self, speed: SpeedNormalization) {
+        self.speed = speed;
+    }
+
+    fn audio_format(&self) -> AudioFormat {
+        self.format
+    }
+
+    fn set_upload_sample_rate(&mut self, sample_rate: u32) {
+        self.format.sample_rate = sample_rate;
+    }
+
+    fn set_upload_codec(&mut self, codec: UploadCodec) {
+        self.format.encoding = codec.encoding();
+    }
+
     fn poll(&mut self) -> Option<SttEvent> {
-        if let Ok(mut events) = self.shared_events.lock() {
-            events.pop_front()
-        } else {
-            None
+        let current = self.generation.load(Ordering::SeqCst);
+        let mut events = self.shared_events.lock().ok()?;
+        while let Some((generation, event)) = events.pop_front() {
+            if generation == current {
+                return Some(event);
+            }
+            tracing::debug!("Dropping Gemini event from stale session {}", generation);
         }
+        None
     }
 
     fn flush(&mut self) {
@@ -252,6 +272,9 @@ impl SttEngine for GeminiEngine {
 
     fn reset(&mut self) {
         self.audio_buffer.clear();
+        // Late responses from the previous session will be discarded
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        self.pending.store(false, Ordering::SeqCst);
         if let Ok(mut events) = self.shared_events.lock() {
             events.clear();
         }
@@ -272,9 +295,14 @@ impl Default for GeminiEngine {
         Self {
             api_key: String::new(),
             language: Language::Auto,
-            audio_buffer: Vec::new(),
+            audio_buffer: AudioAccumulator::default(),
+            guards: AudioGuards::default(),
+            speed: SpeedNormalization::default(),
+            format: AudioFormat::WAV_16K,
             shared_events: Arc::new(Mutex::new(VecDeque::new())),
             pending: Arc::new(AtomicBool::new(false)),
+            generation: Arc::new(AtomicU64::new(0)),
+            api_base: Self::API_BASE.to_string(),
             http_client: reqwest::Client::new(),
         }
     }
@@ -0,0 +1,709 @@
+//! Google Cloud Speech-to-Text v2, streamed over gRPC
+//!
+//! Audio is sent as it is captured on a `StreamingRecognize` call, which
+//! returns interim results while the user speaks (`Partial` events) and a
+//! final result, with word time offsets, at each end of utterance. The
+//! call is authenticated with an API key or a service account key. A
+//! stream accepts about five minutes of audio: longer dictations are sent
+//! on consecutive streams, the next one opened once the previous one has
+//! returned its last result.
+
+use super::engine::{Language, SttEngine, SttError, SttEvent};
+use super::guards::AudioGuards;
+use crate::audio::SpeedNormalization;
+use std::collections::VecDeque;
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc as async_mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::metadata::MetadataValue;
+use tonic::transport::{Channel, ClientTlsConfig, Endpoint};
+
+/// Sample rate of the audio pushed to the engines, sent as is (LINEAR16)
+const CAPTURE_RATE: u32 = 16000;
+
+/// Largest audio payload of one streaming request
+const MAX_REQUEST_BYTES: usize = 15 * 1024;
+
+/// Audio sent on one stream before switching to a new one (4m30s of pcm16)
+const STREAM_BYTES: usize = 270 * CAPTURE_RATE as usize * 2;
+
+/// Longest wait for the last results after the end of the audio
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Scope of the access tokens requested for a service account
+const TOKEN_SCOPE: &str = "https://www.googleapis.com/auth/cloud-platform";
+
+/// Settings of the Google Cloud Speech-to-Text engine
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct GoogleSpeech {
+    /// API key, used instead of the service account when set
+    #[serde(default)]
+    pub api_key: String,
+    /// Service account key file (JSON) downloaded from the Cloud console
+    #[serde(default)]
+    pub service_account_path: String,
+    /// Cloud project billed, empty = the project of the service account
+    #[serde(default)]
+    pub project_id: String,
+    /// Region of the recognizer ("global", "us", "eu", "europe-west4"...)
+    #[serde(default = "default_location")]
+    pub location: String,
+    /// Recognition model ("chirp_3", "long", "short"...)
+    #[serde(default = "default_model")]
+    pub model: String,
+}
+
+fn default_location() -> String {
+    "us".to_string()
+}
+
+fn default_model() -> String {
+    "chirp_3".to_string()
+}
+
+impl Default for GoogleSpeech {
+    fn default() -> Self {
+        Self {
+            api_key: String::new(),
+            service_account_path: String::new(),
+            project_id: String::new(),
+            location: default_location(),
+            model: default_model(),
+        }
+    }
+}
+
+impl GoogleSpeech {
+    /// Whether an API key or a service account is configured
+    pub fn has_credentials(&self) -> bool {
+        !self.api_key.trim().is_empty() || !self.service_account_path.trim().is_empty()
+    }
+}
+
+/// Messages of `google.cloud.speech.v2`, limited to the fields in use
+mod proto {
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct StreamingRecognizeRequest {
+        #[prost(string, tag = "3")]
+        pub recognizer: String,
+        #[prost(oneof = "streaming_recognize_request::StreamingRequest", tags = "6, 5")]
+        pub streaming_request: Option<streaming_recognize_request::StreamingRequest>,
+    }
+
+    pub mod streaming_recognize_request {
+        #[derive(Clone, PartialEq, prost::Oneof)]
+        pub enum StreamingRequest {
+            #[prost(message, tag = "6")]
+            StreamingConfig(super::StreamingRecognitionConfig),
+            #[prost(bytes, tag = "5")]
+            Audio(Vec<u8>),
+        }
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct StreamingRecognitionConfig {
+        #[prost(message, optional, tag = "1")]
+        pub config: Option<RecognitionConfig>,
+        #[prost(message, optional, tag = "2")]
+        pub streaming_features: Option<StreamingRecognitionFeatures>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct StreamingRecognitionFeatures {
+        #[prost(bool, tag = "2")]
+        pub interim_results: bool,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct RecognitionConfig {
+        #[prost(message, optional, tag = "8")]
+        pub explicit_decoding_config: Option<ExplicitDecodingConfig>,
+        #[prost(string, tag = "9")]
+        pub model: String,
+        #[prost(string, repeated, tag = "10")]
+        pub language_codes: Vec<String>,
+        #[prost(message, optional, tag = "2")]
+        pub features: Option<RecognitionFeatures>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ExplicitDecodingConfig {
+        /// 1 = LINEAR16
+        #[prost(int32, tag = "1")]
+        pub encoding: i32,
+        #[prost(int32, tag = "2")]
+        pub sample_rate_hertz: i32,
+        #[prost(int32, tag = "3")]
+        pub audio_channel_count: i32,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct RecognitionFeatures {
+        #[prost(bool, tag = "2")]
+        pub enable_word_time_offsets: bool,
+        #[prost(bool, tag = "4")]
+        pub enable_automatic_punctuation: bool,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct StreamingRecognizeResponse {
+        #[prost(message, repeated, tag = "6")]
+        pub results: Vec<StreamingRecognitionResult>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct StreamingRecognitionResult {
+        #[prost(message, repeated, tag = "1")]
+        pub alternatives: Vec<SpeechRecognitionAlternative>,
+        #[prost(bool, tag = "2")]
+        pub is_final: bool,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct SpeechRecognitionAlternative {
+        #[prost(string, tag = "1")]
+        pub transcript: String,
+        #[prost(message, repeated, tag = "3")]
+        pub words: Vec<WordInfo>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct WordInfo {
+        #[prost(message, optional, tag = "1")]
+        pub start_offset: Option<Duration>,
+        #[prost(message, optional, tag = "2")]
+        pub end_offset: Option<Duration>,
+        #[prost(string, tag = "3")]
+        pub word: String,
+    }
+
+    /// `google.protobuf.Duration`
+    #[derive(Clone, Copy, PartialEq, prost::Message)]
+    pub struct Duration {
+        #[prost(int64, tag = "1")]
+        pub seconds: i64,
+        #[prost(int32, tag = "2")]
+        pub nanos: i32,
+    }
+
+    impl Duration {
+        pub fn as_millis(&self) -> i64 {
+            self.seconds * 1000 + i64::from(self.nanos) / 1_000_000
+        }
+    }
+}
+
+/// Fields of a service account key file in use
+#[derive(Debug, Clone, serde::Deserialize)]
+struct ServiceAccountKey {
+    client_email: String,
+    private_key: String,
+    #[serde(default = "default_token_uri")]
+    token_uri: String,
+    #[serde(default)]
+    project_id: String,
+}
+
+fn default_token_uri() -> String {
+    "https://oauth2.googleapis.com/token".to_string()
+}
+
+/// Access token of a service account, with the time it stops being valid
+type CachedToken = Arc<Mutex<Option<(String, Instant)>>>;
+
+/// How the calls are authenticated
+#[derive(Clone)]
+enum Credentials {
+    ApiKey(String),
+    ServiceAccount { key: ServiceAccountKey, token: CachedToken },
+}
+
+impl Credentials {
+    /// Metadata entry added to each call
+    async fn header(&self) -> Result<(&'static str, String), SttError> {
+        match self {
+            Credentials::ApiKey(api_key) => Ok(("x-goog-api-key", api_key.clone())),
+            Credentials::ServiceAccount { key, token } => {
+                let cached = token.lock().ok().and_then(|token| token.clone());
+                if let Some((access_token, valid_until)) = cached {
+                    if Instant::now() < valid_until {
+                        return Ok(("authorization", format!("Bearer {}", access_token)));
+                    }
+                }
+                let (access_token, lifetime) = fetch_access_token(key).await?;
+                // Renewed a minute early, so a token never expires mid-stream
+                let valid_until = Instant::now() + lifetime.saturating_sub(Duration::from_secs(60));
+                if let Ok(mut token) = token.lock() {
+                    *token = Some((access_token.clone(), valid_until));
+                }
+                Ok(("authorization", format!("Bearer {}", access_token)))
+            }
+        }
+    }
+}
+
+/// Exchange a JWT signed with the service account key for an access token
+async fn fetch_access_token(key: &ServiceAccountKey) -> Result<(String, Duration), SttError> {
+    #[derive(serde::Serialize)]
+    struct Claims<'a> {
+        iss: &'a str,
+        scope: &'a str,
+        aud: &'a str,
+        iat: i64,
+        exp: i64,
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let claims = Claims {
+        iss: &key.client_email,
+        scope: TOKEN_SCOPE,
+        aud: &key.token_uri,
+        iat: now,
+        exp: now + 3600,
+    };
+    let signing_key = jsonwebtoken::EncodingKey::from_rsa_pem(key.private_key.as_bytes())
+        .map_err(|e| SttError::ModelLoadError(format!("Invalid service account key: {}", e)))?;
+    let assertion = jsonwebtoken::encode(&jsonwebtoken::Header::new(jsonwebtoken::Algorithm::RS256), &claims, &signing_key)
+        .map_err(|e| SttError::InferenceError(format!("Cannot sign the token request: {}", e)))?;
+
+    let response = reqwest::Client::new()
+        .post(&key.token_uri)
+        .form(&[("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"), ("assertion", &assertion)])
+        .send()
+        .await
+        .map_err(|e| SttError::InferenceError(format!("Network error: {}", e)))?;
+    let status = response.status();
+    let body: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| SttError::InferenceError(format!("Network error: {}", e)))?;
+    if !status.is_success() {
+        let reason = body["error_description"].as_str().or(body["error"].as_str()).unwrap_or("unknown error");
+        return Err(SttError::InferenceError(format!("Google token error (HTTP {}): {}", status, reason)));
+    }
+    let access_token = body["access_token"]
+        .as_str()
+        .ok_or_else(|| SttError::InferenceError("Google token response without access_token".to_string()))?;
+    let lifetime = Duration::from_secs(body["expires_in"].as_u64().unwrap_or(3600));
+    Ok((access_token.to_string(), lifetime))
+}
+
+/// Recognition settings of one dictation
+#[derive(Clone)]
+struct StreamSettings {
+    endpoint: String,
+    /// `projects/{project}/locations/{location}/recognizers/_`
+    recognizer: String,
+    model: String,
+    language_code: String,
+    credentials: Credentials,
+}
+
+/// Connection opened for one dictation
+struct GoogleSession {
+    /// pcm16 audio, dropped to end the dictation
+    audio: async_mpsc::UnboundedSender<Vec<u8>>,
+    /// Closed by the session task when it ends (locked only to be `Sync`)
+    events: Mutex<mpsc::Receiver<SttEvent>>,
+}
+
+impl GoogleSession {
+    /// Connect from a dedicated thread
+    fn open(settings: StreamSettings) -> Self {
+        let (audio, audio_rx) = async_mpsc::unbounded_channel();
+        let (events_tx, events) = mpsc::channel();
+        std::thread::spawn(move || {
+            let rt = match tokio::runtime::Runtime::new() {
+                Ok(rt) => rt,
+                Err(e) => {
+                    let _ = events_tx.send(SttEvent::Error(format!("Cannot start runtime: {}", e)));
+                    return;
+                }
+            };
+            if let Err(e) = rt.block_on(run_session(settings, audio_rx, &events_tx)) {
+                tracing::error!("Google Speech error: {}", e);
+                let _ = events_tx.send(SttEvent::Error(e.to_string()));
+            }
+        });
+        Self {
+            audio,
+            events: Mutex::new(events),
+        }
+    }
+}
+
+/// Send the audio on consecutive streams until the dictation ends
+async fn run_session(
+    settings: StreamSettings,
+    mut audio: async_mpsc::UnboundedReceiver<Vec<u8>>,
+    events: &mpsc::Sender<SttEvent>,
+) -> Result<(), SttError> {
+    let network = |e: tonic::transport::Error| SttError::InferenceError(format!("Network error: {}", e));
+    let channel = Endpoint::from_shared(settings.endpoint.clone())
+        .map_err(network)?
+        .tls_config(ClientTlsConfig::new().with_native_roots())
+        .map_err(network)?
+        .connect()
+        .await
+        .map_err(network)?;
+    tracing::info!("Google Speech session opened");
+
+    while stream_once(&channel, &settings, &mut audio, events).await? {
+        tracing::debug!("Google Speech stream limit reached, opening the next stream");
+    }
+    tracing::info!("Google Speech session closed");
+    Ok(())
+}
+
+/// Drive one `StreamingRecognize` call; `true` if audio is left for another one
+async fn stream_once(
+    channel: &Channel,
+    settings: &StreamSettings,
+    audio: &mut async_mpsc::UnboundedReceiver<Vec<u8>>,
+    events: &mpsc::Sender<SttEvent>,
+) -> Result<bool, SttError> {
+    use proto::streaming_recognize_request::StreamingRequest;
+
+    let status_error = |status: tonic::Status| {
+        SttError::InferenceError(format!("Google Speech error ({:?}): {}", status.code(), status.message()))
+    };
+
+    let (requests, requests_rx) = async_mpsc::unbounded_channel();
+    let config = proto::StreamingRecognitionConfig {
+        config: Some(proto::RecognitionConfig {
+            explicit_decoding_config: Some(proto::ExplicitDecodingConfig {
+                encoding: 1,
+                sample_rate_hertz: CAPTURE_RATE as i32,
+                audio_channel_count: 1,
+            }),
+            model: settings.model.clone(),
+            language_codes: vec![settings.language_code.clone()],
+            features: Some(proto::RecognitionFeatures {
+                enable_word_time_offsets: true,
+                enable_automatic_punctuation: true,
+            }),
+        }),
+        streaming_features: Some(proto::StreamingRecognitionFeatures { interim_results: true }),
+    };
+    let _ = requests.send(proto::StreamingRecognizeRequest {
+        recognizer: settings.recognizer.clone(),
+        streaming_request: Some(StreamingRequest::StreamingConfig(config)),
+    });
+
+    let mut request = tonic::Request::new(UnboundedReceiverStream::new(requests_rx));
+    let (name, value) = settings.credentials.header().await?;
+    let value = MetadataValue::try_from(value).map_err(|e| SttError::InferenceError(e.to_string()))?;
+    request.metadata_mut().insert(name, value);
+    let routing = MetadataValue::try_from(format!("recognizer={}", settings.recognizer))
+        .map_err(|e| SttError::InferenceError(e.to_string()))?;
+    request.metadata_mut().insert("x-goog-request-params", routing);
+
+    let mut grpc = tonic::client::Grpc::new(channel.clone());
+    grpc.ready()
+        .await
+        .map_err(|e| SttError::InferenceError(format!("Network error: {}", e)))?;
+    let path = PathAndQuery::from_static("/google.cloud.speech.v2.Speech/StreamingRecognize");
+    let codec = tonic::codec::ProstCodec::<proto::StreamingRecognizeRequest, proto::StreamingRecognizeResponse>::default();
+    let mut responses = grpc.streaming(request, path, codec).await.map_err(status_error)?.into_inner();
+
+    // Dropped to half-close the stream: the server then sends its last results
+    let mut requests = Some(requests);
+    let mut sent_bytes = 0;
+    let mut more = false;
+    loop {
+        tokio::select! {
+            chunk = audio.recv(), if requests.is_some() => {
+                let Some(chunk) = chunk else {
+                    requests = None;
+                    continue;
+                };
+                sent_bytes += chunk.len();
+                if let Some(requests) = &requests {
+                    for piece in chunk.chunks(MAX_REQUEST_BYTES) {
+                        let _ = requests.send(proto::StreamingRecognizeRequest {
+                            recognizer: String::new(),
+                            streaming_request: Some(StreamingRequest::Audio(piece.to_vec())),
+                        });
+                    }
+                }
+                if sent_bytes >= STREAM_BYTES {
+                    requests = None;
+                    more = true;
+                }
+            }
+            response = responses.message() => {
+                let Some(response) = response.map_err(status_error)? else {
+                    break;
+                };
+                send_results(&response, events);
+            }
+        }
+    }
+    Ok(more)
+}
+
+/// Turn the results of a response into events: finals first, then the interim text
+fn send_results(response: &proto::StreamingRecognizeResponse, events: &mpsc::Sender<SttEvent>) {
+    let mut interim = Vec::new();
+    for result in &response.results {
+        let Some(alternative) = result.alternatives.first() else {
+            continue;
+        };
+        let transcript = alternative.transcript.trim();
+        if transcript.is_empty() {
+            continue;
+        }
+        if !result.is_final {
+            interim.push(transcript);
+            continue;
+        }
+        let offset = |word: Option<&proto::WordInfo>, end: bool| {
+            word.and_then(|word| if end { word.end_offset } else { word.start_offset })
+                .map(|offset| offset.as_millis())
+                .unwrap_or_default()
+        };
+        tracing::debug!(
+            "Google Speech final, {} words from {} to {} ms",
+            alternative.words.len(),
+            offset(alternative.words.first(), false),
+            offset(alternative.words.last(), true)
+        );
+        let _ = events.send(SttEvent::Final(transcript.to_string()));
+    }
+    if !interim.is_empty() {
+        let _ = events.send(SttEvent::Partial(interim.join(" ")));
+    }
+}
+
+/// BCP-47 code of a language, with the most common region for the bare ones
+fn language_code(language: &Language) -> String {
+    match language {
+        Language::Auto => "auto",
+        Language::French => "fr-FR",
+        Language::English => "en-US",
+        Language::Spanish => "es-ES",
+        Language::German => "de-DE",
+        Language::Italian => "it-IT",
+        Language::Portuguese => "pt-BR",
+        Language::Other(code) => code,
+    }
+    .to_string()
+}
+
+/// STT engine streaming to Google Cloud Speech-to-Text v2
+pub struct GoogleSpeechEngine {
+    credentials: Credentials,
+    project_id: String,
+    location: String,
+    model: String,
+    language: Language,
+    /// Minimum duration checked on flush (speech detection is server side)
+    guards: AudioGuards,
+    /// Connection of the current dictation, opened on the first samples
+    session: Option<GoogleSession>,
+    /// Samples pushed in the current dictation
+    pushed_samples: usize,
+    /// A transcript was received in the current dictation
+    transcribed: bool,
+    /// Events ready to be consumed
+    events: VecDeque<SttEvent>,
+}
+
+impl GoogleSpeechEngine {
+    /// Build the engine, reading the service account key when no API key is set
+    pub fn start(settings: &GoogleSpeech) -> Result<Self, SttError> {
+        let (credentials, key_project) = if !settings.api_key.trim().is_empty() {
+            (Credentials::ApiKey(settings.api_key.trim().to_string()), String::new())
+        } else if !settings.service_account_path.trim().is_empty() {
+            let path = settings.service_account_path.trim();
+            let data = std::fs::read_to_string(path)
+                .map_err(|e| SttError::ModelNotFound(format!("Service account key {}: {}", path, e)))?;
+            let key: ServiceAccountKey = serde_json::from_str(&data)
+                .map_err(|e| SttError::ModelLoadError(format!("Invalid service account key: {}", e)))?;
+            let project = key.project_id.clone();
+            let token = Arc::new(Mutex::new(None));
+            (Credentials::ServiceAccount { key, token }, project)
+        } else {
+            return Err(SttError::ModelNotFound(
+                "Google API key or service account required".to_string(),
+            ));
+        };
+
+        let project_id = match settings.project_id.trim() {
+            "" => key_project,
+            project => project.to_string(),
+        };
+        if project_id.is_empty() {
+            return Err(SttError::ModelNotFound("Google Cloud project required".to_string()));
+        }
+
+        Ok(Self {
+            credentials,
+            project_id,
+            location: settings.location.trim().to_string(),
+            model: settings.model.trim().to_string(),
+            language: Language::Auto,
+            guards: AudioGuards::default(),
+            session: None,
+            pushed_samples: 0,
+            transcribed: false,
+            events: VecDeque::new(),
+        })
+    }
+
+    /// Session of the current dictation, connecting on first use
+    fn session(&mut self) -> &GoogleSession {
+        let endpoint = match self.location.as_str() {
+            "" | "global" => "https://speech.googleapis.com".to_string(),
+            location => format!("https://{}-speech.googleapis.com", location),
+        };
+        let location = if self.location.is_empty() { "global" } else { &self.location };
+        let settings = StreamSettings {
+            endpoint,
+            recognizer: format!("projects/{}/locations/{}/recognizers/_", self.project_id, location),
+            model: self.model.clone(),
+            language_code: language_code(&self.language),
+            credentials: self.credentials.clone(),
+        };
+        self.session.get_or_insert_with(|| GoogleSession::open(settings))
+    }
+
+    /// Move the events received so far to the queue
+    fn drain_session(&mut self) {
+        let received: Vec<SttEvent> = match self.session.as_ref().and_then(|session| session.events.lock().ok()) {
+            Some(events) => events.try_iter().collect(),
+            None => return,
+        };
+        for event in received {
+            self.record(event);
+        }
+    }
+
+    /// Forget the current dictation, closing its connection
+    fn end_session(&mut self) {
+        // Dropping the sender ends the session task
+        self.session = None;
+        self.pushed_samples = 0;
+        self.transcribed = false;
+    }
+
+    fn record(&mut self, event: SttEvent) {
+        if matches!(event, SttEvent::Final(_)) {
+            self.transcribed = true;
+        }
+        self.events.push_back(event);
+    }
+}
+
+impl SttEngine for GoogleSpeechEngine {
+    fn load(service_account_path: &str) -> Result<Self, SttError> {
+        tracing::info!("Initializing Google Cloud Speech-to-Text");
+        Self::start(&GoogleSpeech {
+            service_account_path: service_account_path.to_string(),
+            ..GoogleSpeech::default()
+        })
+    }
+
+    fn set_language(&mut self, language: Language) {
+        self.language = language.clone();
+        tracing::debug!("Google Speech language set: {:?}", language);
+    }
+
+    fn language(&self) -> &Language {
+        &self.language
+    }
+
+    fn push_audio(&mut self, pcm: &[f32]) {
+        if pcm.is_empty() {
+            return;
+        }
+        self.pushed_samples += pcm.len();
+        let bytes: Vec<u8> = pcm
+            .iter()
+            .flat_map(|&sample| ((sample * 32767.0).clamp(-32768.0, 32767.0) as i16).to_le_bytes())
+            .collect();
+        let _ = self.session().audio.send(bytes);
+    }
+
+    fn set_memory_limit(&mut self, _samples: usize) {
+        // Nothing is buffered, audio leaves as it is captured
+    }
+
+    fn set_guards(&mut self, guards: AudioGuards) {
+        self.guards = guards;
+    }
+
+    fn set_speed_normalization(&mut self, _speed: SpeedNormalization) {
+        // Cannot stretch audio that is already sent
+    }
+
+    fn poll(&mut self) -> Option<SttEvent> {
+        self.drain_session();
+        self.events.pop_front()
+    }
+
+    fn flush(&mut self) {
+        tracing::info!(
+            "Flush Google Speech: {} samples ({:.1}s)",
+            self.pushed_samples,
+            self.pushed_samples as f32 / CAPTURE_RATE as f32
+        );
+        if self.pushed_samples < self.guards.min_samples {
+            let samples_to_ms = |samples: usize| (samples as u64 * 1000) / CAPTURE_RATE as u64;
+            self.events.push_back(SttEvent::TooShort {
+                duration_ms: samples_to_ms(self.pushed_samples),
+                min_duration_ms: samples_to_ms(self.guards.min_samples),
+            });
+            self.end_session();
+            return;
+        }
+
+        let Some(session) = self.session.take() else {
+            self.end_session();
+            return;
+        };
+        // End of the audio: the last stream is half-closed and returns its final results
+        drop(session.audio);
+        let events = session.events.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let deadline = Instant::now() + FLUSH_TIMEOUT;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match events.recv_timeout(remaining) {
+                Ok(event) => self.record(event),
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    tracing::warn!("Timeout waiting for Google Speech results");
+                    self.events.push_back(SttEvent::Error("Google Speech timed out".to_string()));
+                    break;
+                }
+            }
+        }
+
+        let failed = self.events.iter().any(|event| matches!(event, SttEvent::Error(_)));
+        if !self.transcribed && !failed {
+            tracing::info!("Google Speech result: no speech");
+            self.events.push_back(SttEvent::NoSpeech);
+        }
+        self.end_session();
+    }
+
+    fn reset(&mut self) {
+        self.end_session();
+        self.events.clear();
+        tracing::debug!("Google Speech engine reset");
+    }
+
+    fn streams_partials(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &str {
+        "Google Speech"
+    }
+
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
@@ -4,125 +4,134 @@
 //! Accumulates all audio, then sends in a single call on flush (stop).
 
 use super::engine::{Language, SttEngine, SttError, SttEvent};
+use super::guards::AudioGuards;
+use super::request_log::{record_exchange, RequestSummary};
+use super::responses::{parse_response, TranscriptionResponse};
+use super::worker::RequestWorker;
+use crate::audio::{encode, AudioAccumulator, AudioFormat, SpeedNormalization};
 use std::collections::VecDeque;
-use std::io::Cursor;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 
 /// STT engine based on the Groq Whisper API
 pub struct GroqEngine {
     api_key: String,
     language: Language,
-    /// Accumulates all audio until flush
-    audio_buffer: Vec<f32>,
-    /// Events ready to be consumed
-    shared_events: Arc<Mutex<VecDeque<SttEvent>>>,
+    /// Accumulates all audio until flush (spilled to disk when long)
+    audio_buffer: AudioAccumulator,
+    /// Duration and silence thresholds checked before sending
+    guards: AudioGuards,
+    /// Slowing down of fast speech before sending
+    speed: SpeedNormalization,
+    /// Upload format, 16kHz WAV unless another sample rate is configured
+    format: AudioFormat,
+    /// Events ready to be consumed, tagged with their session generation
+    shared_events: Arc<Mutex<VecDeque<(u64, SttEvent)>>>,
     /// Flag indicating a request is in progress
     pending: Arc<AtomicBool>,
+    /// Current session generation, bumped on reset
+    generation: Arc<AtomicU64>,
+    /// API root, overridden to target a local mock provider
+    api_base: String,
     http_client: reqwest::Client,
 }
 
 impl GroqEngine {
+    /// Transcription model
+    const MODEL: &'static str = "whisper-large-v3-turbo";
+
+    /// Production API root
+    const API_BASE: &'static str = "https://api.groq.com/openai/v1";
+
     pub fn with_api_key(api_key: String) -> Self {
         Self {
             api_key,
             language: Language::Auto,
-            audio_buffer: Vec::new(),
+            audio_buffer: AudioAccumulator::default(),
+            guards: AudioGuards::default(),
+            speed: SpeedNormalization::default(),
+            format: AudioFormat::WAV_16K,
             shared_events: Arc::new(Mutex::new(VecDeque::new())),
             pending: Arc::new(AtomicBool::new(false)),
+            generation: Arc::new(AtomicU64::new(0)),
+            api_base: Self::API_BASE.to_string(),
             http_client: reqwest::Client::new(),
         }
     }
 
-    /// Convert f32 samples to WAV bytes
-    fn samples_to_wav(samples: &[f32]) -> Result<Vec<u8>, SttError> {
-        let spec = hound::WavSpec {
-            channels: 1,
-            sample_rate: 16000,
-            bits_per_sample: 16,
-            sample_format: hound::SampleFormat::Int,
-        };
-
-        let mut cursor = Cursor::new(Vec::new());
-        {
-            let mut writer = hound::WavWriter::new(&mut cursor, spec)
-                .map_err(|e| SttError::InferenceError(format!("WAV error: {}", e)))?;
-
-            for &sample in samples {
-                let sample_i16 = (sample * 32767.0).clamp(-32768.0, 32767.0) as i16;
-                writer
-                    .write_sample(sample_i16)
-                    .map_err(|e| SttError::InferenceError(format!("WAV write error: {}", e)))?;
-            }
-
-            writer
-                .finalize()
-                .map_err(|e| SttError::InferenceError(format!("WAV finalize error: {}", e)))?;
-        }
-
-        let data = cursor.into_inner();
-        tracing::debug!("WAV header: {:?}", &data[..4.min(data.len())]);
-        Ok(data)
+    /// Send the requests to `api_base` instead of the provider (simulation)
+    #[cfg_attr(not(feature = "sim"), allow(dead_code))]
+    pub fn with_api_base(mut self, api_base: &str) -> Self {
+        self.api_base = api_base.trim_end_matches('/').to_string();
+        self
     }
 
     /// Groq Whisper API call
     async fn transcribe_async(
         client: reqwest::Client,
         api_key: String,
-        audio_data: Vec<f32>,
+        api_base: String,
+        mut audio_data: AudioAccumulator,
+        format: AudioFormat,
         language: Option<String>,
     ) -> Result<String, SttError> {
-        let wav_data = Self::samples_to_wav(&audio_data)?;
+        let audio_bytes = encode(&mut audio_data, format)
+            .map_err(|e| SttError::InferenceError(e.to_string()))?;
 
         let duration_secs = audio_data.len() as f32 / 16000.0;
         tracing::info!(
-            "Sending to Groq: {:.1}s audio, {} bytes WAV",
+            "Sending to Groq: {:.1}s audio, {} bytes",
             duration_secs,
-            wav_data.len()
+            audio_bytes.len()
         );
+        let audio_size = audio_bytes.len();
 
-        let file_part = reqwest::multipart::Part::bytes(wav_data)
-            .file_name("audio.wav")
-            .mime_str("audio/wav")
+        let file_part = reqwest::multipart::Part::bytes(audio_bytes)
+            .file_name(format.file_name())
+            .mime_str(format.mime_type())
             .map_err(|e| SttError::InferenceError(e.to_string()))?;
 
         let mut form = reqwest::multipart::Form::new()
             .part("file", file_part)
-            .text("model", "whisper-large-v3-turbo");
+            .text("model", Self::MODEL);
 
-        if let Some(lang) = language {
-            form = form.text("language", lang);
+        if let Some(ref lang) = language {
+            form = form.text("language", lang.clone());
         }
 
+        let url = format!("{}/audio/transcriptions", api_base);
         let response = client
-            .post("https://api.groq.com/openai/v1/audio/transcriptions")
+            .post(&url)
             .header("Authorization", format!("Bearer {}", api_key))
             .multipart(form)
             .send()
             .await
             .map_err(|e| SttError::InferenceError(format!("Network error: {}", e)))?;
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| SttError::InferenceError(format!("Network error: {}", e)))?;
+        let request = RequestSummary {
+            url: &url,
+            model: Self::MODEL,
+            language: language.as_deref(),
+            audio_bytes: audio_size,
+            duration_secs,
+        };
+        record_exchange("Groq", &request, status.as_u16(), &body);
+
+        if !status.is_success() {
             return Err(SttError::InferenceError(format!(
                 "Groq API error {}: {}",
-                status, error_text
+                status, body
             )));
         }
 
-        let json: serde_json::Value = response
-            .json()
-            .await
-            .map_err(|e| SttError::InferenceError(format!("JSON error: {}", e)))?;
+        let parsed: TranscriptionResponse = parse_response("Groq", &body)?;
 
-        let text = json["text"]
-            .as_str()
-            .unwrap_or("")
-            .trim()
-            .to_string();
-
-        Ok(text)
+        Ok(parsed.text.trim().to_string())
     }
 
     /// Send all accumulated audio buffer to the API
@@ -131,49 +140,33 @@ impl GroqEngine {
             return;
         }
 
-        // Ignore if less than 1 second of audio
-        if self.audio_buffer.len() < 16000 {
-            tracing::debug!(
-                "Audio too short ({} samples), skipped",
-                self.audio_buffer.len()
-            );
+        // Ignore audio too short or too quiet to hold speech
+        if let Some(event) = self.guards.check(&mut self.audio_buffer) {
             self.audio_buffer.clear();
+            if let Ok(mut events) = self.shared_events.lock() {
+                events.push_back((self.generation.load(Ordering::SeqCst), event));
+            }
             return;
         }
 
-        let audio_data = std::mem::take(&mut self.audio_buffer);
+        let audio_data = self.audio_buffer.take();
+        let speed = self.speed;
+        let format = self.format;
         let client = self.http_client.clone();
         let api_key = self.api_key.clone();
+        let api_base = self.api_base.clone();
         let language = match &self.language {
             Language::Auto => None,
             lang => Some(lang.code().to_string()),
         };
-        let shared_events = Arc::clone(&self.shared_events);
-        let pending = Arc::clone(&self.pending);
-
-        pending.store(true, Ordering::SeqCst);
+        let worker = RequestWorker::new("Groq", &self.shared_events, &self.pending, &self.generation);
 
         let duration = audio_data.len() as f32 / 16000.0;
         tracing::info!("Groq transcription of {:.1}s audio...", duration);
 
-        std::thread::spawn(move || {
-            let rt = tokio::runtime::Runtime::new().unwrap();
-            rt.block_on(async {
-                match Self::transcribe_async(client, api_key, audio_data, language).await {
-                    Ok(text) => {
-                        if !text.is_empty() {
-                            tracing::info!("Groq result: {}", text);
-                            if let Ok(mut events) = shared_events.lock() {
-                                events.push_back(SttEvent::Final(text));
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        tracing::error!("Groq error: {}", e);
-                    }
-                }
-                pending.store(false, Ordering::SeqCst);
-            });
+        worker.spawn(move || {
+            let audio_data = speed.apply(audio_data);
+            Self::transcribe_async(client, api_key, api_base, audio_data, format, language)
         });
     }
 
@@ -215,12 +208,36 @@ impl SttEngine for GroqEngine {
         self.audio_buffer.extend_from_slice(pcm);
     }
 
+    fn set_memory_limit(&mut self, samples: usize) {
+        self.audio_buffer.set_memory_limit(samples);
+    }
+
+    fn set_guards(&mut self, guards: AudioGuards) {
+        self.guards = guards;
+    }
+
+    fn set_speed_normalization(&mut self, speed: SpeedNormalization) {
+        self.speed = speed;
+    }
+
+    fn audio_format(&self) -> AudioFormat {
+        self.format
+    }
+
+    fn set_upload_sample_rate(&mut self, sample_rate: u32) {
+        self.format.sample_rate = sample_rate;
+    }
+
     fn poll(&mut self) -> Option<SttEvent> {
-        if let Ok(mut events) = self.shared_events.lock() {
-            events.pop_front()
-        } else {
-            None
+        let current = self.generation.load(Ordering::SeqCst);
+        let mut events = self.shared_events.lock().ok()?;
+        while let Some((generation, event)) = events.pop_front() {
+            if generation == current {
+                return Some(event);
+            }
+            tracing::debug!("Dropping Groq event from stale session {}", generation);
         }
+        None
     }
 
     fn flush(&mut self) {
@@ -235,6 +252,9 @@ impl SttEngine for GroqEngine {
 
     fn reset(&mut self) {
         self.audio_buffer.clear();
+        // Late responses from the previous session will be discarded
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        self.pending.store(false, Ordering::SeqCst);
         if let Ok(mut events) = self.shared_events.lock() {
             events.clear();
         }
@@ -0,0 +1,72 @@
+//! Pre-send audio guards
+//!
+//! Dictations too short or too quiet to hold speech are rejected before
+//! any API call, with an event explaining why.
+
+use super::engine::SttEvent;
+use crate::audio::AudioAccumulator;
+
+/// Sample rate of the audio pushed to the engines
+const SAMPLE_RATE: usize = 16000;
+
+/// Thresholds checked before sending the audio
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioGuards {
+    /// Minimum duration, in samples at 16kHz (0 = no minimum)
+    pub min_samples: usize,
+    /// RMS level under which the whole dictation is considered silent (0 = off)
+    pub silence_rms: f32,
+}
+
+impl Default for AudioGuards {
+    fn default() -> Self {
+        Self {
+            min_samples: SAMPLE_RATE,
+            silence_rms: 0.0,
+        }
+    }
+}
+
+impl AudioGuards {
+    /// Build guards from a duration in milliseconds and an RMS threshold
+    pub fn new(min_duration_ms: u64, silence_rms: f32) -> Self {
+        Self {
+            min_samples: (min_duration_ms as usize * SAMPLE_RATE) / 1000,
+            silence_rms,
+        }
+    }
+
+    /// Event explaining why `audio` must not be sent, if any
+    pub fn check(&self, audio: &mut AudioAccumulator) -> Option<SttEvent> {
+        if audio.len() < self.min_samples {
+            tracing::debug!(
+                "Audio too short ({} samples, minimum {}), skipped",
+                audio.len(),
+                self.min_samples
+            );
+            return Some(SttEvent::TooShort {
+                duration_ms: (audio.len() * 1000 / SAMPLE_RATE) as u64,
+                min_duration_ms: (self.min_samples * 1000 / SAMPLE_RATE) as u64,
+            });
+        }
+
+        if self.silence_rms > 0.0 && !audio.is_empty() {
+            let mut sum_squares = 0.0f64;
+            let scan = audio.try_for_each_chunk(|chunk| {
+                sum_squares += chunk.iter().map(|s| (*s as f64) * (*s as f64)).sum::<f64>();
+                Ok::<(), std::io::Error>(())
+            });
+            if let Err(e) = scan {
+                tracing::warn!("Cannot measure audio level: {}", e);
+                return None;
+            }
+            let rms = (sum_squares / audio.len() as f64).sqrt() as f32;
+            if rms < self.silence_rms {
+                tracing::debug!("Audio silent (RMS {:.4} < {:.4}), skipped", rms, self.silence_rms);
+                return Some(SttEvent::NoSpeech);
+            }
+        }
+
+        None
+    }
+}
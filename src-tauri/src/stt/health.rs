@@ -0,0 +1,157 @@
+//! Provider health probing
+//!
+//! Each configured provider's models endpoint is called in the background
+//! (and on demand from the settings), so a revoked key or a provider
+//! outage shows up before a dictation is lost to it. The last result per
+//! engine is kept until the next probe.
+
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Settings of the background health probes
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct EngineHealth {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Time between two probes of every provider
+    #[serde(default = "default_interval_secs")]
+    pub interval_secs: u64,
+    /// Answers slower than this mark the provider as degraded
+    #[serde(default = "default_slow_ms")]
+    pub slow_ms: u64,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_interval_secs() -> u64 {
+    300
+}
+
+fn default_slow_ms() -> u64 {
+    3000
+}
+
+impl Default for EngineHealth {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            interval_secs: default_interval_secs(),
+            slow_ms: default_slow_ms(),
+        }
+    }
+}
+
+/// Shortest time between two background probes
+pub const MIN_PROBE_INTERVAL_SECS: u64 = 30;
+
+/// A probe taking longer than this fails
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// State of a provider, as seen by the last probe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EngineStatus {
+    Ok,
+    /// Slow, rate limited, failing or unreachable
+    Degraded,
+    /// Key rejected by the provider
+    Unauthorized,
+    /// No key set, not probed
+    Unconfigured,
+}
+
+/// Last probe of a provider
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct EngineHealthReport {
+    pub engine: String,
+    pub status: EngineStatus,
+    /// Round trip of the probe (0 when not probed)
+    pub latency_ms: u64,
+    /// HTTP status or error of a failed probe
+    pub details: Option<String>,
+    /// Unix time of the probe, in milliseconds
+    pub checked_at_ms: u64,
+}
+
+/// Models endpoint of an HTTP engine, with its key sent as a bearer token or Google key
+fn models_request(client: &reqwest::Client, engine: &str, api_key: &str) -> Option<reqwest::RequestBuilder> {
+    let request = match engine {
+        "openai" => client.get("https://api.openai.com/v1/models").bearer_auth(api_key),
+        "groq" => client.get("https://api.groq.com/openai/v1/models").bearer_auth(api_key),
+        "voxtral" => client.get("https://api.mistral.ai/v1/models").bearer_auth(api_key),
+        "gemini" => client
+            .get("https://generativelanguage.googleapis.com/v1beta/models")
+            .header("x-goog-api-key", api_key),
+        _ => return None,
+    };
+    Some(request.timeout(PROBE_TIMEOUT))
+}
+
+fn reports() -> MutexGuard<'static, BTreeMap<String, EngineHealthReport>> {
+    static REPORTS: OnceLock<Mutex<BTreeMap<String, EngineHealthReport>>> = OnceLock::new();
+    REPORTS
+        .get_or_init(|| Mutex::new(BTreeMap::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Probe one provider
+async fn probe(client: &reqwest::Client, engine: &str, api_key: &str, slow_ms: u64) -> EngineHealthReport {
+    let started = Instant::now();
+    let (status, details) = if api_key.trim().is_empty() {
+        (EngineStatus::Unconfigured, None)
+    } else {
+        match models_request(client, engine, api_key) {
+            None => (EngineStatus::Unconfigured, None),
+            Some(request) => match request.send().await {
+                Ok(response) if response.status().is_success() => {
+                    if started.elapsed().as_millis() as u64 > slow_ms {
+                        (EngineStatus::Degraded, Some("slow response".to_string()))
+                    } else {
+                        (EngineStatus::Ok, None)
+                    }
+                }
+                Ok(response) => match response.status().as_u16() {
+                    401 | 403 => (EngineStatus::Unauthorized, Some(format!("HTTP {}", response.status()))),
+                    _ => (EngineStatus::Degraded, Some(format!("HTTP {}", response.status()))),
+                },
+                Err(e) => (EngineStatus::Degraded, Some(format!("Network error: {}", e))),
+            },
+        }
+    };
+    let probed = status != EngineStatus::Unconfigured;
+    EngineHealthReport {
+        engine: engine.to_string(),
+        status,
+        latency_ms: if probed { started.elapsed().as_millis() as u64 } else { 0 },
+        details,
+        checked_at_ms: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default(),
+    }
+}
+
+/// Probe every `(engine, api_key)` provider, returning whether a status changed
+pub async fn probe_engines(engines: &[(&str, String)], slow_ms: u64) -> bool {
+    let client = reqwest::Client::new();
+    let mut changed = false;
+    for (engine, api_key) in engines {
+        let report = probe(&client, engine, api_key, slow_ms).await;
+        if report.status != EngineStatus::Ok {
+            tracing::info!("{} health: {:?} {}", engine, report.status, report.details.as_deref().unwrap_or(""));
+        }
+        let status = report.status;
+        let previous = reports().insert(engine.to_string(), report);
+        changed |= previous.map(|previous| previous.status) != Some(status);
+    }
+    changed
+}
+
+/// Last probe of every provider
+pub fn engine_status() -> Vec<EngineHealthReport> {
+    reports().values().cloned().collect()
+}
@@ -0,0 +1,245 @@
+//! Per-provider limit on concurrent requests
+//!
+//! Every request thread takes a permit of its provider before sending.
+//! Past the limit, requests wait in a first-in first-out queue instead of
+//! all hitting the API at once, so a burst (chunked or speculative sends,
+//! retries) does not trip the provider rate limit and cascade into 429s.
+//! Limits are changed at runtime; queued requests see the new value. A
+//! request whose dictation is cancelled leaves the queue without being sent.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Condvar, Mutex, MutexGuard, OnceLock};
+use std::time::{Duration, Instant};
+
+/// How often a queued request checks whether its dictation was cancelled
+const CANCEL_POLL: Duration = Duration::from_millis(50);
+
+/// Concurrent requests allowed per provider
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ConcurrencyLimits {
+    /// Requests in flight per provider (0 = unlimited)
+    #[serde(default = "default_max_concurrent")]
+    pub max_concurrent: usize,
+    /// Overrides keyed by engine ("openai", "groq", "voxtral", "gemini"...)
+    #[serde(default)]
+    pub per_engine: BTreeMap<String, usize>,
+}
+
+fn default_max_concurrent() -> usize {
+    2
+}
+
+impl Default for ConcurrencyLimits {
+    fn default() -> Self {
+        Self {
+            max_concurrent: default_max_concurrent(),
+            per_engine: BTreeMap::new(),
+        }
+    }
+}
+
+impl ConcurrencyLimits {
+    fn limit(&self, engine: &str) -> usize {
+        self.per_engine.get(engine).copied().unwrap_or(self.max_concurrent)
+    }
+}
+
+/// Queue and counters of one provider
+#[derive(Debug, Default)]
+struct Slot {
+    in_flight: usize,
+    /// Tickets of the waiting requests, oldest first
+    waiting: VecDeque<u64>,
+    next_ticket: u64,
+    completed: u64,
+    queued_total: u64,
+    max_queued: usize,
+    total_wait_ms: u64,
+    max_wait_ms: u64,
+}
+
+#[derive(Debug, Default)]
+struct Limiter {
+    limits: ConcurrencyLimits,
+    slots: BTreeMap<String, Slot>,
+}
+
+fn limiter() -> &'static (Mutex<Limiter>, Condvar) {
+    static LIMITER: OnceLock<(Mutex<Limiter>, Condvar)> = OnceLock::new();
+    LIMITER.get_or_init(|| (Mutex::new(Limiter::default()), Condvar::new()))
+}
+
+fn lock() -> MutexGuard<'static, Limiter> {
+    limiter().0.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Apply new limits, waking the queued requests that now fit
+pub fn set_concurrency_limits(limits: &ConcurrencyLimits) {
+    lock().limits = limits.clone();
+    limiter().1.notify_all();
+}
+
+/// Right to send one request, released on drop
+pub(super) struct Permit {
+    engine: String,
+}
+
+/// Wait for a free slot of `engine` (lowercase engine name), in arrival order
+///
+/// `None` once `cancelled` returns true: the request is dropped from the
+/// queue instead of being sent. Blocks the calling thread, only call it
+/// from a request thread.
+pub(super) fn acquire(engine: &str, cancelled: impl Fn() -> bool) -> Option<Permit> {
+    let (_, freed) = limiter();
+    let mut guard = lock();
+    let started = Instant::now();
+    if cancelled() {
+        return None;
+    }
+
+    let state = &mut *guard;
+    let limit = state.limits.limit(engine);
+    let slot = state.slots.entry(engine.to_string()).or_default();
+    if slot.waiting.is_empty() && (limit == 0 || slot.in_flight < limit) {
+        slot.in_flight += 1;
+        return Some(Permit {
+            engine: engine.to_string(),
+        });
+    }
+
+    let ticket = slot.next_ticket;
+    slot.next_ticket += 1;
+    slot.waiting.push_back(ticket);
+    slot.queued_total += 1;
+    slot.max_queued = slot.max_queued.max(slot.waiting.len());
+    tracing::info!(
+        "{} request queued ({} in flight, {} waiting)",
+        engine,
+        slot.in_flight,
+        slot.waiting.len()
+    );
+
+    loop {
+        // Timed out regularly, a cancellation does not notify the queue
+        guard = freed
+            .wait_timeout(guard, CANCEL_POLL)
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .0;
+        let state = &mut *guard;
+        let limit = state.limits.limit(engine);
+        let Some(slot) = state.slots.get_mut(engine) else {
+            continue;
+        };
+        if cancelled() {
+            slot.waiting.retain(|&waiting| waiting != ticket);
+            tracing::info!("{} request dropped from the queue, its dictation was cancelled", engine);
+            drop(guard);
+            // The next in line may be at the front now
+            freed.notify_all();
+            return None;
+        }
+        if slot.waiting.front() == Some(&ticket) && (limit == 0 || slot.in_flight < limit) {
+            slot.waiting.pop_front();
+            slot.in_flight += 1;
+            let waited_ms = started.elapsed().as_millis() as u64;
+            slot.total_wait_ms += waited_ms;
+            slot.max_wait_ms = slot.max_wait_ms.max(waited_ms);
+            tracing::info!("{} request sent after {} ms in queue", engine, waited_ms);
+            drop(guard);
+            // The next in line may fit too
+            freed.notify_all();
+            return Some(Permit {
+                engine: engine.to_string(),
+            });
+        }
+    }
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        if let Some(slot) = lock().slots.get_mut(&self.engine) {
+            slot.in_flight = slot.in_flight.saturating_sub(1);
+            slot.completed += 1;
+        }
+        limiter().1.notify_all();
+    }
+}
+
+/// Queue state and counters of a provider, since startup
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RequestMetrics {
+    pub engine: String,
+    /// Current limit (0 = unlimited)
+    pub limit: usize,
+    pub in_flight: usize,
+    pub queued: usize,
+    pub completed: u64,
+    /// Requests that had to wait for a slot
+    pub queued_total: u64,
+    /// Longest queue seen
+    pub max_queued: usize,
+    /// Average wait of the requests that were queued
+    pub average_wait_ms: u64,
+    pub max_wait_ms: u64,
+}
+
+/// Metrics of every provider that sent a request
+pub fn request_metrics() -> Vec<RequestMetrics> {
+    let state = lock();
+    state
+        .slots
+        .iter()
+        .map(|(engine, slot)| RequestMetrics {
+            engine: engine.clone(),
+            limit: state.limits.limit(engine),
+            in_flight: slot.in_flight,
+            queued: slot.waiting.len(),
+            completed: slot.completed,
+            queued_total: slot.queued_total,
+            max_queued: slot.max_queued,
+            average_wait_ms: slot.total_wait_ms.checked_div(slot.queued_total).unwrap_or(0),
+            max_wait_ms: slot.max_wait_ms,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    fn queued(engine: &str) -> usize {
+        request_metrics()
+            .into_iter()
+            .find(|metrics| metrics.engine == engine)
+            .map_or(0, |metrics| metrics.queued)
+    }
+
+    #[test]
+    fn cancelled_request_leaves_the_queue_unsent() {
+        let engine = "limiter-test-cancel";
+        let mut limits = ConcurrencyLimits::default();
+        limits.per_engine.insert(engine.to_string(), 1);
+        set_concurrency_limits(&limits);
+
+        let in_flight = acquire(engine, || false).expect("free slot");
+        let cancel = Arc::new(AtomicBool::new(false));
+        let waiter = {
+            let cancel = Arc::clone(&cancel);
+            std::thread::spawn(move || acquire(engine, || cancel.load(Ordering::SeqCst)).is_some())
+        };
+        while queued(engine) == 0 {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        cancel.store(true, Ordering::SeqCst);
+        assert!(!waiter.join().unwrap(), "cancelled request got a slot");
+        assert_eq!(queued(engine), 0);
+
+        // The next request is served as soon as the slot is free
+        drop(in_flight);
+        assert!(acquire(engine, || false).is_some());
+        assert!(acquire(engine, || true).is_none());
+    }
+}
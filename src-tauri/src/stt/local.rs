@@ -0,0 +1,178 @@
+//! Local inference engine (whisper.cpp, Vosk...)
+//!
+//! Audio is accumulated until flush, then transcribed by a sandboxed worker
+//! process (see `sandbox`). Nothing leaves the machine.
+
+use super::engine::{Language, SttEngine, SttError, SttEvent};
+use super::guards::AudioGuards;
+use super::sandbox::WorkerProcess;
+use super::worker::{RequestWorker, SharedEvents};
+use crate::audio::{encode, AudioAccumulator, AudioFormat, SpeedNormalization};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Worker program running the local model
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LocalWorker {
+    /// Executable, e.g. "dictea-worker" or "python3"
+    #[serde(default)]
+    pub command: String,
+    /// Arguments, e.g. ["worker.py", "--model", "~/models/vosk-fr"]
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// STT engine delegating to a local worker process
+pub struct LocalEngine {
+    language: Language,
+    /// Accumulates all audio until flush (spilled to disk when long)
+    audio_buffer: AudioAccumulator,
+    guards: AudioGuards,
+    speed: SpeedNormalization,
+    /// Shared with the request threads, one transcription at a time
+    process: Arc<Mutex<WorkerProcess>>,
+    shared_events: SharedEvents,
+    pending: Arc<AtomicBool>,
+    generation: Arc<AtomicU64>,
+    name: String,
+}
+
+impl LocalEngine {
+    const AUDIO_FORMAT: AudioFormat = AudioFormat::WAV_16K;
+
+    /// Start the worker and check its protocol version
+    pub fn start(worker: &LocalWorker) -> Result<Self, SttError> {
+        let mut process = WorkerProcess::new(&worker.command, &worker.args);
+        process.ensure_started()?;
+        let name = match process.engine() {
+            "" => "Local worker".to_string(),
+            engine => format!("Local ({})", engine),
+        };
+        Ok(Self {
+            language: Language::Auto,
+            audio_buffer: AudioAccumulator::default(),
+            guards: AudioGuards::default(),
+            speed: SpeedNormalization::default(),
+            process: Arc::new(Mutex::new(process)),
+            shared_events: Arc::new(Mutex::new(VecDeque::new())),
+            pending: Arc::new(AtomicBool::new(false)),
+            generation: Arc::new(AtomicU64::new(0)),
+            name,
+        })
+    }
+
+    fn send_full_audio(&mut self) {
+        if self.audio_buffer.is_empty() {
+            return;
+        }
+
+        if let Some(event) = self.guards.check(&mut self.audio_buffer) {
+            self.audio_buffer.clear();
+            if let Ok(mut events) = self.shared_events.lock() {
+                events.push_back((self.generation.load(Ordering::SeqCst), event));
+            }
+            return;
+        }
+
+        let audio_data = self.audio_buffer.take();
+        let speed = self.speed;
+        let process = Arc::clone(&self.process);
+        let language = match &self.language {
+            Language::Auto => None,
+            lang => Some(lang.code().to_string()),
+        };
+        let worker = RequestWorker::new("Local", &self.shared_events, &self.pending, &self.generation);
+
+        tracing::info!("Local transcription of {:.1}s audio...", audio_data.len() as f32 / 16000.0);
+        worker.spawn(move || async move {
+            let mut audio_data = speed.apply(audio_data);
+            let wav = encode(&mut audio_data, Self::AUDIO_FORMAT).map_err(|e| SttError::InferenceError(e.to_string()))?;
+            // A request thread that panicked while transcribing poisons the lock, the worker is still usable
+            let mut process = process.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            process.transcribe(&wav, language.as_deref())
+        });
+    }
+
+    /// Wait for the current request to complete (max 5 min, local models are slower)
+    fn wait_for_pending(&self) {
+        let start = std::time::Instant::now();
+        while self.pending.load(Ordering::SeqCst) {
+            if start.elapsed() > std::time::Duration::from_secs(300) {
+                tracing::warn!("Timeout waiting for the local worker");
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+    }
+}
+
+impl SttEngine for LocalEngine {
+    fn load(command: &str) -> Result<Self, SttError> {
+        Self::start(&LocalWorker {
+            command: command.to_string(),
+            args: Vec::new(),
+        })
+    }
+
+    fn set_language(&mut self, language: Language) {
+        self.language = language;
+    }
+
+    fn language(&self) -> &Language {
+        &self.language
+    }
+
+    fn push_audio(&mut self, pcm: &[f32]) {
+        self.audio_buffer.extend_from_slice(pcm);
+    }
+
+    fn set_memory_limit(&mut self, samples: usize) {
+        self.audio_buffer.set_memory_limit(samples);
+    }
+
+    fn set_guards(&mut self, guards: AudioGuards) {
+        self.guards = guards;
+    }
+
+    fn set_speed_normalization(&mut self, speed: SpeedNormalization) {
+        self.speed = speed;
+    }
+
+    fn audio_format(&self) -> AudioFormat {
+        Self::AUDIO_FORMAT
+    }
+
+    fn poll(&mut self) -> Option<SttEvent> {
+        let current = self.generation.load(Ordering::SeqCst);
+        let mut events = self.shared_events.lock().ok()?;
+        while let Some((generation, event)) = events.pop_front() {
+            if generation == current {
+                return Some(event);
+            }
+        }
+        None
+    }
+
+    fn flush(&mut self) {
+        self.send_full_audio();
+        self.wait_for_pending();
+    }
+
+    fn reset(&mut self) {
+        self.audio_buffer.clear();
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        self.pending.store(false, Ordering::SeqCst);
+        if let Ok(mut events) = self.shared_events.lock() {
+            events.clear();
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
@@ -2,13 +2,46 @@
 //!
 //! Provides traits and implementations for voice transcription.
 
+mod assemblyai;
+mod cache;
 mod engine;
 mod gemini;
+mod google_speech;
 mod groq;
+mod guards;
+mod health;
+mod limiter;
+mod local;
 mod openai;
+mod openai_realtime;
+mod overlap;
+mod request_log;
+mod responses;
+mod router;
+mod sandbox;
+mod speaker;
+mod speculative;
 mod voxtral;
+#[cfg(feature = "whisper-local")]
+mod whisper_local;
+mod worker;
+pub use assemblyai::AssemblyAiEngine;
+pub use cache::{CacheStore, CachedEngine, TranscriptCache};
 pub use engine::{SttEngine, SttEvent, SttError, Language};
 pub use gemini::GeminiEngine;
+pub use google_speech::{GoogleSpeech, GoogleSpeechEngine};
 pub use groq::GroqEngine;
+pub use guards::AudioGuards;
+pub use health::{engine_status, probe_engines, EngineHealth, EngineHealthReport, MIN_PROBE_INTERVAL_SECS};
+pub use limiter::{request_metrics, set_concurrency_limits, ConcurrencyLimits, RequestMetrics};
+pub use local::{LocalEngine, LocalWorker};
 pub use openai::OpenAiEngine;
+pub use openai_realtime::OpenAiRealtimeEngine;
+pub use request_log::set_request_logging;
+pub use responses::{parse_response, ChatCompletionResponse};
+pub use router::{EngineRouting, RoutedEngine};
+pub use speaker::{find_profile, SpeakerProfile};
+pub use speculative::{EngineFactory, SmartStop, SpeculativeEngine};
 pub use voxtral::VoxtralEngine;
+#[cfg(feature = "whisper-local")]
+pub use whisper_local::{start_whisper_local, whisper_worker_main, WHISPER_WORKER_ARG};
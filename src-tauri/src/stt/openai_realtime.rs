@@ -0,0 +1,424 @@
+//! OpenAI Realtime transcription, streamed over a WebSocket
+//!
+//! Audio is sent as it is captured. The server cuts turns on its own VAD
+//! and returns the words of each turn as deltas, surfaced as `Partial`
+//! events while the user is still speaking; every finished turn becomes a
+//! `Final`. On flush the remaining audio is committed and the session is
+//! closed once every turn is transcribed.
+
+use super::engine::{Language, SttEngine, SttError, SttEvent};
+use super::guards::AudioGuards;
+use crate::audio::{LinearResampler, SpeedNormalization};
+use base64::Engine as _;
+use futures_util::{SinkExt, StreamExt};
+use std::collections::VecDeque;
+use std::sync::{mpsc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc as async_mpsc;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+/// Sample rate of the audio pushed to the engines
+const CAPTURE_RATE: u32 = 16000;
+
+/// Sample rate required by the Realtime API (pcm16)
+const UPLOAD_RATE: u32 = 24000;
+
+/// Longest wait for the last turns after the final commit
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Message from the engine to its session task
+enum Outgoing {
+    /// Base64 pcm16 chunk
+    Audio(String),
+    /// End of the dictation: commit what is left and close once transcribed
+    Commit,
+}
+
+/// Turn committed by the server, waiting for its transcript
+struct Turn {
+    item_id: String,
+    partial: String,
+    transcript: Option<String>,
+}
+
+/// Connection opened for one dictation
+struct RealtimeSession {
+    outgoing: async_mpsc::UnboundedSender<Outgoing>,
+    /// Closed by the session task when it ends (locked only to be `Sync`)
+    events: Mutex<mpsc::Receiver<SttEvent>>,
+}
+
+impl RealtimeSession {
+    /// Connect from a dedicated thread
+    fn open(url: String, api_key: String, language: Option<String>) -> Self {
+        let (outgoing, outgoing_rx) = async_mpsc::unbounded_channel();
+        let (events_tx, events) = mpsc::channel();
+        std::thread::spawn(move || {
+            let rt = match tokio::runtime::Runtime::new() {
+                Ok(rt) => rt,
+                Err(e) => {
+                    let _ = events_tx.send(SttEvent::Error(format!("Cannot start runtime: {}", e)));
+                    return;
+                }
+            };
+            if let Err(e) = rt.block_on(run_session(url, api_key, language, outgoing_rx, &events_tx)) {
+                tracing::error!("OpenAI Realtime error: {}", e);
+                let _ = events_tx.send(SttEvent::Error(e.to_string()));
+            }
+        });
+        Self {
+            outgoing,
+            events: Mutex::new(events),
+        }
+    }
+}
+
+/// Session settings sent right after connecting
+fn session_update(language: Option<&str>) -> serde_json::Value {
+    let mut transcription = serde_json::json!({ "model": OpenAiRealtimeEngine::MODEL });
+    if let Some(language) = language {
+        transcription["language"] = serde_json::Value::from(language);
+    }
+    serde_json::json!({
+        "type": "transcription_session.update",
+        "session": {
+            "input_audio_format": "pcm16",
+            "input_audio_transcription": transcription,
+            "turn_detection": { "type": "server_vad", "silence_duration_ms": 500 },
+        }
+    })
+}
+
+/// Pending partials, oldest turn first
+fn pending_text(turns: &VecDeque<Turn>) -> String {
+    turns
+        .iter()
+        .filter(|turn| turn.transcript.is_none() && !turn.partial.is_empty())
+        .map(|turn| turn.partial.trim())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Send the transcripts of the finished turns, in speaking order
+fn release_finished(turns: &mut VecDeque<Turn>, events: &mpsc::Sender<SttEvent>) {
+    while turns.front().is_some_and(|turn| turn.transcript.is_some()) {
+        let text = turns.pop_front().and_then(|turn| turn.transcript).unwrap_or_default();
+        if !text.is_empty() {
+            let _ = events.send(SttEvent::Final(text));
+        }
+    }
+    // A Final clears the partial, show the turns still pending again
+    let pending = pending_text(turns);
+    if !pending.is_empty() {
+        let _ = events.send(SttEvent::Partial(pending));
+    }
+}
+
+/// Drive the WebSocket until the dictation is committed and transcribed
+async fn run_session(
+    url: String,
+    api_key: String,
+    language: Option<String>,
+    mut outgoing: async_mpsc::UnboundedReceiver<Outgoing>,
+    events: &mpsc::Sender<SttEvent>,
+) -> Result<(), SttError> {
+    let network = |e: tokio_tungstenite::tungstenite::Error| SttError::InferenceError(format!("Network error: {}", e));
+
+    let mut request = url.into_client_request().map_err(network)?;
+    let headers = request.headers_mut();
+    let authorization = HeaderValue::from_str(&format!("Bearer {}", api_key))
+        .map_err(|e| SttError::InferenceError(e.to_string()))?;
+    headers.insert("Authorization", authorization);
+    headers.insert("OpenAI-Beta", HeaderValue::from_static("realtime=v1"));
+
+    let (socket, _) = tokio_tungstenite::connect_async(request).await.map_err(network)?;
+    let (mut sink, mut stream) = socket.split();
+    tracing::info!("OpenAI Realtime session opened");
+
+    sink.send(WsMessage::Text(session_update(language.as_deref()).to_string()))
+        .await
+        .map_err(network)?;
+
+    let mut turns: VecDeque<Turn> = VecDeque::new();
+    let mut committing = false;
+    // The final commit is acknowledged by `committed` or a `commit_empty` error
+    let mut awaiting_commit = false;
+    loop {
+        tokio::select! {
+            message = outgoing.recv(), if !committing => {
+                let payload = match message {
+                    Some(Outgoing::Audio(audio)) => {
+                        serde_json::json!({ "type": "input_audio_buffer.append", "audio": audio })
+                    }
+                    // Engine reset or dropped: nothing left to wait for
+                    None => break,
+                    Some(Outgoing::Commit) => {
+                        committing = true;
+                        awaiting_commit = true;
+                        serde_json::json!({ "type": "input_audio_buffer.commit" })
+                    }
+                };
+                sink.send(WsMessage::Text(payload.to_string())).await.map_err(network)?;
+            }
+            message = stream.next() => {
+                let text = match message {
+                    Some(Ok(WsMessage::Text(text))) => text,
+                    Some(Ok(WsMessage::Close(_))) | None => {
+                        return Err(SttError::InferenceError("OpenAI Realtime connection closed".to_string()));
+                    }
+                    Some(Ok(_)) => continue,
+                    Some(Err(e)) => return Err(network(e)),
+                };
+                let event: serde_json::Value = match serde_json::from_str(&text) {
+                    Ok(event) => event,
+                    Err(e) => {
+                        tracing::warn!("Unreadable OpenAI Realtime event: {}", e);
+                        continue;
+                    }
+                };
+                let item_id = event["item_id"].as_str().unwrap_or_default();
+                match event["type"].as_str().unwrap_or_default() {
+                    "input_audio_buffer.committed" => {
+                        awaiting_commit = false;
+                        turns.push_back(Turn {
+                            item_id: item_id.to_string(),
+                            partial: String::new(),
+                            transcript: None,
+                        });
+                    }
+                    "conversation.item.input_audio_transcription.delta" => {
+                        if let Some(turn) = turns.iter_mut().find(|turn| turn.item_id == item_id) {
+                            turn.partial.push_str(event["delta"].as_str().unwrap_or_default());
+                            let _ = events.send(SttEvent::Partial(pending_text(&turns)));
+                        }
+                    }
+                    "conversation.item.input_audio_transcription.completed" => {
+                        if let Some(turn) = turns.iter_mut().find(|turn| turn.item_id == item_id) {
+                            let transcript = event["transcript"].as_str().unwrap_or_default();
+                            turn.transcript = Some(transcript.trim().to_string());
+                        }
+                        release_finished(&mut turns, events);
+                    }
+                    "conversation.item.input_audio_transcription.failed" => {
+                        let reason = event["error"]["message"].as_str().unwrap_or("unknown error");
+                        return Err(SttError::InferenceError(format!("OpenAI Realtime error: {}", reason)));
+                    }
+                    "error" => {
+                        // The server VAD already committed everything that was said
+                        if committing && event["error"]["code"] == "input_audio_buffer_commit_empty" {
+                            tracing::debug!("Nothing left to commit");
+                            awaiting_commit = false;
+                        } else {
+                            let reason = event["error"]["message"].as_str().unwrap_or("unknown error");
+                            return Err(SttError::InferenceError(format!("OpenAI Realtime error: {}", reason)));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if committing && !awaiting_commit && turns.is_empty() {
+            break;
+        }
+    }
+
+    let _ = sink.send(WsMessage::Close(None)).await;
+    tracing::info!("OpenAI Realtime session closed");
+    Ok(())
+}
+
+/// STT engine streaming to the OpenAI Realtime transcription API
+pub struct OpenAiRealtimeEngine {
+    api_key: String,
+    language: Language,
+    /// Minimum duration checked on flush (speech detection is server side)
+    guards: AudioGuards,
+    /// Connection of the current dictation, opened on the first samples
+    session: Option<RealtimeSession>,
+    /// 16kHz to 24kHz, continuous across chunks
+    resampler: LinearResampler,
+    /// Samples pushed in the current dictation
+    pushed_samples: usize,
+    /// A transcript was received in the current dictation
+    transcribed: bool,
+    /// Events ready to be consumed
+    events: VecDeque<SttEvent>,
+}
+
+impl OpenAiRealtimeEngine {
+    /// Transcription model
+    const MODEL: &'static str = "gpt-4o-transcribe";
+
+    /// Realtime endpoint, transcription only
+    const URL: &'static str = "wss://api.openai.com/v1/realtime?intent=transcription";
+
+    pub fn with_api_key(api_key: String) -> Self {
+        Self {
+            api_key,
+            language: Language::Auto,
+            guards: AudioGuards::default(),
+            session: None,
+            resampler: LinearResampler::new(CAPTURE_RATE, UPLOAD_RATE),
+            pushed_samples: 0,
+            transcribed: false,
+            events: VecDeque::new(),
+        }
+    }
+
+    /// Session of the current dictation, connecting on first use
+    fn session(&mut self) -> &RealtimeSession {
+        let url = Self::URL.to_string();
+        let api_key = self.api_key.clone();
+        let language = match &self.language {
+            Language::Auto => None,
+            lang => Some(lang.code().to_string()),
+        };
+        self.session
+            .get_or_insert_with(|| RealtimeSession::open(url, api_key, language))
+    }
+
+    /// Move the events received so far to the queue
+    fn drain_session(&mut self) {
+        let received: Vec<SttEvent> = match self.session.as_ref().and_then(|session| session.events.lock().ok()) {
+            Some(events) => events.try_iter().collect(),
+            None => return,
+        };
+        for event in received {
+            self.record(event);
+        }
+    }
+
+    /// Forget the current dictation, closing its connection
+    fn end_session(&mut self) {
+        // Dropping the sender ends the session task
+        self.session = None;
+        self.resampler = LinearResampler::new(CAPTURE_RATE, UPLOAD_RATE);
+        self.pushed_samples = 0;
+        self.transcribed = false;
+    }
+
+    fn record(&mut self, event: SttEvent) {
+        if matches!(event, SttEvent::Final(_)) {
+            self.transcribed = true;
+        }
+        self.events.push_back(event);
+    }
+}
+
+impl SttEngine for OpenAiRealtimeEngine {
+    fn load(api_key: &str) -> Result<Self, SttError> {
+        if api_key.is_empty() {
+            return Err(SttError::ModelNotFound(
+                "OpenAI API key required".to_string(),
+            ));
+        }
+
+        tracing::info!("Initializing OpenAI Realtime API");
+        Ok(Self::with_api_key(api_key.to_string()))
+    }
+
+    fn set_language(&mut self, language: Language) {
+        self.language = language.clone();
+        tracing::debug!("OpenAI Realtime language set: {:?}", language);
+    }
+
+    fn language(&self) -> &Language {
+        &self.language
+    }
+
+    fn push_audio(&mut self, pcm: &[f32]) {
+        if pcm.is_empty() {
+            return;
+        }
+        self.pushed_samples += pcm.len();
+        let mut resampled = Vec::with_capacity(pcm.len() * 3 / 2 + 1);
+        self.resampler.process(pcm, &mut resampled);
+        let bytes: Vec<u8> = resampled
+            .iter()
+            .flat_map(|&sample| ((sample * 32767.0).clamp(-32768.0, 32767.0) as i16).to_le_bytes())
+            .collect();
+        let audio = base64::engine::general_purpose::STANDARD.encode(bytes);
+        let _ = self.session().outgoing.send(Outgoing::Audio(audio));
+    }
+
+    fn set_memory_limit(&mut self, _samples: usize) {
+        // Nothing is buffered, audio leaves as it is captured
+    }
+
+    fn set_guards(&mut self, guards: AudioGuards) {
+        self.guards = guards;
+    }
+
+    fn set_speed_normalization(&mut self, _speed: SpeedNormalization) {
+        // Cannot stretch audio that is already sent
+    }
+
+    fn poll(&mut self) -> Option<SttEvent> {
+        self.drain_session();
+        self.events.pop_front()
+    }
+
+    fn flush(&mut self) {
+        tracing::info!(
+            "Flush OpenAI Realtime: {} samples ({:.1}s)",
+            self.pushed_samples,
+            self.pushed_samples as f32 / CAPTURE_RATE as f32
+        );
+        if self.pushed_samples < self.guards.min_samples {
+            let samples_to_ms = |samples: usize| (samples as u64 * 1000) / CAPTURE_RATE as u64;
+            self.events.push_back(SttEvent::TooShort {
+                duration_ms: samples_to_ms(self.pushed_samples),
+                min_duration_ms: samples_to_ms(self.guards.min_samples),
+            });
+            self.end_session();
+            return;
+        }
+
+        let Some(session) = self.session.take() else {
+            self.end_session();
+            return;
+        };
+        let _ = session.outgoing.send(Outgoing::Commit);
+        let events = session.events.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let deadline = Instant::now() + FLUSH_TIMEOUT;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match events.recv_timeout(remaining) {
+                Ok(event) => self.record(event),
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    tracing::warn!("Timeout waiting for OpenAI Realtime transcripts");
+                    self.events.push_back(SttEvent::Error("OpenAI Realtime timed out".to_string()));
+                    break;
+                }
+            }
+        }
+
+        let failed = self.events.iter().any(|event| matches!(event, SttEvent::Error(_)));
+        if !self.transcribed && !failed {
+            tracing::info!("OpenAI Realtime result: no speech");
+            self.events.push_back(SttEvent::NoSpeech);
+        }
+        self.end_session();
+    }
+
+    fn reset(&mut self) {
+        self.end_session();
+        self.events.clear();
+        tracing::debug!("OpenAI Realtime engine reset");
+    }
+
+    fn streams_partials(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &str {
+        "OpenAI Realtime"
+    }
+
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
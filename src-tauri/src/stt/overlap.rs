@@ -0,0 +1,156 @@
+//! Merging of transcripts from adjacent audio chunks
+//!
+//! Chunks sent separately meet at a seam: when their audio overlaps, the
+//! words around it come back twice, and the engines often garble the word
+//! cut at the edge. The tail of a transcript is aligned with the head of the
+//! next one on normalized words, and the common run is kept once.
+
+/// Words searched for a common run at each side of a seam
+const MAX_OVERLAP_WORDS: usize = 16;
+
+/// Shortest common run taken as an overlap, so a single repeated word
+/// ("no. No, ...") is not mistaken for one
+const MIN_OVERLAP_WORDS: usize = 2;
+
+/// Words cut at the edge of a chunk, skipped at each side of the seam
+const EDGE_WORDS: usize = 1;
+
+/// Word compared without case and punctuation
+fn normalize(word: &str) -> String {
+    word.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+/// Common run at the seam: (end of the run in `left`, start and length in `right`)
+fn align(left: &[String], right: &[String]) -> Option<(usize, usize, usize)> {
+    let mut best: Option<(usize, usize, usize, usize)> = None;
+    for skipped_left in 0..=EDGE_WORDS.min(left.len()) {
+        for skipped_right in 0..=EDGE_WORDS.min(right.len()) {
+            let left_end = left.len() - skipped_left;
+            let available = left_end.min(right.len() - skipped_right).min(MAX_OVERLAP_WORDS);
+            let run = (MIN_OVERLAP_WORDS..=available).rev().find(|&len| {
+                left[left_end - len..left_end] == right[skipped_right..skipped_right + len]
+            });
+            let Some(len) = run else {
+                continue;
+            };
+            // Longest run first, then the fewest skipped words
+            let skipped = skipped_left + skipped_right;
+            if best.is_none_or(|(best_len, _, _, best_skipped)| {
+                len > best_len || (len == best_len && skipped < best_skipped)
+            }) {
+                best = Some((len, left_end, skipped_right, skipped));
+            }
+        }
+    }
+    best.map(|(len, left_end, right_start, _)| (left_end, right_start, len))
+}
+
+/// Merge the transcripts of two adjacent chunks, dropping their common words
+///
+/// The first half of the common run is taken from `left` and the second one
+/// from `right`, each side being the more reliable away from its edge.
+/// Without a common run the two transcripts are joined with a space.
+pub fn merge_pair(left: &str, right: &str) -> String {
+    let left_words: Vec<&str> = left.split_whitespace().collect();
+    let right_words: Vec<&str> = right.split_whitespace().collect();
+    if left_words.is_empty() || right_words.is_empty() {
+        return left_words.into_iter().chain(right_words).collect::<Vec<_>>().join(" ");
+    }
+
+    let left_tail = &left_words[left_words.len().saturating_sub(MAX_OVERLAP_WORDS + EDGE_WORDS)..];
+    let right_head = &right_words[..right_words.len().min(MAX_OVERLAP_WORDS + EDGE_WORDS)];
+    let normalized_tail: Vec<String> = left_tail.iter().map(|word| normalize(word)).collect();
+    let normalized_head: Vec<String> = right_head.iter().map(|word| normalize(word)).collect();
+
+    let Some((tail_end, head_start, len)) = align(&normalized_tail, &normalized_head) else {
+        return format!("{} {}", left_words.join(" "), right_words.join(" "));
+    };
+    let offset = left_words.len() - left_tail.len();
+    let kept_from_left = len - len / 2;
+    left_words[..offset + tail_end - len + kept_from_left]
+        .iter()
+        .chain(&right_words[head_start + kept_from_left..])
+        .copied()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Merge the transcripts of consecutive chunks, in order
+pub fn merge_transcripts<S: AsRef<str>>(texts: &[S]) -> String {
+    texts
+        .iter()
+        .map(AsRef::as_ref)
+        .filter(|text| !text.trim().is_empty())
+        .fold(String::new(), |merged, text| merge_pair(&merged, text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_overlap_is_kept_once() {
+        assert_eq!(
+            merge_pair("the quick brown fox", "brown fox jumps over"),
+            "the quick brown fox jumps over"
+        );
+        assert_eq!(
+            merge_pair("one two three four five", "three four five six"),
+            "one two three four five six"
+        );
+    }
+
+    #[test]
+    fn word_cut_at_the_edge_is_dropped() {
+        // Left chunk ends in the middle of "fox"
+        assert_eq!(
+            merge_pair("the quick brown fo", "quick brown fox jumps"),
+            "the quick brown fox jumps"
+        );
+        // Right chunk starts in the middle of "quick"
+        assert_eq!(
+            merge_pair("the quick brown fox", "ick brown fox jumps"),
+            "the quick brown fox jumps"
+        );
+    }
+
+    #[test]
+    fn case_and_punctuation_do_not_prevent_alignment() {
+        assert_eq!(
+            merge_pair("we met at the station.", "At the station, we took a cab"),
+            "we met at the station, we took a cab"
+        );
+    }
+
+    #[test]
+    fn single_repeated_word_is_not_an_overlap() {
+        assert_eq!(merge_pair("I said no.", "No, I won't."), "I said no. No, I won't.");
+    }
+
+    #[test]
+    fn disjoint_chunks_are_joined() {
+        assert_eq!(merge_pair("hello there", "general kenobi"), "hello there general kenobi");
+        assert_eq!(merge_pair("", " only right "), "only right");
+        assert_eq!(merge_pair("only left", ""), "only left");
+    }
+
+    #[test]
+    fn identical_chunks_collapse() {
+        assert_eq!(merge_pair("a b c d", "a b c d"), "a b c d");
+    }
+
+    #[test]
+    fn overlap_beyond_the_search_window_is_not_searched() {
+        let long: Vec<String> = (0..40).map(|i| format!("w{}", i)).collect();
+        let left = long[..30].join(" ");
+        let right = long[5..40].join(" ");
+        // The common run starts further than the window: both are kept
+        assert_eq!(merge_pair(&left, &right), format!("{} {}", left, right));
+    }
+
+    #[test]
+    fn chunks_are_merged_in_order() {
+        assert_eq!(merge_transcripts(&["a b c", "", "b c d e", "d e f"]), "a b c d e f");
+        assert_eq!(merge_transcripts::<&str>(&[]), "");
+    }
+}
@@ -0,0 +1,89 @@
+//! Debug dump of provider requests and responses
+//!
+//! When enabled, every transcription request writes one JSON file to the
+//! debug directory: request metadata (endpoint, model, language, audio
+//! size) and the full provider response. Headers are never logged, so API
+//! keys stay out of the dump. Toggled at runtime, no rebuild needed.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+/// Directory receiving the dumps, `None` when logging is off
+fn log_dir() -> &'static Mutex<Option<PathBuf>> {
+    static LOG_DIR: OnceLock<Mutex<Option<PathBuf>>> = OnceLock::new();
+    LOG_DIR.get_or_init(|| Mutex::new(None))
+}
+
+/// Turn the dump on (into `dir`) or off (`None`)
+pub fn set_request_logging(dir: Option<PathBuf>) {
+    if let Some(ref dir) = dir {
+        tracing::info!("Provider request logging enabled: {}", dir.display());
+    }
+    if let Ok(mut current) = log_dir().lock() {
+        *current = dir;
+    }
+}
+
+/// Request metadata, without credentials
+#[derive(Debug, serde::Serialize)]
+pub(super) struct RequestSummary<'a> {
+    pub url: &'a str,
+    pub model: &'a str,
+    pub language: Option<&'a str>,
+    pub audio_bytes: usize,
+    pub duration_secs: f32,
+}
+
+/// Drop the query string, where some APIs accept the key
+fn sanitize_url(url: &str) -> &str {
+    url.split('?').next().unwrap_or(url)
+}
+
+/// Dump one exchange if logging is enabled
+pub(super) fn record_exchange(engine: &str, request: &RequestSummary, status: u16, body: &str) {
+    let Some(dir) = log_dir().lock().ok().and_then(|dir| dir.clone()) else {
+        return;
+    };
+    if let Err(e) = write_exchange(&dir, engine, request, status, body) {
+        tracing::warn!("Cannot write provider request log to {}: {}", dir.display(), e);
+    }
+}
+
+fn write_exchange(
+    dir: &Path,
+    engine: &str,
+    request: &RequestSummary,
+    status: u16,
+    body: &str,
+) -> std::io::Result<()> {
+    static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+    std::fs::create_dir_all(dir)?;
+    let timestamp_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default();
+    let sequence = SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    let slug: String = engine
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect();
+    let path = dir.join(format!("{}-{}-{}.json", timestamp_ms, sequence, slug));
+
+    // Keep the response as JSON when it is, as text otherwise (malformed bodies)
+    let response = serde_json::from_str::<serde_json::Value>(body)
+        .unwrap_or_else(|_| serde_json::Value::String(body.to_string()));
+    let entry = serde_json::json!({
+        "engine": engine,
+        "timestamp_ms": timestamp_ms,
+        "request": RequestSummary {
+            url: sanitize_url(request.url),
+            ..*request
+        },
+        "status": status,
+        "response": response,
+    });
+    let json = serde_json::to_string_pretty(&entry).map_err(std::io::Error::other)?;
+    std::fs::write(&path, json)
+}
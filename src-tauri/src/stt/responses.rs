@@ -0,0 +1,115 @@
+//! Typed provider responses
+//!
+//! Unexpected shapes (schema changes, error payloads returned with a 2xx
+//! status) become explicit `SttError::InferenceError` values instead of
+//! silent empty transcripts.
+
+use super::engine::SttError;
+use serde::de::DeserializeOwned;
+
+/// Maximum length of the raw body quoted in parse errors
+const BODY_EXCERPT_LEN: usize = 200;
+
+/// Parse a provider response body into its expected schema
+pub fn parse_response<T: DeserializeOwned>(provider: &str, body: &str) -> Result<T, SttError> {
+    serde_json::from_str(body).map_err(|e| {
+        let excerpt: String = body.chars().take(BODY_EXCERPT_LEN).collect();
+        SttError::InferenceError(format!(
+            "Unexpected {} response ({}): {}",
+            provider, e, excerpt
+        ))
+    })
+}
+
+/// OpenAI-compatible `/audio/transcriptions` response (OpenAI, Groq, Mistral)
+#[derive(Debug, serde::Deserialize)]
+pub struct TranscriptionResponse {
+    pub text: String,
+}
+
+/// Gemini `generateContent` response
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiResponse {
+    #[serde(default)]
+    pub candidates: Vec<GeminiCandidate>,
+    pub prompt_feedback: Option<GeminiPromptFeedback>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiCandidate {
+    pub content: Option<GeminiContent>,
+    pub finish_reason: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct GeminiContent {
+    #[serde(default)]
+    pub parts: Vec<GeminiPart>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct GeminiPart {
+    pub text: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiPromptFeedback {
+    pub block_reason: Option<String>,
+}
+
+impl GeminiResponse {
+    /// Text of the first candidate, or an error explaining why there is none
+    pub fn into_text(self) -> Result<String, SttError> {
+        if let Some(reason) = self.prompt_feedback.and_then(|f| f.block_reason) {
+            return Err(SttError::InferenceError(format!("Gemini blocked the request: {}", reason)));
+        }
+
+        let candidate = self
+            .candidates
+            .into_iter()
+            .next()
+            .ok_or_else(|| SttError::InferenceError("Gemini returned no candidate".to_string()))?;
+
+        match candidate.content {
+            Some(content) => Ok(content
+                .parts
+                .into_iter()
+                .filter_map(|part| part.text)
+                .collect::<String>()),
+            None => Err(SttError::InferenceError(format!(
+                "Gemini returned no content (finish reason: {})",
+                candidate.finish_reason.as_deref().unwrap_or("unknown")
+            ))),
+        }
+    }
+}
+
+/// OpenAI-compatible `/chat/completions` response
+#[derive(Debug, serde::Deserialize)]
+pub struct ChatCompletionResponse {
+    pub choices: Vec<ChatChoice>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct ChatChoice {
+    pub message: ChatMessage,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct ChatMessage {
+    pub content: Option<String>,
+}
+
+impl ChatCompletionResponse {
+    /// Content of the first choice
+    pub fn into_content(self) -> Result<String, SttError> {
+        self.choices
+            .into_iter()
+            .next()
+            .and_then(|choice| choice.message.content)
+            .ok_or_else(|| SttError::InferenceError("Chat response has no content".to_string()))
+    }
+}
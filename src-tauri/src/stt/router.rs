@@ -0,0 +1,203 @@
+//! Engine selection by dictation length
+//!
+//! Short clips go to the lowest-latency engine, long recordings to the
+//! cheapest one, anything in between to the configured engine. Audio is
+//! buffered by the router and only handed to an engine at flush time, once
+//! the length is known.
+
+use super::engine::{Language, SttEngine, SttError, SttEvent};
+use super::guards::AudioGuards;
+use crate::audio::{AudioAccumulator, AudioFormat, SpeedNormalization};
+
+/// Sample rate of the audio pushed to the engines
+const SAMPLE_RATE: usize = 16000;
+
+/// Length thresholds and the engines they route to
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct EngineRouting {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Engine for dictations up to `short_max_secs` (lowest latency)
+    #[serde(default = "default_short_engine")]
+    pub short_engine: String,
+    #[serde(default = "default_short_max_secs")]
+    pub short_max_secs: u32,
+    /// Engine for dictations of `long_min_secs` or more (cheapest)
+    #[serde(default = "default_long_engine")]
+    pub long_engine: String,
+    #[serde(default = "default_long_min_secs")]
+    pub long_min_secs: u32,
+}
+
+fn default_short_engine() -> String {
+    "groq".to_string()
+}
+
+fn default_short_max_secs() -> u32 {
+    15
+}
+
+fn default_long_engine() -> String {
+    "voxtral".to_string()
+}
+
+fn default_long_min_secs() -> u32 {
+    120
+}
+
+impl Default for EngineRouting {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            short_engine: default_short_engine(),
+            short_max_secs: default_short_max_secs(),
+            long_engine: default_long_engine(),
+            long_min_secs: default_long_min_secs(),
+        }
+    }
+}
+
+/// Engine picked for the last flush
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Route {
+    Short,
+    Default,
+    Long,
+}
+
+/// Engine wrapper dispatching each flush by audio length
+pub struct RoutedEngine {
+    default: Box<dyn SttEngine>,
+    /// `None` when unavailable (no API key...), the default engine is used
+    short: Option<Box<dyn SttEngine>>,
+    long: Option<Box<dyn SttEngine>>,
+    short_max_samples: usize,
+    long_min_samples: usize,
+    /// Audio of the current dictation, until flush
+    buffer: AudioAccumulator,
+    language: Language,
+    active: Route,
+}
+
+impl RoutedEngine {
+    pub fn new(
+        routing: &EngineRouting,
+        default: Box<dyn SttEngine>,
+        short: Option<Box<dyn SttEngine>>,
+        long: Option<Box<dyn SttEngine>>,
+    ) -> Self {
+        Self {
+            default,
+            short,
+            long,
+            short_max_samples: routing.short_max_secs as usize * SAMPLE_RATE,
+            long_min_samples: routing.long_min_secs as usize * SAMPLE_RATE,
+            buffer: AudioAccumulator::default(),
+            language: Language::Auto,
+            active: Route::Default,
+        }
+    }
+
+    fn route_for(&self, samples: usize) -> Route {
+        if samples <= self.short_max_samples && self.short.is_some() {
+            Route::Short
+        } else if samples >= self.long_min_samples && self.long.is_some() {
+            Route::Long
+        } else {
+            Route::Default
+        }
+    }
+
+    fn engine(&mut self, route: Route) -> &mut Box<dyn SttEngine> {
+        match route {
+            Route::Short => self.short.as_mut().unwrap_or(&mut self.default),
+            Route::Long => self.long.as_mut().unwrap_or(&mut self.default),
+            Route::Default => &mut self.default,
+        }
+    }
+
+    fn for_each_engine(&mut self, mut f: impl FnMut(&mut Box<dyn SttEngine>)) {
+        f(&mut self.default);
+        if let Some(ref mut short) = self.short {
+            f(short);
+        }
+        if let Some(ref mut long) = self.long {
+            f(long);
+        }
+    }
+}
+
+impl SttEngine for RoutedEngine {
+    fn load(_model_path: &str) -> Result<Self, SttError> {
+        Err(SttError::ModelNotFound("the router is built from its engines".to_string()))
+    }
+
+    fn set_language(&mut self, language: Language) {
+        self.language = language.clone();
+        self.for_each_engine(|engine| engine.set_language(language.clone()));
+    }
+
+    fn language(&self) -> &Language {
+        &self.language
+    }
+
+    fn push_audio(&mut self, pcm: &[f32]) {
+        self.buffer.extend_from_slice(pcm);
+    }
+
+    fn set_memory_limit(&mut self, samples: usize) {
+        self.buffer.set_memory_limit(samples);
+        self.for_each_engine(|engine| engine.set_memory_limit(samples));
+    }
+
+    fn set_guards(&mut self, guards: AudioGuards) {
+        self.for_each_engine(|engine| engine.set_guards(guards));
+    }
+
+    fn set_speed_normalization(&mut self, speed: SpeedNormalization) {
+        self.for_each_engine(|engine| engine.set_speed_normalization(speed));
+    }
+
+    fn audio_format(&self) -> AudioFormat {
+        self.default.audio_format()
+    }
+
+    fn poll(&mut self) -> Option<SttEvent> {
+        self.engine(self.active).poll()
+    }
+
+    fn flush(&mut self) {
+        let route = self.route_for(self.buffer.len());
+        let mut audio = self.buffer.take();
+        let engine = self.engine(route);
+        tracing::info!(
+            "{:.1}s dictation routed to {} ({:?})",
+            audio.len() as f32 / SAMPLE_RATE as f32,
+            engine.name(),
+            route
+        );
+        let handed = audio.try_for_each_chunk(|chunk| {
+            engine.push_audio(chunk);
+            Ok::<(), std::io::Error>(())
+        });
+        if let Err(e) = handed {
+            tracing::error!("Cannot read buffered audio for routing: {}", e);
+        }
+        engine.flush();
+        self.active = route;
+    }
+
+    fn reset(&mut self) {
+        self.buffer.clear();
+        self.active = Route::Default;
+        self.for_each_engine(|engine| engine.reset());
+    }
+
+    fn name(&self) -> &str {
+        "Length-based routing"
+    }
+
+    fn is_ready(&self) -> bool {
+        self.default.is_ready()
+    }
+}
@@ -0,0 +1,254 @@
+//! Local inference in a separate worker process
+//!
+//! Native inference code (whisper.cpp, Vosk...) runs in a child process so
+//! a crash or an out-of-memory kill cannot take the app down. The worker
+//! speaks JSON lines over stdin/stdout:
+//!
+//! ```text
+//! -> {"type":"hello","protocol_min":1,"protocol_max":1}
+//! <- {"type":"hello","protocol":1,"engine":"vosk 0.3.45"}
+//! -> {"type":"transcribe","id":1,"language":"fr","wav":"<base64 16kHz WAV>"}
+//! <- {"type":"result","id":1,"text":"..."}   or   {"type":"error","id":1,"message":"..."}
+//! ```
+//!
+//! Its stderr goes to the logs. A worker that dies or hangs is killed and
+//! restarted on the next request, which is retried once.
+
+use super::engine::SttError;
+use base64::Engine;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// Protocol versions understood by the app
+const PROTOCOL_MIN: u32 = 1;
+const PROTOCOL_MAX: u32 = 1;
+
+/// Time allowed for the worker to start and load its model
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Time allowed for one transcription
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Restarts tolerated within `RESTART_WINDOW` before giving up
+const MAX_RESTARTS: usize = 3;
+const RESTART_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(serde::Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Request<'a> {
+    Hello { protocol_min: u32, protocol_max: u32 },
+    Transcribe { id: u64, language: Option<&'a str>, wav: String },
+}
+
+#[derive(serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Reply {
+    Hello {
+        protocol: u32,
+        #[serde(default)]
+        engine: String,
+    },
+    Result { id: u64, text: String },
+    Error {
+        #[serde(default)]
+        id: Option<u64>,
+        message: String,
+    },
+}
+
+/// Running worker and the thread reading its stdout
+struct Running {
+    child: Child,
+    stdin: ChildStdin,
+    /// One line per reply, disconnected when the worker exits
+    replies: mpsc::Receiver<String>,
+}
+
+impl Running {
+    fn send(&mut self, request: &Request) -> Result<(), SttError> {
+        let mut line = serde_json::to_string(request).map_err(|e| SttError::InferenceError(e.to_string()))?;
+        line.push('\n');
+        self.stdin
+            .write_all(line.as_bytes())
+            .and_then(|_| self.stdin.flush())
+            .map_err(|e| SttError::InferenceError(format!("Worker unreachable: {}", e)))
+    }
+
+    fn receive(&mut self, timeout: Duration) -> Result<Reply, SttError> {
+        let line = self.replies.recv_timeout(timeout).map_err(|e| match e {
+            mpsc::RecvTimeoutError::Timeout => SttError::InferenceError("Worker timed out".to_string()),
+            mpsc::RecvTimeoutError::Disconnected => {
+                // stdout closes just before the exit, give it a moment to be reaped
+                std::thread::sleep(Duration::from_millis(200));
+                let status = self.child.try_wait().ok().flatten();
+                SttError::InferenceError(format!("Worker exited ({})", describe_exit(status)))
+            }
+        })?;
+        serde_json::from_str(&line)
+            .map_err(|e| SttError::InferenceError(format!("Invalid worker reply: {} ({})", e, line)))
+    }
+}
+
+impl Drop for Running {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn describe_exit(status: Option<std::process::ExitStatus>) -> String {
+    match status {
+        Some(status) => status.to_string(),
+        None => "still running".to_string(),
+    }
+}
+
+/// Supervised worker process, started on first use
+pub(super) struct WorkerProcess {
+    command: String,
+    args: Vec<String>,
+    running: Option<Running>,
+    /// Engine reported by the worker in its handshake
+    engine: String,
+    next_id: u64,
+    restarts: Vec<Instant>,
+}
+
+impl WorkerProcess {
+    pub(super) fn new(command: &str, args: &[String]) -> Self {
+        Self {
+            command: command.to_string(),
+            args: args.to_vec(),
+            running: None,
+            engine: String::new(),
+            next_id: 1,
+            restarts: Vec::new(),
+        }
+    }
+
+    /// Start the worker if needed and check its protocol version
+    pub(super) fn ensure_started(&mut self) -> Result<(), SttError> {
+        if self.running.is_some() {
+            return Ok(());
+        }
+
+        let mut child = Command::new(&self.command)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| SttError::ModelLoadError(format!("Cannot start worker '{}': {}", self.command, e)))?;
+
+        let stdin = child.stdin.take().ok_or(SttError::NotInitialized)?;
+        let stdout = child.stdout.take().ok_or(SttError::NotInitialized)?;
+        let stderr = child.stderr.take().ok_or(SttError::NotInitialized)?;
+
+        let (replies_tx, replies) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if !line.trim().is_empty() && replies_tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        std::thread::spawn(move || {
+            for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                tracing::debug!("worker: {}", line);
+            }
+        });
+
+        let mut running = Running { child, stdin, replies };
+        running.send(&Request::Hello {
+            protocol_min: PROTOCOL_MIN,
+            protocol_max: PROTOCOL_MAX,
+        })?;
+        match running.receive(HANDSHAKE_TIMEOUT)? {
+            Reply::Hello { protocol, engine } if (PROTOCOL_MIN..=PROTOCOL_MAX).contains(&protocol) => {
+                tracing::info!("Local worker started: {} (protocol {})", engine, protocol);
+                self.engine = engine;
+            }
+            Reply::Hello { protocol, .. } => {
+                return Err(SttError::ModelLoadError(format!(
+                    "Worker protocol {} unsupported (expected {} to {})",
+                    protocol, PROTOCOL_MIN, PROTOCOL_MAX
+                )));
+            }
+            Reply::Error { message, .. } => return Err(SttError::ModelLoadError(message)),
+            Reply::Result { .. } => {
+                return Err(SttError::ModelLoadError("Worker skipped the handshake".to_string()));
+            }
+        }
+        self.running = Some(running);
+        Ok(())
+    }
+
+    /// Engine name reported by the worker, empty until started
+    pub(super) fn engine(&self) -> &str {
+        &self.engine
+    }
+
+    /// Transcribe a WAV file, restarting a dead worker once
+    pub(super) fn transcribe(&mut self, wav: &[u8], language: Option<&str>) -> Result<String, SttError> {
+        let wav = base64::engine::general_purpose::STANDARD.encode(wav);
+        match self.try_transcribe(&wav, language) {
+            Ok(text) => Ok(text),
+            Err(e) if self.running.is_none() => {
+                tracing::warn!("Local worker failed ({}), restarting", e);
+                self.note_restart()?;
+                self.try_transcribe(&wav, language)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Refuse to restart a worker that keeps crashing
+    fn note_restart(&mut self) -> Result<(), SttError> {
+        self.restarts.retain(|at| at.elapsed() < RESTART_WINDOW);
+        if self.restarts.len() >= MAX_RESTARTS {
+            return Err(SttError::InferenceError(format!(
+                "Worker crashed {} times in {}s, not restarting",
+                MAX_RESTARTS,
+                RESTART_WINDOW.as_secs()
+            )));
+        }
+        self.restarts.push(Instant::now());
+        Ok(())
+    }
+
+    fn try_transcribe(&mut self, wav: &str, language: Option<&str>) -> Result<String, SttError> {
+        self.ensure_started()?;
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let running = self.running.as_mut().ok_or(SttError::NotInitialized)?;
+        let reply = running
+            .send(&Request::Transcribe {
+                id,
+                language,
+                wav: wav.to_string(),
+            })
+            .and_then(|_| running.receive(REQUEST_TIMEOUT));
+        let reply = match reply {
+            Ok(reply) => reply,
+            Err(e) => {
+                // Dead or hung: killed on drop, started again next time
+                self.running = None;
+                return Err(e);
+            }
+        };
+
+        match reply {
+            Reply::Result { id: reply_id, text } if reply_id == id => Ok(text.trim().to_string()),
+            Reply::Error { id: Some(reply_id), message } if reply_id == id => Err(SttError::InferenceError(message)),
+            Reply::Error { id: None, message } => Err(SttError::InferenceError(message)),
+            _ => {
+                // Out of sync with the worker, start over
+                self.running = None;
+                Err(SttError::InferenceError(format!("Unexpected worker reply to request {}", id)))
+            }
+        }
+    }
+}
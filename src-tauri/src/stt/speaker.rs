@@ -0,0 +1,23 @@
+//! Speaker profiles of the local Whisper engine
+//!
+//! A heavy accent is better served by a model fine-tuned on the speaker's
+//! voice (or with a LoRA adapter merged in). Each profile points to its
+//! own model, with an optional initial prompt (names, jargon). The profile
+//! set for the session wins over the configured one, so several people
+//! sharing a machine keep their own model without touching the settings.
+
+/// Local Whisper model adapted to a speaker
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SpeakerProfile {
+    pub name: String,
+    /// GGML/GGUF model, fine-tuned or LoRA merged
+    pub model_path: String,
+    /// Text the decoding is primed with: spelling of names, vocabulary
+    #[serde(default)]
+    pub prompt: String,
+}
+
+/// Profile called `name` among `profiles`
+pub fn find_profile<'a>(profiles: &'a [SpeakerProfile], name: &str) -> Option<&'a SpeakerProfile> {
+    profiles.iter().find(|profile| profile.name == name)
+}
@@ -0,0 +1,346 @@
+//! Smart stop: transcription started during pauses
+//!
+//! Once the speaker has been silent for a while, the speech so far is sent
+//! in the background while capture goes on. Resuming speech starts a new
+//! segment, sent at the next pause; on stop only the last segment is left
+//! to transcribe, and the segments are merged in order. Segments are cut
+//! on silence so no word is split, and the words an engine repeats at a
+//! seam are kept once. If any of them fails, the whole dictation is sent
+//! again through the regular engine.
+
+use super::engine::{Language, SttEngine, SttError, SttEvent};
+use super::guards::AudioGuards;
+use super::overlap::merge_transcripts;
+use crate::audio::{AudioAccumulator, AudioFormat, SpeedNormalization};
+use crate::vad::VoiceActivityDetector;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+/// Sample rate of the audio pushed to the engines
+const SAMPLE_RATE: usize = 16000;
+
+/// Pause after which the speech so far is sent
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SmartStop {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_silence_ms")]
+    pub silence_ms: u64,
+}
+
+fn default_silence_ms() -> u64 {
+    1200
+}
+
+impl Default for SmartStop {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            silence_ms: default_silence_ms(),
+        }
+    }
+}
+
+/// Builds a fresh engine for each background segment
+pub type EngineFactory = Arc<dyn Fn() -> Option<Box<dyn SttEngine>> + Send + Sync>;
+
+/// Segment sent in the background
+struct Segment {
+    handle: Option<JoinHandle<Result<String, String>>>,
+    result: Option<Result<String, String>>,
+}
+
+impl Segment {
+    /// Collect the result once the thread is done, waiting for it if `block`
+    fn harvest(&mut self, block: bool) -> Option<&Result<String, String>> {
+        if self.result.is_none() && self.handle.as_ref().is_some_and(|h| block || h.is_finished()) {
+            let handle = self.handle.take()?;
+            self.result = Some(handle.join().unwrap_or_else(|_| Err("segment thread panicked".to_string())));
+        }
+        self.result.as_ref()
+    }
+}
+
+/// Transcribe one segment with its own engine
+///
+/// Segments hold speech by construction, so the duration and silence guards
+/// of the whole dictation are not applied to them.
+fn transcribe_segment(
+    factory: &EngineFactory,
+    language: Language,
+    speed: SpeedNormalization,
+    samples: &[f32],
+) -> Result<String, String> {
+    let mut engine = factory().ok_or_else(|| "engine unavailable".to_string())?;
+    engine.set_language(language);
+    engine.set_guards(AudioGuards::new(0, 0.0));
+    engine.set_speed_normalization(speed);
+    engine.push_audio(samples);
+    engine.flush();
+
+    let mut texts = Vec::new();
+    while let Some(event) = engine.poll() {
+        match event {
+            SttEvent::Final(text) if !text.trim().is_empty() => texts.push(text.trim().to_string()),
+            SttEvent::Error(message) => return Err(message),
+            _ => {}
+        }
+    }
+    Ok(texts.join(" "))
+}
+
+/// Engine wrapper sending the speech at each pause
+pub struct SpeculativeEngine {
+    inner: Box<dyn SttEngine>,
+    factory: EngineFactory,
+    vad: Mutex<Box<dyn VoiceActivityDetector>>,
+    silence_samples: usize,
+    language: Language,
+    speed: SpeedNormalization,
+    /// Whole dictation, sent through `inner` if a segment fails
+    full: AudioAccumulator,
+    /// Incomplete VAD frame carried over to the next push
+    pending: Vec<f32>,
+    /// Audio since the last cut
+    current: Vec<f32>,
+    current_has_speech: bool,
+    /// Trailing silent samples in `current`
+    silence_run: usize,
+    segments: Vec<Segment>,
+    /// Segments already reported in a Partial event
+    announced: usize,
+    events: VecDeque<SttEvent>,
+}
+
+impl SpeculativeEngine {
+    pub fn new(
+        inner: Box<dyn SttEngine>,
+        factory: EngineFactory,
+        vad: Box<dyn VoiceActivityDetector>,
+        settings: &SmartStop,
+    ) -> Self {
+        Self {
+            inner,
+            factory,
+            vad: Mutex::new(vad),
+            silence_samples: settings.silence_ms as usize * SAMPLE_RATE / 1000,
+            language: Language::Auto,
+            speed: SpeedNormalization::default(),
+            full: AudioAccumulator::default(),
+            pending: Vec::new(),
+            current: Vec::new(),
+            current_has_speech: false,
+            silence_run: 0,
+            segments: Vec::new(),
+            announced: 0,
+            events: VecDeque::new(),
+        }
+    }
+
+    /// Send the speech since the last cut in the background
+    fn cut_segment(&mut self) {
+        let samples = std::mem::take(&mut self.current);
+        self.current_has_speech = false;
+        self.silence_run = 0;
+
+        tracing::info!(
+            "Pause detected, transcribing {:.1}s in the background",
+            samples.len() as f32 / SAMPLE_RATE as f32
+        );
+        let factory = Arc::clone(&self.factory);
+        let language = self.language.clone();
+        let speed = self.speed;
+        let handle = std::thread::spawn(move || transcribe_segment(&factory, language, speed, &samples));
+        self.segments.push(Segment {
+            handle: Some(handle),
+            result: None,
+        });
+    }
+
+    /// Partial event with the segments transcribed so far, if there are new ones
+    fn announce_progress(&mut self) -> Option<SttEvent> {
+        let mut done = 0;
+        for segment in &mut self.segments {
+            match segment.harvest(false) {
+                Some(Ok(_)) => done += 1,
+                _ => break,
+            }
+        }
+        if done <= self.announced {
+            return None;
+        }
+        self.announced = done;
+        let texts: Vec<&String> = self.segments[..done]
+            .iter()
+            .filter_map(|segment| segment.result.as_ref()?.as_ref().ok())
+            .collect();
+        let text = merge_transcripts(&texts);
+        Some(SttEvent::Partial(text))
+    }
+
+    /// Send the whole dictation through the regular engine
+    fn transcribe_whole(&mut self) {
+        let mut audio = self.full.take();
+        let handed = audio.try_for_each_chunk(|chunk| {
+            self.inner.push_audio(chunk);
+            Ok::<(), std::io::Error>(())
+        });
+        if let Err(e) = handed {
+            tracing::error!("Cannot read buffered audio: {}", e);
+        }
+        self.inner.flush();
+        while let Some(event) = self.inner.poll() {
+            self.events.push_back(event);
+        }
+    }
+
+    fn clear_session(&mut self) {
+        self.full.clear();
+        self.pending.clear();
+        self.current.clear();
+        self.current_has_speech = false;
+        self.silence_run = 0;
+        // Threads still running finish on their own, their result is dropped
+        self.segments.clear();
+        self.announced = 0;
+        if let Ok(mut vad) = self.vad.lock() {
+            vad.reset();
+        }
+    }
+}
+
+impl SttEngine for SpeculativeEngine {
+    fn load(_model_path: &str) -> Result<Self, SttError> {
+        Err(SttError::ModelNotFound("smart stop wraps an existing engine".to_string()))
+    }
+
+    fn set_language(&mut self, language: Language) {
+        self.language = language.clone();
+        self.inner.set_language(language);
+    }
+
+    fn language(&self) -> &Language {
+        &self.language
+    }
+
+    fn push_audio(&mut self, pcm: &[f32]) {
+        self.full.extend_from_slice(pcm);
+
+        let Ok(mut vad) = self.vad.lock() else {
+            self.current.extend_from_slice(pcm);
+            return;
+        };
+        let frame_samples = vad.frame_samples();
+        let mut pending = std::mem::take(&mut self.pending);
+        pending.extend_from_slice(pcm);
+        let frames = pending.len() / frame_samples;
+        let mut cuts = Vec::new();
+        for (index, frame) in pending.chunks_exact(frame_samples).enumerate() {
+            if vad.is_speech(frame) {
+                self.current_has_speech = true;
+                self.silence_run = 0;
+            } else {
+                self.silence_run += frame_samples;
+            }
+            if self.current_has_speech && self.silence_run >= self.silence_samples {
+                cuts.push((index + 1) * frame_samples);
+                self.current_has_speech = false;
+                self.silence_run = 0;
+            }
+        }
+        drop(vad);
+
+        let mut start = 0;
+        for cut in cuts {
+            self.current.extend_from_slice(&pending[start..cut]);
+            start = cut;
+            self.cut_segment();
+        }
+        self.current.extend_from_slice(&pending[start..frames * frame_samples]);
+        pending.drain(..frames * frame_samples);
+        self.pending = pending;
+    }
+
+    fn set_memory_limit(&mut self, samples: usize) {
+        self.full.set_memory_limit(samples);
+        self.inner.set_memory_limit(samples);
+    }
+
+    fn set_guards(&mut self, guards: AudioGuards) {
+        self.inner.set_guards(guards);
+    }
+
+    fn set_speed_normalization(&mut self, speed: SpeedNormalization) {
+        self.speed = speed;
+        self.inner.set_speed_normalization(speed);
+    }
+
+    fn audio_format(&self) -> AudioFormat {
+        self.inner.audio_format()
+    }
+
+    fn poll(&mut self) -> Option<SttEvent> {
+        if let Some(event) = self.events.pop_front() {
+            return Some(event);
+        }
+        self.announce_progress()
+    }
+
+    fn flush(&mut self) {
+        self.current.append(&mut self.pending);
+
+        // No pause during the dictation: the regular path, with its guards
+        if self.segments.is_empty() {
+            self.transcribe_whole();
+            self.clear_session();
+            return;
+        }
+
+        let tail = std::mem::take(&mut self.current);
+        let tail_text = if self.current_has_speech {
+            transcribe_segment(&self.factory, self.language.clone(), self.speed, &tail)
+        } else {
+            Ok(String::new())
+        };
+
+        let mut texts = Vec::new();
+        let mut failure = tail_text.as_ref().err().cloned();
+        for segment in &mut self.segments {
+            match segment.harvest(true) {
+                Some(Ok(text)) => texts.push(text.clone()),
+                Some(Err(e)) => failure = Some(e.clone()),
+                None => failure = Some("segment lost".to_string()),
+            }
+        }
+        texts.extend(tail_text.ok());
+
+        if let Some(e) = failure {
+            tracing::warn!("Background segment failed ({}), sending the whole dictation", e);
+            self.transcribe_whole();
+        } else {
+            let text = merge_transcripts(&texts);
+            tracing::info!("Smart stop merged {} segments", self.segments.len() + 1);
+            self.events.push_back(if text.is_empty() {
+                SttEvent::NoSpeech
+            } else {
+                SttEvent::Final(text)
+            });
+        }
+        self.clear_session();
+    }
+
+    fn reset(&mut self) {
+        self.clear_session();
+        self.events.clear();
+        self.inner.reset();
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+}
@@ -0,0 +1,149 @@
+//! Offline Whisper engine (whisper.cpp through whisper-rs)
+//!
+//! Loads a GGML/GGUF model from disk and transcribes on this machine, no
+//! audio is sent anywhere. whisper.cpp runs in a worker process, the app
+//! itself started as `dictea --whisper-worker <model> [prompt]`, speaking
+//! the `sandbox` protocol: a crash or an out-of-memory kill in native code
+//! cannot take the app down. Behind the `whisper-local` cargo feature.
+
+use super::engine::SttError;
+use super::local::{LocalEngine, LocalWorker};
+use base64::Engine;
+use std::io::{BufRead, Write};
+use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+/// First argument starting the app as a Whisper worker
+pub const WHISPER_WORKER_ARG: &str = "--whisper-worker";
+
+/// Version of the `sandbox` protocol spoken by the worker
+const PROTOCOL: u32 = 1;
+
+/// Start the Whisper worker for `model_path`, priming every decoding with
+/// `prompt` (speaker profile, empty = none)
+pub fn start_whisper_local(model_path: &str, prompt: &str) -> Result<LocalEngine, SttError> {
+    if !std::path::Path::new(model_path).is_file() {
+        return Err(SttError::ModelNotFound(model_path.to_string()));
+    }
+    let app = std::env::current_exe().map_err(|e| SttError::ModelLoadError(format!("Cannot locate the app: {}", e)))?;
+
+    tracing::info!("Loading Whisper model {}", model_path);
+    let mut args = vec![WHISPER_WORKER_ARG.to_string(), model_path.to_string()];
+    if !prompt.trim().is_empty() {
+        args.push(prompt.trim().to_string());
+    }
+    LocalEngine::start(&LocalWorker {
+        command: app.to_string_lossy().into_owned(),
+        args,
+    })
+}
+
+/// Request of the app, as sent by `sandbox`
+#[derive(serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum WorkerRequest {
+    Hello { protocol_min: u32, protocol_max: u32 },
+    Transcribe { id: u64, language: Option<String>, wav: String },
+}
+
+/// Entry point of `dictea --whisper-worker <model> [prompt]`: answers the
+/// requests of the app until stdin closes, returning the process exit code
+pub fn whisper_worker_main(mut args: impl Iterator<Item = String>) -> i32 {
+    let Some(model_path) = args.next() else {
+        eprintln!("usage: dictea {} <model> [prompt]", WHISPER_WORKER_ARG);
+        return 2;
+    };
+    let prompt = args.next().unwrap_or_default();
+
+    // The handshake is answered with the load error, if any
+    let context = match WhisperContext::new_with_params(&model_path, WhisperContextParameters::default()) {
+        Ok(context) => context,
+        Err(e) => {
+            let message = format!("Cannot load {}: {}", model_path, e);
+            let _ = send(&serde_json::json!({ "type": "error", "message": message }));
+            return 1;
+        }
+    };
+
+    for line in std::io::stdin().lock().lines().map_while(Result::ok) {
+        let reply = match serde_json::from_str::<WorkerRequest>(&line) {
+            Ok(WorkerRequest::Hello {
+                protocol_min,
+                protocol_max,
+            }) if (protocol_min..=protocol_max).contains(&PROTOCOL) => {
+                serde_json::json!({ "type": "hello", "protocol": PROTOCOL, "engine": "whisper.cpp" })
+            }
+            Ok(WorkerRequest::Hello { .. }) => {
+                serde_json::json!({ "type": "error", "message": format!("Only protocol {} is supported", PROTOCOL) })
+            }
+            Ok(WorkerRequest::Transcribe { id, language, wav }) => {
+                match decode_wav(&wav).and_then(|samples| transcribe(&context, &samples, language.as_deref(), &prompt)) {
+                    Ok(text) => serde_json::json!({ "type": "result", "id": id, "text": text }),
+                    Err(message) => serde_json::json!({ "type": "error", "id": id, "message": message }),
+                }
+            }
+            Err(e) => serde_json::json!({ "type": "error", "message": format!("Invalid request: {}", e) }),
+        };
+        if send(&reply).is_err() {
+            break;
+        }
+    }
+    0
+}
+
+/// Write one reply line on stdout
+fn send(reply: &serde_json::Value) -> std::io::Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    writeln!(stdout, "{}", reply)?;
+    stdout.flush()
+}
+
+/// Samples of the base64 16 kHz mono WAV sent by the app
+fn decode_wav(wav: &str) -> Result<Vec<f32>, String> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(wav)
+        .map_err(|e| format!("Invalid audio: {}", e))?;
+    let mut reader = hound::WavReader::new(std::io::Cursor::new(bytes)).map_err(|e| format!("WAV read error: {}", e))?;
+    reader
+        .samples::<i16>()
+        .map(|sample| sample.map(|sample| sample as f32 / 32768.0))
+        .collect::<Result<_, _>>()
+        .map_err(|e| format!("WAV read error: {}", e))
+}
+
+/// Decode the whole dictation (blocking, CPU bound)
+fn transcribe(context: &WhisperContext, samples: &[f32], language: Option<&str>, prompt: &str) -> Result<String, String> {
+    let mut state = context
+        .create_state()
+        .map_err(|e| format!("Whisper state error: {}", e))?;
+
+    let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+    params.set_language(Some(language.unwrap_or("auto")));
+    if !prompt.is_empty() {
+        params.set_initial_prompt(prompt);
+    }
+    let threads = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4).min(8);
+    params.set_n_threads(threads as i32);
+    params.set_print_progress(false);
+    params.set_print_realtime(false);
+    params.set_print_special(false);
+    params.set_print_timestamps(false);
+
+    let started = std::time::Instant::now();
+    state
+        .full(params, samples)
+        .map_err(|e| format!("Whisper inference error: {}", e))?;
+
+    let segments = state.full_n_segments().map_err(|e| e.to_string())?;
+    let mut text = String::new();
+    for index in 0..segments {
+        let segment = state.full_get_segment_text(index).map_err(|e| e.to_string())?;
+        text.push_str(&segment);
+    }
+    // stderr ends up in the logs of the app
+    eprintln!(
+        "Whisper decoded {:.1}s audio in {:.1}s",
+        samples.len() as f32 / 16000.0,
+        started.elapsed().as_secs_f32()
+    );
+    Ok(text.trim().to_string())
+}
@@ -0,0 +1,114 @@
+//! Supervised request threads of the HTTP engines
+//!
+//! Each flush sends its audio from a dedicated thread. A panic in there
+//! (runtime creation, encoding, response handling) must still end the
+//! request: it is caught and reported as an `Error` event, and the pending
+//! flag is released so the next dictation is not blocked. Requests wait
+//! for a slot of their provider first (see `limiter`), and are dropped
+//! unsent if their session is reset meanwhile.
+
+use super::engine::{SttError, SttEvent};
+use super::limiter;
+use std::any::Any;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Events queue shared between an engine and its request threads
+pub(super) type SharedEvents = Arc<Mutex<VecDeque<(u64, SttEvent)>>>;
+
+/// One in-flight request, bound to the session that sent it
+pub(super) struct RequestWorker {
+    engine: &'static str,
+    events: SharedEvents,
+    pending: Arc<AtomicBool>,
+    current_generation: Arc<AtomicU64>,
+    generation: u64,
+}
+
+impl RequestWorker {
+    /// Mark a request as pending for the current session
+    pub(super) fn new(
+        engine: &'static str,
+        events: &SharedEvents,
+        pending: &Arc<AtomicBool>,
+        generation: &Arc<AtomicU64>,
+    ) -> Self {
+        pending.store(true, Ordering::SeqCst);
+        Self {
+            engine,
+            events: Arc::clone(events),
+            pending: Arc::clone(pending),
+            current_generation: Arc::clone(generation),
+            generation: generation.load(Ordering::SeqCst),
+        }
+    }
+
+    /// Run `request` on its own thread and queue its transcript
+    pub(super) fn spawn<F, Fut>(self, request: F)
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<String, SttError>>,
+    {
+        std::thread::spawn(move || {
+            let result = {
+                // Queued behind the other requests of the provider, released even on panic
+                let stale = || self.current_generation.load(Ordering::SeqCst) != self.generation;
+                let Some(_permit) = limiter::acquire(&self.engine.to_lowercase(), stale) else {
+                    // Cancelled while queued, a newer session owns the pending flag
+                    return;
+                };
+                panic::catch_unwind(AssertUnwindSafe(|| {
+                    let rt = tokio::runtime::Runtime::new()
+                        .map_err(|e| SttError::InferenceError(format!("Cannot start runtime: {}", e)))?;
+                    rt.block_on(request())
+                }))
+            };
+
+            let event = match result {
+                Ok(Ok(text)) if text.is_empty() => {
+                    tracing::info!("{} result: no speech", self.engine);
+                    SttEvent::NoSpeech
+                }
+                Ok(Ok(text)) => {
+                    tracing::info!("{} result: {}", self.engine, text);
+                    SttEvent::Final(text)
+                }
+                Ok(Err(e)) => {
+                    tracing::error!("{} error: {}", self.engine, e);
+                    SttEvent::Error(e.to_string())
+                }
+                Err(payload) => {
+                    let reason = panic_reason(payload.as_ref());
+                    tracing::error!("{} request thread panicked: {}", self.engine, reason);
+                    SttEvent::Error(format!("{} request crashed: {}", self.engine, reason))
+                }
+            };
+            self.finish(event);
+        });
+    }
+
+    /// Queue the outcome and release the pending flag
+    fn finish(self, event: SttEvent) {
+        // A panic while holding the queue must not lose later events
+        let mut events = self.events.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        events.push_back((self.generation, event));
+        drop(events);
+
+        // A newer session owns the flag if the engine was reset meanwhile
+        if self.current_generation.load(Ordering::SeqCst) == self.generation {
+            self.pending.store(false, Ordering::SeqCst);
+        }
+    }
+}
+
+/// Message carried by a panic payload
+fn panic_reason(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic")
+}
@@ -0,0 +1,59 @@
+//! Monthly spend caps per provider
+
+use super::UsageLedger;
+use std::collections::BTreeMap;
+
+/// Share of the cap at which the user is warned
+const WARNING_RATIO: f64 = 0.8;
+
+/// Monthly spend caps and what to do once one is reached
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CostBudget {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Cap in USD by engine id, engines without one are unlimited
+    #[serde(default)]
+    pub monthly_usd: BTreeMap<String, f64>,
+    /// Engine used instead of a capped one (empty = only warn)
+    #[serde(default)]
+    pub fallback_engine: String,
+}
+
+/// Spend of an engine relative to its cap
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BudgetStatus {
+    Within,
+    /// Past `WARNING_RATIO` of the cap
+    Warning { spent: f64, cap: f64 },
+    Exceeded { spent: f64, cap: f64 },
+}
+
+impl CostBudget {
+    /// Where `engine` stands against its cap this month
+    pub fn status(&self, ledger: &UsageLedger, engine: &str) -> BudgetStatus {
+        let cap = match self.monthly_usd.get(engine) {
+            Some(&cap) if self.enabled && cap > 0.0 => cap,
+            _ => return BudgetStatus::Within,
+        };
+        let spent = ledger.estimated_usd(engine);
+        if spent >= cap {
+            BudgetStatus::Exceeded { spent, cap }
+        } else if spent >= cap * WARNING_RATIO {
+            BudgetStatus::Warning { spent, cap }
+        } else {
+            BudgetStatus::Within
+        }
+    }
+
+    /// Engine to dictate with instead of `engine`, if its cap is reached
+    pub fn fallback_for(&self, ledger: &UsageLedger, engine: &str) -> Option<&str> {
+        let fallback = self.fallback_engine.as_str();
+        if fallback.is_empty() || fallback == engine {
+            return None;
+        }
+        match self.status(ledger, fallback) {
+            BudgetStatus::Exceeded { .. } => None,
+            _ => Some(fallback),
+        }
+    }
+}
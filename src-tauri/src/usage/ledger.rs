@@ -0,0 +1,123 @@
+//! Monthly usage ledger, persisted as JSON
+
+use chrono::Local;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// Estimated USD price of one minute of audio, by engine id
+///
+/// Public list prices of the models in use; the bill may differ slightly
+/// (rounding per request, free tiers).
+fn price_per_minute(engine: &str) -> f64 {
+    match engine {
+        // whisper-1
+        "openai" => 0.006,
+        // whisper-large-v3-turbo, $0.04/hour
+        "groq" => 0.04 / 60.0,
+        // voxtral-mini-latest
+        "voxtral" => 0.001,
+        // gemini-2.5-flash, ~1920 audio tokens per minute at $1/M
+        "gemini" => 0.00192,
+        // Speech-to-Text v2 standard models, $0.016/minute
+        "google" => 0.016,
+        // Universal Streaming plus the async pass on flush, $0.15/hour each
+        "assemblyai" => 0.30 / 60.0,
+        _ => 0.0,
+    }
+}
+
+/// Usage of one provider over the current month
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProviderUsage {
+    pub engine: String,
+    pub audio_secs: f64,
+    pub estimated_usd: f64,
+    /// Monthly cap (0 = none)
+    pub cap_usd: f64,
+}
+
+/// Audio seconds sent per month and per engine
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct UsageLedger {
+    #[serde(skip)]
+    path: PathBuf,
+    /// "YYYY-MM" -> engine id -> seconds
+    #[serde(default)]
+    months: BTreeMap<String, BTreeMap<String, f64>>,
+    /// "YYYY-MM/engine" already warned about, to warn once per month
+    #[serde(default)]
+    warned: BTreeSet<String>,
+}
+
+impl UsageLedger {
+    /// Read the ledger, starting empty if missing or unreadable
+    pub fn load(path: &Path) -> Self {
+        let mut ledger: Self = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| match serde_json::from_str(&content) {
+                Ok(ledger) => Some(ledger),
+                Err(e) => {
+                    tracing::warn!("Usage ledger unreadable, starting over: {}", e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        ledger.path = path.to_path_buf();
+        ledger
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Usage dir error: {}", e))?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(&self.path, content).map_err(|e| format!("Usage ledger write error: {}", e))
+    }
+
+    fn current_month() -> String {
+        Local::now().format("%Y-%m").to_string()
+    }
+
+    /// Add `secs` of audio sent to `engine` this month
+    pub fn record(&mut self, engine: &str, secs: f64) {
+        let month = Self::current_month();
+        // Only the current month is ever checked, older ones are just kept for reference
+        *self.months.entry(month).or_default().entry(engine.to_string()).or_default() += secs;
+    }
+
+    /// Audio seconds sent to `engine` this month
+    pub fn audio_secs(&self, engine: &str) -> f64 {
+        self.months
+            .get(&Self::current_month())
+            .and_then(|engines| engines.get(engine))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Estimated spend on `engine` this month
+    pub fn estimated_usd(&self, engine: &str) -> f64 {
+        self.audio_secs(engine) / 60.0 * price_per_minute(engine)
+    }
+
+    /// Remember the warning for `engine`; false if already given this month
+    pub fn mark_warned(&mut self, engine: &str) -> bool {
+        self.warned.insert(format!("{}/{}", Self::current_month(), engine))
+    }
+
+    /// This month's usage of every engine used or capped
+    pub fn summary(&self, caps: &BTreeMap<String, f64>) -> Vec<ProviderUsage> {
+        let mut engines: BTreeSet<&str> = caps.keys().map(String::as_str).collect();
+        if let Some(used) = self.months.get(&Self::current_month()) {
+            engines.extend(used.keys().map(String::as_str));
+        }
+        engines
+            .into_iter()
+            .map(|engine| ProviderUsage {
+                engine: engine.to_string(),
+                audio_secs: self.audio_secs(engine),
+                estimated_usd: self.estimated_usd(engine),
+                cap_usd: caps.get(engine).copied().unwrap_or(0.0),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,10 @@
+//! Provider usage tracking module
+//!
+//! Counts the audio sent to each paid provider per month, estimates what it
+//! costs and checks it against the monthly caps set by the user.
+
+mod budget;
+mod ledger;
+
+pub use budget::{BudgetStatus, CostBudget};
+pub use ledger::{ProviderUsage, UsageLedger};
@@ -0,0 +1,50 @@
+//! VAD trait and backend selection
+
+use super::energy::EnergyVad;
+use crate::config::{AppConfig, VadBackend};
+
+/// Frame-level speech detector, fed with PCM float32 mono at 16kHz
+#[allow(dead_code)]
+pub trait VoiceActivityDetector: Send {
+    /// Number of samples per analysed frame
+    fn frame_samples(&self) -> usize;
+
+    /// Whether the frame (exactly `frame_samples()` long) holds speech
+    fn is_speech(&mut self, frame: &[f32]) -> bool;
+
+    /// Forget the state carried between frames
+    fn reset(&mut self);
+
+    /// Return the backend name
+    fn name(&self) -> &str;
+}
+
+/// Build the detector selected in config
+///
+/// Backends not compiled in, or failing to initialize, fall back to the
+/// energy detector.
+pub fn create_detector(config: &AppConfig) -> Box<dyn VoiceActivityDetector> {
+    let energy = || -> Box<dyn VoiceActivityDetector> { Box::new(EnergyVad::new(config.silence_threshold)) };
+
+    match config.vad_backend {
+        VadBackend::Energy => energy(),
+
+        #[cfg(feature = "vad-webrtc")]
+        VadBackend::WebRtc => Box::new(super::webrtc::WebRtcVad::new(config.vad_aggressiveness)),
+
+        #[cfg(feature = "vad-silero")]
+        VadBackend::Silero => match super::silero::SileroVad::load(&config.silero_model_path) {
+            Ok(vad) => Box::new(vad),
+            Err(e) => {
+                tracing::error!("Silero VAD unavailable, using energy detection: {}", e);
+                energy()
+            }
+        },
+
+        #[allow(unreachable_patterns)]
+        ref backend => {
+            tracing::warn!("VAD backend {:?} not built in, using energy detection", backend);
+            energy()
+        }
+    }
+}
@@ -0,0 +1,42 @@
+//! Energy-based VAD
+//!
+//! Frame RMS against a fixed threshold. Cheap and good enough in a quiet
+//! room, fooled by steady background noise.
+
+use super::VoiceActivityDetector;
+
+/// Analysis frame: 20ms at 16kHz
+const FRAME_SAMPLES: usize = 320;
+
+/// RMS level used when no silence threshold is configured
+const DEFAULT_SPEECH_RMS: f32 = 0.01;
+
+pub struct EnergyVad {
+    speech_rms: f32,
+}
+
+impl EnergyVad {
+    /// `speech_rms` is the frame level above which audio counts as speech (0 = default)
+    pub fn new(speech_rms: f32) -> Self {
+        Self {
+            speech_rms: if speech_rms > 0.0 { speech_rms } else { DEFAULT_SPEECH_RMS },
+        }
+    }
+}
+
+impl VoiceActivityDetector for EnergyVad {
+    fn frame_samples(&self) -> usize {
+        FRAME_SAMPLES
+    }
+
+    fn is_speech(&mut self, frame: &[f32]) -> bool {
+        let rms = (frame.iter().map(|s| s * s).sum::<f32>() / frame.len().max(1) as f32).sqrt();
+        rms >= self.speech_rms
+    }
+
+    fn reset(&mut self) {}
+
+    fn name(&self) -> &str {
+        "energy"
+    }
+}
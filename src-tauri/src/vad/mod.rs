@@ -0,0 +1,17 @@
+//! Voice activity detection module
+//!
+//! Every feature needing to tell speech from silence (meeting turn
+//! segmentation, silence auto-stop, the overlay level indicator, and later
+//! pre-roll trimming) goes through the `VoiceActivityDetector` trait, so
+//! the backend can be picked in config.
+
+mod detector;
+mod energy;
+mod silence;
+#[cfg(feature = "vad-silero")]
+mod silero;
+#[cfg(feature = "vad-webrtc")]
+mod webrtc;
+
+pub use detector::{create_detector, VoiceActivityDetector};
+pub use silence::AutoStop;
@@ -0,0 +1,86 @@
+//! Silence-based auto-stop
+//!
+//! Runs the microphone audio through a speech detector and reports when the
+//! pause after the last spoken frame gets long enough to end the dictation.
+
+use super::energy::EnergyVad;
+use super::{create_detector, VoiceActivityDetector};
+use crate::config::AppConfig;
+
+/// Sample rate of the analysed audio
+const SAMPLE_RATE: usize = 16000;
+
+/// Settings of the silence auto-stop
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AutoStop {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Pause after speech that stops the dictation
+    #[serde(default = "default_silence_ms")]
+    pub silence_ms: u64,
+    /// RMS level under which a frame is silent (0 = the VAD backend in config)
+    #[serde(default)]
+    pub threshold: f32,
+}
+
+fn default_silence_ms() -> u64 {
+    2000
+}
+
+impl Default for AutoStop {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            silence_ms: default_silence_ms(),
+            threshold: 0.0,
+        }
+    }
+}
+
+impl AutoStop {
+    /// Timer for a new dictation
+    pub fn timer(&self, config: &AppConfig) -> SilenceTimer {
+        let detector: Box<dyn VoiceActivityDetector> = if self.threshold > 0.0 {
+            Box::new(EnergyVad::new(self.threshold))
+        } else {
+            create_detector(config)
+        };
+        SilenceTimer {
+            detector,
+            pending: Vec::new(),
+            heard_speech: false,
+            silent_samples: 0,
+            limit_samples: (self.silence_ms as usize).saturating_mul(SAMPLE_RATE) / 1000,
+        }
+    }
+}
+
+/// Length of the silence since the last speech of a dictation
+pub struct SilenceTimer {
+    detector: Box<dyn VoiceActivityDetector>,
+    /// Samples waiting to fill a detector frame
+    pending: Vec<f32>,
+    /// The pause before the first words never stops the dictation
+    heard_speech: bool,
+    silent_samples: usize,
+    limit_samples: usize,
+}
+
+impl SilenceTimer {
+    /// Feed 16kHz mono samples, true once the pause after speech is long enough
+    pub fn push(&mut self, samples: &[f32]) -> bool {
+        self.pending.extend_from_slice(samples);
+        let frame_len = self.detector.frame_samples();
+        let frames = self.pending.len() / frame_len;
+        for frame in self.pending.chunks_exact(frame_len) {
+            if self.detector.is_speech(frame) {
+                self.heard_speech = true;
+                self.silent_samples = 0;
+            } else if self.heard_speech {
+                self.silent_samples += frame_len;
+            }
+        }
+        self.pending.drain(..frames * frame_len);
+        self.heard_speech && self.silent_samples >= self.limit_samples
+    }
+}
@@ -0,0 +1,88 @@
+//! Silero VAD (ONNX)
+//!
+//! Small neural detector, the most accurate in noisy places (cafés,
+//! open spaces). Needs the `silero_vad.onnx` model (v5) on disk.
+
+use super::VoiceActivityDetector;
+use ort::session::Session;
+use ort::value::Tensor;
+
+/// Analysis window expected by the v5 model at 16kHz
+const FRAME_SAMPLES: usize = 512;
+
+/// Tail of the previous window prepended to each input
+const CONTEXT_SAMPLES: usize = 64;
+
+/// Size of the recurrent state (2 x 1 x 128)
+const STATE_LEN: usize = 2 * 128;
+
+/// Speech probability above which a frame counts as speech
+const SPEECH_PROBABILITY: f32 = 0.5;
+
+pub struct SileroVad {
+    session: Session,
+    state: Vec<f32>,
+    context: Vec<f32>,
+}
+
+impl SileroVad {
+    /// Load the ONNX model
+    pub fn load(model_path: &str) -> Result<Self, String> {
+        if model_path.is_empty() {
+            return Err("no model path configured".to_string());
+        }
+        let session = Session::builder()
+            .and_then(|builder| builder.commit_from_file(model_path))
+            .map_err(|e| format!("Cannot load {}: {}", model_path, e))?;
+        Ok(Self {
+            session,
+            state: vec![0.0; STATE_LEN],
+            context: vec![0.0; CONTEXT_SAMPLES],
+        })
+    }
+
+    /// Speech probability of one window
+    fn probability(&mut self, frame: &[f32]) -> Result<f32, ort::Error> {
+        let mut input = Vec::with_capacity(CONTEXT_SAMPLES + frame.len());
+        input.extend_from_slice(&self.context);
+        input.extend_from_slice(frame);
+
+        let outputs = self.session.run(ort::inputs![
+            "input" => Tensor::from_array(([1usize, input.len()], input))?,
+            "state" => Tensor::from_array(([2usize, 1, 128], self.state.clone()))?,
+            "sr" => Tensor::from_array(([1usize], vec![16000i64]))?,
+        ]?)?;
+
+        let (_, probability) = outputs["output"].try_extract_raw_tensor::<f32>()?;
+        let (_, state) = outputs["stateN"].try_extract_raw_tensor::<f32>()?;
+        self.state.copy_from_slice(state);
+        self.context
+            .copy_from_slice(&frame[frame.len().saturating_sub(CONTEXT_SAMPLES)..]);
+        Ok(probability.first().copied().unwrap_or(0.0))
+    }
+}
+
+impl VoiceActivityDetector for SileroVad {
+    fn frame_samples(&self) -> usize {
+        FRAME_SAMPLES
+    }
+
+    fn is_speech(&mut self, frame: &[f32]) -> bool {
+        match self.probability(frame) {
+            Ok(probability) => probability >= SPEECH_PROBABILITY,
+            Err(e) => {
+                tracing::warn!("Silero VAD inference error: {}", e);
+                false
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.state.fill(0.0);
+        self.context.fill(0.0);
+    }
+
+    fn name(&self) -> &str {
+        "silero"
+    }
+}
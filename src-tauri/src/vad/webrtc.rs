@@ -0,0 +1,60 @@
+//! WebRTC VAD (libfvad)
+//!
+//! GMM-based detector from the WebRTC project, robust to steady noise at a
+//! negligible CPU cost.
+
+use super::VoiceActivityDetector;
+use webrtc_vad::{SampleRate, Vad, VadMode};
+
+/// Analysis frame: 30ms at 16kHz
+const FRAME_SAMPLES: usize = 480;
+
+pub struct WebRtcVad {
+    vad: Vad,
+    mode: u8,
+}
+
+// SAFETY: the fvad instance has no thread affinity, it is only ever used
+// through `&mut self`
+unsafe impl Send for WebRtcVad {}
+
+/// Map 0..=3 to the fvad modes (higher = filters more non-speech)
+fn vad_mode(aggressiveness: u8) -> VadMode {
+    match aggressiveness {
+        0 => VadMode::Quality,
+        1 => VadMode::LowBitrate,
+        2 => VadMode::Aggressive,
+        _ => VadMode::VeryAggressive,
+    }
+}
+
+impl WebRtcVad {
+    pub fn new(aggressiveness: u8) -> Self {
+        Self {
+            vad: Vad::new_with_rate_and_mode(SampleRate::Rate16kHz, vad_mode(aggressiveness)),
+            mode: aggressiveness,
+        }
+    }
+}
+
+impl VoiceActivityDetector for WebRtcVad {
+    fn frame_samples(&self) -> usize {
+        FRAME_SAMPLES
+    }
+
+    fn is_speech(&mut self, frame: &[f32]) -> bool {
+        let pcm: Vec<i16> = frame
+            .iter()
+            .map(|s| (s * 32767.0).clamp(-32768.0, 32767.0) as i16)
+            .collect();
+        self.vad.is_voice_segment(&pcm).unwrap_or(false)
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.mode);
+    }
+
+    fn name(&self) -> &str {
+        "webrtc"
+    }
+}
@@ -0,0 +1,136 @@
+//! End-to-end dictations through the app, each HTTP engine against a mock
+//! provider
+//!
+//! Run with `cargo test --features sim`. The app itself is launched
+//! (`dictea --scenarios`), so a display is needed (`xvfb-run` on a headless
+//! Linux machine).
+
+#![cfg(feature = "sim")]
+
+use dictea_lib::sim::{run_scenarios, AudioFixture, Dictation, DictationOutcome, Fixture, MockProvider, Provider};
+use std::process::Command;
+
+/// Dictation transcribed and handed back
+const TRANSCRIBED: [&str; 7] = [
+    "state:recording",
+    "recording_started",
+    "state:transcribing",
+    "recording_stopped",
+    "state:processing",
+    "processing_started",
+    "state:idle",
+];
+
+/// Dictation ending without any recognized speech
+const NO_SPEECH: [&str; 6] = [
+    "state:recording",
+    "recording_started",
+    "state:transcribing",
+    "recording_stopped",
+    "no_speech",
+    "state:no_speech",
+];
+
+/// Dictation whose transcription failed
+const FAILED: [&str; 5] = [
+    "state:recording",
+    "recording_started",
+    "state:transcribing",
+    "recording_stopped",
+    "state:error",
+];
+
+/// Short dictation: a pause, a spoken phrase, a pause
+fn phrase() -> Vec<f32> {
+    AudioFixture::new(7).silence(200).speech(1200, 0.3).silence(300).samples()
+}
+
+/// The app built for the tests, its data kept out of the real app data
+fn app() -> Command {
+    let data = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("sim-engines");
+    let mut app = Command::new(env!("CARGO_BIN_EXE_dictea"));
+    app.env("XDG_DATA_HOME", data.join("data"))
+        .env("XDG_CONFIG_HOME", data.join("config"));
+    app
+}
+
+/// Dictate `audio` once with every provider, each answered with the
+/// fixture `fixture` gives for it
+fn dictate_all(
+    fixture: impl Fn(Provider) -> Fixture,
+    audio: &[f32],
+) -> Vec<(Provider, DictationOutcome, MockProvider)> {
+    let mocks: Vec<_> = Provider::ALL
+        .into_iter()
+        .map(|provider| (provider, MockProvider::start(vec![fixture(provider)]).expect("mock provider")))
+        .collect();
+    let dictations: Vec<_> = mocks
+        .iter()
+        .map(|(provider, mock)| Dictation {
+            provider: *provider,
+            api_base: mock.base_url().to_string(),
+            audio: audio.to_vec(),
+        })
+        .collect();
+    let outcomes = run_scenarios(app(), &dictations).expect("scenarios");
+    assert_eq!(outcomes.len(), mocks.len());
+    mocks
+        .into_iter()
+        .zip(outcomes)
+        .map(|((provider, mock), outcome)| (provider, outcome, mock))
+        .collect()
+}
+
+#[test]
+fn success_is_transcribed_and_returned() {
+    for (provider, outcome, mock) in dictate_all(|provider| Fixture::transcript(provider, " Hello world "), &phrase()) {
+        assert_eq!(outcome.events, TRANSCRIBED, "{:?}: {:?}", provider, outcome);
+        assert_eq!(outcome.text, "Hello world", "{:?}", provider);
+        assert_eq!(outcome.error, None, "{:?}", provider);
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 1, "{:?}", provider);
+        assert_eq!(requests[0].method, "POST");
+        assert!(requests[0].authenticated);
+        assert!(requests[0].body_len > 0);
+    }
+}
+
+#[test]
+fn empty_transcript_is_no_speech() {
+    for (provider, outcome, _mock) in dictate_all(|provider| Fixture::transcript(provider, "  "), &phrase()) {
+        assert_eq!(outcome.events, NO_SPEECH, "{:?}: {:?}", provider, outcome);
+        assert_eq!(outcome.text, "", "{:?}", provider);
+    }
+}
+
+#[test]
+fn http_errors_are_reported_with_their_status() {
+    for (fixture, status) in [(Fixture::unauthorized(), "401"), (Fixture::rate_limited(), "429")] {
+        for (provider, outcome, _mock) in dictate_all(|_| fixture.clone(), &phrase()) {
+            assert_eq!(outcome.events, FAILED, "{:?}: {:?}", provider, outcome);
+            assert!(
+                outcome.error.as_deref().is_some_and(|error| error.contains(status)),
+                "{:?}: {:?}",
+                provider,
+                outcome
+            );
+        }
+    }
+}
+
+#[test]
+fn malformed_json_is_an_error() {
+    for (provider, outcome, _mock) in dictate_all(|_| Fixture::malformed(), &phrase()) {
+        assert_eq!(outcome.events, FAILED, "{:?}: {:?}", provider, outcome);
+        assert!(outcome.error.is_some(), "{:?}", provider);
+    }
+}
+
+#[test]
+fn short_audio_is_not_sent() {
+    let audio = AudioFixture::new(7).tone(300, 220.0, 0.3).samples();
+    for (provider, outcome, mock) in dictate_all(|provider| Fixture::transcript(provider, "unused"), &audio) {
+        assert_eq!(outcome.events, NO_SPEECH, "{:?}: {:?}", provider, outcome);
+        assert!(mock.requests().is_empty(), "{:?}", provider);
+    }
+}
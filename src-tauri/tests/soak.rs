@@ -0,0 +1,26 @@
+//! Leak regression: repeated dictations must leave no task, timer or
+//! capture thread behind
+//!
+//! Run with `cargo test --features sim`. The app itself is launched
+//! (`dictea --soak`), so a display is needed (`xvfb-run` on a headless
+//! Linux machine).
+
+#![cfg(feature = "sim")]
+
+use dictea_lib::sim::SOAK_ARG;
+use std::process::Command;
+
+#[test]
+fn repeated_dictations_do_not_leak() {
+    // Keeps the provider usage of the simulated dictations out of the real app data
+    let data = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("soak");
+    let output = Command::new(env!("CARGO_BIN_EXE_dictea"))
+        .args([SOAK_ARG, "60"])
+        .env("XDG_DATA_HOME", data.join("data"))
+        .env("XDG_CONFIG_HOME", data.join("config"))
+        .output()
+        .expect("app launch");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}\n{}", stdout, String::from_utf8_lossy(&output.stderr));
+    assert!(!stdout.contains("LEAK"), "{}", stdout);
+}
@@ -1,9 +1,11 @@
 import { useState, useEffect } from "react";
 import { getCurrentWindow } from "@tauri-apps/api/window";
-import { I18nContext, getStoredLang, translations, type AppLang, type TranslationKey } from "./i18n";
+import { I18nContext, formatMessage, getStoredLang, translations, type AppLang, type TranslationKey } from "./i18n";
 import { SettingsView } from "./components/SettingsView";
 import { OverlayView } from "./components/OverlayView";
+import { CaptionsView } from "./components/CaptionsView";
 import { ToastProvider } from "./components/Toast";
+import type { Message } from "./types";
 
 function App() {
   const [windowLabel, setWindowLabel] = useState<string | null>(null);
@@ -15,6 +17,7 @@ function App() {
   };
 
   const t = (key: TranslationKey) => translations[lang][key];
+  const tm = (message: Message) => formatMessage(lang, message);
 
   useEffect(() => {
     setWindowLabel(getCurrentWindow().label);
@@ -25,9 +28,15 @@ function App() {
   }
 
   return (
-    <I18nContext.Provider value={{ t, lang, setLang }}>
+    <I18nContext.Provider value={{ t, tm, lang, setLang }}>
       <ToastProvider>
-        {windowLabel === "overlay" ? <OverlayView /> : <SettingsView />}
+        {windowLabel.startsWith("overlay") ? (
+          <OverlayView />
+        ) : windowLabel === "captions" ? (
+          <CaptionsView />
+        ) : (
+          <SettingsView />
+        )}
       </ToastProvider>
     </I18nContext.Provider>
   );
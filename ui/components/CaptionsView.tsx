@@ -0,0 +1,50 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import type { AppConfig, Captions, SyncState } from "../types";
+
+export function CaptionsView() {
+  const [text, setText] = useState("");
+  const [style, setStyle] = useState<Captions | null>(null);
+
+  useEffect(() => {
+    // No event permissions in this window: the backend drives it through eval
+    (window as any).__captionsSetText = (v: string) => setText(v);
+    (window as any).__captionsSetStyle = (v: Captions) => setStyle(v);
+
+    // The webview loads after the window is first shown: catch up on the state
+    invoke<AppConfig>("get_config")
+      .then((config) => setStyle((current) => current ?? config.captions))
+      .catch(() => {});
+    invoke<SyncState>("sync_state")
+      .then(({ status, transcription }) => {
+        if (status.state === "recording") {
+          setText([transcription.final_text, transcription.partial_text].filter(Boolean).join(" "));
+        }
+      })
+      .catch(() => {});
+
+    return () => {
+      delete (window as any).__captionsSetText;
+      delete (window as any).__captionsSetStyle;
+    };
+  }, []);
+
+  if (!style) {
+    return null;
+  }
+
+  return (
+    <div className="captions-container">
+      <div
+        className="captions-box"
+        style={{
+          fontSize: `${style.font_size}px`,
+          color: style.text_color,
+          background: `rgba(0, 0, 0, ${style.background_opacity})`,
+        }}
+      >
+        {text || null}
+      </div>
+    </div>
+  );
+}
@@ -1,7 +1,21 @@
 import { useState, useEffect, useRef } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { useI18n } from "../i18n";
+import type { Message, SyncState } from "../types";
+
+/** Output of the next dictation when it differs from the settings */
+type OutputOverride = "raw" | "processed" | null;
 
 export function OverlayView() {
+  const { t, tm } = useI18n();
   const [processing, setProcessing] = useState(false);
+  const [outputOverride, setOutputOverride] = useState<OutputOverride>(null);
+  // Transcript so far, from streaming engines
+  const [liveText, setLiveText] = useState("");
+  // Why the dictation ended on its own, until the next one starts
+  const [autoStopped, setAutoStopped] = useState<Message | null>(null);
+  // Microphone muted at the OS level, the dictation is paused meanwhile
+  const [muted, setMuted] = useState(false);
   const barsRef = useRef<(HTMLSpanElement | null)[]>([]);
   const targetLevelRef = useRef(0);
   const smoothLevelRef = useRef(0);
@@ -29,15 +43,62 @@ export function OverlayView() {
       });
       rafRef.current = requestAnimationFrame(animate);
     };
-    rafRef.current = requestAnimationFrame(animate);
+    // Hidden between dictations: no animation frames while idle
+    const startAnimation = () => {
+      if (!rafRef.current) rafRef.current = requestAnimationFrame(animate);
+    };
+    const stopAnimation = () => {
+      cancelAnimationFrame(rafRef.current);
+      rafRef.current = 0;
+    };
 
-    (window as any).__overlaySetProcessing = (v: boolean) => setProcessing(v);
+    (window as any).__overlaySetProcessing = (v: boolean) => {
+      setProcessing(v);
+      setLiveText("");
+      setMuted(false);
+      if (!v) setAutoStopped(null);
+    };
+    (window as any).__overlaySetText = (v: string) => setLiveText(v);
     (window as any).__overlaySetLevel = (v: number) => { targetLevelRef.current = v; };
+    (window as any).__overlaySetOutputOverride = (v: OutputOverride) => setOutputOverride(v);
+    (window as any).__overlaySetAutoStopped = (v: Message) => setAutoStopped(v);
+    (window as any).__overlaySetMuted = (v: boolean) => setMuted(v);
+
+    // The webview may load after the dictation started: catch up on the state
+    invoke<SyncState>("sync_state")
+      .then(({ status, transcription, events }) => {
+        setProcessing(status.state === "transcribing" || status.state === "processing");
+        if (status.state === "recording") {
+          setLiveText([transcription.final_text, transcription.partial_text].filter(Boolean).join(" "));
+        }
+        const lastOverride = events.filter((e) => e.event === "output_override").pop();
+        if (lastOverride) setOutputOverride(lastOverride.payload as OutputOverride);
+        const lastMute = events.filter((e) => e.event === "microphone_muted").pop();
+        if (lastMute && status.state === "recording") setMuted(lastMute.payload as boolean);
+      })
+      .catch(() => {});
+
+    // Only ask for level updates while the overlay is actually displayed
+    const setInterest = (interested: boolean) =>
+      invoke("set_level_interest", { interested }).catch(() => {});
+    const onVisibilityChange = () => {
+      setInterest(!document.hidden);
+      if (document.hidden) stopAnimation();
+      else startAnimation();
+    };
+    onVisibilityChange();
+    document.addEventListener("visibilitychange", onVisibilityChange);
 
     return () => {
-      cancelAnimationFrame(rafRef.current);
+      stopAnimation();
+      document.removeEventListener("visibilitychange", onVisibilityChange);
+      setInterest(false);
       delete (window as any).__overlaySetProcessing;
+      delete (window as any).__overlaySetText;
       delete (window as any).__overlaySetLevel;
+      delete (window as any).__overlaySetOutputOverride;
+      delete (window as any).__overlaySetAutoStopped;
+      delete (window as any).__overlaySetMuted;
     };
   }, []);
 
@@ -50,6 +111,10 @@ export function OverlayView() {
             <span className="processing-dot" />
             <span className="processing-dot" />
           </div>
+        ) : liveText ? (
+          <div className="overlay-live-text">
+            <span>{liveText}</span>
+          </div>
         ) : (
           <div className="waveform">
             {Array.from({ length: 5 }).map((_, i) => (
@@ -61,6 +126,19 @@ export function OverlayView() {
             ))}
           </div>
         )}
+        {!processing && muted && (
+          <span className="muted-badge">{t("overlay_muted")}</span>
+        )}
+        {processing && autoStopped && (
+          <span className="autostop-badge" title={tm(autoStopped)}>
+            {t("overlay_autostopped")}
+          </span>
+        )}
+        {outputOverride && (
+          <span className={`output-override ${outputOverride}`}>
+            {outputOverride === "raw" ? "RAW" : "AI"}
+          </span>
+        )}
       </div>
     </div>
   );
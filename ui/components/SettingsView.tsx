@@ -7,21 +7,71 @@ import { ShortcutPage } from "./pages/ShortcutPage";
 import { SettingsPage } from "./pages/SettingsPage";
 import { useConfig } from "../hooks/useConfig";
 import { useToast } from "./Toast";
-import { PAGE_GLOW_COLORS, type Page } from "../types";
+import { useI18n } from "../i18n";
+import { PAGE_GLOW_COLORS, type Message, type Page } from "../types";
 
 export function SettingsView() {
   const [activePage, setActivePage] = useState<Page>("dictation");
   const config = useConfig();
   const { showToast } = useToast();
+  const { tm } = useI18n();
 
   useEffect(() => {
-    const unlisten = listen<string>("config_error", (event) => {
-      showToast(event.payload);
+    const unlistenConfig = listen<Message>("config_error", (event) => {
+      showToast(tm(event.payload));
+    });
+    const unlistenTooShort = listen<Message>("audio_too_short", (event) => {
+      showToast(tm(event.payload));
+    });
+    const unlistenPasteBlocked = listen<Message>("paste_blocked", (event) => {
+      showToast(tm(event.payload));
+    });
+    const unlistenRecovered = listen<Message>("pipeline_recovered", (event) => {
+      showToast(tm(event.payload));
+    });
+    const unlistenBudgetWarning = listen<Message>("budget_warning", (event) => {
+      showToast(tm(event.payload));
+    });
+    const unlistenBudgetExceeded = listen<Message>("budget_exceeded", (event) => {
+      showToast(tm(event.payload));
+    });
+    const unlistenLanguage = listen<Message>("language_mismatch", (event) => {
+      showToast(tm(event.payload));
+    });
+    const unlistenMeeting = listen<Message>("meeting_suggested", (event) => {
+      showToast(tm(event.payload));
+    });
+    const unlistenClipboardAudio = listen<Message>("clipboard_audio_failed", (event) => {
+      showToast(tm(event.payload));
+    });
+    const unlistenMeetingSummary = listen<Message>("meeting_summary", (event) => {
+      showToast(tm(event.payload));
+    });
+    const unlistenShortcut = listen<Message>("shortcut_unavailable", (event) => {
+      showToast(tm(event.payload));
+    });
+    const unlistenTargetLock = listen<string | null>("target_lock", (event) => {
+      showToast(
+        event.payload === null
+          ? tm({ key: "target_unlocked", args: {} })
+          : tm({ key: "target_locked", args: { app: event.payload } }),
+      );
     });
     return () => {
-      unlisten.then((fn) => fn());
+      unlistenConfig.then((fn) => fn());
+      unlistenTooShort.then((fn) => fn());
+      unlistenPasteBlocked.then((fn) => fn());
+      unlistenRecovered.then((fn) => fn());
+      unlistenBudgetWarning.then((fn) => fn());
+      unlistenBudgetExceeded.then((fn) => fn());
+      unlistenLanguage.then((fn) => fn());
+      unlistenMeeting.then((fn) => fn());
+      unlistenClipboardAudio.then((fn) => fn());
+      unlistenMeetingSummary.then((fn) => fn());
+      unlistenShortcut.then((fn) => fn());
+      unlistenTargetLock.then((fn) => fn());
     };
-  }, [showToast]);
+  }, [showToast, tm]);
 
   return (
     <div className="app-layout">
@@ -41,6 +91,9 @@ export function SettingsView() {
               setOutputLanguage={config.setOutputLanguage}
               reformulate={config.reformulate}
               setReformulate={config.setReformulate}
+              presets={config.presets}
+              activePreset={config.activePreset}
+              setActivePreset={config.setActivePreset}
             />
           )}
 
@@ -59,12 +112,25 @@ export function SettingsView() {
             />
           )}
 
-          {activePage === "shortcut" && <ShortcutPage />}
+          {activePage === "shortcut" && (
+            <ShortcutPage
+              globalShortcut={config.globalShortcut}
+              setGlobalShortcut={config.setGlobalShortcut}
+              cancelShortcut={config.cancelShortcut}
+              setCancelShortcut={config.setCancelShortcut}
+              shortcutMode={config.shortcutMode}
+              setShortcutMode={config.setShortcutMode}
+              pasteMethod={config.pasteMethod}
+              setPasteMethod={config.setPasteMethod}
+            />
+          )}
 
           {activePage === "settings" && (
             <SettingsPage
               audioDevice={config.audioDevice}
               setAudioDevice={config.setAudioDevice}
+              noiseSuppression={config.noiseSuppression}
+              setNoiseSuppression={config.setNoiseSuppression}
             />
           )}
         </div>
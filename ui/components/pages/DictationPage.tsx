@@ -1,11 +1,15 @@
 import { useI18n } from "../../i18n";
 import { OUTPUT_LANGUAGES } from "../../types";
+import type { PromptPreset } from "../../types";
 
 interface DictationPageProps {
   outputLanguage: string;
   setOutputLanguage: (v: string) => void;
   reformulate: boolean;
   setReformulate: (v: boolean) => void;
+  presets: PromptPreset[];
+  activePreset: string;
+  setActivePreset: (v: string) => void;
 }
 
 export function DictationPage({
@@ -13,6 +17,9 @@ export function DictationPage({
   setOutputLanguage,
   reformulate,
   setReformulate,
+  presets,
+  activePreset,
+  setActivePreset,
 }: DictationPageProps) {
   const { t } = useI18n();
 
@@ -56,6 +63,25 @@ export function DictationPage({
           </div>
         </label>
       </div>
+
+      {reformulate && (
+        <div className="settings-section">
+          <h2>{t("reformulation_preset")}</h2>
+          <p className="hint">{t("reformulation_preset_hint")}</p>
+          <select
+            className="settings-select"
+            value={activePreset}
+            onChange={(e) => setActivePreset(e.target.value)}
+          >
+            <option value="">{t("reformulation_preset_default")}</option>
+            {presets.map((preset) => (
+              <option key={preset.name} value={preset.name}>
+                {preset.name}
+              </option>
+            ))}
+          </select>
+        </div>
+      )}
     </>
   );
 }
@@ -1,4 +1,22 @@
-import { useI18n } from "../../i18n";
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { useI18n, type TranslationKey } from "../../i18n";
+import type { EngineHealthReport } from "../../types";
+
+const ENGINES: [string, TranslationKey][] = [
+  ["openai", "openai_api"],
+  ["groq", "groq_api"],
+  ["voxtral", "voxtral_api"],
+  ["gemini", "gemini_api"],
+];
+
+const STATUS_KEYS: Record<EngineHealthReport["status"], TranslationKey> = {
+  ok: "engine_status_ok",
+  degraded: "engine_status_degraded",
+  unauthorized: "engine_status_unauthorized",
+  unconfigured: "engine_status_unconfigured",
+};
 
 interface EnginePageProps {
   apiKey: string;
@@ -26,6 +44,33 @@ export function EnginePage({
   setSttEngine,
 }: EnginePageProps) {
   const { t } = useI18n();
+  const [health, setHealth] = useState<Record<string, EngineHealthReport>>({});
+  const [checking, setChecking] = useState(false);
+
+  const applyHealth = (reports: EngineHealthReport[]) =>
+    setHealth(Object.fromEntries(reports.map((report) => [report.engine, report])));
+
+  const checkEngines = (refresh: boolean) => {
+    setChecking(true);
+    invoke<EngineHealthReport[]>("get_engine_status", { refresh })
+      .then(applyHealth)
+      .catch(() => {})
+      .finally(() => setChecking(false));
+  };
+
+  useEffect(() => {
+    checkEngines(false);
+    const unlisten = listen<EngineHealthReport[]>("engine_status", (event) => applyHealth(event.payload));
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Status shown next to the engine name, when it is not usable as is
+  const statusSuffix = (engine: string) => {
+    const status = health[engine]?.status;
+    return status && status !== "ok" ? ` (${t(STATUS_KEYS[status])})` : "";
+  };
 
   const engineConfig: Record<string, {
     label: string; hint: string; key: string;
@@ -68,13 +113,35 @@ export function EnginePage({
           value={sttEngine}
           onChange={(e) => setSttEngine(e.target.value)}
         >
-          <option value="openai">{t("openai_api")}</option>
-          <option value="groq">{t("groq_api")}</option>
-          <option value="voxtral">{t("voxtral_api")}</option>
-          <option value="gemini">{t("gemini_api")}</option>
+          <option value="openai">{t("openai_api")}{statusSuffix("openai")}</option>
+          <option value="groq">{t("groq_api")}{statusSuffix("groq")}</option>
+          <option value="voxtral">{t("voxtral_api")}{statusSuffix("voxtral")}</option>
+          <option value="gemini">{t("gemini_api")}{statusSuffix("gemini")}</option>
         </select>
       </div>
 
+      <div className="settings-section">
+        <h2>{t("engine_status")}</h2>
+        <p className="hint">{t("engine_status_hint")}</p>
+        <div className="models-list">
+          {ENGINES.map(([engine, labelKey]) => {
+            const report = health[engine];
+            return (
+              <div className="model-item" key={engine} title={report?.details ?? undefined}>
+                <span className="model-label">{t(labelKey)}</span>
+                <code className={`model-name engine-status-${report?.status ?? "unknown"}`}>
+                  {report ? t(STATUS_KEYS[report.status]) : "-"}
+                  {report && report.status !== "unconfigured" && ` · ${report.latency_ms} ms`}
+                </code>
+              </div>
+            );
+          })}
+        </div>
+        <button className="btn-secondary" onClick={() => checkEngines(true)} disabled={checking}>
+          {checking ? t("engine_status_checking") : t("check_engines_now")}
+        </button>
+      </div>
+
       <div className="settings-section">
         <h2>{current.label}</h2>
         <p className="hint">{current.hint}</p>
@@ -8,12 +8,19 @@ import { useAudioDevices } from "../../hooks/useAudioDevices";
 interface SettingsPageProps {
   audioDevice: string;
   setAudioDevice: (v: string) => void;
+  noiseSuppression: boolean;
+  setNoiseSuppression: (v: boolean) => void;
 }
 
-export function SettingsPage({ audioDevice, setAudioDevice }: SettingsPageProps) {
-  const { t, lang, setLang } = useI18n();
+export function SettingsPage({
+  audioDevice,
+  setAudioDevice,
+  noiseSuppression,
+  setNoiseSuppression,
+}: SettingsPageProps) {
+  const { t, tm, lang, setLang } = useI18n();
   const { status, version, checkAndDownload, dismiss } = useUpdater();
-  const { devices, level, refreshDevices, startPreview, stopPreview } =
+  const { devices, level, previewError, refreshDevices, startPreview, stopPreview } =
     useAudioDevices();
   const [appVersion, setAppVersion] = useState("");
 
@@ -101,6 +108,28 @@ export function SettingsPage({ audioDevice, setAudioDevice }: SettingsPageProps)
             />
           </div>
         </div>
+        {previewError && (
+          <p className="hint">
+            {previewError.code === "mic_denied" ? t("mic_denied_hint") : tm(previewError.message)}
+          </p>
+        )}
+      </div>
+
+      <div className="settings-section">
+        <label className="toggle-row">
+          <div className="toggle-row-text">
+            <h2>{t("noise_suppression")}</h2>
+            <p className="hint">{t("noise_suppression_hint")}</p>
+          </div>
+          <div className="toggle-switch">
+            <input
+              type="checkbox"
+              checked={noiseSuppression}
+              onChange={(e) => setNoiseSuppression(e.target.checked)}
+            />
+            <span className="toggle-slider" />
+          </div>
+        </label>
       </div>
 
       <div className="settings-section">
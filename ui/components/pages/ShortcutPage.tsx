@@ -1,34 +1,136 @@
+import { Fragment, useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
 import { useI18n } from "../../i18n";
+import type { PasteMethod, PastePermissions, ShortcutMode } from "../../types";
 
-export function ShortcutPage() {
+interface ShortcutPageProps {
+  globalShortcut: string;
+  setGlobalShortcut: (v: string) => void;
+  cancelShortcut: string;
+  setCancelShortcut: (v: string) => void;
+  shortcutMode: ShortcutMode;
+  setShortcutMode: (v: ShortcutMode) => void;
+  pasteMethod: PasteMethod;
+  setPasteMethod: (v: PasteMethod) => void;
+}
+
+const IS_MAC = navigator.userAgent.includes("Mac");
+
+/** Keys of a shortcut as displayed ("CmdOrCtrl+Shift+Space" -> Cmd, Shift, Space) */
+function shortcutKeys(shortcut: string): string[] {
+  return shortcut
+    .split("+")
+    .filter(Boolean)
+    .map((key) => (key === "CmdOrCtrl" || key === "CommandOrControl" ? (IS_MAC ? "Cmd" : "Ctrl") : key));
+}
+
+function ShortcutDisplay({ shortcut }: { shortcut: string }) {
+  return (
+    <div className="shortcut-display">
+      {shortcutKeys(shortcut).map((key, i) => (
+        <Fragment key={i}>
+          {i > 0 && <span className="kbd-plus">+</span>}
+          <kbd>{key}</kbd>
+        </Fragment>
+      ))}
+    </div>
+  );
+}
+
+export function ShortcutPage({
+  globalShortcut,
+  setGlobalShortcut,
+  cancelShortcut,
+  setCancelShortcut,
+  shortcutMode,
+  setShortcutMode,
+  pasteMethod,
+  setPasteMethod,
+}: ShortcutPageProps) {
   const { t } = useI18n();
+  const [permissions, setPermissions] = useState<PastePermissions | null>(null);
+
+  useEffect(() => {
+    const check = () =>
+      invoke<PastePermissions>("check_automation_permissions")
+        .then(setPermissions)
+        .catch(() => {});
+    check();
+    // Re-check when coming back from System Settings
+    window.addEventListener("focus", check);
+    return () => window.removeEventListener("focus", check);
+  }, []);
+
+  const openSettings = (pane: "accessibility" | "automation") => {
+    invoke("open_permission_settings", { pane }).catch(() => {});
+  };
 
   return (
     <>
       <h2 className="page-title">{t("page_shortcut")}</h2>
 
+      {permissions && !(permissions.accessibility && permissions.automation) && (
+        <div className="settings-section">
+          <h2>{t("paste_permissions")}</h2>
+          <p className="hint">{t("paste_permissions_hint")}</p>
+          {!permissions.accessibility && (
+            <button className="btn-secondary" onClick={() => openSettings("accessibility")}>
+              {t("open_accessibility_settings")}
+            </button>
+          )}
+          {!permissions.automation && (
+            <button className="btn-secondary" onClick={() => openSettings("automation")}>
+              {t("open_automation_settings")}
+            </button>
+          )}
+        </div>
+      )}
+
       <div className="settings-section">
         <h2>{t("global_shortcut")}</h2>
-        <p className="hint">{t("shortcut_hint")}</p>
-        <div className="shortcut-display">
-          <kbd>Cmd</kbd>
-          <span className="kbd-plus">+</span>
-          <kbd>Shift</kbd>
-          <span className="kbd-plus">+</span>
-          <kbd>Space</kbd>
-        </div>
+        <p className="hint">{t(shortcutMode === "hold" ? "shortcut_hold_hint" : "shortcut_hint")}</p>
+        <ShortcutDisplay shortcut={globalShortcut} />
+        <input
+          type="text"
+          className="settings-input"
+          value={globalShortcut}
+          onChange={(e) => setGlobalShortcut(e.target.value)}
+          placeholder="CmdOrCtrl+Shift+Space"
+        />
+        <select
+          className="settings-select"
+          value={shortcutMode}
+          onChange={(e) => setShortcutMode(e.target.value as ShortcutMode)}
+        >
+          <option value="toggle">{t("shortcut_mode_toggle")}</option>
+          <option value="hold">{t("shortcut_mode_hold")}</option>
+        </select>
       </div>
 
       <div className="settings-section">
         <h2>{t("cancel_shortcut")}</h2>
         <p className="hint">{t("cancel_shortcut_hint")}</p>
-        <div className="shortcut-display">
-          <kbd>Cmd</kbd>
-          <span className="kbd-plus">+</span>
-          <kbd>Shift</kbd>
-          <span className="kbd-plus">+</span>
-          <kbd>C</kbd>
-        </div>
+        <ShortcutDisplay shortcut={cancelShortcut} />
+        <input
+          type="text"
+          className="settings-input"
+          value={cancelShortcut}
+          onChange={(e) => setCancelShortcut(e.target.value)}
+          placeholder="CmdOrCtrl+Shift+C"
+        />
+      </div>
+
+      <div className="settings-section">
+        <h2>{t("paste_method")}</h2>
+        <p className="hint">{t("paste_method_hint")}</p>
+        <select
+          className="settings-select"
+          value={pasteMethod}
+          onChange={(e) => setPasteMethod(e.target.value as PasteMethod)}
+        >
+          <option value="clipboard">{t("paste_method_clipboard")}</option>
+          <option value="type">{t("paste_method_type")}</option>
+        </select>
       </div>
     </>
   );
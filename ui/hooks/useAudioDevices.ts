@@ -1,10 +1,12 @@
 import { useState, useEffect, useRef, useCallback } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
+import type { DicteaError } from "../types";
 
 export function useAudioDevices() {
   const [devices, setDevices] = useState<string[]>([]);
   const [level, setLevel] = useState(0);
+  const [previewError, setPreviewError] = useState<DicteaError | null>(null);
   const previewActive = useRef(false);
 
   const refreshDevices = useCallback(async () => {
@@ -20,8 +22,10 @@ export function useAudioDevices() {
     try {
       await invoke("start_mic_preview", { deviceName });
       previewActive.current = true;
+      setPreviewError(null);
     } catch (e) {
       console.error("Failed to start mic preview:", e);
+      setPreviewError(e as DicteaError);
     }
   }, []);
 
@@ -45,5 +49,5 @@ export function useAudioDevices() {
     };
   }, []);
 
-  return { devices, level, refreshDevices, startPreview, stopPreview };
+  return { devices, level, previewError, refreshDevices, startPreview, stopPreview };
 }
@@ -1,6 +1,6 @@
 import { useState, useEffect, useRef, useCallback } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import type { AppConfig } from "../types";
+import type { AppConfig, PasteMethod, ShortcutMode } from "../types";
 
 export function useConfig() {
   const [config, setConfig] = useState<AppConfig | null>(null);
@@ -9,9 +9,15 @@ export function useConfig() {
   const [geminiApiKey, setGeminiApiKey] = useState("");
   const [groqApiKey, setGroqApiKey] = useState("");
   const [audioDevice, setAudioDevice] = useState("");
+  const [noiseSuppression, setNoiseSuppression] = useState(false);
   const [sttEngine, setSttEngine] = useState("openai");
   const [outputLanguage, setOutputLanguage] = useState("auto");
   const [reformulate, setReformulate] = useState(false);
+  const [activePreset, setActivePreset] = useState("");
+  const [globalShortcut, setGlobalShortcut] = useState("");
+  const [cancelShortcut, setCancelShortcut] = useState("");
+  const [shortcutMode, setShortcutMode] = useState<ShortcutMode>("toggle");
+  const [pasteMethod, setPasteMethod] = useState<PasteMethod>("clipboard");
   const loaded = useRef(false);
 
   useEffect(() => {
@@ -20,13 +26,19 @@ export function useConfig() {
         const appConfig = await invoke<AppConfig>("get_config");
         setConfig(appConfig);
         setReformulate(appConfig.reformulate);
+        setActivePreset(appConfig.reformulation?.active_preset || "");
         setApiKey(appConfig.openai_api_key);
         setMistralApiKey(appConfig.mistral_api_key);
         setGeminiApiKey(appConfig.gemini_api_key);
         setGroqApiKey(appConfig.groq_api_key || "");
         setAudioDevice(appConfig.audio_device || "");
+        setNoiseSuppression(appConfig.noise_suppression?.enabled ?? false);
         setSttEngine(appConfig.stt_engine || "openai");
         setOutputLanguage(appConfig.output_language);
+        setGlobalShortcut(appConfig.global_shortcut);
+        setCancelShortcut(appConfig.cancel_shortcut);
+        setShortcutMode(appConfig.shortcut_mode || "toggle");
+        setPasteMethod(appConfig.paste_method || "clipboard");
         loaded.current = true;
       } catch (e) {
         console.error(e);
@@ -45,12 +57,34 @@ export function useConfig() {
         gemini_api_key: geminiApiKey,
         groq_api_key: groqApiKey,
         audio_device: audioDevice,
+        noise_suppression: { ...config.noise_suppression, enabled: noiseSuppression },
         stt_engine: sttEngine,
         output_language: outputLanguage,
         reformulate,
+        reformulation: { ...config.reformulation, active_preset: activePreset },
+        global_shortcut: globalShortcut,
+        cancel_shortcut: cancelShortcut,
+        shortcut_mode: shortcutMode,
+        paste_method: pasteMethod,
       },
     }).catch(console.error);
-  }, [config, apiKey, mistralApiKey, geminiApiKey, groqApiKey, audioDevice, sttEngine, outputLanguage, reformulate]);
+  }, [
+    config,
+    apiKey,
+    mistralApiKey,
+    geminiApiKey,
+    groqApiKey,
+    audioDevice,
+    noiseSuppression,
+    sttEngine,
+    outputLanguage,
+    reformulate,
+    activePreset,
+    globalShortcut,
+    cancelShortcut,
+    shortcutMode,
+    pasteMethod,
+  ]);
 
   useEffect(() => {
     if (!loaded.current) return;
@@ -69,11 +103,24 @@ export function useConfig() {
     setGroqApiKey,
     audioDevice,
     setAudioDevice,
+    noiseSuppression,
+    setNoiseSuppression,
     sttEngine,
     setSttEngine,
     outputLanguage,
     setOutputLanguage,
     reformulate,
     setReformulate,
+    presets: config?.reformulation?.presets ?? [],
+    activePreset,
+    setActivePreset,
+    globalShortcut,
+    setGlobalShortcut,
+    cancelShortcut,
+    setCancelShortcut,
+    shortcutMode,
+    setShortcutMode,
+    pasteMethod,
+    setPasteMethod,
   };
 }
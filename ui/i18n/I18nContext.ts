@@ -1,18 +1,28 @@
 import { createContext, useContext } from "react";
 import { translations, type TranslationKey, type AppLang } from "./translations";
+import type { Message } from "../types";
 
 interface I18nValue {
   t: (key: TranslationKey) => string;
+  tm: (message: Message) => string;
   lang: AppLang;
   setLang: (l: AppLang) => void;
 }
 
 export const I18nContext = createContext<I18nValue>({
   t: (key) => translations.en[key],
+  tm: (message) => formatMessage("en", message),
   lang: "en",
   setLang: () => {},
 });
 
+/** Localize a backend message, filling its {name} placeholders */
+export function formatMessage(lang: AppLang, message: Message): string {
+  const template: string =
+    (translations[lang] as Record<string, string>)[message.key] ?? message.key;
+  return template.replace(/\{(\w+)\}/g, (_, name) => message.args[name] ?? "");
+}
+
 export function useI18n() {
   return useContext(I18nContext);
 }
@@ -1,2 +1,2 @@
 export { translations, type TranslationKey, type AppLang } from "./translations";
-export { I18nContext, useI18n, getStoredLang } from "./I18nContext";
+export { I18nContext, useI18n, getStoredLang, formatMessage } from "./I18nContext";
@@ -16,6 +16,9 @@ export const translations = {
     output_language_hint_translate: "Le texte sera traduit automatiquement",
     reformulate: "Reformuler",
     reformulate_hint: "L'IA corrige la grammaire, supprime les hesitations et repetitions",
+    reformulation_preset: "Style de reformulation",
+    reformulation_preset_hint: "Prompt utilise pour reformuler (email, Slack, message de commit...)",
+    reformulation_preset_default: "Par defaut",
     stt_engine: "Moteur STT",
     stt_engine_hint: "Choisissez le moteur de transcription",
     openai_api: "OpenAI Whisper API",
@@ -23,8 +26,19 @@ export const translations = {
     api_key_hint: "Votre cle API pour utiliser Whisper et GPT",
     global_shortcut: "Raccourci global",
     shortcut_hint: "Appuyez pour demarrer, appuyez a nouveau pour transcrire et coller",
+    shortcut_hold_hint: "Maintenez pour dicter, relachez pour transcrire et coller",
+    shortcut_mode_toggle: "Appuyer pour demarrer / arreter",
+    shortcut_mode_hold: "Maintenir pour parler",
+    paste_method: "Methode de collage",
+    paste_method_hint: "La saisie touche par touche fonctionne dans les applications qui bloquent le collage (terminaux, VM, Citrix), mais elle est plus lente.",
+    paste_method_clipboard: "Presse-papiers + coller",
+    paste_method_type: "Saisie touche par touche",
     cancel_shortcut: "Annuler",
     cancel_shortcut_hint: "Annule l'enregistrement en cours sans coller",
+    paste_permissions: "Autorisations de collage",
+    paste_permissions_hint: "macOS bloque le collage automatique tant que Dictea n'est pas autorise",
+    open_accessibility_settings: "Ouvrir Accessibilite",
+    open_automation_settings: "Ouvrir Automatisation",
     overlay_listening: "Ecoute...",
     overlay_speak: "Parlez maintenant...",
     overlay_stop: "Arreter et coller",
@@ -43,18 +57,148 @@ export const translations = {
     api_key_gemini_hint: "Votre cle API pour utiliser Gemini",
     api_key_required_mistral: "Cle API Mistral requise. Configurez-la dans Reglages > Moteur.",
     api_key_required_gemini: "Cle API Gemini requise. Configurez-la dans Reglages > Moteur.",
+    api_key_required_google: "Cle API ou compte de service Google Cloud requis. Configurez-le dans Reglages > Moteur.",
     api_key_required_groq: "Cle API Groq requise. Configurez-la dans Reglages > Moteur.",
+    api_key_required_assemblyai: "Cle API AssemblyAI requise. Configurez-la dans Reglages > Moteur.",
     api_key_required: "Cle API OpenAI requise. Configurez-la dans Reglages > Moteur.",
     groq_api: "Groq (Whisper)",
     api_key_groq: "Cle API Groq",
     api_key_groq_hint: "Votre cle API pour utiliser Whisper via Groq",
     audio_device: "Microphone",
     audio_device_hint: "Selectionnez le peripherique d'entree audio",
+    mic_denied_hint: "Acces au micro refuse : autorisez Dictea dans les reglages de confidentialite du systeme",
     audio_device_default: "Par defaut (systeme)",
+    noise_suppression: "Reduction du bruit",
+    noise_suppression_hint: "Retire le bruit de fond (ventilateur, clavier) avant la transcription",
     models_used: "Modeles utilises",
+    engine_status: "Etat des fournisseurs",
+    engine_status_hint: "Verifie regulierement que chaque cle est acceptee et que le service repond",
+    engine_status_ok: "OK",
+    engine_status_degraded: "degrade",
+    engine_status_unauthorized: "cle refusee",
+    engine_status_unconfigured: "sans cle",
+    engine_status_checking: "Verification...",
+    check_engines_now: "Verifier maintenant",
     models_used_hint: "Ces modeles sont appeles via votre cle API",
     model_transcription: "Transcription",
     model_reformulation: "Reformulation & traduction",
+    engine_error: "Erreur du moteur {engine} : {details}",
+    audio_error: "Erreur audio : {details}",
+    copy_error: "Erreur de copie : {details}",
+    clipboard_error: "Presse-papiers indisponible : {details}",
+    playback_error: "Lecture impossible : {details}",
+    recording_not_found: "Enregistrement introuvable : {id}",
+    invalid_shortcut: "Raccourci invalide : {value}",
+    unknown_engine: "Moteur inconnu : {value}",
+    invalid_language: "Code de langue invalide : {value}",
+    monitor_name_required: "Nom d'ecran requis",
+    invalid_paste_shortcut: "Raccourci de collage invalide : {value}",
+    indicator_unavailable: "Indicateur d'enregistrement indisponible, capture annulee",
+    marker_phrase_required: "Phrase de marqueur requise",
+    invalid_silence_threshold: "Seuil de silence invalide (0 a 1) : {value}",
+    invalid_vad_aggressiveness: "Agressivite VAD invalide (0 a 3) : {value}",
+    vad_model_required: "Chemin du modele Silero requis",
+    invalid_speed_factor: "Facteur de ralentissement invalide (1 a 1,5) : {value}",
+    invalid_speed_threshold: "Seuil de debit invalide : {value}",
+    note_path_required: "Chemin de la note requis",
+    hid_device_required: "Identifiants vendeur et produit du peripherique requis",
+    gamepad_button_required: "Bouton de manette requis",
+    invalid_gesture_delay: "Delai de geste invalide : {value} ms (100 a 2000)",
+    invalid_port: "Port invalide (1024 a 65535) : {value}",
+    control_socket_token_required: "Jeton du socket de controle requis",
+    duplicate_shortcut: "Raccourci deja utilise pour une autre action : {value}",
+    shortcut_unavailable: "Raccourci deja pris par une autre application : {value}",
+    invalid_smart_stop_delay: "Delai de pause invalide (300 a 10000 ms) : {value}",
+    invalid_cache_limits: "Limites du cache invalides : {entries} entrees (1 a 10000), {hours} h (1 a 720)",
+    local_worker_required: "Indiquez la commande du worker local pour le moteur local",
+    whisper_model_required: "Indiquez le fichier du modele Whisper (GGML/GGUF) pour le moteur hors ligne",
+    invalid_network_address: "Adresse d'ecoute invalide (ex. 0.0.0.0:5004) : {value}",
+    invalid_network_sender: "Emetteur audio invalide (ex. 192.168.1.20 ou 192.168.1.20:5004) : {value}",
+    invalid_network_format: "Format audio reseau non supporte : {rate} Hz, {channels} canaux (8000 a 48000 Hz, 1 ou 2 canaux)",
+    live_feed_token_required: "Jeton du flux en direct requis",
+    schema_fields_required: "Le schema JSON doit avoir au moins un champ",
+    schema_field_name_required: "Chaque champ du schema doit avoir un nom",
+    rule_actions_required: "Chaque regle doit avoir au moins une action",
+    file_path_required: "Chemin du fichier requis",
+    invalid_webhook_url: "URL de webhook invalide (https requis) : {value}",
+    task_token_required: "Jeton d'API du gestionnaire de taches requis",
+    task_capture_error: "Creation de la tache impossible : {details}",
+    gmail_token_required: "Jeton d'acces Gmail requis",
+    issue_repository_required: "Depot requis pour creer les tickets ({profile})",
+    issue_token_required: "Jeton d'acces requis pour creer les tickets ({profile})",
+    issue_profile_required: "Au moins un profil de tickets est requis",
+    issue_profile_name_required: "Chaque profil de tickets doit avoir un nom",
+    duplicate_issue_profile: "Profil de tickets en double : {value}",
+    unknown_issue_profile: "Profil de tickets inconnu : {value}",
+    issue_creation_error: "Creation du ticket impossible : {details}",
+    email_draft_error: "Creation du brouillon impossible : {details}",
+    search_template_text_required: "Le modele d'URL de recherche doit contenir {placeholder} : {value}",
+    search_launch_error: "Ouverture de la recherche impossible : {details}",
+    meeting_device_required: "Choisissez la source audio systeme du mode reunion automatique",
+    meeting_suggested: "Reunion en cours : {title}. Le mode reunion peut l'enregistrer avec les autres participants",
+    invalid_auto_stop_delay: "Delai d'arret automatique invalide (500 a 60000 ms) : {value}",
+    recording_autostopped: "Dictee arretee apres {silence_secs} s de silence",
+    overlay_autostopped: "SILENCE",
+    overlay_muted: "MICRO COUPE",
+    invalid_level_update_fps: "Frequence du niveau audio invalide (1 a 60) : {value}",
+    invalid_watchdog_timeout: "Delai du watchdog invalide (0 ou au moins 60 s) : {value}",
+    invalid_routing_thresholds: "Seuils de routage invalides : court ({short} s) doit etre inferieur a long ({long} s)",
+    invalid_budget_cap: "Budget mensuel invalide pour {engine} : {value}",
+    budget_warning: "{engine} : {spent} $ estimes sur un budget mensuel de {cap} $",
+    budget_exceeded: "Budget mensuel de {engine} atteint ({spent} $ / {cap} $)",
+    budget_fallback: "Budget mensuel de {engine} atteint ({spent} $ / {cap} $), dictee avec {fallback}",
+    language_mismatch: "Texte en \"{detected}\" au lieu de \"{expected}\", la traduction a echoue",
+    nothing_to_undo: "Aucune dictee recente a annuler dans cette application",
+    locked_by_policy: "Reglage impose par votre organisation ({path})",
+    policy_unreadable: "Politique de votre organisation illisible ({path}), reglages verrouilles : {details}",
+    invalid_sync_url: "URL de synchronisation invalide (https ou depot Git en https/ssh) : {value}",
+    profile_sync_error: "Synchronisation des profils impossible : {details}",
+    invalid_live_paste_interval: "Intervalle de saisie en direct invalide (50 a 5000 ms) : {value}",
+    undo_error: "Impossible d'annuler la dictee : {details}",
+    pipeline_recovered: "La transcription etait bloquee, elle a ete reinitialisee",
+    pipeline_session_closed: "La session de transcription n'est pas ouverte, reessayez",
+    paste_blocked_elevated: "{app} tourne en administrateur : texte copie, collez-le manuellement",
+    paste_tool_missing: "Session Wayland sans wtype ni ydotool : texte copie, collez-le manuellement",
+    paste_blocked_self: "Le focus n'est pas revenu a l'application : texte copie, collez-le manuellement",
+    preset_incomplete: "Chaque preset de reformulation doit avoir un nom et un prompt",
+    duplicate_preset: "Preset de reformulation en double : {value}",
+    unknown_preset: "Preset de reformulation inconnu : {value}",
+    replacement_phrase_required: "Remplacement ou commande sans expression dans la table {value}",
+    invalid_caption_font_size: "Taille du texte des sous-titres invalide (12 a 128) : {value}",
+    invalid_caption_lines: "Nombre de lignes de sous-titres invalide (1 a 6) : {value}",
+    invalid_caption_opacity: "Opacite du fond des sous-titres invalide (0 a 1) : {value}",
+    invalid_quick_note_duration: "La duree maximale d'une note rapide doit etre d'au moins 1 seconde",
+    quick_note_error: "Enregistrement de la note impossible : {details}",
+    invalid_upload_sample_rate: "Frequence d'envoi non prise en charge (8000, 16000 ou 24000 Hz) : {value}",
+    annotation_needs_meeting: "Les marqueurs ne s'ajoutent que pendant un enregistrement en mode reunion",
+    invalid_health_interval: "Intervalle de verification trop court (30 s minimum) : {value} s",
+    clipboard_no_audio: "Le presse-papiers ne contient ni fichier ni donnees audio",
+    clipboard_audio_error: "Lecture de l'audio du presse-papiers impossible : {details}",
+    clipboard_audio_silent: "Aucune parole dans l'audio du presse-papiers",
+    target_lock_unavailable: "Impossible de verrouiller la fenetre au premier plan sur ce systeme",
+    target_lock_own_window: "Placez la fenetre cible au premier plan avant de la verrouiller",
+    target_lock_lost: "La fenetre verrouillee a disparu : verrou retire, texte colle dans la fenetre active",
+    target_locked: "Dictees verrouillees sur {app}",
+    target_unlocked: "Verrou de la fenetre cible retire",
+    unsupported_upload_codec: "Ce moteur n'accepte que le WAV : {value}",
+    invalid_speaker_profile: "Profil de locuteur sans nom, sans modele ou en double : {value}",
+    unknown_speaker_profile: "Profil de locuteur inconnu : {value}",
+    invalid_history_sync_url: "URL de synchronisation de l'historique invalide (WebDAV https, http sur localhost uniquement, ou s3://bucket/cle) : {value}",
+    history_passphrase_required: "Une phrase secrete est requise pour chiffrer l'historique synchronise",
+    history_sync_unconfigured: "Synchronisation de l'historique non configuree",
+    history_sync_error: "Synchronisation de l'historique impossible : {details}",
+    google_project_required: "Indiquez le projet Google Cloud utilise avec la cle API",
+    meeting_summary_started: "Resume de la reunion en cours...",
+    meeting_summary_written: "Resume de la reunion ajoute a {path}",
+    meeting_summary_failed: "Resume de la reunion impossible : {details}",
+    audio_too_short: "Dictee trop courte ({duration_ms} ms, minimum {min_duration_ms} ms)",
+    invalid_marker_template: "Le modele doit inclure la liste des elements : {value}",
+    unknown_field: "Parametre inconnu",
+    invalid_value: "Valeur invalide : {details}",
+    patch_not_object: "Modification de configuration invalide",
+    transcription_error: "Echec de la transcription : {details}",
+    error_generic: "Erreur : {details}",
+    invalid_config: "Configuration invalide",
   },
   en: {
     subtitle: "Voice dictation",
@@ -73,6 +217,9 @@ export const translations = {
     output_language_hint_translate: "Text will be translated automatically",
     reformulate: "Reformulate",
     reformulate_hint: "AI corrects grammar, removes hesitations and repetitions",
+    reformulation_preset: "Reformulation style",
+    reformulation_preset_hint: "Prompt used to reformulate (email, Slack, commit message...)",
+    reformulation_preset_default: "Default",
     stt_engine: "STT Engine",
     stt_engine_hint: "Choose the transcription engine",
     openai_api: "OpenAI Whisper API",
@@ -80,8 +227,19 @@ export const translations = {
     api_key_hint: "Your API key to use Whisper and GPT",
     global_shortcut: "Global shortcut",
     shortcut_hint: "Press to start, press again to transcribe and paste",
+    shortcut_hold_hint: "Hold to dictate, release to transcribe and paste",
+    shortcut_mode_toggle: "Press to start / stop",
+    shortcut_mode_hold: "Push-to-talk",
+    paste_method: "Paste method",
+    paste_method_hint: "Typing works in apps that block pasting (terminals, VMs, Citrix), but is slower.",
+    paste_method_clipboard: "Clipboard + paste",
+    paste_method_type: "Type the text",
     cancel_shortcut: "Cancel",
     cancel_shortcut_hint: "Cancel the current recording without pasting",
+    paste_permissions: "Paste permissions",
+    paste_permissions_hint: "macOS blocks automatic paste until Dictea is allowed",
+    open_accessibility_settings: "Open Accessibility",
+    open_automation_settings: "Open Automation",
     overlay_listening: "Listening...",
     overlay_speak: "Speak now...",
     overlay_stop: "Stop and paste",
@@ -100,18 +258,148 @@ export const translations = {
     api_key_gemini_hint: "Your API key to use Gemini",
     api_key_required_mistral: "Mistral API key required. Configure it in Settings > Engine.",
     api_key_required_gemini: "Gemini API key required. Configure it in Settings > Engine.",
+    api_key_required_google: "Google Cloud API key or service account required. Configure it in Settings > Engine.",
     api_key_required_groq: "Groq API key required. Configure it in Settings > Engine.",
+    api_key_required_assemblyai: "AssemblyAI API key required. Configure it in Settings > Engine.",
     api_key_required: "OpenAI API key required. Configure it in Settings > Engine.",
     groq_api: "Groq (Whisper)",
     api_key_groq: "Groq API key",
     api_key_groq_hint: "Your API key to use Whisper via Groq",
     audio_device: "Microphone",
     audio_device_hint: "Select the audio input device",
+    mic_denied_hint: "Microphone access denied: allow Dictea in the system privacy settings",
     audio_device_default: "Default (system)",
+    noise_suppression: "Noise suppression",
+    noise_suppression_hint: "Removes background noise (fan, keyboard) before transcription",
     models_used: "Models used",
+    engine_status: "Provider status",
+    engine_status_hint: "Regularly checks that each key is accepted and the service answers",
+    engine_status_ok: "OK",
+    engine_status_degraded: "degraded",
+    engine_status_unauthorized: "key rejected",
+    engine_status_unconfigured: "no key",
+    engine_status_checking: "Checking...",
+    check_engines_now: "Check now",
     models_used_hint: "These models are called via your API key",
     model_transcription: "Transcription",
     model_reformulation: "Reformulation & translation",
+    engine_error: "{engine} engine error: {details}",
+    audio_error: "Audio error: {details}",
+    copy_error: "Copy error: {details}",
+    clipboard_error: "Clipboard unavailable: {details}",
+    playback_error: "Cannot play recording: {details}",
+    recording_not_found: "Recording not found: {id}",
+    invalid_shortcut: "Invalid shortcut: {value}",
+    unknown_engine: "Unknown engine: {value}",
+    invalid_language: "Invalid language code: {value}",
+    monitor_name_required: "Monitor name required",
+    invalid_paste_shortcut: "Invalid paste shortcut: {value}",
+    indicator_unavailable: "Recording indicator unavailable, capture cancelled",
+    marker_phrase_required: "Marker phrase required",
+    invalid_silence_threshold: "Invalid silence threshold (0 to 1): {value}",
+    invalid_vad_aggressiveness: "Invalid VAD aggressiveness (0 to 3): {value}",
+    vad_model_required: "Silero model path required",
+    invalid_speed_factor: "Invalid slow-down factor (1 to 1.5): {value}",
+    invalid_speed_threshold: "Invalid speaking rate threshold: {value}",
+    note_path_required: "Note path is required",
+    hid_device_required: "Device vendor and product ids are required",
+    gamepad_button_required: "Gamepad button is required",
+    invalid_gesture_delay: "Invalid gesture delay: {value} ms (100 to 2000)",
+    invalid_port: "Invalid port (1024 to 65535): {value}",
+    control_socket_token_required: "Control socket token is required",
+    duplicate_shortcut: "Shortcut already used for another action: {value}",
+    shortcut_unavailable: "Shortcut already taken by another application: {value}",
+    invalid_smart_stop_delay: "Invalid pause delay (300 to 10000 ms): {value}",
+    invalid_cache_limits: "Invalid cache limits: {entries} entries (1 to 10000), {hours} h (1 to 720)",
+    local_worker_required: "Set the local worker command to use the local engine",
+    whisper_model_required: "Set the Whisper model file (GGML/GGUF) to use the offline engine",
+    invalid_network_address: "Invalid listen address (e.g. 0.0.0.0:5004): {value}",
+    invalid_network_sender: "Invalid audio sender (e.g. 192.168.1.20 or 192.168.1.20:5004): {value}",
+    invalid_network_format: "Unsupported network audio format: {rate} Hz, {channels} channels (8000 to 48000 Hz, 1 or 2 channels)",
+    live_feed_token_required: "Live feed token is required",
+    schema_fields_required: "The JSON schema needs at least one field",
+    schema_field_name_required: "Every schema field needs a name",
+    rule_actions_required: "Each rule needs at least one action",
+    file_path_required: "File path is required",
+    invalid_webhook_url: "Invalid webhook URL (https required): {value}",
+    task_token_required: "Task manager API token is required",
+    task_capture_error: "Could not create the task: {details}",
+    gmail_token_required: "Gmail access token is required",
+    issue_repository_required: "Repository is required to file issues ({profile})",
+    issue_token_required: "Access token is required to file issues ({profile})",
+    issue_profile_required: "At least one issue profile is required",
+    issue_profile_name_required: "Every issue profile needs a name",
+    duplicate_issue_profile: "Duplicate issue profile: {value}",
+    unknown_issue_profile: "Unknown issue profile: {value}",
+    issue_creation_error: "Could not create the issue: {details}",
+    email_draft_error: "Could not create the draft: {details}",
+    search_template_text_required: "The search URL template must contain {placeholder}: {value}",
+    search_launch_error: "Could not open the search: {details}",
+    meeting_device_required: "Choose the system audio source of the automatic meeting mode",
+    meeting_suggested: "Meeting in progress: {title}. Meeting mode can record it with the other participants",
+    invalid_auto_stop_delay: "Invalid auto-stop delay (500 to 60000 ms): {value}",
+    recording_autostopped: "Dictation stopped after {silence_secs} s of silence",
+    overlay_autostopped: "SILENCE",
+    overlay_muted: "MIC MUTED",
+    invalid_level_update_fps: "Invalid audio level update rate (1 to 60): {value}",
+    invalid_watchdog_timeout: "Invalid watchdog timeout (0 or at least 60 s): {value}",
+    invalid_routing_thresholds: "Invalid routing thresholds: short ({short} s) must be below long ({long} s)",
+    invalid_budget_cap: "Invalid monthly budget for {engine}: {value}",
+    budget_warning: "{engine}: an estimated {spent} $ spent of the {cap} $ monthly budget",
+    budget_exceeded: "{engine} monthly budget reached ({spent} $ / {cap} $)",
+    budget_fallback: "{engine} monthly budget reached ({spent} $ / {cap} $), dictating with {fallback}",
+    language_mismatch: "Text in \"{detected}\" instead of \"{expected}\", translation failed",
+    nothing_to_undo: "No recent dictation to undo in this application",
+    locked_by_policy: "Setting enforced by your organization ({path})",
+    policy_unreadable: "Your organization policy cannot be read ({path}), settings are locked: {details}",
+    invalid_sync_url: "Invalid sync URL (https or Git repository over https/ssh): {value}",
+    profile_sync_error: "Cannot sync profiles: {details}",
+    invalid_live_paste_interval: "Invalid live paste interval (50 to 5000 ms): {value}",
+    undo_error: "Cannot undo the dictation: {details}",
+    pipeline_recovered: "Transcription was stuck and has been reset",
+    pipeline_session_closed: "The transcription session is not open, try again",
+    paste_blocked_elevated: "{app} runs as administrator: text copied, paste it manually",
+    paste_tool_missing: "Wayland session without wtype or ydotool: text copied, paste it manually",
+    paste_blocked_self: "Focus did not return to the application: text copied, paste it manually",
+    preset_incomplete: "Every reformulation preset needs a name and a prompt",
+    duplicate_preset: "Duplicate reformulation preset: {value}",
+    unknown_preset: "Unknown reformulation preset: {value}",
+    replacement_phrase_required: "Replacement or command without a phrase in the {value} table",
+    invalid_caption_font_size: "Invalid caption text size (12 to 128): {value}",
+    invalid_caption_lines: "Invalid number of caption lines (1 to 6): {value}",
+    invalid_caption_opacity: "Invalid caption background opacity (0 to 1): {value}",
+    invalid_quick_note_duration: "The maximum length of a quick note must be at least 1 second",
+    quick_note_error: "Could not save the note: {details}",
+    invalid_upload_sample_rate: "Unsupported upload sample rate (8000, 16000 or 24000 Hz): {value}",
+    annotation_needs_meeting: "Markers can only be added while recording in meeting mode",
+    invalid_health_interval: "Health check interval too short (30 s minimum): {value} s",
+    clipboard_no_audio: "The clipboard holds no audio file or data",
+    clipboard_audio_error: "Cannot read the clipboard audio: {details}",
+    clipboard_audio_silent: "No speech in the clipboard audio",
+    target_lock_unavailable: "Cannot lock the frontmost window on this system",
+    target_lock_own_window: "Bring the target window to the front before locking it",
+    target_lock_lost: "The locked window is gone: lock released, text pasted into the active window",
+    target_locked: "Dictations locked to {app}",
+    target_unlocked: "Target window lock released",
+    unsupported_upload_codec: "This engine only takes WAV: {value}",
+    invalid_speaker_profile: "Speaker profile without name or model, or duplicated: {value}",
+    unknown_speaker_profile: "Unknown speaker profile: {value}",
+    invalid_history_sync_url: "Invalid history sync URL (WebDAV https, http to localhost only, or s3://bucket/key): {value}",
+    history_passphrase_required: "A passphrase is required to encrypt the synced history",
+    history_sync_unconfigured: "History sync is not configured",
+    history_sync_error: "Cannot sync the history: {details}",
+    google_project_required: "Set the Google Cloud project used with the API key",
+    meeting_summary_started: "Summarizing the meeting...",
+    meeting_summary_written: "Meeting summary added to {path}",
+    meeting_summary_failed: "Cannot summarize the meeting: {details}",
+    audio_too_short: "Dictation too short ({duration_ms} ms, minimum {min_duration_ms} ms)",
+    invalid_marker_template: "Template must include the item list: {value}",
+    unknown_field: "Unknown setting",
+    invalid_value: "Invalid value: {details}",
+    patch_not_object: "Invalid configuration change",
+    transcription_error: "Transcription failed: {details}",
+    error_generic: "Error: {details}",
+    invalid_config: "Invalid configuration",
   },
 } as const;
 
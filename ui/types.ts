@@ -1,15 +1,707 @@
 import type { TranslationKey } from "./i18n";
 
+export type OverlayMonitor =
+  | { mode: "follow_cursor" }
+  | { mode: "follow_focused_window" }
+  | { mode: "named"; name: string }
+  | { mode: "all" };
+
+export type SpeedNormalization =
+  | { mode: "off" }
+  | { mode: "auto"; threshold: number }
+  | { mode: "fixed"; factor: number };
+
+/** Spoken phrase flagging the rest of the sentence, e.g. "action item:" */
+export interface SpokenMarker {
+  phrase: string;
+  title: string;
+}
+
+/** Toggle on each press, or record while the global shortcut is held */
+export type ShortcutMode = "toggle" | "hold";
+
+/** Clipboard + paste shortcut, or keystrokes for apps that block pastes */
+export type PasteMethod = "clipboard" | "type";
+
 export interface AppConfig {
   global_shortcut: string;
+  cancel_shortcut: string;
+  shortcut_mode: ShortcutMode;
   openai_api_key: string;
   output_language: string;
+  /** Translation shown and typed while speaking, pasted at the end */
+  live_translation: LiveTranslation;
+  /** Translate again a result detected in another language */
+  enforce_output_language: boolean;
+  /** Continue the sentence before the caret (capital, leading space) */
+  smart_continuation: boolean;
+  /** Deletes the last pasted dictation, "" = none */
+  undo_shortcut: string;
+  /** Inverts reformulate for the next dictation, "" = none */
+  reformulate_toggle_shortcut: string;
+  /** Transcribes the audio file (or data) of the clipboard into it, "" = none */
+  clipboard_audio_shortcut: string;
+  /** Locks the dictations to the frontmost window (or releases it), "" = none */
+  target_lock_shortcut: string;
+  /** Type the transcript as it is revised (streaming engines) */
+  live_paste: boolean;
+  live_paste_interval_ms: number;
+  /** Stream to the OpenAI Realtime API for live partials (stt_engine "openai") */
+  openai_realtime: boolean;
   reformulate: boolean;
+  reformulation: ReformulationPrompts;
+  text_replacements: TextReplacements;
   stt_engine: string;
   mistral_api_key: string;
   gemini_api_key: string;
+  /** Google Cloud Speech-to-Text credentials and model (stt_engine "google") */
+  google_speech: GoogleSpeech;
   groq_api_key: string;
+  assemblyai_api_key: string;
+  /** Worker process running the local model (stt_engine "local") */
+  local_worker: LocalWorker;
+  /** GGML/GGUF model file of the offline engine (stt_engine "whisper-local") */
+  whisper_model_path: string;
+  /** Local Whisper models adapted to a speaker */
+  speaker_profiles: SpeakerProfile[];
+  /** Speaker profile of the local engine, "" = whisper_model_path */
+  speaker_profile: string;
+  transcript_cache: TranscriptCache;
+  smart_stop: SmartStop;
   audio_device: string;
+  keep_recordings: boolean;
+  recordings_max_mb: number;
+  recordings_max_age_days: number;
+  /** Keep the text of each dictation in the history */
+  keep_history: boolean;
+  /** 0 = unlimited */
+  history_max_entries: number;
+  /** Encrypted history shared between machines */
+  history_sync: HistorySync;
+  overlay_monitor: OverlayMonitor;
+  overlay_position: "monitor_top" | "near_caret";
+  /** Live transcript in large text, as personal captions */
+  captions: Captions;
+  audio_memory_limit_secs: number;
+  /** Pause the dictation and warn while the microphone is muted at the OS level */
+  mute_awareness: MuteAwareness;
+  noise_suppression: NoiseSuppression;
+  paste_method: PasteMethod;
+  /** Per-app paste methods, keyed by app name */
+  paste_method_apps: Record<string, PasteMethod>;
+  /** Paste key sequence, e.g. "Ctrl+Shift+V" (empty = platform default) */
+  paste_shortcut: string;
+  /** Per-app paste key sequences, keyed by app name */
+  paste_shortcut_apps: Record<string, string>;
+  /** Keep the overlay visible during the whole capture */
+  require_recording_indicator: boolean;
+  /** Spoken markers extracted into lists appended to the transcript */
+  spoken_markers: SpokenMarker[];
+  /** Layout of each marker list ({title}, {items}) */
+  marker_section_template: string;
+  /** Dictations shorter than this are not sent (ms, 0 = no minimum) */
+  min_audio_duration_ms: number;
+  /** Per-engine overrides of the minimum duration */
+  min_audio_duration_ms_by_engine: Record<string, number>;
+  /** RMS level under which a dictation is considered silent (0 = off) */
+  silence_threshold: number;
+  auto_stop: AutoStop;
+  /** System audio device captured with the microphone (empty = meeting mode off) */
+  system_audio_device: string;
+  /** Network stream transcribed instead of the microphone */
+  network_audio: NetworkAudio;
+  meeting_label_me: string;
+  meeting_label_them: string;
+  /** Points of a meeting flagged with a shortcut */
+  annotations: Annotations;
+  /** TL;DR and action items appended to the transcript file of a meeting */
+  meeting_summary: MeetingSummary;
+  calendar: CalendarAwareness;
+  /** Speech detector used for segmentation */
+  vad_backend: "energy" | "webrtc" | "silero";
+  /** WebRTC VAD aggressiveness (0-3) */
+  vad_aggressiveness: number;
+  silero_model_path: string;
+  /** Slowing down of fast speech before upload */
+  speed_normalization: SpeedNormalization;
+  raw_to_secondary_clipboard: boolean;
+  event_history_size: number;
+  level_update_fps: number;
+  /** 0 = watchdog off */
+  watchdog_timeout_secs: number;
+  /** Dump provider requests/responses (no API keys) for debugging */
+  request_logging: boolean;
+  /** Supports ~, {date}, {time} and {profile} */
+  transcript_file: string;
+  note_output: NoteOutput;
+  task_capture: TaskCapture;
+  chat_targets: ChatTarget[];
+  email_draft: EmailDraft;
+  /** First matching rule wins, default = note/chat if enabled, then paste */
+  output_rules: OutputRule[];
+  structured_output: StructuredOutput;
+  issue_tracker: IssueTracker;
+  search_launcher: SearchLauncher;
+  quick_note: QuickNote;
+  control_socket: ControlSocket;
+  live_feed: LiveFeed;
+  hid_trigger: HidTrigger;
+  gamepad_trigger: GamepadTrigger;
+  /** Headset play/pause button (Bluetooth, media keys) toggles the dictation */
+  media_key_trigger: boolean;
+  shortcut_gestures: ShortcutGestures;
+  engine_routing: EngineRouting;
+  /** Upload sample rate per engine, 16000 Hz when not set */
+  upload_sample_rates: Record<string, 8000 | 16000 | 24000>;
+  /** Upload codec per engine (openai, voxtral, gemini), WAV when not set */
+  upload_codecs: Record<string, "wav" | "flac" | "opus">;
+  request_limits: ConcurrencyLimits;
+  engine_health: EngineHealth;
+  cost_budget: CostBudget;
+  speech_analytics: SpeechAnalytics;
+  profile_sync: ProfileSync;
+}
+
+/** 16-bit PCM received over UDP, as RTP (L16) or raw little-endian datagrams */
+export interface NetworkAudio {
+  /** Listen address, e.g. "0.0.0.0:5004", "" = microphone */
+  address: string;
+  protocol: "rtp" | "raw_pcm";
+  sample_rate: number;
+  channels: number;
+  /** Only sender accepted, "ip" or "ip:port", "" = first sender heard */
+  sender: string;
+}
+
+/** Speech sent in the background after a pause, merged on stop */
+export interface SmartStop {
+  enabled: boolean;
+  silence_ms: number;
+}
+
+/** Transcripts reused when the exact same audio is sent again */
+export interface TranscriptCache {
+  enabled: boolean;
+  max_entries: number;
+  ttl_hours: number;
+}
+
+/** Local model run in a sandboxed process speaking JSON lines on stdin/stdout */
+export interface LocalWorker {
+  command: string;
+  args: string[];
+}
+
+/** Team profile (markers, output rules...) pulled from a URL or Git repo */
+export interface ProfileSync {
+  /** "" = off */
+  url: string;
+  /** File inside the Git repository */
+  repo_file: string;
+}
+
+/** Monthly spend caps in USD by engine id, estimated from the audio sent */
+export interface CostBudget {
+  enabled: boolean;
+  monthly_usd: Record<string, number>;
+  /** Engine used once a cap is reached, "" = only warn */
+  fallback_engine: string;
+}
+
+export interface ProviderUsage {
+  engine: string;
+  audio_secs: number;
+  estimated_usd: number;
+  /** 0 = no cap */
+  cap_usd: number;
+}
+
+export interface SpeechAnalytics {
+  enabled: boolean;
+  /** Counted case-insensitively, phrases allowed */
+  filler_words: string[];
+}
+
+export interface SpeechStats {
+  created_at: number;
+  recording_id: string | null;
+  words: number;
+  /** Recording time, pauses included */
+  duration_secs: number;
+  words_per_minute: number;
+  fillers: Record<string, number>;
+}
+
+export interface DailySpeechStats {
+  /** Local date, "YYYY-MM-DD" */
+  date: string;
+  dictations: number;
+  words: number;
+  duration_secs: number;
+  words_per_minute: number;
+  fillers: number;
+  fillers_per_100_words: number;
+}
+
+/** Returned by `get_stats` */
+export interface SpeechTrends {
+  daily: DailySpeechStats[];
+  recent: SpeechStats[];
+}
+
+/** Engine chosen by dictation length, the default engine in between */
+export interface EngineRouting {
+  enabled: boolean;
+  short_engine: string;
+  short_max_secs: number;
+  long_engine: string;
+  long_min_secs: number;
+}
+
+/** Toggle shortcut gestures: double press cancels, holding is push-to-talk */
+export interface ShortcutGestures {
+  enabled: boolean;
+  double_press_ms: number;
+  long_press_ms: number;
+}
+
+export type TriggerMode = "toggle" | "push_to_talk";
+
+/** Foot pedal read as raw HID reports */
+export interface HidTrigger {
+  enabled: boolean;
+  vendor_id: number;
+  product_id: number;
+  /** Byte of the input report holding the button state */
+  report_byte: number;
+  button_mask: number;
+  mode: TriggerMode;
+}
+
+/** Gamepad button bound to the dictation */
+export interface GamepadTrigger {
+  enabled: boolean;
+  /** gilrs button name: South, RightTrigger, Start... */
+  button: string;
+  mode: TriggerMode;
+}
+
+/** Local WebSocket (ws://127.0.0.1:port/?token=...) for Stream Deck and other controllers */
+export interface ControlSocket {
+  enabled: boolean;
+  port: number;
+  token: string;
+}
+
+/** Live transcript on http://host:port/?token=... (companion page) and /events (SSE) */
+export interface LiveFeed {
+  enabled: boolean;
+  port: number;
+  token: string;
+  /** Listen on the local network, not only this computer */
+  lan: boolean;
+  /** Receives each update as a JSON POST, "" = none */
+  webhook_url: string;
+}
+
+/** Tracker, repository and credentials issues are filed with */
+export interface IssueProfile {
+  name: string;
+  provider: "github" | "gitlab";
+  /** owner/repo on GitHub, project path or id on GitLab */
+  repository: string;
+  token: string;
+  gitlab_url: string;
+  labels: string[];
+}
+
+/** Dictation filed as a GitHub/GitLab issue, with its own shortcut */
+export interface IssueTracker {
+  enabled: boolean;
+  profiles: IssueProfile[];
+  /** Profile issues are filed with, "" = the first one */
+  active_profile: string;
+  shortcut: string;
+}
+
+/** Stop and paste after a pause in the dictation */
+export interface AutoStop {
+  enabled: boolean;
+  silence_ms: number;
+  /** RMS level under which audio is silent, 0 = the VAD backend */
+  threshold: number;
+}
+
+/** Meeting mode during the meetings of the local calendar (macOS, Windows) */
+export interface CalendarAwareness {
+  enabled: boolean;
+  /** Start meeting mode instead of only suggesting it */
+  auto_meeting_mode: boolean;
+  /** System audio device used when system_audio_device is not set */
+  meeting_audio_device: string;
+}
+
+export interface SearchLauncher {
+  enabled: boolean;
+  /** URL or app URI opened, {text} standing for the dictation */
+  url_template: string;
+  shortcut: string;
+}
+
+/** Meeting points flagged during the capture */
+export interface Annotations {
+  /** Empty = no shortcut */
+  shortcut: string;
+  /** Inline "[MM:SS] label" lines or SRT chapters after the transcript */
+  format: "inline" | "chapters";
+  /** Text of the markers added without one */
+  label: string;
+}
+
+/** Summarization pass run once a meeting ends */
+export interface MeetingSummary {
+  enabled: boolean;
+  /** Instructions of the chat model, "" = TL;DR and action items */
+  prompt: string;
+}
+
+/** Time-boxed voice note, titled and kept in the history instead of pasted */
+export interface QuickNote {
+  enabled: boolean;
+  shortcut: string;
+  /** The recording stops on its own after this long */
+  max_secs: number;
+  /** Markdown file also receiving the notes (empty = history only) */
+  path: string;
+}
+
+/** Field of the JSON object requested in structured output mode */
+export interface SchemaField {
+  name: string;
+  kind: "string" | "string_list" | "number" | "boolean";
+  description: string;
+  required: boolean;
+}
+
+/** Borderless window showing the live transcript in large text */
+export interface Captions {
+  enabled: boolean;
+  font_size: number;
+  max_lines: number;
+  /** CSS color */
+  text_color: string;
+  /** 0 (transparent) to 1 */
+  background_opacity: number;
+  /** Minimum delay between two updates, finals excepted */
+  update_interval_ms: number;
+}
+
+/** Detection of a microphone muted at the OS level (mute key, menu bar) */
+export interface MuteAwareness {
+  enabled: boolean;
+  /** How often the OS mute state is read during a dictation */
+  poll_interval_ms: number;
+  /** Run of digital silence read as a muted microphone (0 = OS state only) */
+  digital_silence_ms: number;
+}
+
+/** RNNoise denoising of the microphone audio before transcription */
+export interface NoiseSuppression {
+  enabled: boolean;
+}
+
+export interface LiveTranslation {
+  /** Target language per dictation profile ("dictation", "meeting") */
+  languages: Record<string, string>;
+  /** Time between two translations of the segment being spoken */
+  interval_ms: number;
+}
+
+/** End-to-end encrypted history shared between machines */
+export interface HistorySync {
+  enabled: boolean;
+  /** WebDAV file (https://...) or S3 object (s3://bucket/key) */
+  url: string;
+  /** WebDAV user, or S3 access key id */
+  username: string;
+  /** WebDAV password, or S3 secret access key */
+  password: string;
+  s3_region: string;
+  /** S3-compatible endpoint, "" = AWS */
+  s3_endpoint: string;
+  /** Encryption passphrase, the same on every machine */
+  passphrase: string;
+  interval_mins: number;
+}
+
+/** Result of the sync_history command */
+export interface SyncReport {
+  pulled: number;
+  total: number;
+}
+
+/** Google Cloud Speech-to-Text engine settings */
+export interface GoogleSpeech {
+  /** API key, used instead of the service account when set */
+  api_key: string;
+  /** Service account key file (JSON) */
+  service_account_path: string;
+  /** Cloud project, "" = the project of the service account */
+  project_id: string;
+  /** Recognizer region: "global", "us", "eu", "europe-west4"... */
+  location: string;
+  /** "chirp_3", "long", "short"... */
+  model: string;
+}
+
+/** Local Whisper model adapted to a speaker */
+export interface SpeakerProfile {
+  name: string;
+  /** GGML/GGUF model, fine-tuned or LoRA merged */
+  model_path: string;
+  /** Names and vocabulary the decoding is primed with */
+  prompt: string;
+}
+
+/** Named reformulation prompt */
+export interface PromptPreset {
+  name: string;
+  prompt: string;
+}
+
+/** Reformulation prompt: active preset, else custom prompt, else built-in */
+export interface ReformulationPrompts {
+  /** Replaces the built-in prompt, "" = built-in */
+  custom_prompt: string;
+  presets: PromptPreset[];
+  /** "" = custom or built-in prompt */
+  active_preset: string;
+}
+
+/** Replacements and spoken commands ("virgule" -> ",") of one language */
+export interface ReplacementTable {
+  replacements: Record<string, string>;
+  commands: Record<string, string>;
+}
+
+/** Tables keyed by language code, "default" fills in missing phrases */
+export interface TextReplacements {
+  enabled: boolean;
+  tables: Record<string, ReplacementTable>;
+}
+
+/** The chat model returns a validated JSON object instead of prose */
+export interface StructuredOutput {
+  enabled: boolean;
+  schema: SchemaField[];
+  max_retries: number;
+}
+
+export type OutputAction =
+  | { action: "clipboard" }
+  | { action: "paste"; submit: boolean }
+  | { action: "note" }
+  | { action: "file"; path: string }
+  | { action: "chat" }
+  | { action: "webhook"; url: string };
+
+/** Routing rule for finished dictations */
+export interface OutputRule {
+  when: { profile?: string | null; app?: string | null };
+  actions: OutputAction[];
+}
+
+/** Dictation into a mail draft, with its own shortcut */
+export interface EmailDraft {
+  enabled: boolean;
+  method: "mailto" | "gmail";
+  to: string;
+  /** OAuth token with the gmail.compose scope */
+  gmail_access_token: string;
+  shortcut: string;
+}
+
+/** Slack/Teams incoming webhook receiving dictations */
+export interface ChatTarget {
+  service: "slack" | "teams";
+  webhook_url: string;
+  /** Profiles posting here ("dictation", "meeting"), empty = all */
+  profiles: string[];
+}
+
+/** Dictation straight into a task manager, with its own shortcut */
+export interface TaskCapture {
+  enabled: boolean;
+  service: "todoist" | "ticktick";
+  api_token: string;
+  /** Empty = inbox */
+  project_id: string;
+  shortcut: string;
+}
+
+/** Markdown note receiving finished dictations (Obsidian daily note...) */
+export interface NoteOutput {
+  enabled: boolean;
+  /** Path template: ~, {date}, {time}, {profile} */
+  path: string;
+  /** Entry template, also gets {text} and {tags} */
+  entry_template: string;
+  tags: string[];
+}
+
+/** Localizable message returned by the backend (key + placeholder values) */
+export interface Message {
+  key: string;
+  args: Record<string, string>;
+}
+
+/** Payload of the "dictea://state" event */
+export type AppStatus =
+  | { state: "idle" }
+  | { state: "recording"; engine: string }
+  | { state: "transcribing" }
+  | { state: "processing"; text: string }
+  | { state: "no_speech" }
+  | { state: "pasting"; text: string }
+  | { state: "error"; message: Message };
+
+/** Result of the stop_and_return command */
+export interface DictationResult {
+  raw_text: string;
+  text: string;
+  no_speech: boolean;
+  recording_id: string | null;
+  /** Validated JSON object, in structured output mode */
+  structured: Record<string, unknown> | null;
+  /** Title generated for a quick note */
+  title: string | null;
+}
+
+/** State event kept in the backend replay history */
+export interface RecordedEvent {
+  seq: number;
+  event: string;
+  payload: unknown;
+  timestamp_ms: number;
+}
+
+/** Coarse phase of the current dictation */
+export type DictationPhase = "idle" | "recording" | "transcribing" | "processing" | "pasting";
+
+/** Result of the sync_state command, for webviews that attach late */
+
+export interface SyncState {
+  status: AppStatus;
+  transcription: {
+    is_recording: boolean;
+    partial_text: string;
+    final_text: string;
+    last_recording_id: string | null;
+    last_engine_id: string | null;
+    error: string | null;
+    no_speech: boolean;
+    phase: DictationPhase;
+    phase_since_ms: number;
+    recording_started_ms: number | null;
+    /** Language requested for the dictation, null = auto */
+    language: string | null;
+  };
+  events: RecordedEvent[];
+}
+
+/** Finished dictation, from the get_history command */
+export interface HistoryEntry {
+  id: string;
+  created_at: number;
+  engine: string;
+  duration_secs: number;
+  raw_text: string;
+  text: string;
+  reformulated: boolean;
+  translated: boolean;
+  recording_id: string | null;
+  /** Title generated for a quick note */
+  title: string | null;
+}
+
+/** Transcription requests sent at once per provider, the others wait in a queue */
+export interface ConcurrencyLimits {
+  /** 0 = unlimited */
+  max_concurrent: number;
+  /** Overrides keyed by engine ("openai", "groq", "voxtral", "gemini"...) */
+  per_engine: Record<string, number>;
+}
+
+/** Result of the get_request_metrics command, one entry per provider used */
+export interface RequestMetrics {
+  engine: string;
+  limit: number;
+  in_flight: number;
+  queued: number;
+  completed: number;
+  queued_total: number;
+  max_queued: number;
+  average_wait_ms: number;
+  max_wait_ms: number;
+}
+
+/** Background probes of the providers' models endpoint */
+export interface EngineHealth {
+  enabled: boolean;
+  interval_secs: number;
+  /** Answers slower than this mark the provider as degraded */
+  slow_ms: number;
+}
+
+/** Result of the get_engine_status command, one entry per HTTP provider */
+export interface EngineHealthReport {
+  engine: string;
+  status: "ok" | "degraded" | "unauthorized" | "unconfigured";
+  latency_ms: number;
+  /** HTTP status or error of a failed probe */
+  details: string | null;
+  checked_at_ms: number;
+}
+
+/** Result of the get_resource_usage debug command, all zero when idle */
+export interface ResourceUsage {
+  audio_streams: number;
+  session_tasks: number;
+  timers: number;
+}
+
+/** Result of the check_automation_permissions command (always granted outside macOS) */
+export interface PastePermissions {
+  accessibility: boolean;
+  automation: boolean;
+}
+
+/** Field-level error returned by set_config */
+export interface FieldError {
+  field: keyof AppConfig;
+  message: Message;
+}
+
+/** Stable error category, to pick the recovery UI */
+export type ErrorCode =
+  | "missing_key"
+  | "invalid_key"
+  | "mic_denied"
+  | "audio"
+  | "network"
+  | "quota"
+  | "invalid_config"
+  | "not_found"
+  | "internal";
+
+/** Error returned by every command */
+export interface DicteaError {
+  code: ErrorCode;
+  message: Message;
+  /** Raw error text (provider response, OS error...) */
+  details: string | null;
+  /** Offending fields, for invalid_config */
+  fields?: FieldError[];
 }
 
 export type Page = "dictation" | "engine" | "shortcut" | "settings";